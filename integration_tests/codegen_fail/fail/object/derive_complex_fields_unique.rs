@@ -0,0 +1,16 @@
+use juniper::{graphql_object, GraphQLObject};
+
+#[derive(GraphQLObject)]
+#[graphql(complex)]
+struct User {
+    name: String,
+}
+
+#[graphql_object(complex)]
+impl User {
+    fn name(&self) -> String {
+        self.name.to_uppercase()
+    }
+}
+
+fn main() {}
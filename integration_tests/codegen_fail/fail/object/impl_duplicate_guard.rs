@@ -0,0 +1,17 @@
+use juniper::{graphql_object, Arguments, FieldResult};
+
+fn allow(_: &(), _: &Arguments) -> FieldResult<()> {
+    Ok(())
+}
+
+struct Query;
+
+#[graphql_object]
+impl Query {
+    #[graphql(guard = allow, guard = allow)]
+    fn secret() -> &'static str {
+        "42"
+    }
+}
+
+fn main() {}
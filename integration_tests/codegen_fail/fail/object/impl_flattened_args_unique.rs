@@ -0,0 +1,19 @@
+use juniper::{graphql_object, GraphQLInputObject};
+
+#[derive(GraphQLInputObject)]
+struct Pagination {
+    offset: i32,
+    limit: i32,
+}
+
+struct Query;
+
+#[graphql_object]
+impl Query {
+    #[graphql(arguments(page(flatten)))]
+    fn numbers(page: Pagination, limit: i32) -> Vec<i32> {
+        vec![page.offset, page.limit, limit]
+    }
+}
+
+fn main() {}
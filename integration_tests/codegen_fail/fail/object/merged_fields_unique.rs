@@ -0,0 +1,30 @@
+use juniper::{graphql_object, EmptyMutation, EmptySubscription, GraphQLMergedObject, RootNode};
+
+struct UserQuery;
+
+#[graphql_object]
+impl UserQuery {
+    fn count() -> i32 {
+        1
+    }
+}
+
+struct BillingQuery;
+
+#[graphql_object]
+impl BillingQuery {
+    fn count() -> i32 {
+        2
+    }
+}
+
+#[derive(GraphQLMergedObject)]
+struct Query(UserQuery, BillingQuery);
+
+fn main() {
+    let _ = RootNode::new(
+        Query(UserQuery, BillingQuery),
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+}
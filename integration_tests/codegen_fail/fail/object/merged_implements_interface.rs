@@ -0,0 +1,29 @@
+use juniper::{graphql_interface, GraphQLMergedObject, GraphQLObject};
+
+#[graphql_interface(for = UserQuery)]
+trait Node {
+    fn id(&self) -> i32;
+}
+
+#[derive(GraphQLObject)]
+#[graphql(impl = NodeValue)]
+struct UserQuery {
+    id: i32,
+}
+
+#[graphql_interface]
+impl Node for UserQuery {
+    fn id(&self) -> i32 {
+        self.id
+    }
+}
+
+#[derive(GraphQLObject)]
+struct BillingQuery {
+    invoice_count: i32,
+}
+
+#[derive(GraphQLMergedObject)]
+struct Query(UserQuery, BillingQuery);
+
+fn main() {}
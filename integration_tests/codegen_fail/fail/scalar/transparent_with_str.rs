@@ -0,0 +1,7 @@
+use juniper::GraphQLScalar;
+
+#[derive(GraphQLScalar)]
+#[graphql(transparent, with_str)]
+struct UserId(String);
+
+fn main() {}
@@ -0,0 +1,146 @@
+use std::{sync::Mutex, time::Duration};
+
+use juniper::{
+    graphql_object,
+    http::{BatchConfig, BatchExecution, GraphQLBatchRequest, GraphQLRequest},
+    Context, DefaultScalarValue, EmptySubscription, RootNode,
+};
+
+#[derive(Default)]
+struct Log(Mutex<Vec<i32>>);
+
+impl Context for Log {}
+
+struct Query;
+
+#[graphql_object(context = Log)]
+impl Query {
+    fn ping() -> bool {
+        true
+    }
+}
+
+struct Mutation;
+
+#[graphql_object(context = Log)]
+impl Mutation {
+    async fn push(context: &Log, value: i32, delay_ms: i32) -> i32 {
+        tokio::time::sleep(Duration::from_millis(delay_ms as u64)).await;
+        context.0.lock().unwrap().push(value);
+        value
+    }
+}
+
+type Schema = RootNode<'static, Query, Mutation, EmptySubscription<Log>>;
+
+fn schema() -> Schema {
+    Schema::new(Query, Mutation, EmptySubscription::new())
+}
+
+fn batch(size: usize) -> GraphQLBatchRequest<DefaultScalarValue> {
+    serde_json::from_value(serde_json::Value::Array(
+        (0..size)
+            .map(|i| {
+                serde_json::json!({
+                    "query": format!(
+                        "mutation {{ push(value: {}, delayMs: {}) }}",
+                        i,
+                        (size - i) * 10,
+                    ),
+                })
+            })
+            .collect(),
+    ))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn executes_batch_concurrently_by_default() {
+    let schema = schema();
+    let log = Log::default();
+
+    let req = batch(3);
+    let resp = req.execute(&schema, &log).await;
+
+    assert!(resp.is_ok());
+    assert_eq!(*log.0.lock().unwrap(), vec![2, 1, 0]);
+}
+
+#[tokio::test]
+async fn executes_batch_sequentially() {
+    let schema = schema();
+    let config = BatchConfig {
+        execution: BatchExecution::Sequential,
+        ..BatchConfig::default()
+    };
+    let log = Log::default();
+
+    let req = batch(3);
+    let resp = req.execute_with_batch_config(&schema, &log, config).await;
+
+    assert_eq!(
+        serde_json::to_value(&resp).unwrap(),
+        serde_json::json!([
+            {"data": {"push": 0}},
+            {"data": {"push": 1}},
+            {"data": {"push": 2}},
+        ]),
+    );
+    assert_eq!(*log.0.lock().unwrap(), vec![0, 1, 2]);
+}
+
+#[tokio::test]
+async fn rejects_too_large_batch() {
+    let schema = schema();
+    let config = BatchConfig {
+        max_size: Some(2),
+        ..BatchConfig::default()
+    };
+    let log = Log::default();
+
+    let req = batch(3);
+    let resp = req.execute_with_batch_config(&schema, &log, config).await;
+
+    assert_eq!(resp.status_code(), 400);
+    assert_eq!(
+        serde_json::to_value(&resp).unwrap(),
+        serde_json::json!({"errors": [{
+            "message": "batch of 3 operations exceeds the limit of 2 operations",
+            "extensions": {"code": "BATCH_TOO_LARGE", "maxBatchSize": 2},
+        }]}),
+    );
+    assert!(log.0.lock().unwrap().is_empty());
+
+    let req = batch(2);
+    let resp = req.execute_with_batch_config(&schema, &log, config).await;
+
+    assert_eq!(resp.status_code(), 200);
+}
+
+#[tokio::test]
+async fn rejects_batch_when_disabled() {
+    let schema = schema();
+    let log = Log::default();
+
+    let req = batch(1);
+    let resp = req.execute_sync_with_batch_config(&schema, &log, BatchConfig::disabled());
+
+    assert_eq!(resp.status_code(), 400);
+    assert_eq!(
+        serde_json::to_value(&resp).unwrap(),
+        serde_json::json!({"errors": [{
+            "message": "batch requests are not supported",
+            "extensions": {"code": "BATCHING_DISABLED"},
+        }]}),
+    );
+
+    let req = GraphQLBatchRequest::Single(GraphQLRequest::new("{ ping }".into(), None, None));
+    let resp = req
+        .execute_with_batch_config(&schema, &log, BatchConfig::disabled())
+        .await;
+
+    assert_eq!(
+        serde_json::to_value(&resp).unwrap(),
+        serde_json::json!({"data": {"ping": true}}),
+    );
+}
@@ -0,0 +1,143 @@
+use juniper::{
+    graphql_object, http::GraphQLRequest, CachePolicy, CacheScope, EmptyMutation,
+    EmptySubscription, FieldResult, GraphQLObject, RootNode,
+};
+
+#[derive(GraphQLObject)]
+#[graphql(cache_control(max_age = 120))]
+struct Post {
+    title: String,
+    #[graphql(cache_control(max_age = 30))]
+    votes: i32,
+}
+
+#[derive(GraphQLObject)]
+struct Author {
+    name: String,
+}
+
+#[derive(GraphQLObject)]
+struct Stats {
+    count: i32,
+    author: Author,
+}
+
+#[derive(GraphQLObject)]
+struct Viewer {
+    #[graphql(cache_control(scope = "PRIVATE"))]
+    name: String,
+}
+
+struct Query;
+
+#[graphql_object(cache_control(max_age = 60))]
+impl Query {
+    fn post() -> Post {
+        Post {
+            title: "Hello".into(),
+            votes: 3,
+        }
+    }
+
+    fn viewer() -> Viewer {
+        Viewer {
+            name: "alice".into(),
+        }
+    }
+
+    fn stats() -> Stats {
+        Stats {
+            count: 1,
+            author: Author { name: "bob".into() },
+        }
+    }
+
+    #[graphql(cache_control(max_age = 0))]
+    fn now() -> i32 {
+        42
+    }
+
+    fn fail() -> FieldResult<i32> {
+        Err("failed".into())
+    }
+}
+
+type Schema = RootNode<'static, Query, EmptyMutation, EmptySubscription>;
+
+fn schema() -> Schema {
+    Schema::new(Query, EmptyMutation::new(), EmptySubscription::new())
+}
+
+async fn policy_of(query: &str) -> Option<CachePolicy> {
+    let schema = schema();
+    let req = GraphQLRequest::new(query.into(), None, None);
+    req.execute(&schema, &()).await.cache_policy()
+}
+
+#[tokio::test]
+async fn uses_root_object_hint() {
+    assert_eq!(
+        policy_of("{ __typename }").await,
+        Some(CachePolicy {
+            max_age: 60,
+            scope: CacheScope::Public,
+        }),
+    );
+}
+
+#[tokio::test]
+async fn takes_minimum_max_age() {
+    assert_eq!(
+        policy_of("{ post { title } }").await.map(|p| p.max_age),
+        Some(60),
+    );
+    assert_eq!(
+        policy_of("{ post { title votes } }")
+            .await
+            .map(|p| p.max_age),
+        Some(30),
+    );
+}
+
+#[tokio::test]
+async fn propagates_private_scope() {
+    let policy = policy_of("{ post { title } viewer { name } }")
+        .await
+        .unwrap();
+
+    assert_eq!(policy.scope, CacheScope::Private);
+    assert_eq!(
+        policy.header_value().as_deref(),
+        Some("max-age=60, private")
+    );
+}
+
+#[tokio::test]
+async fn zero_max_age_disables_header() {
+    let policy = policy_of("{ post { title } now }").await.unwrap();
+
+    assert_eq!(policy.max_age, 0);
+    assert_eq!(policy.header_value(), None);
+}
+
+#[tokio::test]
+async fn errors_are_not_cacheable() {
+    assert_eq!(policy_of("{ post { title } fail }").await, None);
+    assert_eq!(policy_of("{ unknown }").await, None);
+}
+
+#[tokio::test]
+async fn leaf_fields_inherit_hint() {
+    assert_eq!(
+        policy_of("{ stats { count } }").await.map(|p| p.max_age),
+        Some(60),
+    );
+}
+
+#[tokio::test]
+async fn unhinted_object_fields_are_not_cacheable() {
+    let policy = policy_of("{ stats { author { name } } }").await.unwrap();
+
+    assert_eq!(policy.max_age, 0);
+    assert_eq!(policy.header_value(), None);
+}
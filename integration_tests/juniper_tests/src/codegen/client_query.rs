@@ -0,0 +1,295 @@
+//! Tests for `#[derive(GraphQLQuery)]` macro of the `juniper_client_codegen` crate.
+
+use juniper::{
+    graphql_object,
+    http::{GraphQLQuery as _, GraphQLRequest},
+    snapshot::assert_schema_snapshot,
+    EmptySubscription, GraphQLEnum, GraphQLInputObject, GraphQLObject, GraphQLScalar, GraphQLUnion,
+    RootNode, ID,
+};
+use juniper_client_codegen::GraphQLQuery;
+
+const SCHEMA_PATH: &str = "src/codegen/client_query/schema.graphql";
+
+mod server {
+    use super::*;
+
+    #[derive(Clone, Copy, GraphQLEnum, PartialEq)]
+    pub enum Episode {
+        NewHope,
+        Empire,
+        Jedi,
+    }
+
+    #[derive(Clone, GraphQLScalar)]
+    #[graphql(transparent)]
+    pub struct Email(String);
+
+    #[derive(Clone, GraphQLObject)]
+    pub struct Human {
+        id: ID,
+        name: String,
+        home_planet: Option<String>,
+        email: Email,
+    }
+
+    #[derive(Clone, GraphQLObject)]
+    pub struct Droid {
+        id: ID,
+        name: String,
+        primary_function: Option<String>,
+    }
+
+    #[derive(GraphQLUnion)]
+    pub enum SearchResult {
+        Human(Human),
+        Droid(Droid),
+    }
+
+    #[derive(GraphQLInputObject)]
+    pub struct HumanFilter {
+        name_prefix: String,
+        episode: Option<Episode>,
+    }
+
+    fn luke() -> Human {
+        Human {
+            id: "1000".to_owned().into(),
+            name: "Luke Skywalker".into(),
+            home_planet: Some("Tatooine".into()),
+            email: Email("luke@rebels.org".into()),
+        }
+    }
+
+    fn leia() -> Human {
+        Human {
+            id: "1003".to_owned().into(),
+            name: "Leia Organa".into(),
+            home_planet: None,
+            email: Email("leia@rebels.org".into()),
+        }
+    }
+
+    fn r2d2() -> Droid {
+        Droid {
+            id: "2001".to_owned().into(),
+            name: "R2-D2".into(),
+            primary_function: Some("Astromech".into()),
+        }
+    }
+
+    pub struct Query;
+
+    #[graphql_object]
+    impl Query {
+        fn hero(episode: Option<Episode>) -> Human {
+            match episode {
+                Some(Episode::Empire) => luke(),
+                _ => leia(),
+            }
+        }
+
+        fn search(text: String) -> Vec<SearchResult> {
+            vec![
+                SearchResult::Human(luke()),
+                SearchResult::Droid(r2d2()),
+                SearchResult::Human(leia()),
+            ]
+            .into_iter()
+            .filter(|r| {
+                let name = match r {
+                    SearchResult::Human(h) => &h.name,
+                    SearchResult::Droid(d) => &d.name,
+                };
+                name.to_lowercase().contains(&text.to_lowercase())
+            })
+            .collect()
+        }
+
+        fn humans(filter: HumanFilter) -> Vec<Human> {
+            vec![luke(), leia()]
+                .into_iter()
+                .filter(|h| h.name.starts_with(&filter.name_prefix))
+                .filter(|_| filter.episode != Some(Episode::Jedi))
+                .collect()
+        }
+    }
+
+    pub struct Mutation;
+
+    #[graphql_object]
+    impl Mutation {
+        fn rename(id: ID, name: String) -> Option<Human> {
+            vec![luke(), leia()]
+                .into_iter()
+                .find(|h| h.id == id)
+                .map(|h| Human { name, ..h })
+        }
+    }
+}
+
+/// Representation of the `Email` scalar in the client.
+#[derive(Clone, Debug, juniper::serde::Deserialize, PartialEq)]
+#[serde(crate = "juniper::serde")]
+pub struct Email(String);
+
+impl juniper::ToInputValue for Email {
+    fn to_input_value(&self) -> juniper::InputValue {
+        juniper::InputValue::scalar(self.0.clone())
+    }
+}
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/codegen/client_query/schema.graphql",
+    query = "query Hero($episode: Episode) { hero(episode: $episode) { id name contact: email } }"
+)]
+struct Hero;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/codegen/client_query/schema.graphql",
+    query_path = "src/codegen/client_query/search.graphql"
+)]
+struct Search;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/codegen/client_query/schema.graphql",
+    query_path = "src/codegen/client_query/humans.graphql"
+)]
+struct Humans;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/codegen/client_query/schema.graphql",
+    query_path = "src/codegen/client_query/humans.graphql",
+    operation = "Rename"
+)]
+struct RenameHuman;
+
+fn schema<'q>() -> RootNode<'q, server::Query, server::Mutation, EmptySubscription> {
+    RootNode::new(server::Query, server::Mutation, EmptySubscription::new())
+}
+
+/// Executes the given `request` against the [`schema`], deserializing the `data` of the response.
+async fn execute<Q: juniper::http::GraphQLQuery>(request: GraphQLRequest) -> Q::ResponseData {
+    let schema = schema();
+    let response = request.execute(&schema, &()).await;
+    assert!(response.is_ok(), "{:?}", serde_json::to_string(&response));
+    let mut response = serde_json::to_value(&response).unwrap();
+    serde_json::from_value(response["data"].take()).unwrap()
+}
+
+#[test]
+fn schema_matches_snapshot() {
+    assert_schema_snapshot(&schema(), SCHEMA_PATH);
+}
+
+#[test]
+fn implements_query() {
+    assert_eq!(Hero::OPERATION_NAME, Some("Hero"));
+    assert_eq!(Search::OPERATION_NAME, Some("Search"));
+    assert_eq!(Humans::OPERATION_NAME, Some("Humans"));
+    assert_eq!(RenameHuman::OPERATION_NAME, Some("Rename"));
+    assert!(Search::QUERY.contains("fragment DroidFields on Droid"));
+
+    let request = Hero::build_request(hero::Variables {
+        episode: Some(hero::Episode::NewHope),
+    });
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        serde_json::json!({
+            "query": Hero::QUERY,
+            "operationName": "Hero",
+            "variables": {"episode": "NEW_HOPE"},
+        }),
+    );
+}
+
+#[tokio::test]
+async fn deserializes_responses() {
+    let data = execute::<Hero>(Hero::build_request(hero::Variables {
+        episode: Some(hero::Episode::Empire),
+    }))
+    .await;
+    assert_eq!(
+        data,
+        hero::ResponseData {
+            hero: hero::Hero {
+                id: "1000".to_owned().into(),
+                name: "Luke Skywalker".into(),
+                contact: Email("luke@rebels.org".into()),
+            },
+        },
+    );
+
+    let data = execute::<Humans>(Humans::build_request(humans::Variables {
+        filter: humans::HumanFilter {
+            name_prefix: "L".into(),
+            episode: Some(humans::Episode::Empire),
+        },
+    }))
+    .await;
+    assert_eq!(
+        data.humans,
+        vec![
+            humans::Humans {
+                name: "Luke Skywalker".into(),
+                home_planet: Some("Tatooine".into()),
+            },
+            humans::Humans {
+                name: "Leia Organa".into(),
+                home_planet: None,
+            },
+        ],
+    );
+
+    let data = execute::<RenameHuman>(RenameHuman::build_request(rename_human::Variables {
+        id: "1003".to_owned().into(),
+        name: "Leia Organa Solo".into(),
+    }))
+    .await;
+    assert_eq!(
+        data.rename,
+        Some(rename_human::Rename {
+            id: "1003".to_owned().into(),
+            name: "Leia Organa Solo".into(),
+        }),
+    );
+}
+
+#[tokio::test]
+async fn makes_fields_of_narrower_fragments_optional() {
+    let data = execute::<Search>(Search::build_request(search::Variables {
+        text: "R".into(),
+        with_ids: true,
+    }))
+    .await;
+    assert_eq!(
+        data.search,
+        vec![
+            search::Search {
+                typename: "Human".into(),
+                name: Some("Luke Skywalker".into()),
+                home_planet: Some("Tatooine".into()),
+                primary_function: None,
+                id: None,
+            },
+            search::Search {
+                typename: "Droid".into(),
+                name: Some("R2-D2".into()),
+                home_planet: None,
+                primary_function: Some("Astromech".into()),
+                id: Some("2001".to_owned().into()),
+            },
+            search::Search {
+                typename: "Human".into(),
+                name: Some("Leia Organa".into()),
+                home_planet: None,
+                primary_function: None,
+                id: None,
+            },
+        ],
+    );
+}
@@ -1,7 +1,10 @@
+use std::marker::PhantomData;
+
 use fnv::FnvHashMap;
 use juniper::{
-    DefaultScalarValue, FromInputValue, GraphQLEnum, GraphQLType, InputValue, Registry,
-    ToInputValue,
+    execute, graphql_object, graphql_value, meta::MetaType, DefaultScalarValue, EmptyMutation,
+    EmptySubscription, FromInputValue, GraphQLEnum, GraphQLType, InputValue, Registry, RootNode,
+    ToInputValue, Variables,
 };
 
 pub struct CustomContext {}
@@ -23,6 +26,21 @@ enum NoRenameEnum {
     AnotherVariant,
 }
 
+#[derive(GraphQLEnum, Debug, PartialEq)]
+#[graphql(rename_all = "camelCase")]
+enum CamelCaseEnum {
+    OneVariant,
+    AnotherVariant,
+}
+
+#[derive(GraphQLEnum, Debug, PartialEq)]
+#[graphql(rename_all = "PascalCase")]
+#[allow(non_camel_case_types)]
+enum PascalCaseEnum {
+    one_variant,
+    another_variant,
+}
+
 /// Enum doc.
 #[derive(GraphQLEnum)]
 enum DocEnum {
@@ -56,6 +74,28 @@ enum ContextEnum {
     A,
 }
 
+#[derive(GraphQLEnum, Debug, PartialEq)]
+#[graphql(rename_all = "none")]
+enum DataEnum {
+    Plain,
+    Tuple(i32, String),
+    Named {
+        id: i32,
+    },
+    #[graphql(default = "DataEnum::Custom(7)")]
+    Custom(i32),
+    #[graphql(skip)]
+    Skipped(String),
+}
+
+#[derive(GraphQLEnum, Debug, PartialEq)]
+enum GenericEnum<T> {
+    First,
+    Second,
+    #[graphql(skip)]
+    _Marker(PhantomData<T>),
+}
+
 #[test]
 fn test_derived_enum() {
     // Ensure that rename works.
@@ -134,3 +174,134 @@ fn test_doc_custom_context() {
     test_context(ContextEnum::A);
     // test_context(OverrideDocEnum::Foo); does not work
 }
+
+fn enum_value_names<T: GraphQLType<DefaultScalarValue, TypeInfo = ()>>() -> Vec<String> {
+    let mut registry: Registry = Registry::new(FnvHashMap::default());
+    match T::meta(&(), &mut registry) {
+        MetaType::Enum(meta) => meta.values.into_iter().map(|v| v.name).collect(),
+        _ => panic!("not an enum"),
+    }
+}
+
+#[test]
+fn test_rename_all_variants() {
+    assert_eq!(
+        enum_value_names::<CamelCaseEnum>(),
+        ["oneVariant", "anotherVariant"],
+    );
+    assert_eq!(
+        <_ as ToInputValue>::to_input_value(&CamelCaseEnum::AnotherVariant),
+        InputValue::scalar("anotherVariant"),
+    );
+    assert_eq!(
+        FromInputValue::<DefaultScalarValue>::from_input_value(&InputValue::enum_value(
+            "oneVariant"
+        )),
+        Some(CamelCaseEnum::OneVariant),
+    );
+
+    assert_eq!(
+        enum_value_names::<PascalCaseEnum>(),
+        ["OneVariant", "AnotherVariant"],
+    );
+    assert_eq!(
+        <_ as ToInputValue>::to_input_value(&PascalCaseEnum::another_variant),
+        InputValue::scalar("AnotherVariant"),
+    );
+    assert_eq!(
+        FromInputValue::<DefaultScalarValue>::from_input_value(&InputValue::enum_value(
+            "OneVariant"
+        )),
+        Some(PascalCaseEnum::one_variant),
+    );
+}
+
+#[test]
+fn test_data_variants() {
+    assert_eq!(
+        enum_value_names::<DataEnum>(),
+        ["Plain", "Tuple", "Named", "Custom"],
+    );
+
+    assert_eq!(
+        <_ as ToInputValue>::to_input_value(&DataEnum::Tuple(1, "a".into())),
+        InputValue::scalar("Tuple"),
+    );
+    assert_eq!(
+        <_ as ToInputValue>::to_input_value(&DataEnum::Named { id: 1 }),
+        InputValue::scalar("Named"),
+    );
+    assert_eq!(
+        <_ as ToInputValue>::to_input_value(&DataEnum::Skipped("a".into())),
+        InputValue::<DefaultScalarValue>::null(),
+    );
+
+    assert_eq!(
+        FromInputValue::<DefaultScalarValue>::from_input_value(&InputValue::enum_value("Tuple")),
+        Some(DataEnum::Tuple(0, String::new())),
+    );
+    assert_eq!(
+        FromInputValue::<DefaultScalarValue>::from_input_value(&InputValue::enum_value("Named")),
+        Some(DataEnum::Named { id: 0 }),
+    );
+    assert_eq!(
+        FromInputValue::<DefaultScalarValue>::from_input_value(&InputValue::enum_value("Custom")),
+        Some(DataEnum::Custom(7)),
+    );
+    assert_eq!(
+        <DataEnum as FromInputValue>::from_input_value(&InputValue::enum_value("Skipped")),
+        None,
+    );
+}
+
+#[test]
+fn test_generic_enum() {
+    assert_eq!(
+        <GenericEnum<String> as GraphQLType<DefaultScalarValue>>::name(&()),
+        Some("GenericEnum"),
+    );
+    assert_eq!(enum_value_names::<GenericEnum<i32>>(), ["FIRST", "SECOND"]);
+    assert_eq!(
+        <GenericEnum<()> as FromInputValue>::from_input_value(&InputValue::enum_value("SECOND")),
+        Some(GenericEnum::Second),
+    );
+}
+
+struct Query;
+
+#[graphql_object]
+impl Query {
+    fn data(skipped: bool) -> DataEnum {
+        if skipped {
+            DataEnum::Skipped("hidden".into())
+        } else {
+            DataEnum::Named { id: 1 }
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_resolving_skipped_variant_fails() {
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+
+    let (res, errs) = execute(
+        "{ a: data(skipped: false) b: data(skipped: true) }",
+        None,
+        &schema,
+        &Variables::new(),
+        &(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(res, graphql_value!(None));
+    assert_eq!(errs.len(), 1);
+    assert_eq!(
+        errs[0].error().message(),
+        "Variant of enum DataEnum not present in the schema",
+    );
+}
@@ -0,0 +1,229 @@
+use std::{fmt, str::FromStr};
+
+use crate::custom_scalar::MyScalarValue;
+use juniper::{
+    execute, graphql_object, graphql_value, DefaultScalarValue, EmptyMutation, EmptySubscription,
+    FromInputValue, GraphQLError, GraphQLScalar, GraphQLType, InputValue, ParseError, RootNode,
+    ToInputValue, Value, Variables,
+};
+
+/// Identifier of a user.
+#[derive(Debug, GraphQLScalar, PartialEq)]
+struct UserId(String);
+
+#[derive(Debug, GraphQLScalar, PartialEq)]
+#[graphql(name = "Cents", description = "Amount of money in cents.")]
+struct Money {
+    cents: i32,
+}
+
+#[derive(Debug, GraphQLScalar, PartialEq)]
+#[graphql(transparent, scalar = MyScalarValue)]
+struct LargeId(i64);
+
+#[derive(Debug, GraphQLScalar, PartialEq)]
+#[graphql(with_str)]
+struct Semver {
+    major: u32,
+    minor: u32,
+}
+
+impl fmt::Display for Semver {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+impl FromStr for Semver {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (major, minor) = s.split_once('.').ok_or("expected `major.minor`")?;
+        Ok(Self {
+            major: major.parse().map_err(|_| "invalid major version")?,
+            minor: minor.parse().map_err(|_| "invalid minor version")?,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, GraphQLScalar, PartialEq)]
+#[graphql(with_str, name = "Level")]
+enum LogLevel {
+    Info,
+    Warn,
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Info => "info",
+            Self::Warn => "warn",
+        })
+    }
+}
+
+impl FromStr for LogLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "info" => Ok(Self::Info),
+            "warn" => Ok(Self::Warn),
+            _ => Err(format!("unknown log level `{}`", s)),
+        }
+    }
+}
+
+struct Query;
+
+#[graphql_object]
+impl Query {
+    fn user_id(id: UserId) -> UserId {
+        id
+    }
+
+    fn price() -> Money {
+        Money { cents: 250 }
+    }
+
+    fn bump_minor(version: Semver) -> Semver {
+        Semver {
+            minor: version.minor + 1,
+            ..version
+        }
+    }
+
+    fn level(level: Option<LogLevel>) -> LogLevel {
+        level.unwrap_or(LogLevel::Warn)
+    }
+}
+
+fn schema<'q>() -> RootNode<'q, Query, EmptyMutation, EmptySubscription> {
+    RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+}
+
+#[test]
+fn newtype_delegates_to_inner_type() {
+    assert_eq!(
+        <UserId as GraphQLType<DefaultScalarValue>>::name(&()),
+        Some("UserId"),
+    );
+
+    let input: InputValue = InputValue::scalar("u1");
+    let id: UserId = FromInputValue::from_input_value(&input).unwrap();
+    assert_eq!(id, UserId("u1".into()));
+    assert_eq!(
+        ToInputValue::<DefaultScalarValue>::to_input_value(&id),
+        input
+    );
+
+    let input: InputValue<MyScalarValue> = InputValue::scalar(i64::MAX);
+    let id: LargeId = FromInputValue::from_input_value(&input).unwrap();
+    assert_eq!(id, LargeId(i64::MAX));
+    assert_eq!(ToInputValue::<MyScalarValue>::to_input_value(&id), input);
+}
+
+#[tokio::test]
+async fn exposes_names_and_descriptions() {
+    let doc = r#"{
+        userId: __type(name: "UserId") { kind description }
+        cents: __type(name: "Cents") { kind description }
+        semver: __type(name: "Semver") { kind description }
+        level: __type(name: "Level") { kind }
+    }"#;
+
+    assert_eq!(
+        execute(doc, None, &schema(), &Variables::new(), &()).await,
+        Ok((
+            graphql_value!({
+                "userId": {"kind": "SCALAR", "description": "Identifier of a user."},
+                "cents": {"kind": "SCALAR", "description": "Amount of money in cents."},
+                "semver": {"kind": "SCALAR", "description": None},
+                "level": {"kind": "SCALAR"},
+            }),
+            vec![],
+        )),
+    );
+}
+
+#[tokio::test]
+async fn resolves_and_parses_scalars() {
+    let doc = r#"query($level: Level) {
+        userId(id: "u1")
+        price
+        bumpMinor(version: "1.2")
+        level(level: $level)
+    }"#;
+
+    let vars = vec![("level".to_owned(), InputValue::scalar("info"))]
+        .into_iter()
+        .collect();
+
+    assert_eq!(
+        execute(doc, None, &schema(), &vars, &()).await,
+        Ok((
+            graphql_value!({
+                "userId": "u1",
+                "price": 250,
+                "bumpMinor": "1.3",
+                "level": "info",
+            }),
+            vec![],
+        )),
+    );
+}
+
+#[tokio::test]
+async fn rejects_unparsable_literal() {
+    let doc = r#"{ bumpMinor(version: "1.x") }"#;
+
+    let schema = schema();
+    let err = execute(doc, None, &schema, &Variables::new(), &())
+        .await
+        .unwrap_err();
+
+    match err {
+        GraphQLError::ParseError(e) => assert_eq!(
+            e.item,
+            ParseError::InvalidScalarError("invalid minor version".into()),
+        ),
+        e => panic!("unexpected error: {}", e),
+    }
+}
+
+#[tokio::test]
+async fn rejects_unparsable_variable() {
+    let doc = r#"query($level: Level) { level(level: $level) }"#;
+
+    let vars = vec![("level".to_owned(), InputValue::scalar("debug"))]
+        .into_iter()
+        .collect();
+
+    let schema = schema();
+    let err = execute(doc, None, &schema, &vars, &()).await.unwrap_err();
+
+    assert!(
+        err.to_string()
+            .contains(r#"Expected "Level": unknown log level `debug`"#),
+        "unexpected error: {}",
+        err,
+    );
+}
+
+#[test]
+fn serializes_via_display() {
+    let version = Semver { major: 2, minor: 0 };
+    assert_eq!(
+        ToInputValue::<DefaultScalarValue>::to_input_value(&version),
+        InputValue::scalar("2.0"),
+    );
+
+    let input: InputValue = InputValue::scalar(1);
+    assert_eq!(<Semver as FromInputValue>::from_input_value(&input), None);
+
+    let value: Value = Value::scalar("warn");
+    assert_eq!(
+        value.as_string_value(),
+        Some(LogLevel::Warn.to_string().as_str()),
+    );
+}
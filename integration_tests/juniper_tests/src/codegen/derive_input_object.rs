@@ -83,6 +83,7 @@ impl<'a> GraphQLType<DefaultScalarValue> for &'a Fake {
                 name: "fake".to_string(),
                 description: None,
                 deprecation_status: juniper::meta::DeprecationStatus::Current,
+                visibility: None,
             }],
         );
         meta.into_meta()
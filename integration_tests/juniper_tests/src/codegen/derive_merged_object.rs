@@ -0,0 +1,230 @@
+use juniper::{
+    execute, execute_sync, graphql_object, graphql_value, EmptyMutation, EmptySubscription,
+    GraphQLMergedObject, GraphQLObject, RootNode, Variables,
+};
+
+struct Context {
+    user_count: i32,
+}
+
+impl juniper::Context for Context {}
+
+struct UserQuery;
+
+#[graphql_object(context = Context)]
+impl UserQuery {
+    fn user_count(context: &Context) -> i32 {
+        context.user_count
+    }
+
+    async fn user_name(id: i32) -> String {
+        format!("user-{}", id)
+    }
+}
+
+#[derive(GraphQLObject)]
+#[graphql(context = Context)]
+struct BillingQuery {
+    invoice_count: i32,
+}
+
+#[derive(GraphQLObject)]
+#[graphql(complex, context = Context)]
+struct SupportQuery {
+    open_tickets: i32,
+}
+
+#[graphql_object(complex, context = Context)]
+impl SupportQuery {
+    fn closed_tickets(&self) -> i32 {
+        self.open_tickets * 10
+    }
+}
+
+#[derive(GraphQLMergedObject)]
+#[graphql(context = Context)]
+struct BackOfficeQuery(BillingQuery, SupportQuery);
+
+/// The root query.
+#[derive(GraphQLMergedObject)]
+#[graphql(name = "Query", context = Context)]
+struct MergedQuery {
+    users: UserQuery,
+    back_office: BackOfficeQuery,
+}
+
+fn schema<'q>() -> RootNode<'q, MergedQuery, EmptyMutation<Context>, EmptySubscription<Context>> {
+    RootNode::new(
+        MergedQuery {
+            users: UserQuery,
+            back_office: BackOfficeQuery(
+                BillingQuery { invoice_count: 7 },
+                SupportQuery { open_tickets: 3 },
+            ),
+        },
+        EmptyMutation::<Context>::new(),
+        EmptySubscription::<Context>::new(),
+    )
+}
+
+#[tokio::test]
+async fn merges_fields_in_order() {
+    let doc = r#"{
+        __type(name: "Query") {
+            description
+            fields { name args { name } }
+        }
+    }"#;
+
+    let ctx = Context { user_count: 0 };
+
+    assert_eq!(
+        execute(doc, None, &schema(), &Variables::new(), &ctx).await,
+        Ok((
+            graphql_value!({"__type": {
+                "description": "The root query.",
+                "fields": [
+                    {"name": "userCount", "args": []},
+                    {"name": "userName", "args": [{"name": "id"}]},
+                    {"name": "invoiceCount", "args": []},
+                    {"name": "openTickets", "args": []},
+                    {"name": "closedTickets", "args": []},
+                ],
+            }}),
+            vec![],
+        )),
+    );
+}
+
+#[tokio::test]
+async fn does_not_expose_merged_types() {
+    let doc = r#"{
+        userQuery: __type(name: "UserQuery") { name }
+        backOffice: __type(name: "BackOfficeQuery") { name }
+    }"#;
+
+    let ctx = Context { user_count: 0 };
+
+    assert_eq!(
+        execute(doc, None, &schema(), &Variables::new(), &ctx).await,
+        Ok((
+            graphql_value!({"userQuery": None, "backOffice": None}),
+            vec![],
+        )),
+    );
+}
+
+#[tokio::test]
+async fn resolves_fields_of_merged_objects_async() {
+    let doc = r#"{
+        userCount
+        userName(id: 5)
+        invoiceCount
+        openTickets
+        closedTickets
+        __typename
+    }"#;
+
+    let ctx = Context { user_count: 42 };
+
+    assert_eq!(
+        execute(doc, None, &schema(), &Variables::new(), &ctx).await,
+        Ok((
+            graphql_value!({
+                "userCount": 42,
+                "userName": "user-5",
+                "invoiceCount": 7,
+                "openTickets": 3,
+                "closedTickets": 30,
+                "__typename": "Query",
+            }),
+            vec![],
+        )),
+    );
+}
+
+#[test]
+fn resolves_fields_of_merged_objects_sync() {
+    let doc = r#"{
+        userCount
+        invoiceCount
+        closedTickets
+    }"#;
+
+    let ctx = Context { user_count: 1 };
+
+    assert_eq!(
+        execute_sync(doc, None, &schema(), &Variables::new(), &ctx),
+        Ok((
+            graphql_value!({"userCount": 1, "invoiceCount": 7, "closedTickets": 30}),
+            vec![],
+        )),
+    );
+}
+
+mod carried_over {
+    use juniper::{http::GraphQLRequest, CachePolicy, CacheScope};
+
+    use super::*;
+
+    /// Users of the service.
+    #[derive(GraphQLObject)]
+    #[graphql(cache_control(max_age = 60))]
+    struct UserQuery {
+        user_count: i32,
+    }
+
+    /// Invoices of the users.
+    #[derive(GraphQLObject)]
+    #[graphql(cache_control(max_age = 120, scope = "PRIVATE"))]
+    struct BillingQuery {
+        invoice_count: i32,
+    }
+
+    #[derive(GraphQLMergedObject)]
+    #[graphql(name = "Query")]
+    struct MergedQuery(UserQuery, BillingQuery);
+
+    fn schema<'q>() -> RootNode<'q, MergedQuery, EmptyMutation, EmptySubscription> {
+        RootNode::new(
+            MergedQuery(
+                UserQuery { user_count: 1 },
+                BillingQuery { invoice_count: 2 },
+            ),
+            EmptyMutation::new(),
+            EmptySubscription::new(),
+        )
+    }
+
+    #[tokio::test]
+    async fn joins_descriptions() {
+        let doc = r#"{
+            __type(name: "Query") {
+                description
+            }
+        }"#;
+
+        assert_eq!(
+            execute(doc, None, &schema(), &Variables::new(), &()).await,
+            Ok((
+                graphql_value!({"__type": {
+                    "description": "Users of the service.\n\nInvoices of the users.",
+                }}),
+                vec![],
+            )),
+        );
+    }
+
+    #[tokio::test]
+    async fn restricts_cache_control_hints() {
+        let req = GraphQLRequest::new("{ userCount }".into(), None, None);
+
+        assert_eq!(
+            req.execute(&schema(), &()).await.cache_policy(),
+            Some(CachePolicy {
+                max_age: 60,
+                scope: CacheScope::Private,
+            }),
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use fnv::FnvHashMap;
 use juniper::{
-    execute, graphql_object, DefaultScalarValue, EmptyMutation, EmptySubscription, GraphQLObject,
-    GraphQLType, Object, Registry, RootNode, Value, Variables,
+    execute, graphql_object, graphql_value, DefaultScalarValue, EmptyMutation, EmptySubscription,
+    GraphQLObject, GraphQLType, Object, Registry, RootNode, Value, Variables,
 };
 
 #[derive(GraphQLObject, Debug, PartialEq)]
@@ -67,6 +67,20 @@ struct NoRenameObj {
     another_field: i32,
 }
 
+#[derive(GraphQLObject, Debug, PartialEq)]
+#[allow(non_snake_case)]
+struct AcronymObj {
+    ID: i32,
+    URL_path: String,
+}
+
+#[derive(GraphQLObject, Debug, PartialEq)]
+#[graphql(rename_all = "camelCase")]
+#[allow(non_snake_case)]
+struct CamelCaseObj {
+    Name: bool,
+}
+
 struct Context;
 impl juniper::Context for Context {}
 
@@ -127,6 +141,17 @@ impl Query {
             another_field: 146,
         }
     }
+
+    fn acronym_obj() -> AcronymObj {
+        AcronymObj {
+            ID: 1,
+            URL_path: "/".into(),
+        }
+    }
+
+    fn camel_case_obj() -> CamelCaseObj {
+        CamelCaseObj { Name: true }
+    }
 }
 
 struct NoRenameQuery;
@@ -424,6 +449,37 @@ async fn test_no_rename_obj() {
     );
 }
 
+#[tokio::test]
+async fn test_default_rename_keeps_first_char() {
+    let doc = r#"
+        {
+            acronymObj {
+                ID
+                URLPath
+            }
+            camelCaseObj {
+                name
+            }
+        }"#;
+
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+
+    assert_eq!(
+        execute(doc, None, &schema, &Variables::new(), &()).await,
+        Ok((
+            graphql_value!({
+                "acronymObj": {"ID": 1, "URLPath": "/"},
+                "camelCaseObj": {"name": true},
+            }),
+            vec![],
+        )),
+    );
+}
+
 async fn check_descriptions(
     object_name: &str,
     object_description: &Value,
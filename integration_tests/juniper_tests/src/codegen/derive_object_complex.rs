@@ -0,0 +1,224 @@
+use juniper::{
+    execute, execute_sync, graphql_object, graphql_value, Arguments, EmptyMutation,
+    EmptySubscription, FieldError, FieldResult, GraphQLObject, Guard, RootNode, ScalarValue,
+    Variables,
+};
+
+struct Context {
+    is_admin: bool,
+}
+
+impl juniper::Context for Context {}
+
+struct AdminOnly;
+
+impl<S: ScalarValue> Guard<Context, S> for AdminOnly {
+    fn check(&self, ctx: &Context, _: &Arguments<S>) -> FieldResult<(), S> {
+        if ctx.is_admin {
+            Ok(())
+        } else {
+            Err(FieldError::from("Forbidden"))
+        }
+    }
+}
+
+/// A user.
+#[derive(GraphQLObject)]
+#[graphql(complex, context = Context)]
+struct User {
+    first_name: String,
+    last_name: String,
+    #[graphql(skip)]
+    password: String,
+}
+
+#[graphql_object(complex, context = Context)]
+impl User {
+    /// Full name of the user.
+    #[graphql(arguments(separator(default = " ".to_owned())))]
+    fn full_name(&self, separator: String) -> String {
+        format!("{}{}{}", self.first_name, separator, self.last_name)
+    }
+
+    async fn initials(&self) -> String {
+        self.first_name
+            .chars()
+            .take(1)
+            .chain(self.last_name.chars().take(1))
+            .collect()
+    }
+
+    #[graphql(guard = "AdminOnly")]
+    fn password_length(&self) -> i32 {
+        self.password.len() as i32
+    }
+}
+
+#[derive(GraphQLObject)]
+#[graphql(complex)]
+struct OnlyComputed {
+    #[graphql(skip)]
+    value: i32,
+}
+
+#[graphql_object(complex)]
+impl OnlyComputed {
+    fn doubled(&self) -> i32 {
+        self.value * 2
+    }
+}
+
+#[derive(GraphQLObject)]
+#[graphql(complex)]
+struct OnlyDerived {
+    value: i32,
+}
+
+#[graphql_object(complex)]
+impl OnlyDerived {}
+
+struct Query;
+
+#[graphql_object(context = Context)]
+impl Query {
+    fn user() -> User {
+        User {
+            first_name: "Ada".into(),
+            last_name: "Lovelace".into(),
+            password: "hunter2".into(),
+        }
+    }
+
+    fn only_computed() -> OnlyComputed {
+        OnlyComputed { value: 21 }
+    }
+
+    fn only_derived() -> OnlyDerived {
+        OnlyDerived { value: 42 }
+    }
+}
+
+fn schema<'q>() -> RootNode<'q, Query, EmptyMutation<Context>, EmptySubscription<Context>> {
+    RootNode::new(
+        Query,
+        EmptyMutation::<Context>::new(),
+        EmptySubscription::<Context>::new(),
+    )
+}
+
+#[tokio::test]
+async fn lists_derived_fields_before_resolver_fields() {
+    let doc = r#"{
+        __type(name: "User") {
+            description
+            fields { name description args { name defaultValue } }
+        }
+    }"#;
+
+    let ctx = Context { is_admin: false };
+
+    assert_eq!(
+        execute(doc, None, &schema(), &Variables::new(), &ctx).await,
+        Ok((
+            graphql_value!({"__type": {
+                "description": "A user.",
+                "fields": [
+                    {"name": "firstName", "description": None, "args": []},
+                    {"name": "lastName", "description": None, "args": []},
+                    {
+                        "name": "fullName",
+                        "description": "Full name of the user.",
+                        "args": [{"name": "separator", "defaultValue": "\" \""}],
+                    },
+                    {"name": "initials", "description": None, "args": []},
+                    {"name": "passwordLength", "description": None, "args": []},
+                ],
+            }}),
+            vec![],
+        )),
+    );
+}
+
+#[tokio::test]
+async fn resolves_derived_and_resolver_fields_async() {
+    let doc = r#"{
+        user {
+            firstName
+            fullName(separator: "_")
+            initials
+            passwordLength
+        }
+        onlyComputed { doubled }
+    }"#;
+
+    let ctx = Context { is_admin: true };
+
+    assert_eq!(
+        execute(doc, None, &schema(), &Variables::new(), &ctx).await,
+        Ok((
+            graphql_value!({
+                "user": {
+                    "firstName": "Ada",
+                    "fullName": "Ada_Lovelace",
+                    "initials": "AL",
+                    "passwordLength": 7,
+                },
+                "onlyComputed": {"doubled": 42},
+            }),
+            vec![],
+        )),
+    );
+}
+
+#[test]
+fn resolves_derived_and_resolver_fields_sync() {
+    let doc = r#"{
+        user { lastName fullName }
+    }"#;
+
+    let ctx = Context { is_admin: true };
+
+    assert_eq!(
+        execute_sync(doc, None, &schema(), &Variables::new(), &ctx),
+        Ok((
+            graphql_value!({"user": {"lastName": "Lovelace", "fullName": "Ada Lovelace"}}),
+            vec![],
+        )),
+    );
+}
+
+#[tokio::test]
+async fn checks_guards_of_resolver_fields() {
+    let doc = r#"{
+        user { passwordLength }
+    }"#;
+
+    let ctx = Context { is_admin: false };
+
+    let (res, errs) = execute(doc, None, &schema(), &Variables::new(), &ctx)
+        .await
+        .unwrap();
+
+    assert_eq!(res, graphql_value!(None));
+    assert_eq!(errs.len(), 1);
+    assert_eq!(errs[0].error().message(), "Forbidden");
+    assert_eq!(errs[0].path(), ["user", "passwordLength"]);
+}
+
+#[tokio::test]
+async fn resolves_derived_fields_with_empty_complex_impl() {
+    let doc = r#"{
+        onlyDerived { value }
+    }"#;
+
+    let ctx = Context { is_admin: false };
+
+    assert_eq!(
+        execute(doc, None, &schema(), &Variables::new(), &ctx).await,
+        Ok((graphql_value!({"onlyDerived": {"value": 42}}), vec![])),
+    );
+    assert_eq!(
+        execute_sync(doc, None, &schema(), &Variables::new(), &ctx),
+        Ok((graphql_value!({"onlyDerived": {"value": 42}}), vec![])),
+    );
+}
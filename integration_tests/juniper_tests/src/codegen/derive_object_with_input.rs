@@ -0,0 +1,185 @@
+//! Tests for `#[derive(GraphQLObject)]` with the `input_object` attribute.
+
+use juniper::{
+    execute, graphql_object, graphql_value, marker::IsOutputType, DefaultScalarValue,
+    EmptyMutation, EmptySubscription, FromInputValue, GraphQLObject, GraphQLType,
+    GraphQLValueAsync, InputValue, RootNode, Variables,
+};
+
+/// Postal address.
+#[derive(Clone, Debug, GraphQLObject, PartialEq)]
+#[graphql(input_object)]
+pub struct Address {
+    /// Street and house number.
+    street: String,
+    #[graphql(name = "zip", default = "\"00000\".to_owned()")]
+    postal_code: String,
+}
+
+#[derive(GraphQLObject)]
+#[graphql(input_object = "NewCustomer", rename_all = "snake_case")]
+struct Customer {
+    #[graphql(skip_input)]
+    id: i32,
+    full_name: String,
+    #[graphql(input_type = AddressInput)]
+    billing_address: Address,
+    #[graphql(skip_output)]
+    password: String,
+}
+
+/// Type parameters used only by skipped input fields are left out of the input object.
+#[derive(GraphQLObject)]
+#[graphql(input_object, scalar = DefaultScalarValue)]
+struct Tagged<T>
+where
+    T: GraphQLType<DefaultScalarValue, Context = (), TypeInfo = ()>
+        + GraphQLValueAsync<DefaultScalarValue>
+        + IsOutputType<DefaultScalarValue>
+        + Sync,
+{
+    label: String,
+    #[graphql(skip_input)]
+    value: T,
+}
+
+struct Query;
+
+#[graphql_object(scalar = DefaultScalarValue)]
+impl Query {
+    fn echo_address(address: AddressInput) -> Address {
+        address.into()
+    }
+
+    fn register(customer: CustomerInput) -> Customer {
+        let CustomerInput {
+            full_name,
+            billing_address,
+            password,
+        } = customer;
+        Customer {
+            id: password.len() as i32,
+            full_name,
+            billing_address: billing_address.into(),
+            password,
+        }
+    }
+
+    fn tag(tagged: TaggedInput) -> Tagged<i32> {
+        Tagged {
+            value: tagged.label.len() as i32,
+            label: tagged.label,
+        }
+    }
+}
+
+fn schema<'q>() -> RootNode<'q, Query, EmptyMutation, EmptySubscription> {
+    RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+}
+
+#[test]
+fn derives_input_object_type() {
+    assert_eq!(
+        <AddressInput as GraphQLType<DefaultScalarValue>>::name(&()),
+        Some("AddressInput"),
+    );
+    assert_eq!(
+        <CustomerInput as GraphQLType<DefaultScalarValue>>::name(&()),
+        Some("NewCustomer"),
+    );
+
+    let input: InputValue = InputValue::object(
+        vec![("street", InputValue::scalar("Main St 1"))]
+            .into_iter()
+            .collect(),
+    );
+    let address: Address = AddressInput::from_input_value(&input).unwrap().into();
+    assert_eq!(
+        address,
+        Address {
+            street: "Main St 1".into(),
+            postal_code: "00000".into(),
+        },
+    );
+}
+
+#[tokio::test]
+async fn exposes_fields_on_each_side() {
+    let doc = r#"{
+        address: __type(name: "AddressInput") {
+            kind
+            description
+            inputFields { name description defaultValue }
+        }
+        customer: __type(name: "Customer") { fields { name } }
+        newCustomer: __type(name: "NewCustomer") { inputFields { name type { ofType { name } } } }
+    }"#;
+
+    assert_eq!(
+        execute(doc, None, &schema(), &Variables::new(), &()).await,
+        Ok((
+            graphql_value!({
+                "address": {
+                    "kind": "INPUT_OBJECT",
+                    "description": "Postal address.",
+                    "inputFields": [
+                        {
+                            "name": "street",
+                            "description": "Street and house number.",
+                            "defaultValue": None,
+                        },
+                        {"name": "zip", "description": None, "defaultValue": "\"00000\""},
+                    ],
+                },
+                "customer": {
+                    "fields": [
+                        {"name": "id"},
+                        {"name": "full_name"},
+                        {"name": "billing_address"},
+                    ],
+                },
+                "newCustomer": {
+                    "inputFields": [
+                        {"name": "full_name", "type": {"ofType": {"name": "String"}}},
+                        {"name": "billing_address", "type": {"ofType": {"name": "AddressInput"}}},
+                        {"name": "password", "type": {"ofType": {"name": "String"}}},
+                    ],
+                },
+            }),
+            vec![],
+        )),
+    );
+}
+
+#[tokio::test]
+async fn resolves_with_input_objects() {
+    let doc = r#"{
+        echoAddress(address: {street: "Main St 1", zip: "12345"}) { street zip }
+        register(customer: {
+            full_name: "Jane Doe",
+            billing_address: {street: "Side St 2"},
+            password: "secret",
+        }) {
+            id
+            full_name
+            billing_address { street zip }
+        }
+        tag(tagged: {label: "four"}) { label value }
+    }"#;
+
+    assert_eq!(
+        execute(doc, None, &schema(), &Variables::new(), &()).await,
+        Ok((
+            graphql_value!({
+                "echoAddress": {"street": "Main St 1", "zip": "12345"},
+                "register": {
+                    "id": 6,
+                    "full_name": "Jane Doe",
+                    "billing_address": {"street": "Side St 2", "zip": "00000"},
+                },
+                "tag": {"label": "four", "value": 4},
+            }),
+            vec![],
+        )),
+    );
+}
@@ -0,0 +1,293 @@
+//! Tests for GraphQL type names composed from type parameters, like `#[graphql(name = "{T}Page")]`.
+
+use juniper::{
+    execute, graphql_object, graphql_value,
+    marker::{IsInputType, IsOutputType},
+    DefaultScalarValue, EmptyMutation, EmptySubscription, FromInputValue, GraphQLInputObject,
+    GraphQLObject, GraphQLType, GraphQLValueAsync, RootNode, ToInputValue, Variables,
+};
+
+/// Output types which generic objects of this module are instantiated with.
+trait Node:
+    GraphQLType<DefaultScalarValue, Context = (), TypeInfo = ()>
+    + GraphQLValueAsync<DefaultScalarValue>
+    + IsOutputType<DefaultScalarValue>
+    + Sync
+{
+}
+
+impl<T> Node for T where
+    T: GraphQLType<DefaultScalarValue, Context = (), TypeInfo = ()>
+        + GraphQLValueAsync<DefaultScalarValue>
+        + IsOutputType<DefaultScalarValue>
+        + Sync
+{
+}
+
+/// Input types which generic input objects of this module are instantiated with.
+trait Input:
+    GraphQLType<DefaultScalarValue, TypeInfo = ()>
+    + FromInputValue
+    + ToInputValue
+    + IsInputType<DefaultScalarValue>
+{
+}
+
+impl<T> Input for T where
+    T: GraphQLType<DefaultScalarValue, TypeInfo = ()>
+        + FromInputValue
+        + ToInputValue
+        + IsInputType<DefaultScalarValue>
+{
+}
+
+#[derive(GraphQLObject)]
+struct User {
+    name: String,
+}
+
+#[derive(GraphQLObject)]
+struct Order {
+    total: i32,
+}
+
+#[derive(GraphQLObject)]
+#[graphql(name = "{T}Page", scalar = DefaultScalarValue)]
+struct Page<T: Node> {
+    items: Vec<T>,
+    total_count: i32,
+}
+
+struct Edge<N> {
+    node: N,
+}
+
+#[graphql_object(name = "{N}Edge", scalar = DefaultScalarValue)]
+impl<N: Node> Edge<N> {
+    fn node(&self) -> &N {
+        &self.node
+    }
+}
+
+#[derive(GraphQLInputObject)]
+#[graphql(name = "{T}Range", scalar = DefaultScalarValue)]
+struct Range<T: Input> {
+    min: T,
+    max: T,
+}
+
+struct Query;
+
+#[graphql_object(scalar = DefaultScalarValue)]
+impl Query {
+    fn users(first: Range<i32>) -> Page<User> {
+        let items: Vec<_> = vec!["Alice", "Bob", "Carol"]
+            .into_iter()
+            .take(first.max as usize)
+            .skip(first.min as usize)
+            .map(|name| User { name: name.into() })
+            .collect();
+        Page {
+            total_count: items.len() as i32,
+            items,
+        }
+    }
+
+    fn orders() -> Page<Order> {
+        Page {
+            items: vec![Order { total: 10 }],
+            total_count: 1,
+        }
+    }
+
+    fn first_order() -> Edge<Order> {
+        Edge {
+            node: Order { total: 10 },
+        }
+    }
+}
+
+fn schema<'q>() -> RootNode<'q, Query, EmptyMutation, EmptySubscription> {
+    RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+}
+
+#[test]
+fn composes_names_from_type_parameters() {
+    assert_eq!(
+        <Page<User> as GraphQLType<DefaultScalarValue>>::name(&()),
+        Some("UserPage"),
+    );
+    assert_eq!(
+        <Page<Order> as GraphQLType<DefaultScalarValue>>::name(&()),
+        Some("OrderPage"),
+    );
+    assert_eq!(
+        <Edge<Order> as GraphQLType<DefaultScalarValue>>::name(&()),
+        Some("OrderEdge"),
+    );
+    assert_eq!(
+        <Range<i32> as GraphQLType<DefaultScalarValue>>::name(&()),
+        Some("IntRange"),
+    );
+}
+
+#[tokio::test]
+async fn registers_each_instantiation() {
+    let doc = r#"{
+        userPage: __type(name: "UserPage") { kind }
+        orderPage: __type(name: "OrderPage") { kind }
+        orderEdge: __type(name: "OrderEdge") { kind }
+        intRange: __type(name: "IntRange") { kind }
+    }"#;
+
+    assert_eq!(
+        execute(doc, None, &schema(), &Variables::new(), &()).await,
+        Ok((
+            graphql_value!({
+                "userPage": {"kind": "OBJECT"},
+                "orderPage": {"kind": "OBJECT"},
+                "orderEdge": {"kind": "OBJECT"},
+                "intRange": {"kind": "INPUT_OBJECT"},
+            }),
+            vec![],
+        )),
+    );
+}
+
+#[tokio::test]
+async fn resolves_instantiations() {
+    let doc = r#"{
+        users(first: {min: 1, max: 2}) { __typename items { name } totalCount }
+        orders { __typename items { total } }
+        firstOrder { __typename node { total } }
+    }"#;
+
+    assert_eq!(
+        execute(doc, None, &schema(), &Variables::new(), &()).await,
+        Ok((
+            graphql_value!({
+                "users": {
+                    "__typename": "UserPage",
+                    "items": [{"name": "Bob"}],
+                    "totalCount": 1,
+                },
+                "orders": {"__typename": "OrderPage", "items": [{"total": 10}]},
+                "firstOrder": {"__typename": "OrderEdge", "node": {"total": 10}},
+            }),
+            vec![],
+        )),
+    );
+}
+
+mod collision {
+    use super::*;
+
+    #[derive(GraphQLObject)]
+    #[graphql(name = "Item", scalar = DefaultScalarValue)]
+    struct Item<T: Node> {
+        value: T,
+    }
+
+    #[derive(GraphQLObject)]
+    #[graphql(name = "Pair", scalar = DefaultScalarValue)]
+    struct Pair<T: Node> {
+        first: T,
+        second: T,
+    }
+
+    struct Query;
+
+    #[graphql_object(scalar = DefaultScalarValue)]
+    impl Query {
+        fn int_item() -> Item<i32> {
+            Item { value: 1 }
+        }
+
+        fn string_item() -> Item<String> {
+            Item {
+                value: "one".into(),
+            }
+        }
+
+        fn boxed_item() -> Box<Item<i32>> {
+            Box::new(Item { value: 2 })
+        }
+
+        fn int_pair() -> Pair<i32> {
+            Pair {
+                first: 1,
+                second: 2,
+            }
+        }
+
+        fn bool_pair() -> Pair<bool> {
+            Pair {
+                first: true,
+                second: false,
+            }
+        }
+    }
+
+    #[test]
+    fn panics_once_on_same_names_of_different_types() {
+        let err = std::panic::catch_unwind(|| {
+            RootNode::new(
+                Query,
+                EmptyMutation::<()>::new(),
+                EmptySubscription::<()>::new(),
+            )
+        })
+        .map(drop)
+        .unwrap_err();
+        let msg = err.downcast_ref::<String>().unwrap();
+
+        assert!(
+            msg.contains("GraphQL type name `Item` is used by different types"),
+            "unexpected panic: {}",
+            msg,
+        );
+        assert!(
+            msg.contains("GraphQL type name `Pair` is used by different types"),
+            "unexpected panic: {}",
+            msg,
+        );
+        assert!(!msg.contains("Box"), "unexpected panic: {}", msg);
+    }
+}
+
+mod unnamed_param {
+    use super::*;
+
+    struct Query;
+
+    #[graphql_object(scalar = DefaultScalarValue)]
+    impl Query {
+        fn pages() -> Page<Vec<User>> {
+            Page {
+                items: vec![],
+                total_count: 0,
+            }
+        }
+    }
+
+    #[test]
+    fn panics_on_schema_build() {
+        let err = std::panic::catch_unwind(|| {
+            RootNode::new(
+                Query,
+                EmptyMutation::<()>::new(),
+                EmptySubscription::<()>::new(),
+            )
+        })
+        .map(drop)
+        .unwrap_err();
+        let msg = err.downcast_ref::<String>().unwrap();
+
+        assert!(
+            msg.contains("type parameter `alloc::vec::Vec<")
+                && msg.contains("must be a named GraphQL type to be substituted into its name"),
+            "unexpected panic: {}",
+            msg,
+        );
+    }
+}
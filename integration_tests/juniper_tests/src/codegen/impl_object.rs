@@ -137,3 +137,147 @@ mod raw_argument {
         );
     }
 }
+
+mod guard {
+    use juniper::{
+        graphql_object, graphql_value, Arguments, EmptyMutation, EmptySubscription, FieldError,
+        FieldResult, GraphQLObject, Guard, RootNode, ScalarValue, Variables,
+    };
+
+    struct Context {
+        is_admin: bool,
+    }
+
+    impl juniper::Context for Context {}
+
+    struct AdminOnly;
+
+    impl<S: ScalarValue> Guard<Context, S> for AdminOnly {
+        fn check(&self, ctx: &Context, _: &Arguments<S>) -> FieldResult<(), S> {
+            if ctx.is_admin {
+                Ok(())
+            } else {
+                Err(FieldError::from("Forbidden"))
+            }
+        }
+    }
+
+    fn positive_id<S: ScalarValue>(_: &Context, args: &Arguments<S>) -> FieldResult<(), S> {
+        match args.get::<i32>("id") {
+            Some(id) if id > 0 => Ok(()),
+            _ => Err("Invalid id".into()),
+        }
+    }
+
+    #[derive(GraphQLObject)]
+    #[graphql(context = Context)]
+    struct User {
+        name: String,
+        #[graphql(guard = "AdminOnly")]
+        email: String,
+    }
+
+    struct Query;
+
+    #[graphql_object(context = Context)]
+    impl Query {
+        #[graphql(guard = "AdminOnly")]
+        fn secret() -> &'static str {
+            "42"
+        }
+
+        #[graphql(guard = "AdminOnly")]
+        async fn async_secret() -> &'static str {
+            "43"
+        }
+
+        #[graphql(guard = "positive_id")]
+        fn user(id: i32) -> User {
+            User {
+                name: format!("user-{}", id),
+                email: "user@example.com".into(),
+            }
+        }
+    }
+
+    fn schema<'q>() -> RootNode<'q, Query, EmptyMutation<Context>, EmptySubscription<Context>> {
+        RootNode::new(
+            Query,
+            EmptyMutation::<Context>::new(),
+            EmptySubscription::<Context>::new(),
+        )
+    }
+
+    #[tokio::test]
+    async fn resolves_when_allowed() {
+        let doc = r#"{
+            secret
+            asyncSecret
+            user(id: 1) { name email }
+        }"#;
+
+        let ctx = Context { is_admin: true };
+
+        assert_eq!(
+            juniper::execute(doc, None, &schema(), &Variables::new(), &ctx).await,
+            Ok((
+                graphql_value!({
+                    "secret": "42",
+                    "asyncSecret": "43",
+                    "user": {"name": "user-1", "email": "user@example.com"},
+                }),
+                vec![],
+            )),
+        );
+    }
+
+    #[tokio::test]
+    async fn nulls_field_when_denied() {
+        let doc = r#"{
+            user(id: 1) { name email }
+        }"#;
+
+        let ctx = Context { is_admin: false };
+
+        let (res, errs) = juniper::execute(doc, None, &schema(), &Variables::new(), &ctx)
+            .await
+            .unwrap();
+
+        assert_eq!(res, graphql_value!(None));
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].error().message(), "Forbidden");
+        assert_eq!(errs[0].path(), ["user", "email"]);
+    }
+
+    #[tokio::test]
+    async fn denies_sync_and_async_fields() {
+        let ctx = Context { is_admin: false };
+
+        for (doc, field) in &[("{ secret }", "secret"), ("{ asyncSecret }", "asyncSecret")] {
+            let (res, errs) = juniper::execute(doc, None, &schema(), &Variables::new(), &ctx)
+                .await
+                .unwrap();
+
+            assert_eq!(res, graphql_value!(None));
+            assert_eq!(errs.len(), 1);
+            assert_eq!(errs[0].path(), [*field]);
+        }
+    }
+
+    #[tokio::test]
+    async fn checks_arguments() {
+        let doc = r#"{
+            user(id: -1) { name }
+        }"#;
+
+        let ctx = Context { is_admin: true };
+
+        let (res, errs) = juniper::execute(doc, None, &schema(), &Variables::new(), &ctx)
+            .await
+            .unwrap();
+
+        assert_eq!(res, graphql_value!(None));
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].error().message(), "Invalid id");
+    }
+}
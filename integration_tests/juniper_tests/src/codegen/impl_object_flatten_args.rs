@@ -0,0 +1,204 @@
+//! Tests for flattened arguments of `#[graphql_object]` fields.
+
+use std::{marker::PhantomData, panic};
+
+use juniper::{
+    execute, graphql_object, graphql_value, EmptyMutation, EmptySubscription, GraphQLInputObject,
+    RootNode, Value, Variables,
+};
+
+#[derive(GraphQLInputObject)]
+struct Pagination {
+    /// Number of items to skip.
+    #[graphql(default = "0")]
+    offset: i32,
+    /// Maximum number of items to return.
+    #[graphql(default = "2")]
+    limit: i32,
+}
+
+#[derive(GraphQLInputObject)]
+struct Filter {
+    name_contains: Option<String>,
+}
+
+#[derive(GraphQLInputObject)]
+struct Bounds {
+    /// Smallest number to return.
+    min: Option<i32>,
+}
+
+struct Query;
+
+#[graphql_object]
+impl Query {
+    #[graphql(arguments(page(flatten), bounds(flatten)))]
+    fn numbers(page: Pagination, bounds: Bounds) -> Vec<i32> {
+        (bounds.min.unwrap_or(1)..=5)
+            .skip(page.offset as usize)
+            .take(page.limit as usize)
+            .collect()
+    }
+
+    #[graphql(arguments(filter(flatten), page(flatten)))]
+    fn planets(filter: Filter, page: Pagination, reverse: Option<bool>) -> Vec<&'static str> {
+        let mut planets = vec!["Alderaan", "Dagobah", "Hoth", "Naboo", "Tatooine"];
+        if reverse.unwrap_or(false) {
+            planets.reverse();
+        }
+        planets
+            .into_iter()
+            .filter(|p| {
+                filter
+                    .name_contains
+                    .as_ref()
+                    .map_or(true, |s| p.contains(s.as_str()))
+            })
+            .skip(page.offset as usize)
+            .take(page.limit as usize)
+            .collect()
+    }
+}
+
+// Arguments of generic types can't be checked at compile time, so they collide at schema build.
+struct Colliding<T>(PhantomData<T>);
+
+#[graphql_object]
+impl<T: Send + Sync> Colliding<T> {
+    #[graphql(arguments(page(flatten)))]
+    fn numbers(page: Pagination, limit: i32) -> Vec<i32> {
+        vec![page.offset, page.limit, limit]
+    }
+}
+
+async fn run_query(query: &str) -> Value {
+    let schema = RootNode::new(
+        Query,
+        EmptyMutation::<()>::new(),
+        EmptySubscription::<()>::new(),
+    );
+
+    let (result, errs) = execute(query, None, &schema, &Variables::new(), &())
+        .await
+        .expect("Execution failed");
+
+    assert_eq!(errs, []);
+    result
+}
+
+#[tokio::test]
+async fn resolves_flattened_arguments() {
+    let query = r#"{
+        numbers
+        skipped: numbers(offset: 3, limit: 10)
+        bounded: numbers(min: 3)
+        planets(nameContains: "o", limit: 3, reverse: true)
+    }"#;
+
+    assert_eq!(
+        run_query(query).await,
+        graphql_value!({
+            "numbers": [1, 2],
+            "skipped": [4, 5],
+            "bounded": [3, 4],
+            "planets": ["Tatooine", "Naboo", "Hoth"],
+        }),
+    );
+}
+
+#[tokio::test]
+async fn exposes_fields_of_flattened_arguments() {
+    let query = r#"{
+        __type(name: "Query") {
+            fields {
+                name
+                args {
+                    name
+                    description
+                    defaultValue
+                    type { name kind }
+                }
+            }
+        }
+        pagination: __type(name: "Pagination") { name }
+    }"#;
+
+    assert_eq!(
+        run_query(query).await,
+        graphql_value!({
+            "__type": {
+                "fields": [
+                    {
+                        "name": "numbers",
+                        "args": [
+                            {
+                                "name": "offset",
+                                "description": "Number of items to skip.",
+                                "defaultValue": "0",
+                                "type": {"name": "Int", "kind": "SCALAR"},
+                            },
+                            {
+                                "name": "limit",
+                                "description": "Maximum number of items to return.",
+                                "defaultValue": "2",
+                                "type": {"name": "Int", "kind": "SCALAR"},
+                            },
+                            {
+                                "name": "min",
+                                "description": "Smallest number to return.",
+                                "defaultValue": None,
+                                "type": {"name": "Int", "kind": "SCALAR"},
+                            },
+                        ],
+                    },
+                    {
+                        "name": "planets",
+                        "args": [
+                            {
+                                "name": "nameContains",
+                                "description": None,
+                                "defaultValue": None,
+                                "type": {"name": "String", "kind": "SCALAR"},
+                            },
+                            {
+                                "name": "offset",
+                                "description": "Number of items to skip.",
+                                "defaultValue": "0",
+                                "type": {"name": "Int", "kind": "SCALAR"},
+                            },
+                            {
+                                "name": "limit",
+                                "description": "Maximum number of items to return.",
+                                "defaultValue": "2",
+                                "type": {"name": "Int", "kind": "SCALAR"},
+                            },
+                            {
+                                "name": "reverse",
+                                "description": None,
+                                "defaultValue": None,
+                                "type": {"name": "Boolean", "kind": "SCALAR"},
+                            },
+                        ],
+                    },
+                ],
+            },
+            "pagination": None,
+        }),
+    );
+}
+
+#[test]
+fn rejects_colliding_arguments_at_schema_build() {
+    let err = panic::catch_unwind(|| {
+        RootNode::new(
+            Colliding::<()>(PhantomData),
+            EmptyMutation::<()>::new(),
+            EmptySubscription::<()>::new(),
+        )
+    })
+    .map(drop)
+    .unwrap_err();
+    let msg = err.downcast_ref::<String>().unwrap();
+
+    assert_eq!(msg, "Field `numbers` has several arguments named `limit`");
+}
@@ -5270,3 +5270,120 @@ mod external_downcast {
         }
     }
 }
+
+mod guard {
+    use juniper::{Arguments, Guard};
+
+    use super::*;
+
+    struct Context {
+        is_admin: bool,
+    }
+
+    impl juniper::Context for Context {}
+
+    struct AdminOnly;
+
+    impl<S: ScalarValue> Guard<Context, S> for AdminOnly {
+        fn check(&self, ctx: &Context, _: &Arguments<S>) -> FieldResult<(), S> {
+            if ctx.is_admin {
+                Ok(())
+            } else {
+                Err(FieldError::from("Forbidden"))
+            }
+        }
+    }
+
+    #[graphql_interface(for = Human, context = Context)]
+    trait Character {
+        fn id(&self) -> &str;
+
+        #[graphql(guard = "AdminOnly")]
+        fn secret(&self) -> &str;
+
+        #[graphql(guard = AdminOnly)]
+        async fn async_secret(&self) -> &str;
+    }
+
+    #[derive(GraphQLObject)]
+    #[graphql(impl = CharacterValue, context = Context)]
+    struct Human {
+        id: String,
+        secret: String,
+        async_secret: String,
+    }
+
+    #[graphql_interface]
+    impl Character for Human {
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        fn secret(&self) -> &str {
+            &self.secret
+        }
+
+        async fn async_secret(&self) -> &str {
+            &self.async_secret
+        }
+    }
+
+    struct QueryRoot;
+
+    #[graphql_object(context = Context)]
+    impl QueryRoot {
+        fn character(&self) -> CharacterValue {
+            Human {
+                id: "human-32".to_string(),
+                secret: "earth".to_string(),
+                async_secret: "mars".to_string(),
+            }
+            .into()
+        }
+    }
+
+    const DOC: &str = r#"{
+        character {
+            id
+            secret
+            asyncSecret
+        }
+    }"#;
+
+    #[tokio::test]
+    async fn resolves_when_allowed() {
+        let schema = schema(QueryRoot);
+        let ctx = Context { is_admin: true };
+
+        assert_eq!(
+            execute(DOC, None, &schema, &Variables::new(), &ctx).await,
+            Ok((
+                graphql_value!({"character": {
+                    "id": "human-32",
+                    "secret": "earth",
+                    "asyncSecret": "mars",
+                }}),
+                vec![],
+            )),
+        );
+    }
+
+    #[tokio::test]
+    async fn denies_when_not_allowed() {
+        let schema = schema(QueryRoot);
+        let ctx = Context { is_admin: false };
+
+        for field in &["secret", "asyncSecret"] {
+            let doc = format!("{{ character {{ id {} }} }}", field);
+
+            let (res, errs) = execute(&doc, None, &schema, &Variables::new(), &ctx)
+                .await
+                .unwrap();
+
+            assert_eq!(res, graphql_value!(None));
+            assert_eq!(errs.len(), 1);
+            assert_eq!(errs[0].error().message(), "Forbidden");
+            assert_eq!(errs[0].path(), ["character", *field]);
+        }
+    }
+}
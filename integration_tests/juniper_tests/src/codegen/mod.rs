@@ -1,11 +1,19 @@
+mod client_query;
 mod derive_enum;
+mod derive_graphql_scalar;
 mod derive_input_object;
+mod derive_merged_object;
 mod derive_object;
+mod derive_object_complex;
+mod derive_object_with_input;
 mod derive_object_with_raw_idents;
 mod derive_scalar;
+mod generic_names;
 mod impl_object;
+mod impl_object_flatten_args;
 mod impl_scalar;
 mod interface_attr;
+mod rename_all;
 mod scalar_value_transparent;
 mod union_attr;
 mod union_derive;
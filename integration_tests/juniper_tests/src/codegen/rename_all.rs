@@ -0,0 +1,102 @@
+//! Tests for `#[graphql(rename_all = ...)]` attribute argument.
+
+use juniper::{
+    execute, graphql_interface, graphql_object, graphql_value, EmptyMutation, EmptySubscription,
+    GraphQLEnum, GraphQLInputObject, GraphQLObject, RootNode, Variables,
+};
+
+#[graphql_interface(for = Account, rename_all = "snake_case")]
+trait Entity {
+    fn entity_id(&self) -> i32;
+}
+
+#[derive(GraphQLObject)]
+#[graphql(impl = EntityValue, rename_all = "snake_case")]
+struct Account {
+    entity_id: i32,
+    display_name: String,
+    account_kind: AccountKind,
+}
+
+#[graphql_interface]
+impl Entity for Account {
+    fn entity_id(&self) -> i32 {
+        self.entity_id
+    }
+}
+
+#[derive(Clone, Copy, GraphQLEnum)]
+#[graphql(rename_all = "lowercase")]
+enum AccountKind {
+    Personal,
+    SmallBusiness,
+}
+
+#[derive(GraphQLInputObject)]
+#[graphql(rename_all = "PascalCase")]
+struct AccountFilter {
+    account_kind: AccountKind,
+}
+
+struct Query;
+
+#[graphql_object(rename_all = "snake_case")]
+impl Query {
+    fn find_account(account_filter: AccountFilter, display_name: String) -> Account {
+        Account {
+            entity_id: 1,
+            display_name,
+            account_kind: account_filter.account_kind,
+        }
+    }
+
+    fn entity() -> EntityValue {
+        Account {
+            entity_id: 2,
+            display_name: "b".into(),
+            account_kind: AccountKind::Personal,
+        }
+        .into()
+    }
+
+    #[graphql(name = "verbatimName")]
+    fn overridden_name() -> bool {
+        true
+    }
+}
+
+fn schema<'q>() -> RootNode<'q, Query, EmptyMutation, EmptySubscription> {
+    RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+}
+
+#[tokio::test]
+async fn renames_fields_arguments_and_values() {
+    let doc = r#"{
+        find_account(
+            account_filter: { AccountKind: smallbusiness },
+            display_name: "a",
+        ) {
+            entity_id
+            display_name
+            account_kind
+        }
+        entity { entity_id }
+        verbatimName
+    }"#;
+
+    assert_eq!(
+        execute(doc, None, &schema(), &Variables::new(), &()).await,
+        Ok((
+            graphql_value!({
+                "find_account": {
+                    "entity_id": 1,
+                    "display_name": "a",
+                    "account_kind": "smallbusiness",
+                },
+                "entity": {"entity_id": 2},
+                "verbatimName": true,
+            }),
+            vec![],
+        )),
+    );
+}
@@ -0,0 +1,95 @@
+use juniper::{
+    graphql_object, http::GraphQLRequest, DefaultScalarValue, EmptyMutation, EmptySubscription,
+    RootNode, Value,
+};
+
+struct Query;
+
+#[graphql_object]
+impl Query {
+    fn trace_id(executor: &Executor<'_, '_, (), DefaultScalarValue>) -> Option<String> {
+        executor
+            .request_extensions()
+            .and_then(|ext| ext.to_object_value())
+            .and_then(|obj| obj.get("traceId").copied())
+            .and_then(|v| v.as_string_value())
+            .map(Into::into)
+    }
+
+    fn timed(executor: &Executor<'_, '_, (), DefaultScalarValue>) -> i32 {
+        executor.add_response_extension("timing", Value::scalar(42));
+        1
+    }
+}
+
+type Schema = RootNode<'static, Query, EmptyMutation, EmptySubscription>;
+
+fn schema() -> Schema {
+    Schema::new(Query, EmptyMutation::new(), EmptySubscription::new())
+}
+
+#[tokio::test]
+async fn deserializes_request_extensions() {
+    let req: GraphQLRequest =
+        serde_json::from_str(r#"{"query": "{ traceId }", "extensions": {"traceId": "abc"}}"#)
+            .unwrap();
+    let schema = schema();
+
+    let resp = req.execute(&schema, &()).await;
+
+    assert_eq!(
+        serde_json::to_value(&resp).unwrap(),
+        serde_json::json!({"data": {"traceId": "abc"}}),
+    );
+}
+
+#[tokio::test]
+async fn requests_without_extensions() {
+    let req: GraphQLRequest = serde_json::from_str(r#"{"query": "{ traceId }"}"#).unwrap();
+    assert_eq!(req.extensions(), None);
+    assert_eq!(
+        serde_json::to_value(&req).unwrap(),
+        serde_json::json!({"query": "{ traceId }", "operationName": null, "variables": null}),
+    );
+
+    let schema = schema();
+    let resp = req.execute_sync(&schema, &());
+
+    assert_eq!(
+        serde_json::to_value(&resp).unwrap(),
+        serde_json::json!({"data": {"traceId": null}}),
+    );
+}
+
+#[tokio::test]
+async fn serializes_response_extensions() {
+    let req = GraphQLRequest::<DefaultScalarValue>::new("{ timed }".into(), None, None);
+    let schema = schema();
+
+    let mut resp = req.execute(&schema, &()).await;
+    resp.add_extension("cost", Value::scalar(3));
+
+    assert_eq!(
+        serde_json::to_value(&resp).unwrap(),
+        serde_json::json!({
+            "data": {"timed": 1},
+            "extensions": {"timing": 42, "cost": 3},
+        }),
+    );
+}
+
+#[tokio::test]
+async fn serializes_extensions_of_failed_requests() {
+    let req = GraphQLRequest::<DefaultScalarValue>::new("{ unknown }".into(), None, None);
+    let schema = schema();
+
+    let mut resp = req.execute(&schema, &()).await;
+    resp.add_extension("cost", Value::scalar(0));
+
+    let json = serde_json::to_value(&resp).unwrap();
+    assert!(json.get("errors").is_some());
+    assert_eq!(
+        json.get("extensions"),
+        Some(&serde_json::json!({"cost": 0}))
+    );
+}
@@ -3,12 +3,18 @@ mod api;
 #[cfg(test)]
 mod arc_fields;
 #[cfg(test)]
+mod batching;
+#[cfg(test)]
+mod cache_control;
+#[cfg(test)]
 mod codegen;
 #[cfg(test)]
 mod custom_scalar;
 #[cfg(test)]
 mod explicit_null;
 #[cfg(test)]
+mod extensions;
+#[cfg(test)]
 mod infallible_as_field_error;
 #[cfg(test)]
 mod issue_371;
@@ -29,4 +35,8 @@ mod issue_925;
 #[cfg(test)]
 mod issue_945;
 #[cfg(test)]
+mod multipart;
+#[cfg(test)]
 mod pre_parse;
+#[cfg(test)]
+mod visibility;
@@ -0,0 +1,139 @@
+use futures::TryStreamExt as _;
+use juniper::{
+    graphql_object,
+    http::multipart::{parse_multipart_request, MultipartLimits},
+    DefaultScalarValue, EmptySubscription, FieldResult, RootNode, Upload,
+};
+
+struct Query;
+
+#[graphql_object]
+impl Query {
+    fn ping() -> bool {
+        true
+    }
+}
+
+struct Mutation;
+
+#[graphql_object]
+impl Mutation {
+    fn upload(
+        executor: &Executor<'_, '_, (), DefaultScalarValue>,
+        file: Upload,
+    ) -> FieldResult<String> {
+        let file = executor.upload(&file).ok_or("file is missing")?;
+        Ok(format!(
+            "{}:{}",
+            file.filename().unwrap_or_default(),
+            String::from_utf8_lossy(&file.to_vec()?),
+        ))
+    }
+
+    async fn chunks(
+        executor: &Executor<'_, '_, (), DefaultScalarValue>,
+        files: Vec<Upload>,
+    ) -> FieldResult<Vec<i32>> {
+        let mut counts = vec![];
+        for file in files {
+            let file = executor.upload(&file).ok_or("file is missing")?;
+            let chunks = file.stream(2).try_collect::<Vec<_>>().await?;
+            counts.push(chunks.len() as i32);
+        }
+        Ok(counts)
+    }
+}
+
+type Schema = RootNode<'static, Query, Mutation, EmptySubscription>;
+
+fn schema() -> Schema {
+    Schema::new(Query, Mutation, EmptySubscription::new())
+}
+
+const CONTENT_TYPE: &str = "multipart/form-data; boundary=\"xyz\"";
+
+fn body(operations: &str, map: &str, files: &[(&str, &str, &str)]) -> Vec<u8> {
+    let mut body = format!(
+        "--xyz\r\n\
+         Content-Disposition: form-data; name=\"operations\"\r\n\r\n\
+         {}\r\n\
+         --xyz\r\n\
+         Content-Disposition: form-data; name=\"map\"\r\n\r\n\
+         {}\r\n",
+        operations, map,
+    );
+    for (name, filename, content) in files {
+        body.push_str(&format!(
+            "--xyz\r\n\
+             Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n\
+             Content-Type: text/plain\r\n\r\n\
+             {}\r\n",
+            name, filename, content,
+        ));
+    }
+    body.push_str("--xyz--\r\n");
+    body.into_bytes()
+}
+
+#[tokio::test]
+async fn resolves_uploaded_file() {
+    let body = body(
+        r#"{"query": "mutation ($file: Upload!) { upload(file: $file) }", "variables": {"file": null}}"#,
+        r#"{"0": ["variables.file"]}"#,
+        &[("0", "a.txt", "Alpha")],
+    );
+    let req = parse_multipart_request::<DefaultScalarValue>(
+        CONTENT_TYPE,
+        &body,
+        &MultipartLimits::default(),
+    )
+    .unwrap();
+
+    let schema = schema();
+    let resp = req.execute(&schema, &()).await;
+
+    assert_eq!(
+        serde_json::to_value(&resp).unwrap(),
+        serde_json::json!({"data": {"upload": "a.txt:Alpha"}}),
+    );
+}
+
+#[tokio::test]
+async fn streams_multiple_uploaded_files() {
+    let body = body(
+        r#"{"query": "mutation ($files: [Upload!]!) { chunks(files: $files) }", "variables": {"files": [null, null]}}"#,
+        r#"{"0": ["variables.files.0"], "1": ["variables.files.1"]}"#,
+        &[("0", "a.txt", "abcde"), ("1", "b.txt", "ab")],
+    );
+    let req = parse_multipart_request::<DefaultScalarValue>(
+        CONTENT_TYPE,
+        &body,
+        &MultipartLimits::default(),
+    )
+    .unwrap();
+
+    let schema = schema();
+    let resp = req.execute(&schema, &()).await;
+
+    assert_eq!(
+        serde_json::to_value(&resp).unwrap(),
+        serde_json::json!({"data": {"chunks": [3, 1]}}),
+    );
+}
+
+#[tokio::test]
+async fn errors_on_unknown_upload() {
+    let req = juniper::http::GraphQLRequest::<DefaultScalarValue>::new(
+        r#"mutation { upload(file: "0") }"#.into(),
+        None,
+        None,
+    );
+
+    let schema = schema();
+    let resp = req.execute(&schema, &()).await;
+
+    assert_eq!(
+        serde_json::to_value(&resp).unwrap()["errors"][0]["message"],
+        "file is missing",
+    );
+}
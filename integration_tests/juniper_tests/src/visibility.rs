@@ -0,0 +1,226 @@
+use juniper::{
+    execute, graphql_object, graphql_value, EmptyMutation, EmptySubscription, GraphQLEnum,
+    GraphQLInputObject, GraphQLObject, RootNode, Variables,
+};
+
+struct Context {
+    is_staff: bool,
+}
+
+impl juniper::Context for Context {}
+
+fn is_staff(ctx: &Context) -> bool {
+    ctx.is_staff
+}
+
+#[derive(GraphQLEnum)]
+enum Role {
+    User,
+    #[graphql(visible = "is_staff")]
+    Admin,
+}
+
+#[derive(GraphQLObject)]
+#[graphql(visible = "is_staff")]
+struct Audit {
+    entries: i32,
+}
+
+#[derive(GraphQLObject)]
+struct User {
+    name: String,
+    #[graphql(visible = "is_staff")]
+    email: String,
+}
+
+#[derive(GraphQLInputObject)]
+struct Filter {
+    name: Option<String>,
+    #[graphql(visible = "is_staff")]
+    include_deleted: Option<bool>,
+}
+
+struct Query;
+
+#[graphql_object(context = Context)]
+impl Query {
+    fn user() -> User {
+        User {
+            name: "alice".into(),
+            email: "alice@example.com".into(),
+        }
+    }
+
+    #[graphql(arguments(verbose(visible = "is_staff")))]
+    fn users(_filter: Option<Filter>, verbose: Option<bool>) -> i32 {
+        verbose.map_or(1, |v| if v { 2 } else { 1 })
+    }
+
+    fn role(role: Role) -> Role {
+        role
+    }
+
+    #[graphql(visible = "is_staff")]
+    fn internal() -> &'static str {
+        "internal"
+    }
+
+    fn audit() -> Audit {
+        Audit { entries: 3 }
+    }
+}
+
+type Schema = RootNode<'static, Query, EmptyMutation<Context>, EmptySubscription<Context>>;
+
+fn schema() -> Schema {
+    Schema::new(Query, EmptyMutation::new(), EmptySubscription::new()).enable_visibility()
+}
+
+async fn run(schema: &Schema, query: &str, is_staff: bool) -> Result<juniper::Value, String> {
+    execute(
+        query,
+        None,
+        schema,
+        &Variables::new(),
+        &Context { is_staff },
+    )
+    .await
+    .map(|(data, errors)| {
+        assert!(errors.is_empty(), "{:?}", errors);
+        data
+    })
+    .map_err(|e| e.to_string())
+}
+
+#[tokio::test]
+async fn hides_fields_from_introspection() {
+    const DOC: &str = r#"{
+        __type(name: "Query") {
+            fields { name args { name } }
+        }
+    }"#;
+
+    let schema = schema();
+
+    let public = run(&schema, DOC, false).await.unwrap();
+    let public_fields = public
+        .as_object_value()
+        .and_then(|o| o.get_field_value("__type"))
+        .and_then(|t| t.as_object_value())
+        .and_then(|t| t.get_field_value("fields"))
+        .and_then(|f| f.as_list_value())
+        .unwrap();
+    let names = public_fields
+        .iter()
+        .map(|f| {
+            f.as_object_value()
+                .unwrap()
+                .get_field_value("name")
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        vec![
+            &graphql_value!("user"),
+            &graphql_value!("users"),
+            &graphql_value!("role"),
+        ],
+    );
+    assert!(public_fields.contains(&graphql_value!({
+        "name": "users",
+        "args": [{"name": "filter"}],
+    })));
+
+    let staff = run(&schema, DOC, true).await.unwrap();
+    assert!(staff.to_string().contains("internal"));
+    assert!(staff.to_string().contains("verbose"));
+    assert!(staff.to_string().contains("audit"));
+}
+
+#[tokio::test]
+async fn hides_types_from_introspection() {
+    const DOC: &str = r#"{
+        audit: __type(name: "Audit") { name }
+        role: __type(name: "Role") { enumValues { name } }
+        filter: __type(name: "Filter") { inputFields { name } }
+    }"#;
+
+    let schema = schema();
+
+    assert_eq!(
+        run(&schema, DOC, false).await,
+        Ok(graphql_value!({
+            "audit": None,
+            "role": {"enumValues": [{"name": "USER"}]},
+            "filter": {"inputFields": [{"name": "name"}]},
+        })),
+    );
+    assert_eq!(
+        run(&schema, DOC, true).await,
+        Ok(graphql_value!({
+            "audit": {"name": "Audit"},
+            "role": {"enumValues": [{"name": "USER"}, {"name": "ADMIN"}]},
+            "filter": {"inputFields": [{"name": "name"}, {"name": "includeDeleted"}]},
+        })),
+    );
+}
+
+#[tokio::test]
+async fn rejects_hidden_items_in_validation() {
+    let schema = schema();
+
+    for doc in &[
+        "{ internal }",
+        "{ user { email } }",
+        "{ audit { entries } }",
+        "{ users(verbose: true) }",
+        "{ users(filter: {includeDeleted: true}) }",
+        "{ role(role: ADMIN) }",
+        "query($f: Audit) { user { name } }",
+    ] {
+        assert!(run(&schema, doc, false).await.is_err(), "{}", doc);
+    }
+
+    assert_eq!(
+        run(
+            &schema,
+            "{ internal user { email } audit { entries } }",
+            true
+        )
+        .await,
+        Ok(graphql_value!({
+            "internal": "internal",
+            "user": {"email": "alice@example.com"},
+            "audit": {"entries": 3},
+        })),
+    );
+    assert_eq!(
+        run(&schema, "{ users(verbose: true) role(role: ADMIN) }", true).await,
+        Ok(graphql_value!({"users": 2, "role": "ADMIN"})),
+    );
+}
+
+#[tokio::test]
+async fn hides_everything_when_not_enabled() {
+    let schema = Schema::new(Query, EmptyMutation::new(), EmptySubscription::new());
+
+    assert!(run(&schema, "{ internal }", true).await.is_err());
+    assert_eq!(
+        run(&schema, "{ user { name } }", true).await,
+        Ok(graphql_value!({"user": {"name": "alice"}})),
+    );
+}
+
+#[test]
+fn reuses_filtered_schema_per_visibility_set() {
+    let schema = schema();
+    let staff = schema.schema_for(&Context { is_staff: true });
+    let other_staff = schema.schema_for(&Context { is_staff: true });
+    let user = schema.schema_for(&Context { is_staff: false });
+
+    assert!(std::ptr::eq(&*staff, &*other_staff));
+    assert!(!std::ptr::eq(&*staff, &*user));
+    assert!(staff.concrete_type_by_name("Audit").is_some());
+    assert!(user.concrete_type_by_name("Audit").is_none());
+}
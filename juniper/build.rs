@@ -0,0 +1,73 @@
+//! Embeds the GraphQL IDE assets found in the `JUNIPER_ASSETS_DIR` directory with the
+//! `embedded-assets` feature.
+//!
+//! The assets missing there are reported as warnings rather than errors, so the pages load them
+//! from the CDN instead, and building with all the features doesn't require the directory.
+
+use std::{env, fs, path::PathBuf};
+
+/// Paths of the embeddable assets, relative to the `JUNIPER_ASSETS_DIR` directory, being the
+/// `ASSETS` of `GraphiQLSource` and `PlaygroundSource`.
+const ASSETS: &[&str] = &[
+    "react@18.2.0/umd/react.production.min.js",
+    "react-dom@18.2.0/umd/react-dom.production.min.js",
+    "subscriptions-transport-ws@0.11.0/browser/client.js",
+    "graphiql@2.4.7/graphiql.min.js",
+    "graphiql@2.4.7/graphiql.min.css",
+    "@graphiql/plugin-explorer@0.1.20/dist/graphiql-plugin-explorer.umd.js",
+    "@graphiql/plugin-explorer@0.1.20/dist/style.css",
+    "graphql-playground-react@1.7.26/build/static/css/index.css",
+    "graphql-playground-react@1.7.26/build/static/js/middleware.js",
+];
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    if env::var_os("CARGO_FEATURE_EMBEDDED_ASSETS").is_none() {
+        return;
+    }
+    println!("cargo:rerun-if-env-changed=JUNIPER_ASSETS_DIR");
+
+    let dir = env::var_os("JUNIPER_ASSETS_DIR").map(PathBuf::from);
+    match &dir {
+        Some(dir) => println!("cargo:rerun-if-changed={}", dir.display()),
+        None => println!(
+            "cargo:warning=`JUNIPER_ASSETS_DIR` is not set, so no GraphQL IDE assets are \
+             embedded and the pages load them from the CDN",
+        ),
+    }
+
+    let mut arms = String::new();
+    for path in ASSETS {
+        let file = match &dir {
+            Some(dir) => dir.join(path),
+            None => continue,
+        };
+        if file.is_file() {
+            arms.push_str(&format!(
+                "        {:?} => Some(include_str!({:?})),\n",
+                path,
+                file.to_string_lossy(),
+            ));
+        } else {
+            println!(
+                "cargo:warning=GraphQL IDE asset `{}` is not found, so it's loaded from the CDN",
+                file.display(),
+            );
+        }
+    }
+
+    let code = format!(
+        "/// Paths of the embeddable [`Asset`]s.\n\
+         #[cfg(test)]\n\
+         const EMBEDDABLE: &[&str] = &{:?};\n\
+         \n\
+         /// Returns the embedded contents of the [`Asset`] with the given `path`, if any.\n\
+         #[allow(clippy::match_single_binding)]\n\
+         pub(crate) fn embedded(path: &str) -> Option<&'static str> {{\n    \
+             match path {{\n{}        _ => None,\n    }}\n\
+         }}\n",
+        ASSETS, arms,
+    );
+    let out = PathBuf::from(env::var_os("OUT_DIR").expect("`OUT_DIR` is set by Cargo"));
+    fs::write(out.join("embedded_assets.rs"), code).expect("failed to write embedded assets");
+}
@@ -113,7 +113,7 @@ pub struct Directive<'a, S> {
 }
 
 #[allow(missing_docs)]
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
 pub enum OperationType {
     Query,
     Mutation,
@@ -167,6 +167,12 @@ pub trait FromInputValue<S = DefaultScalarValue>: Sized {
         Self::from_input_value(&InputValue::<S>::Null)
             .expect("input value conversion from null must not fail")
     }
+
+    /// Describes why the conversion of the given value fails, to be reported alongside the
+    /// validation error. The default implementation gives no reason.
+    fn from_input_value_error(_v: &InputValue<S>) -> Option<String> {
+        None
+    }
 }
 
 /// Losslessly clones a Rust data type into an InputValue.
@@ -200,6 +206,16 @@ impl<'a> Type<'a> {
     pub fn is_non_null(&self) -> bool {
         matches!(*self, Type::NonNullNamed(_) | Type::NonNullList(_))
     }
+
+    /// Converts this type literal into one owning all of its names.
+    pub(crate) fn into_static(self) -> Type<'static> {
+        match self {
+            Type::Named(n) => Type::Named(Cow::Owned(n.into_owned())),
+            Type::NonNullNamed(n) => Type::NonNullNamed(Cow::Owned(n.into_owned())),
+            Type::List(l) => Type::List(Box::new(l.into_static())),
+            Type::NonNullList(l) => Type::NonNullList(Box::new(l.into_static())),
+        }
+    }
 }
 
 impl<'a> fmt::Display for Type<'a> {
@@ -0,0 +1,151 @@
+//! Cache control hints of a GraphQL response.
+
+use std::{cmp, fmt, str::FromStr};
+
+/// Scope of a cached GraphQL response.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum CacheScope {
+    /// The response is the same for all the clients and may be stored in shared caches.
+    Public,
+
+    /// The response is specific to a single client and must not be stored in shared caches.
+    Private,
+}
+
+impl FromStr for CacheScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "PUBLIC" => Ok(Self::Public),
+            "PRIVATE" => Ok(Self::Private),
+            _ => Err(format!("unknown cache scope `{}`", s)),
+        }
+    }
+}
+
+/// Cache control hint declared on an object type or a field.
+///
+/// Hints are declared via the `#[graphql(cache_control(max_age = 60, scope = "PRIVATE"))]`
+/// attribute of the `#[graphql_object]` and `#[derive(GraphQLObject)]` macros.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct CacheHint {
+    /// Maximum number of seconds the value may be cached for.
+    pub max_age: Option<u32>,
+
+    /// Scope of the cached value.
+    pub scope: Option<CacheScope>,
+}
+
+impl CacheHint {
+    /// Constructs a new [`CacheHint`] out of the given `max_age` and `scope`.
+    pub fn new(max_age: Option<u32>, scope: Option<CacheScope>) -> Self {
+        Self { max_age, scope }
+    }
+
+    /// Restricts this [`CacheHint`] by the `other` one, taking the lowest `max_age` and the most
+    /// private `scope` of both.
+    pub fn restrict(&mut self, other: &Self) {
+        self.max_age = match (self.max_age, other.max_age) {
+            (Some(a), Some(b)) => Some(cmp::min(a, b)),
+            (a, b) => a.or(b),
+        };
+        if other.scope == Some(CacheScope::Private) || self.scope.is_none() {
+            self.scope = other.scope.or(self.scope);
+        }
+    }
+
+    /// Returns the [`CachePolicy`] described by this [`CacheHint`], if any.
+    ///
+    /// A response is only cacheable if at least one of its resolved values declared a `max_age`.
+    pub fn policy(&self) -> Option<CachePolicy> {
+        self.max_age.map(|max_age| CachePolicy {
+            max_age,
+            scope: self.scope.unwrap_or(CacheScope::Public),
+        })
+    }
+}
+
+/// Cache policy of a whole GraphQL response.
+///
+/// It's computed as the most restrictive [`CacheHint`] across all the object types and fields
+/// resolved while executing the operation.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct CachePolicy {
+    /// Maximum number of seconds the response may be cached for.
+    pub max_age: u32,
+
+    /// Scope of the cached response.
+    pub scope: CacheScope,
+}
+
+impl CachePolicy {
+    /// Restricts this [`CachePolicy`] by the `other` one, taking the lowest `max_age` and the
+    /// most private `scope` of both.
+    pub fn restrict(&mut self, other: &Self) {
+        self.max_age = cmp::min(self.max_age, other.max_age);
+        if other.scope == CacheScope::Private {
+            self.scope = CacheScope::Private;
+        }
+    }
+
+    /// Returns the value of the `Cache-Control` HTTP header describing this [`CachePolicy`].
+    ///
+    /// Returns [`None`] if the response must not be cached at all.
+    pub fn header_value(&self) -> Option<String> {
+        if self.max_age == 0 {
+            None
+        } else {
+            Some(self.to_string())
+        }
+    }
+}
+
+impl fmt::Display for CachePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let scope = match self.scope {
+            CacheScope::Public => "public",
+            CacheScope::Private => "private",
+        };
+        write!(f, "max-age={}, {}", self.max_age, scope)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CacheHint, CachePolicy, CacheScope};
+
+    #[test]
+    fn restricts_hints() {
+        let mut hint = CacheHint::default();
+        assert_eq!(hint.policy(), None);
+
+        hint.restrict(&CacheHint::new(None, Some(CacheScope::Private)));
+        assert_eq!(hint.policy(), None);
+
+        hint.restrict(&CacheHint::new(Some(60), Some(CacheScope::Public)));
+        hint.restrict(&CacheHint::new(Some(30), None));
+        assert_eq!(
+            hint.policy(),
+            Some(CachePolicy {
+                max_age: 30,
+                scope: CacheScope::Private,
+            }),
+        );
+    }
+
+    #[test]
+    fn formats_header() {
+        let policy = CachePolicy {
+            max_age: 60,
+            scope: CacheScope::Public,
+        };
+        assert_eq!(policy.header_value().as_deref(), Some("max-age=60, public"));
+
+        let policy = CachePolicy {
+            max_age: 0,
+            scope: CacheScope::Private,
+        };
+        assert_eq!(policy.header_value(), None);
+    }
+}
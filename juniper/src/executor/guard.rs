@@ -0,0 +1,73 @@
+//! Field-level authorization guards.
+
+use crate::{
+    executor::FieldResult,
+    types::base::Arguments,
+    value::{DefaultScalarValue, ScalarValue},
+};
+
+/// Authorization check evaluated before a GraphQL field is resolved.
+///
+/// Guards are attached to fields via the `#[graphql(guard = ...)]` attribute of the
+/// `#[graphql_object]`, `#[derive(GraphQLObject)]` and `#[graphql_interface]` macros. The guard is
+/// checked against the current context and the field's arguments _before_ the resolver runs. If it
+/// returns an error, the resolver is not called at all, the field resolves to `null` and the error
+/// is added to the response, just as if the resolver itself had failed.
+///
+/// A guard of an interface field is checked when the field is resolved through the interface
+/// trait method. Selecting the field on an implementer type, like `... on Human { secret }`,
+/// resolves it with the implementer's own field instead, so that field needs a guard of its own.
+///
+/// Any function of the signature
+/// `Fn(&CtxT, &Arguments<S>) -> FieldResult<(), S>` is a [`Guard`] automatically.
+///
+/// ```rust
+/// use juniper::{graphql_object, Arguments, FieldError, FieldResult, Guard, ScalarValue};
+///
+/// struct Context {
+///     is_admin: bool,
+/// }
+///
+/// impl juniper::Context for Context {}
+///
+/// struct AdminOnly;
+///
+/// impl<S: ScalarValue> Guard<Context, S> for AdminOnly {
+///     fn check(&self, ctx: &Context, _: &Arguments<S>) -> FieldResult<(), S> {
+///         if ctx.is_admin {
+///             Ok(())
+///         } else {
+///             Err(FieldError::from("Forbidden"))
+///         }
+///     }
+/// }
+///
+/// struct Query;
+///
+/// #[graphql_object(context = Context)]
+/// impl Query {
+///     #[graphql(guard = "AdminOnly")]
+///     fn secret() -> &'static str {
+///         "42"
+///     }
+/// }
+/// ```
+pub trait Guard<CtxT, S = DefaultScalarValue>
+where
+    S: ScalarValue,
+{
+    /// Checks whether the field may be resolved with the given `context` and `arguments`.
+    ///
+    /// Returning an error prevents the field from being resolved.
+    fn check(&self, context: &CtxT, arguments: &Arguments<S>) -> FieldResult<(), S>;
+}
+
+impl<CtxT, S, F> Guard<CtxT, S> for F
+where
+    S: ScalarValue,
+    F: Fn(&CtxT, &Arguments<S>) -> FieldResult<(), S>,
+{
+    fn check(&self, context: &CtxT, arguments: &Arguments<S>) -> FieldResult<(), S> {
+        self(context, arguments)
+    }
+}
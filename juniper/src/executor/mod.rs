@@ -8,7 +8,7 @@ use std::{
     sync::{Arc, RwLock},
 };
 
-use fnv::FnvHashMap;
+use fnv::{FnvHashMap, FnvHashSet};
 use futures::Stream;
 
 use crate::{
@@ -16,6 +16,7 @@ use crate::{
         Definition, Document, Fragment, FromInputValue, InputValue, Operation, OperationType,
         Selection, ToInputValue, Type,
     },
+    macros::helper::InputObjectFieldNames,
     parser::{SourcePosition, Spanning},
     schema::{
         meta::{
@@ -30,12 +31,15 @@ use crate::{
         base::{GraphQLType, GraphQLValue},
         name::Name,
         subscriptions::{GraphQLSubscriptionType, GraphQLSubscriptionValue},
+        upload::{Upload, UploadedFile, Uploads},
     },
-    value::{DefaultScalarValue, ParseScalarValue, ScalarValue, Value},
+    value::{DefaultScalarValue, Object, ParseScalarValue, ScalarValue, Value},
     GraphQLError,
 };
 
 pub use self::{
+    cache_control::{CacheHint, CachePolicy, CacheScope},
+    guard::Guard,
     look_ahead::{
         Applies, ChildSelection, ConcreteLookAheadSelection, LookAheadArgument, LookAheadMethods,
         LookAheadSelection, LookAheadValue,
@@ -43,6 +47,8 @@ pub use self::{
     owned_executor::OwnedExecutor,
 };
 
+mod cache_control;
+mod guard;
 mod look_ahead;
 mod owned_executor;
 
@@ -54,6 +60,19 @@ mod owned_executor;
 pub struct Registry<'r, S = DefaultScalarValue> {
     /// Currently registered types
     pub types: FnvHashMap<Name, MetaType<'r, S>>,
+
+    /// Rust types the registered types were first built from
+    sources: FnvHashMap<Name, &'static str>,
+
+    /// Rust types already checked to resolve to the registered type of their name
+    checked: FnvHashSet<(Name, &'static str)>,
+
+    /// Rust types with a different definition than the registered type of their name
+    collisions: FnvHashMap<String, Vec<&'static str>>,
+
+    /// Generic Rust types along with their type parameters without a GraphQL name, substituted
+    /// into their name templates
+    unnamed_params: Vec<(&'static str, &'static str)>,
 }
 
 #[allow(missing_docs)]
@@ -80,6 +99,9 @@ where
     schema: &'a SchemaType<'a, S>,
     context: &'a CtxT,
     errors: &'r RwLock<Vec<ExecutionError<S>>>,
+    cache_hint: &'r RwLock<CacheHint>,
+    request_meta: RequestMeta<'r, S>,
+    response_extensions: &'r RwLock<Object<S>>,
     field_path: Arc<FieldPath<'a>>,
 }
 
@@ -243,6 +265,42 @@ pub type ExecutionResult<S = DefaultScalarValue> = Result<Value<S>, FieldError<S
 pub type ValuesStream<'a, S = DefaultScalarValue> =
     std::pin::Pin<Box<dyn Stream<Item = Result<Value<S>, ExecutionError<S>>> + Send + 'a>>;
 
+/// Metadata of a GraphQL request passed to the executor, besides its query and variables
+pub(crate) struct RequestMeta<'r, S> {
+    pub(crate) extensions: Option<&'r InputValue<S>>,
+    pub(crate) uploads: Option<&'r Uploads>,
+    pub(crate) forbid_mutations: bool,
+}
+
+impl<'r, S> Clone for RequestMeta<'r, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'r, S> Copy for RequestMeta<'r, S> {}
+
+impl<'r, S> Default for RequestMeta<'r, S> {
+    fn default() -> Self {
+        Self {
+            extensions: None,
+            uploads: None,
+            forbid_mutations: false,
+        }
+    }
+}
+
+/// Metadata of a GraphQL response gathered while executing a query, besides its data and errors
+#[derive(Debug)]
+pub(crate) struct ResponseMeta<S> {
+    pub(crate) cache_policy: Option<CachePolicy>,
+    pub(crate) extensions: Object<S>,
+}
+
+/// The result of executing a whole query along with the [`ResponseMeta`] of its response
+pub(crate) type QueryResultWithMeta<'a, S> =
+    Result<(Value<S>, Vec<ExecutionError<S>>, ResponseMeta<S>), GraphQLError<'a>>;
+
 /// The map of variables used for substitution during query execution
 pub type Variables<S = DefaultScalarValue> = HashMap<String, InputValue<S>>;
 
@@ -537,6 +595,9 @@ where
             schema: self.schema,
             context: ctx,
             errors: self.errors,
+            cache_hint: self.cache_hint,
+            request_meta: self.request_meta,
+            response_extensions: self.response_extensions,
             field_path: self.field_path.clone(),
         }
     }
@@ -565,6 +626,9 @@ where
             schema: self.schema,
             context: self.context,
             errors: self.errors,
+            cache_hint: self.cache_hint,
+            request_meta: self.request_meta,
+            response_extensions: self.response_extensions,
             field_path: Arc::new(FieldPath::Field(
                 field_alias,
                 location,
@@ -591,6 +655,9 @@ where
             schema: self.schema,
             context: self.context,
             errors: self.errors,
+            cache_hint: self.cache_hint,
+            request_meta: self.request_meta,
+            response_extensions: self.response_extensions,
             field_path: self.field_path.clone(),
         }
     }
@@ -652,6 +719,92 @@ where
         });
     }
 
+    /// Restricts the [`CachePolicy`] of the whole response by the given [`CacheHint`].
+    ///
+    /// Allows to provide cache hints dynamically, in addition to the ones declared on object
+    /// types and fields.
+    pub fn restrict_cache_policy(&self, hint: &CacheHint) {
+        self.cache_hint.write().unwrap().restrict(hint);
+    }
+
+    /// Restricts the [`CachePolicy`] of the whole response by the hint of the given `field` of
+    /// the `parent_type` being resolved.
+    ///
+    /// A field without a hint makes the whole response uncacheable, unless its parent type
+    /// declares a hint, it returns an object type declaring a hint, or it returns a leaf type and
+    /// isn't a root field (inheriting the hint of its parent field then).
+    pub(crate) fn restrict_cache_policy_by_field(
+        &self,
+        parent_type: &MetaType<S>,
+        field: &Field<S>,
+    ) {
+        if let Some(ref hint) = field.cache_hint {
+            return self.restrict_cache_policy(hint);
+        }
+        if let MetaType::Object(ObjectMeta {
+            cache_hint: Some(_),
+            ..
+        }) = *parent_type
+        {
+            return;
+        }
+
+        let is_root = parent_type.name() == Some(self.schema.query_type_name.as_str());
+        let is_hinted = match self
+            .schema
+            .concrete_type_by_name(field.field_type.innermost_name())
+        {
+            Some(MetaType::Object(ObjectMeta {
+                cache_hint: Some(_),
+                ..
+            })) => true,
+            Some(t) => t.is_leaf() && !is_root,
+            None => false,
+        };
+        if !is_hinted {
+            self.restrict_cache_policy(&CacheHint::new(Some(0), None));
+        }
+    }
+
+    /// Returns the `extensions` of the GraphQL request being executed, if any.
+    ///
+    /// Those are provided by [`GraphQLRequest::execute`] and [`GraphQLRequest::execute_sync`]
+    /// only, and are not available while resolving subscriptions.
+    ///
+    /// [`GraphQLRequest::execute`]: crate::http::GraphQLRequest::execute
+    /// [`GraphQLRequest::execute_sync`]: crate::http::GraphQLRequest::execute_sync
+    pub fn request_extensions(&self) -> Option<&'r InputValue<S>> {
+        self.request_meta.extensions
+    }
+
+    /// Returns the file referenced by the given [`Upload`], if it was uploaded along with the
+    /// GraphQL request being executed.
+    ///
+    /// Files are only uploaded with [GraphQL multipart requests][1], and are not available while
+    /// resolving subscriptions.
+    ///
+    /// [1]: https://github.com/jaydenseric/graphql-multipart-request-spec
+    pub fn upload(&self, upload: &Upload) -> Option<&'r UploadedFile> {
+        self.request_meta.uploads?.get(upload.key())
+    }
+
+    /// Adds an entry to the `extensions` of the GraphQL response, replacing the previous value
+    /// of the same `key`, if any.
+    ///
+    /// The `extensions` are serialized by [`GraphQLResponse`], and so by all the integration
+    /// crates. They are not available while resolving subscriptions.
+    ///
+    /// [`GraphQLResponse`]: crate::http::GraphQLResponse
+    pub fn add_response_extension<K>(&self, key: K, value: Value<S>)
+    where
+        K: AsRef<str> + Into<String>,
+    {
+        self.response_extensions
+            .write()
+            .unwrap()
+            .add_field(key, value);
+    }
+
     /// Returns new [`ExecutionError`] at current location
     pub fn new_error(&self, error: FieldError<S>) -> ExecutionError<S> {
         let mut path = Vec::new();
@@ -737,6 +890,10 @@ where
             schema: self.schema,
             context: self.context,
             errors: RwLock::new(vec![]),
+            cache_hint: RwLock::new(CacheHint::default()),
+            request_extensions: self.request_meta.extensions.cloned(),
+            uploads: self.request_meta.uploads.cloned(),
+            response_extensions: RwLock::new(Object::with_capacity(0)),
             field_path: Arc::clone(&self.field_path),
         }
     }
@@ -795,6 +952,35 @@ pub fn execute_validated_query<'a, 'b, QueryT, MutationT, SubscriptionT, S>(
     variables: &Variables<S>,
     context: &QueryT::Context,
 ) -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+where
+    S: ScalarValue,
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S, Context = QueryT::Context>,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+{
+    execute_validated_query_with_meta(
+        document,
+        operation,
+        root_node,
+        variables,
+        context,
+        RequestMeta::default(),
+    )
+    .map(|(value, errors, _)| (value, errors))
+}
+
+/// Same as [`execute_validated_query`], but also accepts the [`RequestMeta`] of the request and
+/// returns the [`ResponseMeta`] of the response.
+///
+/// Returns `IsMutation` error if a mutation is passed while the [`RequestMeta`] forbids them.
+pub(crate) fn execute_validated_query_with_meta<'a, 'b, QueryT, MutationT, SubscriptionT, S>(
+    document: &'b Document<S>,
+    operation: &'b Spanning<Operation<S>>,
+    root_node: &RootNode<QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+    request_meta: RequestMeta<'_, S>,
+) -> QueryResultWithMeta<'a, S>
 where
     S: ScalarValue,
     QueryT: GraphQLType<S>,
@@ -804,6 +990,9 @@ where
     if operation.item.operation_type == OperationType::Subscription {
         return Err(GraphQLError::IsSubscription);
     }
+    if request_meta.forbid_mutations && operation.item.operation_type == OperationType::Mutation {
+        return Err(GraphQLError::IsMutation);
+    }
 
     let mut fragments = vec![];
     for def in document.iter() {
@@ -825,6 +1014,8 @@ where
     });
 
     let errors = RwLock::new(Vec::new());
+    let cache_hint = RwLock::new(CacheHint::default());
+    let response_extensions = RwLock::new(Object::with_capacity(0));
     let value;
 
     {
@@ -862,6 +1053,9 @@ where
             schema: &root_node.schema,
             context,
             errors: &errors,
+            cache_hint: &cache_hint,
+            request_meta,
+            response_extensions: &response_extensions,
             field_path: Arc::new(FieldPath::Root(operation.start)),
         };
 
@@ -877,7 +1071,18 @@ where
     let mut errors = errors.into_inner().unwrap();
     errors.sort();
 
-    Ok((value, errors))
+    // Mutations' results are never cacheable.
+    let cache_policy = match operation.item.operation_type {
+        OperationType::Query => cache_hint.into_inner().unwrap().policy(),
+        _ => None,
+    };
+
+    let meta = ResponseMeta {
+        cache_policy,
+        extensions: response_extensions.into_inner().unwrap(),
+    };
+
+    Ok((value, errors, meta))
 }
 
 /// Create new `Executor` and start asynchronous query execution.
@@ -889,6 +1094,47 @@ pub async fn execute_validated_query_async<'a, 'b, QueryT, MutationT, Subscripti
     variables: &Variables<S>,
     context: &QueryT::Context,
 ) -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+where
+    QueryT: GraphQLTypeAsync<S>,
+    QueryT::TypeInfo: Sync,
+    QueryT::Context: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+    SubscriptionT::TypeInfo: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    execute_validated_query_async_with_meta(
+        document,
+        operation,
+        root_node,
+        variables,
+        context,
+        RequestMeta::default(),
+    )
+    .await
+    .map(|(value, errors, _)| (value, errors))
+}
+
+/// Same as [`execute_validated_query_async`], but also accepts the [`RequestMeta`] of the
+/// request and returns the [`ResponseMeta`] of the response.
+///
+/// Returns `IsMutation` error if a mutation is passed while the [`RequestMeta`] forbids them.
+pub(crate) async fn execute_validated_query_async_with_meta<
+    'a,
+    'b,
+    QueryT,
+    MutationT,
+    SubscriptionT,
+    S,
+>(
+    document: &'b Document<'a, S>,
+    operation: &'b Spanning<Operation<'_, S>>,
+    root_node: &RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+    request_meta: RequestMeta<'_, S>,
+) -> QueryResultWithMeta<'a, S>
 where
     QueryT: GraphQLTypeAsync<S>,
     QueryT::TypeInfo: Sync,
@@ -902,6 +1148,9 @@ where
     if operation.item.operation_type == OperationType::Subscription {
         return Err(GraphQLError::IsSubscription);
     }
+    if request_meta.forbid_mutations && operation.item.operation_type == OperationType::Mutation {
+        return Err(GraphQLError::IsMutation);
+    }
 
     let mut fragments = vec![];
     for def in document.iter() {
@@ -923,6 +1172,8 @@ where
     });
 
     let errors = RwLock::new(Vec::new());
+    let cache_hint = RwLock::new(CacheHint::default());
+    let response_extensions = RwLock::new(Object::with_capacity(0));
     let value;
 
     {
@@ -960,6 +1211,9 @@ where
             schema: &root_node.schema,
             context,
             errors: &errors,
+            cache_hint: &cache_hint,
+            request_meta,
+            response_extensions: &response_extensions,
             field_path: Arc::new(FieldPath::Root(operation.start)),
         };
 
@@ -981,7 +1235,18 @@ where
     let mut errors = errors.into_inner().unwrap();
     errors.sort();
 
-    Ok((value, errors))
+    // Mutations' results are never cacheable.
+    let cache_policy = match operation.item.operation_type {
+        OperationType::Query => cache_hint.into_inner().unwrap().policy(),
+        _ => None,
+    };
+
+    let meta = ResponseMeta {
+        cache_policy,
+        extensions: response_extensions.into_inner().unwrap(),
+    };
+
+    Ok((value, errors, meta))
 }
 
 #[doc(hidden)]
@@ -1070,6 +1335,8 @@ where
     });
 
     let errors = RwLock::new(Vec::new());
+    let cache_hint = RwLock::new(CacheHint::default());
+    let response_extensions = RwLock::new(Object::with_capacity(0));
     let value;
 
     {
@@ -1106,6 +1373,9 @@ where
             schema: &root_node.schema,
             context,
             errors: &errors,
+            cache_hint: &cache_hint,
+            request_meta: RequestMeta::default(),
+            response_extensions: &response_extensions,
             field_path: Arc::new(FieldPath::Root(operation.start)),
         };
 
@@ -1131,26 +1401,52 @@ where
 {
     /// Construct a new registry
     pub fn new(types: FnvHashMap<Name, MetaType<'r, S>>) -> Registry<'r, S> {
-        Registry { types }
+        Registry {
+            types,
+            sources: FnvHashMap::default(),
+            checked: FnvHashSet::default(),
+            collisions: FnvHashMap::default(),
+            unnamed_params: Vec::new(),
+        }
     }
 
     /// Get the `Type` instance for a given GraphQL type
     ///
     /// If the registry hasn't seen a type with this name before, it will
     /// construct its metadata and store it.
+    ///
+    /// A Rust type with a different definition than the type already
+    /// registered with the same name, e.g. another instantiation of a generic
+    /// type without a name template, is recorded as a collision, reported
+    /// when building the schema.
     pub fn get_type<T>(&mut self, info: &T::TypeInfo) -> Type<'r>
     where
         T: GraphQLType<S> + ?Sized,
     {
         if let Some(name) = T::name(info) {
             let validated_name = name.parse::<Name>().unwrap();
+            let source = std::any::type_name::<T>();
+            let first_check = self.checked.insert((validated_name.clone(), source));
             if !self.types.contains_key(name) {
                 self.insert_placeholder(
                     validated_name.clone(),
                     Type::NonNullNamed(Cow::Owned(name.to_string())),
                 );
+                self.sources.insert(validated_name.clone(), source);
                 let meta = T::meta(info, self);
                 self.types.insert(validated_name, meta);
+            } else if first_check {
+                // Wrappers like `Box<T>` share the name of `T` and build the
+                // same metadata, as do instantiations of generic types not
+                // depending on their type parameters, so the metadata of each
+                // Rust type is built once to compare it.
+                let meta = T::meta(info, self);
+                if !self.types[name].has_same_shape(&meta) {
+                    self.collisions
+                        .entry(name.to_owned())
+                        .or_default()
+                        .push(source);
+                }
             }
             self.types[name].as_type()
         } else {
@@ -1158,6 +1454,47 @@ where
         }
     }
 
+    /// Records that the type parameter `P` substituted into the name template
+    /// of the generic type `T` has no GraphQL name, reported when building the
+    /// schema.
+    #[doc(hidden)]
+    pub fn add_unnamed_type_param<T: ?Sized, P: ?Sized>(&mut self) {
+        let unnamed = (std::any::type_name::<T>(), std::any::type_name::<P>());
+        if !self.unnamed_params.contains(&unnamed) {
+            self.unnamed_params.push(unnamed);
+        }
+    }
+
+    /// Panics if any GraphQL type name is used by Rust types with different
+    /// definitions, or is composed from a type parameter without a GraphQL
+    /// name, listing all of them.
+    pub(crate) fn assert_valid_type_names(&self) {
+        let mut errors = self
+            .collisions
+            .iter()
+            .map(|(name, sources)| {
+                format!(
+                    "GraphQL type name `{}` is used by different types: `{}`, `{}`",
+                    name,
+                    self.sources[name],
+                    sources.join("`, `"),
+                )
+            })
+            .chain(self.unnamed_params.iter().map(|(ty, param)| {
+                format!(
+                    "type parameter `{}` of `{}` must be a named GraphQL type to be substituted \
+                     into its name",
+                    param, ty,
+                )
+            }))
+            .collect::<Vec<_>>();
+        if errors.is_empty() {
+            return;
+        }
+        errors.sort();
+        panic!("{}", errors.join("\n"));
+    }
+
     /// Create a field with the provided name
     pub fn field<T>(&mut self, name: &str, info: &T::TypeInfo) -> Field<'r, S>
     where
@@ -1169,6 +1506,8 @@ where
             arguments: None,
             field_type: self.get_type::<T>(info),
             deprecation_status: DeprecationStatus::Current,
+            visibility: None,
+            cache_hint: None,
         }
     }
 
@@ -1187,6 +1526,8 @@ where
             arguments: None,
             field_type: self.get_type::<I>(info),
             deprecation_status: DeprecationStatus::Current,
+            visibility: None,
+            cache_hint: None,
         }
     }
 
@@ -1214,6 +1555,19 @@ where
         Argument::new(name, self.get_type::<Option<T>>(info)).default_value(value.to_input_value())
     }
 
+    /// Create the arguments of a field flattened from the fields of the input object `T`
+    ///
+    /// The input object itself isn't registered, only the types of its fields are.
+    pub fn flattened_args<T>(&mut self, info: &T::TypeInfo) -> Vec<Argument<'r, S>>
+    where
+        T: GraphQLType<S> + FromInputValue<S> + InputObjectFieldNames<S>,
+    {
+        match T::meta(info, self) {
+            MetaType::InputObject(InputObjectMeta { input_fields, .. }) => input_fields,
+            _ => unreachable!("`InputObjectFieldNames` is only implemented by input objects"),
+        }
+    }
+
     fn insert_placeholder(&mut self, name: Name, of_type: Type<'r>) {
         self.types
             .entry(name)
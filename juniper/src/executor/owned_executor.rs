@@ -4,10 +4,12 @@ use std::{
 };
 
 use crate::{
-    ast::Fragment,
-    executor::FieldPath,
+    ast::{Fragment, InputValue},
+    executor::{CacheHint, FieldPath, RequestMeta},
     parser::SourcePosition,
     schema::model::{SchemaType, TypeType},
+    types::upload::Uploads,
+    value::Object,
     ExecutionError, Executor, Selection, Variables,
 };
 
@@ -22,6 +24,10 @@ pub struct OwnedExecutor<'a, CtxT, S> {
     pub(super) schema: &'a SchemaType<'a, S>,
     pub(super) context: &'a CtxT,
     pub(super) errors: RwLock<Vec<ExecutionError<S>>>,
+    pub(super) cache_hint: RwLock<CacheHint>,
+    pub(super) request_extensions: Option<InputValue<S>>,
+    pub(super) uploads: Option<Uploads>,
+    pub(super) response_extensions: RwLock<Object<S>>,
     pub(super) field_path: Arc<FieldPath<'a>>,
 }
 
@@ -39,6 +45,10 @@ where
             schema: self.schema,
             context: self.context,
             errors: RwLock::new(vec![]),
+            cache_hint: RwLock::new(CacheHint::default()),
+            request_extensions: self.request_extensions.clone(),
+            uploads: self.uploads.clone(),
+            response_extensions: RwLock::new(Object::with_capacity(0)),
             field_path: self.field_path.clone(),
         }
     }
@@ -66,6 +76,10 @@ where
             schema: self.schema,
             context: self.context,
             errors: RwLock::new(vec![]),
+            cache_hint: RwLock::new(CacheHint::default()),
+            request_extensions: self.request_extensions.clone(),
+            uploads: self.uploads.clone(),
+            response_extensions: RwLock::new(Object::with_capacity(0)),
             field_path: self.field_path.clone(),
         }
     }
@@ -99,6 +113,10 @@ where
             schema: self.schema,
             context: self.context,
             errors: RwLock::new(vec![]),
+            cache_hint: RwLock::new(CacheHint::default()),
+            request_extensions: self.request_extensions.clone(),
+            uploads: self.uploads.clone(),
+            response_extensions: RwLock::new(Object::with_capacity(0)),
             field_path: Arc::new(FieldPath::Field(
                 field_alias,
                 location,
@@ -118,6 +136,13 @@ where
             schema: self.schema,
             context: self.context,
             errors: &self.errors,
+            cache_hint: &self.cache_hint,
+            request_meta: RequestMeta {
+                extensions: self.request_extensions.as_ref(),
+                uploads: self.uploads.as_ref(),
+                forbid_mutations: false,
+            },
+            response_extensions: &self.response_extensions,
             field_path: Arc::clone(&self.field_path),
         }
     }
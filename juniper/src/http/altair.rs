@@ -0,0 +1,166 @@
+//! Utility module to generate an [Altair GraphQL Client][Altair] interface.
+//!
+//! [Altair]: https://altairgraphql.dev
+
+use super::assets::{js_headers, js_string, Asset, AssetSource};
+
+/// Builder of the HTML source of an [Altair GraphQL Client][Altair] page.
+///
+/// Altair loads parts of itself lazily, relative to its scripts, so to serve it without network
+/// access the whole `build/dist` directory of the [`altair-static`][1] package should be hosted
+/// under the [assets URL][`AltairSource::assets_url()`].
+///
+/// ```
+/// # use juniper::http::altair::AltairSource;
+/// let altair = AltairSource::new("/graphql")
+///     .subscriptions_endpoint("/subscriptions")
+///     .header("Authorization", "Bearer token")
+///     .query("{ hero { name } }")
+///     .render();
+/// ```
+///
+/// [Altair]: https://altairgraphql.dev
+/// [1]: https://www.npmjs.com/package/altair-static
+#[derive(Clone, Debug, PartialEq)]
+pub struct AltairSource {
+    endpoint: String,
+    subscriptions_endpoint: Option<String>,
+    headers: Vec<(String, String)>,
+    query: Option<String>,
+    variables: Option<String>,
+    assets: AssetSource,
+}
+
+impl AltairSource {
+    /// [`Asset`]s loaded by the page, relative to the CDN or the
+    /// [assets URL][`AltairSource::assets_url()`].
+    pub const ASSETS: &'static [Asset] = &[
+        Asset::stylesheet("altair-static@5.2.13/build/dist/styles.css"),
+        Asset::script("altair-static@5.2.13/build/dist/runtime.js"),
+        Asset::script("altair-static@5.2.13/build/dist/polyfills.js"),
+        Asset::script("altair-static@5.2.13/build/dist/main.js"),
+    ];
+
+    /// Creates a new [`AltairSource`] sending requests to the given `endpoint` URL.
+    ///
+    /// Relative URLs are resolved against the location of the page.
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            subscriptions_endpoint: None,
+            headers: Vec::new(),
+            query: None,
+            variables: None,
+            assets: AssetSource::default(),
+        }
+    }
+
+    /// Sets the WebSocket URL to send subscriptions to.
+    ///
+    /// Relative URLs are resolved against the location of the page.
+    pub fn subscriptions_endpoint(mut self, url: impl Into<String>) -> Self {
+        self.subscriptions_endpoint = Some(url.into());
+        self
+    }
+
+    /// Adds a header sent with every request.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Sets the query shown in the editor initially.
+    pub fn query(mut self, query: impl Into<String>) -> Self {
+        self.query = Some(query.into());
+        self
+    }
+
+    /// Sets the JSON-encoded variables shown in the editor initially.
+    pub fn variables(mut self, variables: impl Into<String>) -> Self {
+        self.variables = Some(variables.into());
+        self
+    }
+
+    /// Loads the [`AltairSource::ASSETS`] from the given base URL, instead of the CDN.
+    pub fn assets_url(mut self, url: impl Into<String>) -> Self {
+        self.assets.set_base_url(url.into());
+        self
+    }
+
+    /// Renders the HTML source of the page.
+    pub fn render(&self) -> String {
+        let optional = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map_or_else(|| "undefined".into(), js_string)
+        };
+        let subscriptions_endpoint = match &self.subscriptions_endpoint {
+            Some(url) => format!("wsUrl({})", js_string(url)),
+            None => "undefined".into(),
+        };
+
+        format!(
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8" />
+  <title>Altair</title>
+{assets}</head>
+<body>
+  <app-root>Loading...</app-root>
+  <script>
+    function wsUrl(url) {{
+      if (url.startsWith('/')) {{
+        return (location.protocol === 'https:' ? 'wss://' : 'ws://') + location.host + url;
+      }}
+      return url.replace(/^http/, 'ws');
+    }}
+
+    window.addEventListener('load', function () {{
+      AltairGraphQL.init({{
+        endpointURL: new URL({endpoint}, location.href).href,
+        subscriptionsEndpoint: {subscriptions_endpoint},
+        initialQuery: {query},
+        initialVariables: {variables},
+        initialHeaders: {headers},
+      }});
+    }});
+  </script>
+</body>
+</html>
+"#,
+            assets = self.assets.render(Self::ASSETS, CDN_URL),
+            endpoint = js_string(&self.endpoint),
+            subscriptions_endpoint = subscriptions_endpoint,
+            query = optional(&self.query),
+            variables = optional(&self.variables),
+            headers = js_headers(&self.headers),
+        )
+    }
+}
+
+const CDN_URL: &str = "https://cdn.jsdelivr.net/npm";
+
+#[cfg(test)]
+mod tests {
+    use super::AltairSource;
+
+    #[test]
+    fn renders_altair_source() {
+        let source = AltairSource::new("/graphql")
+            .subscriptions_endpoint("ws://localhost:8080/subscriptions")
+            .header("Authorization", "Bearer token")
+            .variables(r#"{"episode": "JEDI"}"#)
+            .assets_url("/altair")
+            .render();
+
+        assert!(source.contains(r#"endpointURL: new URL("/graphql", location.href).href,"#));
+        assert!(source
+            .contains(r#"subscriptionsEndpoint: wsUrl("ws://localhost:8080/subscriptions"),"#,));
+        assert!(source.contains("initialQuery: undefined,"));
+        assert!(source.contains(r#"initialVariables: "{\"episode\": \"JEDI\"}","#));
+        assert!(source.contains(r#"initialHeaders: {"Authorization": "Bearer token"},"#));
+        assert!(source.contains(r#"<script src="/altair/altair-static@5.2.13/build/dist/main.js""#));
+        assert!(!source.contains("jsdelivr"));
+    }
+}
@@ -0,0 +1,281 @@
+//! JavaScript and CSS assets loaded by the GraphQL IDE pages.
+//!
+//! By default the pages load their assets from a public CDN. To serve them in environments
+//! without network access, either host the assets yourself and point the page to them with the
+//! `assets_url()` method of its builder, or inline their contents into the page with the
+//! `inline_asset()` one (with [`include_str!`], for example, to embed them into the binary).
+//!
+//! With the `embedded-assets` feature, the GraphiQL and Playground assets are embedded into the
+//! binary at compile time from the directory in the `JUNIPER_ASSETS_DIR` environment variable,
+//! laid out like the CDN (`react@18.2.0/umd/react.production.min.js`, for example), and inlined
+//! into the pages built with the `embedded_assets()` method. The assets missing there, or all of
+//! them if the variable isn't set, are reported as build warnings and loaded from the CDN.
+
+use std::{collections::HashMap, fmt::Write as _};
+
+/// Kind of an [`Asset`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AssetKind {
+    /// JavaScript loaded with a `<script>` tag.
+    Script,
+
+    /// CSS loaded with a `<link rel="stylesheet">` tag.
+    Stylesheet,
+}
+
+/// JavaScript or CSS file loaded by a GraphQL IDE page.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Asset {
+    /// Kind of this [`Asset`].
+    pub kind: AssetKind,
+
+    /// Path of this [`Asset`] relative to the base URL it's loaded from, including the package
+    /// name and version, like `react@18.2.0/umd/react.production.min.js`.
+    pub path: &'static str,
+}
+
+impl Asset {
+    pub(crate) const fn script(path: &'static str) -> Self {
+        Self {
+            kind: AssetKind::Script,
+            path,
+        }
+    }
+
+    pub(crate) const fn stylesheet(path: &'static str) -> Self {
+        Self {
+            kind: AssetKind::Stylesheet,
+            path,
+        }
+    }
+}
+
+/// Where the [`Asset`]s of a page are loaded from.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct AssetSource {
+    base_url: Option<String>,
+    inline: HashMap<String, String>,
+    #[cfg(feature = "embedded-assets")]
+    embedded: bool,
+}
+
+impl AssetSource {
+    pub(crate) fn set_base_url(&mut self, base_url: String) {
+        self.base_url = Some(base_url.trim_end_matches('/').to_owned());
+    }
+
+    pub(crate) fn inline(&mut self, path: String, contents: String) {
+        self.inline.insert(path, contents);
+    }
+
+    #[cfg(feature = "embedded-assets")]
+    pub(crate) fn inline_embedded(&mut self) {
+        self.embedded = true;
+    }
+
+    /// Returns the contents of the given `asset` to be inlined into the page, if any.
+    fn inline_contents(&self, asset: &Asset) -> Option<&str> {
+        let contents = self.inline.get(asset.path).map(String::as_str);
+        #[cfg(feature = "embedded-assets")]
+        let contents = contents.or_else(|| self.embedded.then(|| embedded(asset.path)).flatten());
+        contents
+    }
+
+    /// Renders the tags loading the given `assets`, falling back to the given `cdn_url` when no
+    /// base URL is set.
+    pub(crate) fn render(&self, assets: &[Asset], cdn_url: &str) -> String {
+        let base_url = html_attribute(self.base_url.as_deref().unwrap_or(cdn_url));
+        let mut html = String::new();
+        for asset in assets {
+            match (asset.kind, self.inline_contents(asset)) {
+                (AssetKind::Script, Some(contents)) => writeln!(
+                    html,
+                    "<script>{}</script>",
+                    escape_end_tags(contents, "script"),
+                ),
+                (AssetKind::Stylesheet, Some(contents)) => writeln!(
+                    html,
+                    "<style>{}</style>",
+                    escape_end_tags(contents, "style"),
+                ),
+                (AssetKind::Script, None) => writeln!(
+                    html,
+                    r#"<script src="{}/{}" crossorigin></script>"#,
+                    base_url, asset.path,
+                ),
+                (AssetKind::Stylesheet, None) => writeln!(
+                    html,
+                    r#"<link rel="stylesheet" href="{}/{}" crossorigin />"#,
+                    base_url, asset.path,
+                ),
+            }
+            .unwrap();
+        }
+        html
+    }
+}
+
+/// Escapes the end tags of the given `tag` in the `contents` of an inline `<script>` or
+/// `<style>` element, which would otherwise close the element.
+///
+/// The `/` of every case-insensitive `</tag` is escaped as `\/`, which is still the same string
+/// in JavaScript and the same character in CSS.
+fn escape_end_tags(contents: &str, tag: &str) -> String {
+    let mut escaped = String::with_capacity(contents.len());
+    let mut rest = contents;
+    while let Some(pos) = rest.find("</") {
+        let after = &rest[pos + 2..];
+        let is_end_tag = after
+            .get(..tag.len())
+            .is_some_and(|name| name.eq_ignore_ascii_case(tag));
+        escaped.push_str(&rest[..pos]);
+        escaped.push_str(if is_end_tag { "<\\/" } else { "</" });
+        rest = after;
+    }
+    escaped.push_str(rest);
+    escaped
+}
+
+/// Escapes the given `value` to be embedded into a double-quoted HTML attribute.
+fn html_attribute(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// Generated by the build script out of the `JUNIPER_ASSETS_DIR` directory.
+#[cfg(feature = "embedded-assets")]
+include!(concat!(env!("OUT_DIR"), "/embedded_assets.rs"));
+
+/// Encodes the given `value` as a JavaScript string literal, safe to be embedded into a
+/// `<script>` tag.
+pub(crate) fn js_string(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len() + 2);
+    encoded.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => encoded.push_str("\\\""),
+            '\\' => encoded.push_str("\\\\"),
+            '\n' => encoded.push_str("\\n"),
+            '\r' => encoded.push_str("\\r"),
+            '\t' => encoded.push_str("\\t"),
+            '<' | '>' | '&' | '\u{2028}' | '\u{2029}' => {
+                write!(encoded, "\\u{:04x}", c as u32).unwrap()
+            }
+            c if c.is_control() => write!(encoded, "\\u{:04x}", c as u32).unwrap(),
+            c => encoded.push(c),
+        }
+    }
+    encoded.push('"');
+    encoded
+}
+
+/// Encodes the given `headers` as a JavaScript object literal.
+pub(crate) fn js_headers(headers: &[(String, String)]) -> String {
+    let fields = headers
+        .iter()
+        .map(|(name, value)| format!("{}: {}", js_string(name), js_string(value)))
+        .collect::<Vec<_>>();
+    format!("{{{}}}", fields.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{escape_end_tags, js_headers, js_string, Asset, AssetSource};
+
+    #[test]
+    fn encodes_js_strings() {
+        assert_eq!(js_string("query"), r#""query""#);
+        assert_eq!(js_string("{ a(b: \"c\") }\n"), r#""{ a(b: \"c\") }\n""#,);
+        assert_eq!(
+            js_string("</script><script>alert(1)"),
+            r#""\u003c/script\u003e\u003cscript\u003ealert(1)""#,
+        );
+    }
+
+    #[test]
+    fn encodes_js_headers() {
+        assert_eq!(js_headers(&[]), "{}");
+        assert_eq!(
+            js_headers(&[
+                ("Authorization".into(), "Bearer token".into()),
+                ("X-Tenant".into(), "a\"b".into()),
+            ]),
+            r#"{"Authorization": "Bearer token", "X-Tenant": "a\"b"}"#,
+        );
+    }
+
+    #[test]
+    fn escapes_end_tags() {
+        assert_eq!(
+            escape_end_tags("a</script>b</SCRIPT >c</scripts</div>", "script"),
+            "a<\\/script>b<\\/SCRIPT >c<\\/scripts</div>",
+        );
+        assert_eq!(
+            escape_end_tags("a { content: '</Style>'; }</", "style"),
+            "a { content: '<\\/Style>'; }</",
+        );
+    }
+
+    #[test]
+    fn renders_assets() {
+        let assets = [
+            Asset::script("lib@1.0.0/lib.js"),
+            Asset::stylesheet("lib@1.0.0/lib.css"),
+        ];
+
+        let mut source = AssetSource::default();
+        assert_eq!(
+            source.render(&assets, "https://cdn.example.com"),
+            "<script src=\"https://cdn.example.com/lib@1.0.0/lib.js\" crossorigin></script>\n\
+             <link rel=\"stylesheet\" href=\"https://cdn.example.com/lib@1.0.0/lib.css\" crossorigin />\n",
+        );
+
+        source.set_base_url("/assets/".into());
+        source.inline("lib@1.0.0/lib.js".into(), "var a = '</script>';".into());
+        assert_eq!(
+            source.render(&assets, "https://cdn.example.com"),
+            "<script>var a = '<\\/script>';</script>\n\
+             <link rel=\"stylesheet\" href=\"/assets/lib@1.0.0/lib.css\" crossorigin />\n",
+        );
+
+        source.inline(
+            "lib@1.0.0/lib.css".into(),
+            "a { content: '</style>'; }".into(),
+        );
+        assert_eq!(
+            source.render(&assets, "https://cdn.example.com"),
+            "<script>var a = '<\\/script>';</script>\n\
+             <style>a { content: '<\\/style>'; }</style>\n",
+        );
+        let mut source = AssetSource::default();
+        source.set_base_url("/\"><script>".into());
+        assert_eq!(
+            source.render(&assets[..1], "https://cdn.example.com"),
+            "<script src=\"/&quot;&gt;&lt;script&gt;/lib@1.0.0/lib.js\" crossorigin></script>\n",
+        );
+    }
+
+    #[cfg(feature = "embedded-assets")]
+    #[test]
+    fn embeds_all_assets() {
+        use crate::http::{graphiql::GraphiQLSource, playground::PlaygroundSource};
+
+        let paths = GraphiQLSource::ASSETS
+            .iter()
+            .chain(PlaygroundSource::ASSETS)
+            .map(|asset| asset.path)
+            .collect::<Vec<_>>();
+
+        assert_eq!(paths, super::EMBEDDABLE);
+    }
+}
@@ -0,0 +1,156 @@
+//! Configuration of executing batch requests.
+
+use std::fmt;
+
+use serde::ser::{Serialize, SerializeMap as _, SerializeSeq as _, Serializer};
+
+/// Way of executing the operations of a batch request.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BatchExecution {
+    /// All the operations are executed concurrently.
+    Concurrent,
+
+    /// Operations are executed one by one, in the order they are specified in the batch, so
+    /// each mutation observes the effects of the previous ones.
+    Sequential,
+}
+
+/// Configuration of executing [`GraphQLBatchRequest`]s, passed to
+/// [`GraphQLBatchRequest::execute_with_batch_config()`].
+///
+/// [`GraphQLBatchRequest`]: crate::http::GraphQLBatchRequest
+/// [`GraphQLBatchRequest::execute_with_batch_config()`]: crate::http::GraphQLBatchRequest::execute_with_batch_config
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BatchConfig {
+    /// Whether batch requests are accepted at all.
+    ///
+    /// Defaults to `true`.
+    pub enabled: bool,
+
+    /// Maximum number of operations in a single batch request, if any.
+    ///
+    /// Defaults to `None`, so batch requests of any size are accepted.
+    pub max_size: Option<usize>,
+
+    /// Way of executing the operations of a batch request.
+    ///
+    /// Defaults to [`BatchExecution::Concurrent`].
+    pub execution: BatchExecution,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_size: None,
+            execution: BatchExecution::Concurrent,
+        }
+    }
+}
+
+impl BatchConfig {
+    /// Creates a [`BatchConfig`] rejecting all batch requests.
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Self::default()
+        }
+    }
+
+    /// Checks whether a batch request of the given `size` is allowed by this [`BatchConfig`].
+    pub fn check(&self, size: usize) -> Result<(), BatchError> {
+        if !self.enabled {
+            return Err(BatchError::Disabled);
+        }
+        match self.max_size {
+            Some(max_size) if size > max_size => Err(BatchError::TooLarge { size, max_size }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Error of a batch request rejected by a [`BatchConfig`].
+///
+/// Serializes as a GraphQL response with a single request error, carrying the `code` of the error
+/// in its `extensions`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BatchError {
+    /// Batch requests are [disabled][`BatchConfig::enabled`].
+    Disabled,
+
+    /// Batch request exceeds the [`BatchConfig::max_size`].
+    TooLarge {
+        /// Number of operations in the rejected batch request.
+        size: usize,
+
+        /// Maximum allowed number of operations.
+        max_size: usize,
+    },
+}
+
+impl BatchError {
+    /// Returns the machine-readable code of this [`BatchError`].
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Disabled => "BATCHING_DISABLED",
+            Self::TooLarge { .. } => "BATCH_TOO_LARGE",
+        }
+    }
+}
+
+impl fmt::Display for BatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Disabled => write!(f, "batch requests are not supported"),
+            Self::TooLarge { size, max_size } => write!(
+                f,
+                "batch of {} operations exceeds the limit of {} operations",
+                size, max_size,
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BatchError {}
+
+impl Serialize for BatchError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        struct Error<'a>(&'a BatchError);
+
+        impl<'a> Serialize for Error<'a> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut map = serializer.serialize_map(Some(2))?;
+                map.serialize_entry("message", &self.0.to_string())?;
+                map.serialize_entry("extensions", &Extensions(self.0))?;
+                map.end()
+            }
+        }
+
+        struct Extensions<'a>(&'a BatchError);
+
+        impl<'a> Serialize for Extensions<'a> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut map = serializer.serialize_map(None)?;
+                map.serialize_entry("code", self.0.code())?;
+                if let BatchError::TooLarge { max_size, .. } = self.0 {
+                    map.serialize_entry("maxBatchSize", max_size)?;
+                }
+                map.end()
+            }
+        }
+
+        struct Errors<'a>(&'a BatchError);
+
+        impl<'a> Serialize for Errors<'a> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut seq = serializer.serialize_seq(Some(1))?;
+                seq.serialize_element(&Error(self.0))?;
+                seq.end()
+            }
+        }
+
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry("errors", &Errors(self))?;
+        map.end()
+    }
+}
@@ -0,0 +1,384 @@
+//! In-memory cache of whole GraphQL responses.
+
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    executor::{CachePolicy, CacheScope},
+    value::{DefaultScalarValue, Object, ScalarValue},
+    Value, Variables,
+};
+
+use super::{GraphQLRequest, GraphQLResponse};
+
+/// In-memory cache of GraphQL responses, driven by their [`CachePolicy`].
+///
+/// Responses are keyed by the query, the operation name, the variables and a key derived from the
+/// context by the caller (usually identifying the authenticated client). Only responses without
+/// errors and with a [`CachePolicy`] are stored, and the ones with a [`CacheScope::Private`]
+/// policy are stored only if a context key is provided. A response is only cacheable if all of
+/// its fields are covered by a cache hint, so responses containing unhinted (possibly per-user)
+/// data are never stored.
+///
+/// The cache holds at most [`capacity`] responses, evicting the expired ones and then the least
+/// recently used ones once it's full. Expired responses are also evicted whenever they're looked
+/// up.
+///
+/// See [`GraphQLRequest::execute_cached`] and [`GraphQLRequest::execute_sync_cached`].
+///
+/// [`capacity`]: ResponseCache::capacity
+#[derive(Debug)]
+pub struct ResponseCache<S = DefaultScalarValue> {
+    entries: Mutex<Entries<S>>,
+    capacity: usize,
+}
+
+#[derive(Debug)]
+struct Entries<S> {
+    map: HashMap<EntryKey, Vec<Entry<S>>>,
+    len: usize,
+    clock: u64,
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct EntryKey {
+    query: String,
+    operation_name: Option<String>,
+    context_key: Option<String>,
+}
+
+#[derive(Debug)]
+struct Entry<S> {
+    variables: Variables<S>,
+    value: Value<S>,
+    extensions: Object<S>,
+    scope: CacheScope,
+    expires_at: Instant,
+    last_used: u64,
+}
+
+impl<S> Default for ResponseCache<S> {
+    fn default() -> Self {
+        Self::with_capacity(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl<S> ResponseCache<S> {
+    /// Default maximum number of responses stored by a [`ResponseCache`].
+    pub const DEFAULT_CAPACITY: usize = 1024;
+
+    /// Creates a new empty [`ResponseCache`] storing at most the given number of responses.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: Mutex::new(Entries {
+                map: HashMap::new(),
+                len: 0,
+                clock: 0,
+            }),
+            capacity,
+        }
+    }
+
+    /// Returns the maximum number of responses stored by this [`ResponseCache`].
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of responses currently stored by this [`ResponseCache`], including the
+    /// expired ones not evicted yet.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len
+    }
+
+    /// Indicates whether this [`ResponseCache`] stores no responses.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all the stored responses.
+    pub fn clear(&self) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.map.clear();
+        entries.len = 0;
+    }
+
+    /// Removes all the expired responses.
+    pub fn purge_expired(&self) {
+        self.entries.lock().unwrap().purge_expired(Instant::now());
+    }
+}
+
+impl<S> ResponseCache<S>
+where
+    S: ScalarValue,
+{
+    /// Creates a new empty [`ResponseCache`] storing at most
+    /// [`DEFAULT_CAPACITY`](Self::DEFAULT_CAPACITY) responses.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(super) fn get<'a>(
+        &self,
+        req: &GraphQLRequest<S>,
+        context_key: Option<&str>,
+    ) -> Option<GraphQLResponse<'a, S>> {
+        let key = EntryKey::new(req, context_key);
+        let variables = req.variables();
+        let now = Instant::now();
+
+        let mut entries = self.entries.lock().unwrap();
+        let Entries { map, len, clock } = &mut *entries;
+        let values = map.get_mut(&key)?;
+        let before = values.len();
+        values.retain(|e| e.expires_at > now);
+        *len -= before - values.len();
+        if values.is_empty() {
+            map.remove(&key);
+            return None;
+        }
+
+        let entry = values.iter_mut().find(|e| e.variables == variables)?;
+        *clock += 1;
+        entry.last_used = *clock;
+
+        let policy = CachePolicy {
+            max_age: entry.expires_at.duration_since(now).as_secs() as u32,
+            scope: entry.scope,
+        };
+        Some(GraphQLResponse {
+            result: Ok((entry.value.clone(), vec![])),
+            cache_policy: Some(policy),
+            extensions: entry.extensions.clone(),
+        })
+    }
+
+    pub(super) fn insert(
+        &self,
+        req: &GraphQLRequest<S>,
+        context_key: Option<&str>,
+        resp: &GraphQLResponse<'_, S>,
+    ) {
+        if self.capacity == 0 {
+            return;
+        }
+        let policy = match resp.cache_policy() {
+            Some(p) if p.max_age > 0 => p,
+            _ => return,
+        };
+        if policy.scope == CacheScope::Private && context_key.is_none() {
+            return;
+        }
+        let value = match resp.result {
+            Ok((ref value, _)) => value.clone(),
+            Err(_) => return,
+        };
+
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap();
+        entries.clock += 1;
+        let entry = Entry {
+            variables: req.variables(),
+            value,
+            extensions: resp.extensions.clone(),
+            scope: policy.scope,
+            expires_at: now + Duration::from_secs(policy.max_age.into()),
+            last_used: entries.clock,
+        };
+
+        let key = EntryKey::new(req, context_key);
+        if let Some(values) = entries.map.get_mut(&key) {
+            let before = values.len();
+            values.retain(|e| e.variables != entry.variables);
+            let removed = before - values.len();
+            entries.len -= removed;
+        }
+        if entries.len >= self.capacity {
+            entries.purge_expired(now);
+        }
+        while entries.len >= self.capacity {
+            entries.evict_least_recently_used();
+        }
+
+        entries.map.entry(key).or_default().push(entry);
+        entries.len += 1;
+    }
+}
+
+impl<S> Entries<S> {
+    /// Removes all the entries expired at the given moment.
+    fn purge_expired(&mut self, now: Instant) {
+        let mut len = 0;
+        self.map.retain(|_, values| {
+            values.retain(|e| e.expires_at > now);
+            len += values.len();
+            !values.is_empty()
+        });
+        self.len = len;
+    }
+
+    /// Removes the single least recently used entry.
+    fn evict_least_recently_used(&mut self) {
+        let oldest = self
+            .map
+            .iter()
+            .flat_map(|(key, values)| {
+                values
+                    .iter()
+                    .enumerate()
+                    .map(move |(i, e)| (e.last_used, key, i))
+            })
+            .min_by_key(|(last_used, ..)| *last_used)
+            .map(|(_, key, i)| (key.clone(), i));
+
+        if let Some((key, i)) = oldest {
+            let values = self.map.get_mut(&key).unwrap();
+            values.swap_remove(i);
+            if values.is_empty() {
+                self.map.remove(&key);
+            }
+            self.len -= 1;
+        }
+    }
+}
+
+impl EntryKey {
+    fn new<S: ScalarValue>(req: &GraphQLRequest<S>, context_key: Option<&str>) -> Self {
+        Self {
+            query: req.query.clone(),
+            operation_name: req.operation_name.clone(),
+            context_key: context_key.map(Into::into),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicI32, Ordering};
+
+    use crate::{
+        graphql_object, http::GraphQLRequest, CacheScope, EmptyMutation, EmptySubscription,
+        RootNode,
+    };
+
+    use super::ResponseCache;
+
+    struct Context {
+        hits: AtomicI32,
+    }
+
+    impl crate::Context for Context {}
+
+    struct Query;
+
+    #[graphql_object(context = Context)]
+    impl Query {
+        #[graphql(cache_control(max_age = 60))]
+        fn hits(ctx: &Context) -> i32 {
+            ctx.hits.fetch_add(1, Ordering::SeqCst) + 1
+        }
+
+        #[graphql(cache_control(max_age = 30, scope = "PRIVATE"))]
+        fn me() -> &'static str {
+            "me"
+        }
+
+        fn unhinted(ctx: &Context) -> i32 {
+            ctx.hits.fetch_add(1, Ordering::SeqCst) + 1
+        }
+
+        #[graphql(cache_control(max_age = 60))]
+        fn echo(ctx: &Context, value: i32) -> i32 {
+            ctx.hits.fetch_add(1, Ordering::SeqCst);
+            value
+        }
+    }
+
+    type Schema = RootNode<'static, Query, EmptyMutation<Context>, EmptySubscription<Context>>;
+
+    fn schema() -> Schema {
+        Schema::new(Query, EmptyMutation::new(), EmptySubscription::new())
+    }
+
+    #[test]
+    fn serves_public_responses_from_cache() {
+        let schema = schema();
+        let ctx = Context {
+            hits: Default::default(),
+        };
+        let cache = ResponseCache::new();
+        let req = GraphQLRequest::new("{ hits }".into(), None, None);
+
+        let first = req.execute_sync_cached(&schema, &ctx, &cache, None);
+        let second = req.execute_sync_cached(&schema, &ctx, &cache, None);
+
+        assert_eq!(ctx.hits.load(Ordering::SeqCst), 1);
+        assert_eq!(first.result, second.result);
+        assert_eq!(second.cache_policy().unwrap().scope, CacheScope::Public);
+    }
+
+    #[test]
+    fn caches_private_responses_per_context_key() {
+        let schema = schema();
+        let ctx = Context {
+            hits: Default::default(),
+        };
+        let cache = ResponseCache::new();
+        let req = GraphQLRequest::new("{ hits me }".into(), None, None);
+
+        req.execute_sync_cached(&schema, &ctx, &cache, None);
+        req.execute_sync_cached(&schema, &ctx, &cache, None);
+        assert_eq!(ctx.hits.load(Ordering::SeqCst), 2);
+
+        req.execute_sync_cached(&schema, &ctx, &cache, Some("alice"));
+        req.execute_sync_cached(&schema, &ctx, &cache, Some("alice"));
+        assert_eq!(ctx.hits.load(Ordering::SeqCst), 3);
+
+        req.execute_sync_cached(&schema, &ctx, &cache, Some("bob"));
+        assert_eq!(ctx.hits.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn does_not_store_responses_with_unhinted_fields() {
+        let schema = schema();
+        let ctx = Context {
+            hits: Default::default(),
+        };
+        let cache = ResponseCache::new();
+        let req = GraphQLRequest::new("{ hits unhinted }".into(), None, None);
+
+        req.execute_sync_cached(&schema, &ctx, &cache, None);
+        req.execute_sync_cached(&schema, &ctx, &cache, None);
+
+        assert_eq!(ctx.hits.load(Ordering::SeqCst), 4);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn evicts_least_recently_used_responses() {
+        let schema = schema();
+        let ctx = Context {
+            hits: Default::default(),
+        };
+        let cache = ResponseCache::with_capacity(2);
+        let req =
+            |value: i32| GraphQLRequest::new(format!("{{ echo(value: {}) }}", value), None, None);
+
+        req(1).execute_sync_cached(&schema, &ctx, &cache, None);
+        req(2).execute_sync_cached(&schema, &ctx, &cache, None);
+        req(1).execute_sync_cached(&schema, &ctx, &cache, None);
+        assert_eq!(ctx.hits.load(Ordering::SeqCst), 2);
+
+        req(3).execute_sync_cached(&schema, &ctx, &cache, None);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(ctx.hits.load(Ordering::SeqCst), 3);
+
+        req(1).execute_sync_cached(&schema, &ctx, &cache, None);
+        assert_eq!(ctx.hits.load(Ordering::SeqCst), 3);
+        req(2).execute_sync_cached(&schema, &ctx, &cache, None);
+        assert_eq!(ctx.hits.load(Ordering::SeqCst), 4);
+    }
+}
@@ -0,0 +1,263 @@
+//! Creation of request contexts in a library-agnostic manner.
+//!
+//! A [`ContextFactory`] creates the context of a GraphQL request out of its [`RequestParts`], so
+//! the code authenticating requests and building contexts is written once and accepted by every
+//! integration crate.
+
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    error::Error,
+    fmt,
+    future::Future,
+    net::SocketAddr,
+};
+
+use crate::{value::ScalarValue, FieldError, Value};
+
+use super::GraphQLResponse;
+
+/// Parts of an HTTP request, besides its body, a context is created from.
+///
+/// Integration crates fill these in from the requests of their library. Headers with names or
+/// values not being valid UTF-8 are omitted.
+#[derive(Debug, Default)]
+pub struct RequestParts {
+    /// Method of the request, like `GET` or `POST`.
+    pub method: String,
+
+    /// URI of the request, including its query string.
+    pub uri: String,
+
+    /// Headers of the request, with lowercase names, in their order of appearance.
+    pub headers: Vec<(String, String)>,
+
+    /// Address of the client the request came from, if known.
+    pub remote_addr: Option<SocketAddr>,
+
+    /// Library-specific data attached to the request.
+    ///
+    /// See the documentation of each integration crate for what it puts here.
+    pub extensions: Extensions,
+}
+
+impl RequestParts {
+    /// Creates new [`RequestParts`] of a request with the given `method` and `uri`, without any
+    /// headers or extensions.
+    pub fn new(method: impl Into<String>, uri: impl Into<String>) -> Self {
+        Self {
+            method: method.into(),
+            uri: uri.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Adds a header with the given `name` and `value`.
+    pub fn append_header(&mut self, name: impl AsRef<str>, value: impl Into<String>) {
+        self.headers
+            .push((name.as_ref().to_ascii_lowercase(), value.into()));
+    }
+
+    /// Returns the value of the first header with the given case-insensitive `name`, if any.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Map of values of different types, holding at most one value per type.
+#[derive(Default)]
+pub struct Extensions(HashMap<TypeId, Box<dyn Any + Send + Sync>>);
+
+impl Extensions {
+    /// Creates new empty [`Extensions`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts the given `value`, returning the previous value of the same type, if any.
+    pub fn insert<T: Send + Sync + 'static>(&mut self, value: T) -> Option<T> {
+        self.0
+            .insert(TypeId::of::<T>(), Box::new(value))
+            .and_then(|v| v.downcast().ok().map(|v| *v))
+    }
+
+    /// Returns a reference to the value of the given type, if any.
+    pub fn get<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.0
+            .get(&TypeId::of::<T>())
+            .and_then(|v| v.downcast_ref())
+    }
+
+    /// Returns a mutable reference to the value of the given type, if any.
+    pub fn get_mut<T: Send + Sync + 'static>(&mut self) -> Option<&mut T> {
+        self.0
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|v| v.downcast_mut())
+    }
+
+    /// Removes the value of the given type, returning it, if any.
+    pub fn remove<T: Send + Sync + 'static>(&mut self) -> Option<T> {
+        self.0
+            .remove(&TypeId::of::<T>())
+            .and_then(|v| v.downcast().ok().map(|v| *v))
+    }
+}
+
+impl fmt::Debug for Extensions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Extensions").finish_non_exhaustive()
+    }
+}
+
+/// Error of a [`ContextFactory`], rejecting the request before executing it.
+///
+/// Integration crates respond to the request with the [status code][`ContextError::status_code`]
+/// of this error and a GraphQL response carrying its [message][`ContextError::message`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContextError {
+    status_code: u16,
+    message: String,
+}
+
+impl ContextError {
+    /// Creates a new [`ContextError`] responding with the given `status_code` and `message`.
+    pub fn new(status_code: u16, message: impl Into<String>) -> Self {
+        Self {
+            status_code,
+            message: message.into(),
+        }
+    }
+
+    /// Creates a new [`ContextError`] responding with `401 Unauthorized` and the given `message`.
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        Self::new(401, message)
+    }
+
+    /// Creates a new [`ContextError`] responding with `500 Internal Server Error` and the given
+    /// `message`.
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(500, message)
+    }
+
+    /// Returns the HTTP status code to respond with.
+    pub fn status_code(&self) -> u16 {
+        self.status_code
+    }
+
+    /// Returns the message of this error.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Converts this error into the [`GraphQLResponse`] to respond with.
+    pub fn into_response<'a, S: ScalarValue>(self) -> GraphQLResponse<'a, S> {
+        GraphQLResponse::error(FieldError::new(self.message, Value::null()))
+    }
+}
+
+impl fmt::Display for ContextError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for ContextError {}
+
+/// Asynchronous factory of request contexts, creating them out of [`RequestParts`].
+///
+/// Implemented for closures taking [`RequestParts`] and returning a [`Future`]. As the returned
+/// [`Future`] can't borrow the [`RequestParts`], the data it needs should be copied out of them
+/// beforehand:
+///
+/// ```
+/// # use juniper::http::{ContextError, ContextFactory, RequestParts};
+/// struct Context {
+///     token: String,
+/// }
+///
+/// fn context_factory() -> impl ContextFactory<Context> {
+///     |parts: &RequestParts| {
+///         let token = parts.header("authorization").map(str::to_owned);
+///         async move {
+///             token
+///                 .map(|token| Context { token })
+///                 .ok_or_else(|| ContextError::unauthorized("missing `Authorization` header"))
+///         }
+///     }
+/// }
+/// ```
+#[async_trait::async_trait]
+pub trait ContextFactory<CtxT>: Send + Sync {
+    /// Creates the context of the request with the given [`RequestParts`].
+    ///
+    /// # Errors
+    ///
+    /// If the request should be rejected, before being executed.
+    async fn create(&self, parts: &RequestParts) -> Result<CtxT, ContextError>;
+}
+
+#[async_trait::async_trait]
+impl<CtxT, F, Fut> ContextFactory<CtxT> for F
+where
+    F: Fn(&RequestParts) -> Fut + Send + Sync,
+    Fut: Future<Output = Result<CtxT, ContextError>> + Send,
+    CtxT: Send,
+{
+    async fn create(&self, parts: &RequestParts) -> Result<CtxT, ContextError> {
+        self(parts).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::future;
+
+    use super::{ContextError, ContextFactory, Extensions, RequestParts};
+
+    #[test]
+    fn looks_up_headers_case_insensitively() {
+        let mut parts = RequestParts::new("GET", "/graphql?query={a}");
+        parts.append_header("X-Tenant", "a");
+        parts.append_header("x-tenant", "b");
+
+        assert_eq!(parts.headers[0], ("x-tenant".into(), "a".into()));
+        assert_eq!(parts.header("X-TENANT"), Some("a"));
+        assert_eq!(parts.header("authorization"), None);
+    }
+
+    #[test]
+    fn stores_extensions_by_type() {
+        let mut extensions = Extensions::new();
+        assert_eq!(extensions.insert(1_i32), None);
+        assert_eq!(extensions.insert("a"), None);
+        assert_eq!(extensions.insert(2_i32), Some(1));
+
+        *extensions.get_mut::<&str>().unwrap() = "b";
+        assert_eq!(extensions.get::<i32>(), Some(&2));
+        assert_eq!(extensions.remove::<&str>(), Some("b"));
+        assert_eq!(extensions.get::<&str>(), None);
+        assert_eq!(extensions.get::<u8>(), None);
+    }
+
+    #[tokio::test]
+    async fn creates_context_with_closure() {
+        let factory = |parts: &RequestParts| {
+            future::ready(match parts.header("authorization") {
+                Some(token) => Ok(token.to_owned()),
+                None => Err(ContextError::unauthorized("no token")),
+            })
+        };
+
+        let mut parts = RequestParts::new("POST", "/graphql");
+        assert_eq!(
+            factory.create(&parts).await,
+            Err(ContextError::new(401, "no token")),
+        );
+
+        parts.append_header("Authorization", "secret");
+        assert_eq!(factory.create(&parts).await, Ok("secret".into()));
+    }
+}
@@ -1,5 +1,7 @@
 //! Utility module to generate a GraphiQL interface
 
+use super::assets::{js_headers, js_string, Asset, AssetSource};
+
 /// Generate the HTML source to show a GraphiQL interface
 ///
 /// The subscriptions endpoint URL can optionally be provided. For example:
@@ -111,3 +113,264 @@ pub fn graphiql_source(
         using_subscriptions = subscriptions_endpoint_url.is_some(),
     )
 }
+
+/// Builder of the HTML source of a [GraphiQL] v2 interface, with the [explorer plugin][1].
+///
+/// Subscriptions are sent with the [legacy client][2] of the `graphql-ws` protocol, as served by
+/// `juniper_graphql_ws`.
+///
+/// ```
+/// # use juniper::http::graphiql::GraphiQLSource;
+/// let graphiql = GraphiQLSource::new("/graphql")
+///     .subscriptions_endpoint("/subscriptions")
+///     .header("Authorization", "Bearer token")
+///     .query("{ hero { name } }")
+///     .render();
+/// ```
+///
+/// [GraphiQL]: https://github.com/graphql/graphiql
+/// [1]: https://github.com/graphql/graphiql/tree/main/packages/graphiql-plugin-explorer
+/// [2]: https://github.com/apollographql/subscriptions-transport-ws
+#[derive(Clone, Debug, PartialEq)]
+pub struct GraphiQLSource {
+    endpoint: String,
+    subscriptions_endpoint: Option<String>,
+    headers: Vec<(String, String)>,
+    query: Option<String>,
+    variables: Option<String>,
+    explorer: bool,
+    assets: AssetSource,
+}
+
+impl GraphiQLSource {
+    /// [`Asset`]s loaded by the page, relative to the CDN or the
+    /// [assets URL][`GraphiQLSource::assets_url()`].
+    pub const ASSETS: &'static [Asset] = &[
+        Asset::script("react@18.2.0/umd/react.production.min.js"),
+        Asset::script("react-dom@18.2.0/umd/react-dom.production.min.js"),
+        Asset::script("subscriptions-transport-ws@0.11.0/browser/client.js"),
+        Asset::script("graphiql@2.4.7/graphiql.min.js"),
+        Asset::stylesheet("graphiql@2.4.7/graphiql.min.css"),
+        Asset::script("@graphiql/plugin-explorer@0.1.20/dist/graphiql-plugin-explorer.umd.js"),
+        Asset::stylesheet("@graphiql/plugin-explorer@0.1.20/dist/style.css"),
+    ];
+
+    /// Creates a new [`GraphiQLSource`] sending requests to the given `endpoint` URL.
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            subscriptions_endpoint: None,
+            headers: Vec::new(),
+            query: None,
+            variables: None,
+            explorer: true,
+            assets: AssetSource::default(),
+        }
+    }
+
+    /// Sets the WebSocket URL to send subscriptions to.
+    ///
+    /// Relative URLs are resolved against the location of the page.
+    pub fn subscriptions_endpoint(mut self, url: impl Into<String>) -> Self {
+        self.subscriptions_endpoint = Some(url.into());
+        self
+    }
+
+    /// Adds a header sent with every request.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Sets the query shown in the editor initially.
+    pub fn query(mut self, query: impl Into<String>) -> Self {
+        self.query = Some(query.into());
+        self
+    }
+
+    /// Sets the JSON-encoded variables shown in the editor initially.
+    pub fn variables(mut self, variables: impl Into<String>) -> Self {
+        self.variables = Some(variables.into());
+        self
+    }
+
+    /// Sets whether the explorer plugin is enabled.
+    ///
+    /// Enabled by default.
+    pub fn explorer(mut self, enabled: bool) -> Self {
+        self.explorer = enabled;
+        self
+    }
+
+    /// Loads the [`GraphiQLSource::ASSETS`] from the given base URL, instead of the CDN.
+    pub fn assets_url(mut self, url: impl Into<String>) -> Self {
+        self.assets.set_base_url(url.into());
+        self
+    }
+
+    /// Inlines the given `contents` of the asset with the given `path` into the page, instead of
+    /// loading it.
+    ///
+    /// The `path` should be one of the [`GraphiQLSource::ASSETS`].
+    pub fn inline_asset(mut self, path: impl Into<String>, contents: impl Into<String>) -> Self {
+        self.assets.inline(path.into(), contents.into());
+        self
+    }
+
+    /// Inlines all the [`GraphiQLSource::ASSETS`] embedded with the `embedded-assets` feature into
+    /// the page, except the ones inlined with [`GraphiQLSource::inline_asset()`].
+    #[cfg(feature = "embedded-assets")]
+    pub fn embedded_assets(mut self) -> Self {
+        self.assets.inline_embedded();
+        self
+    }
+
+    /// Renders the HTML source of the page.
+    pub fn render(&self) -> String {
+        let assets = Self::ASSETS
+            .iter()
+            .filter(|a| self.explorer || !a.path.starts_with("@graphiql/plugin-explorer"))
+            .copied()
+            .collect::<Vec<_>>();
+        let subscriptions_client = match &self.subscriptions_endpoint {
+            Some(url) => format!(
+                "new SubscriptionsTransportWs.SubscriptionClient(\
+                     wsUrl({}), {{ reconnect: true, connectionParams: headers }})",
+                js_string(url),
+            ),
+            None => "undefined".into(),
+        };
+        let optional = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map_or_else(|| "undefined".into(), js_string)
+        };
+
+        format!(
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8" />
+  <title>GraphiQL</title>
+  <style>
+    body {{ height: 100%; margin: 0; width: 100%; overflow: hidden; }}
+    #graphiql {{ height: 100vh; }}
+  </style>
+{assets}</head>
+<body>
+  <div id="graphiql">Loading...</div>
+  <script>
+    function wsUrl(url) {{
+      if (url.startsWith('/')) {{
+        return (location.protocol === 'https:' ? 'wss://' : 'ws://') + location.host + url;
+      }}
+      return url.replace(/^http/, 'ws');
+    }}
+
+    var headers = {headers};
+    var fetcher = GraphiQL.createFetcher({{
+      url: {endpoint},
+      headers: headers,
+      legacyWsClient: {subscriptions_client},
+    }});
+    var explorer = {explorer};
+
+    function App() {{
+      var state = React.useState({query});
+      var plugins = explorer
+        ? [GraphiQLPluginExplorer.useExplorerPlugin({{ query: state[0], onEdit: state[1] }})]
+        : [];
+      return React.createElement(GraphiQL, {{
+        fetcher: fetcher,
+        query: state[0],
+        onEditQuery: state[1],
+        variables: {variables},
+        plugins: plugins,
+        defaultEditorToolsVisibility: true,
+      }});
+    }}
+
+    ReactDOM.createRoot(document.getElementById('graphiql')).render(React.createElement(App));
+  </script>
+</body>
+</html>
+"#,
+            assets = self.assets.render(&assets, CDN_URL),
+            headers = js_headers(&self.headers),
+            endpoint = js_string(&self.endpoint),
+            subscriptions_client = subscriptions_client,
+            explorer = self.explorer,
+            query = optional(&self.query),
+            variables = optional(&self.variables),
+        )
+    }
+}
+
+const CDN_URL: &str = "https://unpkg.com";
+
+#[cfg(test)]
+mod tests {
+    use super::GraphiQLSource;
+
+    #[test]
+    fn renders_graphiql_source() {
+        let source = GraphiQLSource::new("/graphql")
+            .subscriptions_endpoint("/subscriptions")
+            .header("Authorization", "Bearer token")
+            .query("{ hero { name } }")
+            .variables(r#"{"episode": "JEDI"}"#)
+            .render();
+
+        assert!(source.contains(r#"url: "/graphql","#));
+        assert!(source.contains(
+            "legacyWsClient: new SubscriptionsTransportWs.SubscriptionClient(\
+             wsUrl(\"/subscriptions\"), { reconnect: true, connectionParams: headers }),",
+        ));
+        assert!(source.contains(r#"var headers = {"Authorization": "Bearer token"};"#));
+        assert!(source.contains(r#"React.useState("{ hero { name } }")"#));
+        assert!(source.contains(r#"variables: "{\"episode\": \"JEDI\"}","#));
+        assert!(source.contains(
+            "https://unpkg.com/@graphiql/plugin-explorer@0.1.20/dist/graphiql-plugin-explorer.umd.js",
+        ));
+    }
+
+    #[test]
+    fn renders_graphiql_source_without_network() {
+        let source = GraphiQLSource::new("/graphql")
+            .explorer(false)
+            .assets_url("/static")
+            .inline_asset("graphiql@2.4.7/graphiql.min.js", "window.GraphiQL = {};")
+            .render();
+
+        assert!(source.contains("legacyWsClient: undefined,"));
+        assert!(source.contains("<script>window.GraphiQL = {};</script>"));
+        assert!(
+            source.contains(r#"<script src="/static/react@18.2.0/umd/react.production.min.js""#)
+        );
+        assert!(!source.contains("unpkg.com"));
+        assert!(!source.contains("plugin-explorer"));
+    }
+
+    #[cfg(feature = "embedded-assets")]
+    #[test]
+    fn renders_graphiql_source_with_embedded_assets() {
+        let source = GraphiQLSource::new("/graphql")
+            .embedded_assets()
+            .inline_asset("graphiql@2.4.7/graphiql.min.js", "window.GraphiQL = {};")
+            .render();
+
+        assert!(source.contains("<script>window.GraphiQL = {};</script>"));
+        for asset in GraphiQLSource::ASSETS {
+            if asset.path == "graphiql@2.4.7/graphiql.min.js" {
+                continue;
+            }
+            // Assets missing in the `JUNIPER_ASSETS_DIR` are loaded from the CDN instead.
+            assert_eq!(
+                source.contains(&format!("/{}\" crossorigin", asset.path)),
+                crate::http::assets::embedded(asset.path).is_none(),
+                "{}",
+                asset.path,
+            );
+        }
+    }
+}
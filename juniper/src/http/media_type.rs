@@ -0,0 +1,125 @@
+//! Content negotiation of GraphQL responses.
+
+use std::fmt;
+
+/// Media type of a GraphQL response sent over HTTP, as of the [GraphQL over HTTP spec][1].
+///
+/// [1]: https://github.com/graphql/graphql-over-http
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ResponseMediaType {
+    /// `application/graphql-response+json` media type, introduced by the GraphQL over HTTP spec.
+    GraphQLResponseJson,
+
+    /// Legacy `application/json` media type.
+    Json,
+}
+
+impl ResponseMediaType {
+    /// Negotiates the [`ResponseMediaType`] of a response to a request with the given `Accept`
+    /// header.
+    ///
+    /// Preference is given to the media type with the higher quality value, or to the
+    /// `application/graphql-response+json` one if equal. Falls back to the legacy
+    /// `application/json` one if the header is missing or accepts neither of them, so clients
+    /// unaware of the spec keep working.
+    pub fn negotiate(accept: Option<&str>) -> Self {
+        let accept = match accept {
+            Some(accept) => accept,
+            None => return Self::Json,
+        };
+
+        let mut best: Option<(Self, f32)> = None;
+        for range in accept.split(',') {
+            let mut params = range.split(';').map(str::trim);
+            let media_type = match params.next().unwrap_or_default() {
+                mt if mt.eq_ignore_ascii_case(Self::GraphQLResponseJson.as_str()) => {
+                    Self::GraphQLResponseJson
+                }
+                mt if mt.eq_ignore_ascii_case(Self::Json.as_str())
+                    || mt.eq_ignore_ascii_case("application/*")
+                    || mt == "*/*" =>
+                {
+                    Self::Json
+                }
+                _ => continue,
+            };
+            let quality = params
+                .find_map(|p| p.strip_prefix("q=").or_else(|| p.strip_prefix("Q=")))
+                .and_then(|q| q.parse::<f32>().ok())
+                .unwrap_or(1.0);
+            if quality <= 0.0 {
+                continue;
+            }
+            let is_better = match best {
+                None => true,
+                Some((best_type, best_quality)) => {
+                    quality > best_quality
+                        || (quality == best_quality
+                            && best_type == Self::Json
+                            && media_type == Self::GraphQLResponseJson)
+                }
+            };
+            if is_better {
+                best = Some((media_type, quality));
+            }
+        }
+        best.map_or(Self::Json, |(media_type, _)| media_type)
+    }
+
+    /// Returns the `Content-Type` header value of this [`ResponseMediaType`].
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::GraphQLResponseJson => "application/graphql-response+json",
+            Self::Json => "application/json",
+        }
+    }
+}
+
+impl fmt::Display for ResponseMediaType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ResponseMediaType;
+
+    #[test]
+    fn negotiates_media_type() {
+        for (accept, expected) in [
+            (None, ResponseMediaType::Json),
+            (Some(""), ResponseMediaType::Json),
+            (Some("*/*"), ResponseMediaType::Json),
+            (Some("text/html"), ResponseMediaType::Json),
+            (Some("application/json"), ResponseMediaType::Json),
+            (
+                Some("application/graphql-response+json"),
+                ResponseMediaType::GraphQLResponseJson,
+            ),
+            (
+                Some("application/json, application/graphql-response+json"),
+                ResponseMediaType::GraphQLResponseJson,
+            ),
+            (
+                Some("application/graphql-response+json;q=0.9, application/json"),
+                ResponseMediaType::Json,
+            ),
+            (
+                Some("application/graphql-response+json, application/json;q=0.9"),
+                ResponseMediaType::GraphQLResponseJson,
+            ),
+            (
+                Some("application/graphql-response+json;q=0, */*"),
+                ResponseMediaType::Json,
+            ),
+        ] {
+            assert_eq!(
+                ResponseMediaType::negotiate(accept),
+                expected,
+                "{:?}",
+                accept
+            );
+        }
+    }
+}
@@ -1,7 +1,17 @@
 //! Utilities for building HTTP endpoints in a library-agnostic manner
 
+pub mod altair;
+pub mod assets;
+mod batch;
+mod cache;
+mod context;
 pub mod graphiql;
+mod media_type;
+#[cfg(feature = "multipart")]
+pub mod multipart;
 pub mod playground;
+mod query;
+pub mod sandbox;
 
 use serde::{
     de,
@@ -9,10 +19,19 @@ use serde::{
     Deserialize, Serialize,
 };
 
+pub use self::{
+    batch::{BatchConfig, BatchError, BatchExecution},
+    cache::ResponseCache,
+    context::{ContextError, ContextFactory, Extensions, RequestParts},
+    media_type::ResponseMediaType,
+    query::GraphQLQuery,
+};
+
 use crate::{
     ast::InputValue,
-    executor::{ExecutionError, ValuesStream},
-    value::{DefaultScalarValue, ScalarValue},
+    executor::{CachePolicy, ExecutionError, QueryResultWithMeta, RequestMeta, ValuesStream},
+    types::upload::Uploads,
+    value::{DefaultScalarValue, Object, ScalarValue},
     FieldError, GraphQLError, GraphQLSubscriptionType, GraphQLType, GraphQLTypeAsync, RootNode,
     Value, Variables,
 };
@@ -34,6 +53,15 @@ where
     operation_name: Option<String>,
     #[serde(bound(deserialize = "InputValue<S>: Deserialize<'de> + Serialize"))]
     variables: Option<InputValue<S>>,
+    #[serde(
+        bound(deserialize = "InputValue<S>: Deserialize<'de> + Serialize"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    extensions: Option<InputValue<S>>,
+    #[serde(skip)]
+    uploads: Option<Uploads>,
+    #[serde(skip)]
+    forbid_mutations: bool,
 }
 
 impl<S> GraphQLRequest<S>
@@ -45,6 +73,43 @@ where
         self.operation_name.as_deref()
     }
 
+    /// Returns the `extensions` associated with this request.
+    ///
+    /// Those are passed to the executor as is, and are accessible in resolvers via
+    /// [`Executor::request_extensions`].
+    ///
+    /// [`Executor::request_extensions`]: crate::Executor::request_extensions
+    pub fn extensions(&self) -> Option<&InputValue<S>> {
+        self.extensions.as_ref()
+    }
+
+    /// Sets the `extensions` associated with this request.
+    pub fn with_extensions(mut self, extensions: Option<InputValue<S>>) -> Self {
+        self.extensions = extensions;
+        self
+    }
+
+    /// Forbids this request to execute a mutation, failing with [`GraphQLError::IsMutation`]
+    /// instead, once the query is parsed and validated for the execution.
+    ///
+    /// The [GraphQL over HTTP spec][1] forbids executing mutations over GET, so integrations
+    /// should forbid them for such requests, and reply with `405 Method Not Allowed` to the
+    /// [rejected][`GraphQLResponse::is_rejected_mutation`] ones.
+    ///
+    /// [1]: https://github.com/graphql/graphql-over-http
+    pub fn forbid_mutations(mut self) -> Self {
+        self.forbid_mutations = true;
+        self
+    }
+
+    fn request_meta(&self) -> RequestMeta<'_, S> {
+        RequestMeta {
+            extensions: self.extensions.as_ref(),
+            uploads: self.uploads.as_ref(),
+            forbid_mutations: self.forbid_mutations,
+        }
+    }
+
     fn variables(&self) -> Variables<S> {
         self.variables
             .as_ref()
@@ -68,6 +133,9 @@ where
             query,
             operation_name,
             variables,
+            extensions: None,
+            uploads: None,
+            forbid_mutations: false,
         }
     }
 
@@ -86,12 +154,13 @@ where
         MutationT: GraphQLType<S, Context = QueryT::Context>,
         SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
     {
-        GraphQLResponse(crate::execute_sync(
+        GraphQLResponse::from_result_with_meta(crate::execute_sync_with_meta(
             &self.query,
             self.operation_name(),
             root_node,
             &self.variables(),
             context,
+            self.request_meta(),
         ))
     }
 
@@ -116,8 +185,69 @@ where
     {
         let op = self.operation_name();
         let vars = &self.variables();
-        let res = crate::execute(&self.query, op, root_node, vars, context).await;
-        GraphQLResponse(res)
+        let meta = self.request_meta();
+        let res = crate::execute_with_meta(&self.query, op, root_node, vars, context, meta).await;
+        GraphQLResponse::from_result_with_meta(res)
+    }
+
+    /// Execute a GraphQL request synchronously using the specified schema and context, serving
+    /// the response from the given [`ResponseCache`] whenever possible.
+    ///
+    /// The `context_key` identifies the client the response is computed for, and is required for
+    /// responses with a [`CacheScope::Private`] policy to be cached at all.
+    ///
+    /// [`CacheScope::Private`]: crate::CacheScope::Private
+    pub fn execute_sync_cached<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        root_node: &'a RootNode<QueryT, MutationT, SubscriptionT, S>,
+        context: &QueryT::Context,
+        cache: &ResponseCache<S>,
+        context_key: Option<&str>,
+    ) -> GraphQLResponse<'a, S>
+    where
+        S: ScalarValue,
+        QueryT: GraphQLType<S>,
+        MutationT: GraphQLType<S, Context = QueryT::Context>,
+        SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+    {
+        if let Some(resp) = cache.get(self, context_key) {
+            return resp;
+        }
+        let resp = self.execute_sync(root_node, context);
+        cache.insert(self, context_key, &resp);
+        resp
+    }
+
+    /// Execute a GraphQL request using the specified schema and context, serving the response
+    /// from the given [`ResponseCache`] whenever possible.
+    ///
+    /// The `context_key` identifies the client the response is computed for, and is required for
+    /// responses with a [`CacheScope::Private`] policy to be cached at all.
+    ///
+    /// [`CacheScope::Private`]: crate::CacheScope::Private
+    pub async fn execute_cached<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+        context: &'a QueryT::Context,
+        cache: &ResponseCache<S>,
+        context_key: Option<&str>,
+    ) -> GraphQLResponse<'a, S>
+    where
+        QueryT: GraphQLTypeAsync<S>,
+        QueryT::TypeInfo: Sync,
+        QueryT::Context: Sync,
+        MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+        MutationT::TypeInfo: Sync,
+        SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+        SubscriptionT::TypeInfo: Sync,
+        S: ScalarValue + Send + Sync,
+    {
+        if let Some(resp) = cache.get(self, context_key) {
+            return resp;
+        }
+        let resp = self.execute(root_node, context).await;
+        cache.insert(self, context_key, &resp);
+        resp
     }
 }
 
@@ -153,11 +283,14 @@ where
 ///
 /// This struct implements Serialize, so you can simply serialize this
 /// to JSON and send it over the wire. Use the `is_ok` method to determine
-/// whether to send a 200 or 400 HTTP status code.
+/// whether to send a 200 or 400 HTTP status code, and the `cache_policy` method
+/// to determine the `Cache-Control` HTTP header to send.
 #[derive(Debug)]
-pub struct GraphQLResponse<'a, S = DefaultScalarValue>(
-    Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>,
-);
+pub struct GraphQLResponse<'a, S = DefaultScalarValue> {
+    result: Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>,
+    cache_policy: Option<CachePolicy>,
+    extensions: Object<S>,
+}
 
 impl<'a, S> GraphQLResponse<'a, S>
 where
@@ -165,12 +298,53 @@ where
 {
     /// Constructs new `GraphQLResponse` using the given result
     pub fn from_result(r: Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>) -> Self {
-        Self(r)
+        Self {
+            result: r,
+            cache_policy: None,
+            extensions: Object::with_capacity(0),
+        }
+    }
+
+    /// Constructs new `GraphQLResponse` using the given result, which carries the metadata
+    /// gathered while executing the query.
+    pub(crate) fn from_result_with_meta(r: QueryResultWithMeta<'a, S>) -> Self {
+        match r {
+            Ok((value, errors, meta)) => Self {
+                result: Ok((value, errors)),
+                cache_policy: meta.cache_policy,
+                extensions: meta.extensions,
+            },
+            Err(e) => Self::from_result(Err(e)),
+        }
     }
 
     /// Constructs an error response outside of the normal execution flow
     pub fn error(error: FieldError<S>) -> Self {
-        GraphQLResponse(Ok((Value::null(), vec![ExecutionError::at_origin(error)])))
+        Self::from_result(Ok((Value::null(), vec![ExecutionError::at_origin(error)])))
+    }
+
+    /// Returns the [`CachePolicy`] of this response, if it may be cached.
+    ///
+    /// Responses containing errors are never cacheable.
+    pub fn cache_policy(&self) -> Option<CachePolicy> {
+        match self.result {
+            Ok((_, ref errors)) if errors.is_empty() => self.cache_policy,
+            _ => None,
+        }
+    }
+
+    /// Returns the `extensions` of this response.
+    pub fn extensions(&self) -> &Object<S> {
+        &self.extensions
+    }
+
+    /// Adds an entry to the `extensions` of this response, replacing the previous value of the
+    /// same `key`, if any.
+    pub fn add_extension<K>(&mut self, key: K, value: Value<S>)
+    where
+        K: AsRef<str> + Into<String>,
+    {
+        self.extensions.add_field(key, value);
     }
 
     /// Was the request successful or not?
@@ -178,7 +352,37 @@ where
     /// Note that there still might be errors in the response even though it's
     /// considered OK. This is by design in GraphQL.
     pub fn is_ok(&self) -> bool {
-        self.0.is_ok()
+        self.result.is_ok()
+    }
+
+    /// Indicates whether the request wasn't executed for being a mutation, while
+    /// [forbidding][`GraphQLRequest::forbid_mutations`] them.
+    pub fn is_rejected_mutation(&self) -> bool {
+        matches!(self.result, Err(GraphQLError::IsMutation))
+    }
+
+    /// Indicates whether the request wasn't executed for being a subscription, which should be
+    /// [resolved into a stream][`resolve_into_stream`] instead.
+    pub fn is_subscription(&self) -> bool {
+        matches!(self.result, Err(GraphQLError::IsSubscription))
+    }
+
+    /// Returns the HTTP status code this response should be sent with.
+    ///
+    /// Following the [GraphQL over HTTP spec][1], this is `405 Method Not Allowed` for
+    /// [rejected mutations][`GraphQLResponse::is_rejected_mutation`], `400 Bad Request` for
+    /// requests failed to be parsed, validated or to select an operation, and `200 OK`
+    /// otherwise, even if resolving some fields errored, regardless of the [`ResponseMediaType`].
+    ///
+    /// [1]: https://github.com/graphql/graphql-over-http
+    pub fn status_code(&self) -> u16 {
+        if self.is_rejected_mutation() {
+            405
+        } else if self.is_ok() {
+            200
+        } else {
+            400
+        }
     }
 }
 
@@ -193,10 +397,10 @@ where
     where
         S: ser::Serializer,
     {
-        match self.0 {
-            Ok((ref res, ref err)) => {
-                let mut map = serializer.serialize_map(None)?;
+        let mut map = serializer.serialize_map(None)?;
 
+        match self.result {
+            Ok((ref res, ref err)) => {
                 map.serialize_key("data")?;
                 map.serialize_value(res)?;
 
@@ -204,16 +408,19 @@ where
                     map.serialize_key("errors")?;
                     map.serialize_value(err)?;
                 }
-
-                map.end()
             }
             Err(ref err) => {
-                let mut map = serializer.serialize_map(Some(1))?;
                 map.serialize_key("errors")?;
                 map.serialize_value(err)?;
-                map.end()
             }
         }
+
+        if self.extensions.field_count() > 0 {
+            map.serialize_key("extensions")?;
+            map.serialize_value(&self.extensions)?;
+        }
+
+        map.end()
     }
 }
 
@@ -256,12 +463,31 @@ where
 {
     /// Execute a GraphQL batch request synchronously using the specified schema and context
     ///
-    /// This is a simple wrapper around the `execute_sync` function exposed in GraphQLRequest.
+    /// This is a simple wrapper around the `execute_sync` function exposed in GraphQLRequest,
+    /// executing batches with the default [`BatchConfig`].
     pub fn execute_sync<'a, QueryT, MutationT, SubscriptionT>(
         &'a self,
         root_node: &'a RootNode<QueryT, MutationT, SubscriptionT, S>,
         context: &QueryT::Context,
     ) -> GraphQLBatchResponse<'a, S>
+    where
+        QueryT: GraphQLType<S>,
+        MutationT: GraphQLType<S, Context = QueryT::Context>,
+        SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+    {
+        self.execute_sync_with_batch_config(root_node, context, BatchConfig::default())
+    }
+
+    /// Execute a GraphQL batch request synchronously using the specified schema and context,
+    /// rejecting batches not allowed by the given [`BatchConfig`].
+    ///
+    /// Batches are always executed sequentially, regardless of the [`BatchConfig::execution`].
+    pub fn execute_sync_with_batch_config<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        root_node: &'a RootNode<QueryT, MutationT, SubscriptionT, S>,
+        context: &QueryT::Context,
+        config: BatchConfig,
+    ) -> GraphQLBatchResponse<'a, S>
     where
         QueryT: GraphQLType<S>,
         MutationT: GraphQLType<S, Context = QueryT::Context>,
@@ -271,23 +497,50 @@ where
             Self::Single(ref req) => {
                 GraphQLBatchResponse::Single(req.execute_sync(root_node, context))
             }
-            Self::Batch(ref reqs) => GraphQLBatchResponse::Batch(
-                reqs.iter()
-                    .map(|req| req.execute_sync(root_node, context))
-                    .collect(),
-            ),
+            Self::Batch(ref reqs) => {
+                if let Err(e) = config.check(reqs.len()) {
+                    return GraphQLBatchResponse::Rejected(e);
+                }
+                GraphQLBatchResponse::Batch(
+                    reqs.iter()
+                        .map(|req| req.execute_sync(root_node, context))
+                        .collect(),
+                )
+            }
         }
     }
 
     /// Executes a GraphQL request using the specified schema and context
     ///
     /// This is a simple wrapper around the `execute` function exposed in
-    /// GraphQLRequest
+    /// GraphQLRequest, executing batches with the default [`BatchConfig`].
     pub async fn execute<'a, QueryT, MutationT, SubscriptionT>(
         &'a self,
         root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
         context: &'a QueryT::Context,
     ) -> GraphQLBatchResponse<'a, S>
+    where
+        QueryT: GraphQLTypeAsync<S>,
+        QueryT::TypeInfo: Sync,
+        QueryT::Context: Sync,
+        MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+        MutationT::TypeInfo: Sync,
+        SubscriptionT: GraphQLSubscriptionType<S, Context = QueryT::Context>,
+        SubscriptionT::TypeInfo: Sync,
+        S: Send + Sync,
+    {
+        self.execute_with_batch_config(root_node, context, BatchConfig::default())
+            .await
+    }
+
+    /// Executes a GraphQL request using the specified schema and context, executing batches as
+    /// specified by the given [`BatchConfig`].
+    pub async fn execute_with_batch_config<'a, QueryT, MutationT, SubscriptionT>(
+        &'a self,
+        root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+        context: &'a QueryT::Context,
+        config: BatchConfig,
+    ) -> GraphQLBatchResponse<'a, S>
     where
         QueryT: GraphQLTypeAsync<S>,
         QueryT::TypeInfo: Sync,
@@ -304,15 +557,43 @@ where
                 GraphQLBatchResponse::Single(resp)
             }
             Self::Batch(reqs) => {
-                let resps = futures::future::join_all(
-                    reqs.iter().map(|req| req.execute(root_node, context)),
-                )
-                .await;
+                if let Err(e) = config.check(reqs.len()) {
+                    return GraphQLBatchResponse::Rejected(e);
+                }
+                let resps = match config.execution {
+                    BatchExecution::Concurrent => {
+                        futures::future::join_all(
+                            reqs.iter().map(|req| req.execute(root_node, context)),
+                        )
+                        .await
+                    }
+                    BatchExecution::Sequential => {
+                        let mut resps = Vec::with_capacity(reqs.len());
+                        for req in reqs {
+                            resps.push(req.execute(root_node, context).await);
+                        }
+                        resps
+                    }
+                };
                 GraphQLBatchResponse::Batch(resps)
             }
         }
     }
 
+    /// Forbids every request of this batch to execute a mutation.
+    ///
+    /// See [`GraphQLRequest::forbid_mutations`] for details.
+    pub fn forbid_mutations(self) -> Self {
+        match self {
+            Self::Single(req) => Self::Single(req.forbid_mutations()),
+            Self::Batch(reqs) => Self::Batch(
+                reqs.into_iter()
+                    .map(GraphQLRequest::forbid_mutations)
+                    .collect(),
+            ),
+        }
+    }
+
     /// The operation names of the request.
     pub fn operation_names(&self) -> Vec<Option<&str>> {
         match self {
@@ -337,6 +618,9 @@ where
     Single(GraphQLResponse<'a, S>),
     /// Result of a batch operation in a GraphQL request.
     Batch(Vec<GraphQLResponse<'a, S>>),
+    /// Batch operation rejected by the [`BatchConfig`] it was executed with, without executing
+    /// any of its operations.
+    Rejected(BatchError),
 }
 
 impl<'a, S> GraphQLBatchResponse<'a, S>
@@ -349,6 +633,52 @@ where
         match self {
             Self::Single(resp) => resp.is_ok(),
             Self::Batch(resps) => resps.iter().all(GraphQLResponse::is_ok),
+            Self::Rejected(_) => false,
+        }
+    }
+
+    /// Indicates whether any of the requests wasn't executed for being a mutation, while
+    /// [forbidding][`GraphQLBatchRequest::forbid_mutations`] them.
+    pub fn is_rejected_mutation(&self) -> bool {
+        match self {
+            Self::Single(resp) => resp.is_rejected_mutation(),
+            Self::Batch(resps) => resps.iter().any(GraphQLResponse::is_rejected_mutation),
+            Self::Rejected(_) => false,
+        }
+    }
+
+    /// Returns the HTTP status code this response should be sent with, being
+    /// `405 Method Not Allowed` if any of the requests is a
+    /// [rejected mutation][`GraphQLBatchResponse::is_rejected_mutation`], `400 Bad Request` if
+    /// any of the requests failed as a whole, or `200 OK` otherwise.
+    ///
+    /// See [`GraphQLResponse::status_code`] for details.
+    pub fn status_code(&self) -> u16 {
+        if self.is_rejected_mutation() {
+            405
+        } else if self.is_ok() {
+            200
+        } else {
+            400
+        }
+    }
+
+    /// Returns the [`CachePolicy`] of all the GraphQLResponse in this operation, being the most
+    /// restrictive one among them.
+    ///
+    /// Returns [`None`] if any of the responses may not be cached.
+    pub fn cache_policy(&self) -> Option<CachePolicy> {
+        match self {
+            Self::Single(resp) => resp.cache_policy(),
+            Self::Batch(resps) => {
+                let mut policies = resps.iter().map(GraphQLResponse::cache_policy);
+                let mut policy = policies.next()??;
+                for p in policies {
+                    policy.restrict(&p?);
+                }
+                Some(policy)
+            }
+            Self::Rejected(_) => None,
         }
     }
 }
@@ -396,6 +726,9 @@ pub mod tests {
         println!("  - test_get_with_variables");
         test_get_with_variables(integration);
 
+        println!("  - test_get_mutation");
+        test_get_mutation(integration);
+
         println!("  - test_simple_post");
         test_simple_post(integration);
 
@@ -506,6 +839,13 @@ pub mod tests {
         );
     }
 
+    fn test_get_mutation<T: HttpIntegration>(integration: &T) {
+        // mutation { hero { name } }
+        let response = integration.get("/?query=mutation%20%7B%20hero%20%7B%20name%20%7D%20%7D");
+
+        assert_eq!(response.status_code, 405);
+    }
+
     fn test_simple_post<T: HttpIntegration>(integration: &T) {
         let response = integration.post_json("/", r#"{"query": "{hero{name}}"}"#);
 
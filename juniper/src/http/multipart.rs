@@ -0,0 +1,737 @@
+//! Parsing of [GraphQL multipart requests][1], uploading files along with GraphQL operations.
+//!
+//! [1]: https://github.com/jaydenseric/graphql-multipart-request-spec
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt,
+    io::{self, Write as _},
+    str,
+};
+
+use serde_json::Value as Json;
+
+use crate::{
+    types::upload::{UploadedFile, Uploads},
+    value::ScalarValue,
+};
+
+use super::GraphQLBatchRequest;
+
+/// Limits applied while parsing a GraphQL multipart request.
+///
+/// They're enforced while the body is being read, so a request exceeding them is rejected before
+/// it's received completely.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MultipartLimits {
+    /// Maximum size of a single uploaded file, in bytes.
+    ///
+    /// Defaults to 10 MiB.
+    pub max_file_size: u64,
+
+    /// Maximum number of files uploaded with a single request.
+    ///
+    /// Defaults to 10.
+    pub max_files: usize,
+
+    /// Maximum size of the `operations` and `map` fields, in bytes.
+    ///
+    /// Defaults to 1 MiB.
+    pub max_field_size: usize,
+}
+
+impl Default for MultipartLimits {
+    fn default() -> Self {
+        Self {
+            max_file_size: 10 * 1024 * 1024,
+            max_files: 10,
+            max_field_size: 1024 * 1024,
+        }
+    }
+}
+
+/// Maximum size of the headers of a single part, in bytes.
+const MAX_PART_HEADERS_SIZE: usize = 8 * 1024;
+
+/// Names of the headers, one of which every multipart request must carry.
+///
+/// Browsers send `multipart/form-data` requests cross-origin without a CORS preflight, so
+/// requiring a header they can only send after one protects against CSRF. These are the headers
+/// recognized by Apollo Client and Apollo Server.
+pub const PREFLIGHT_HEADERS: [&str; 2] = ["apollo-require-preflight", "x-apollo-operation-name"];
+
+/// Error of parsing a GraphQL multipart request.
+#[derive(Debug)]
+pub enum MultipartError {
+    /// None of the [`PREFLIGHT_HEADERS`] is present.
+    MissingPreflightHeader,
+
+    /// The `Content-Type` doesn't specify a multipart boundary.
+    MissingBoundary,
+
+    /// The body is not a valid `multipart/form-data` one.
+    Malformed(&'static str),
+
+    /// The `operations` field is missing.
+    MissingOperations,
+
+    /// The `map` field is missing.
+    MissingMap,
+
+    /// The `operations` or `map` field is not a valid JSON.
+    Json(serde_json::Error),
+
+    /// A path in the `map` field doesn't point to a value in the `operations` field.
+    InvalidPath(String),
+
+    /// A file referenced in the `map` field is missing.
+    MissingFile(String),
+
+    /// A file exceeds [`MultipartLimits::max_file_size`].
+    FileTooLarge(String),
+
+    /// The request exceeds [`MultipartLimits::max_files`].
+    TooManyFiles,
+
+    /// The `operations` or `map` field exceeds [`MultipartLimits::max_field_size`].
+    FieldTooLarge(String),
+
+    /// An uploaded file cannot be spooled to a temporary file.
+    Io(io::Error),
+}
+
+impl fmt::Display for MultipartError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingPreflightHeader => write!(
+                f,
+                "multipart request must carry an `Apollo-Require-Preflight` or \
+                 `X-Apollo-Operation-Name` header",
+            ),
+            Self::MissingBoundary => write!(f, "multipart boundary is missing"),
+            Self::Malformed(reason) => write!(f, "malformed multipart body: {}", reason),
+            Self::MissingOperations => write!(f, "`operations` field is missing"),
+            Self::MissingMap => write!(f, "`map` field is missing"),
+            Self::Json(e) => write!(f, "invalid JSON: {}", e),
+            Self::InvalidPath(path) => write!(f, "invalid `map` path `{}`", path),
+            Self::MissingFile(name) => write!(f, "file `{}` is missing", name),
+            Self::FileTooLarge(name) => write!(f, "file `{}` is too large", name),
+            Self::TooManyFiles => write!(f, "too many files"),
+            Self::FieldTooLarge(name) => write!(f, "`{}` field is too large", name),
+            Self::Io(e) => write!(f, "cannot store uploaded file: {}", e),
+        }
+    }
+}
+
+impl Error for MultipartError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Json(e) => Some(e),
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl MultipartError {
+    /// Indicates whether this error is caused by the server rather than by the request.
+    pub fn is_server_error(&self) -> bool {
+        matches!(self, Self::Io(_))
+    }
+}
+
+/// Indicates whether the given `Content-Type` is the one of a multipart request.
+pub fn is_multipart_request(content_type: &str) -> bool {
+    let mime = content_type.split(';').next().unwrap_or_default();
+    mime.trim().eq_ignore_ascii_case("multipart/form-data")
+}
+
+/// Checks that a multipart request carries one of the [`PREFLIGHT_HEADERS`].
+///
+/// `has_header` is called with a lowercase header name and should indicate whether the request
+/// carries a non-empty header with this name.
+pub fn check_preflight(mut has_header: impl FnMut(&str) -> bool) -> Result<(), MultipartError> {
+    if PREFLIGHT_HEADERS.iter().any(|name| has_header(name)) {
+        Ok(())
+    } else {
+        Err(MultipartError::MissingPreflightHeader)
+    }
+}
+
+/// Parses the given, already read, `body` of a GraphQL multipart request with the given
+/// `Content-Type`.
+///
+/// Prefer [`MultipartParser`] to parse the body while it's being received.
+pub fn parse_multipart_request<S>(
+    content_type: &str,
+    body: &[u8],
+    limits: &MultipartLimits,
+) -> Result<GraphQLBatchRequest<S>, MultipartError>
+where
+    S: ScalarValue,
+{
+    let mut parser = MultipartParser::new(content_type, limits)?;
+    parser.feed(body)?;
+    parser.finish()
+}
+
+/// Incremental parser of a GraphQL multipart request.
+///
+/// The body is [`feed`]ed to it chunk by chunk, as it's being received, and the
+/// [`MultipartLimits`] are checked on every chunk. Uploaded files are spooled to temporary files
+/// instead of being kept in memory.
+///
+/// The uploaded files are attached to the [`finish`]ed requests, so their [`Upload`] arguments
+/// may be resolved via [`Executor::upload`].
+///
+/// [`Executor::upload`]: crate::Executor::upload
+/// [`feed`]: MultipartParser::feed
+/// [`finish`]: MultipartParser::finish
+/// [`Upload`]: crate::Upload
+pub struct MultipartParser {
+    limits: MultipartLimits,
+    delimiter: Vec<u8>,
+    buf: Vec<u8>,
+    state: State,
+    part: Option<Part>,
+    operations: Option<Vec<u8>>,
+    map: Option<Vec<u8>>,
+    files: HashMap<String, UploadedFile>,
+}
+
+/// State of a [`MultipartParser`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum State {
+    /// Before the opening boundary.
+    Preamble,
+    /// Right after a boundary.
+    Boundary,
+    /// Inside the headers of a part.
+    Headers,
+    /// Inside the content of a part.
+    Content,
+    /// After the closing boundary.
+    Epilogue,
+}
+
+/// Part of a `multipart/form-data` body being parsed.
+struct Part {
+    name: String,
+    sink: Sink,
+}
+
+/// Destination of the content of a [`Part`].
+enum Sink {
+    Field(Vec<u8>),
+    File {
+        filename: Option<String>,
+        content_type: Option<String>,
+        file: tempfile::NamedTempFile,
+        len: u64,
+    },
+}
+
+impl MultipartParser {
+    /// Creates a new [`MultipartParser`] of a body with the given `Content-Type`.
+    ///
+    /// # Errors
+    ///
+    /// If the `Content-Type` doesn't specify a multipart boundary.
+    pub fn new(content_type: &str, limits: &MultipartLimits) -> Result<Self, MultipartError> {
+        let boundary = boundary(content_type).ok_or(MultipartError::MissingBoundary)?;
+        Ok(Self {
+            limits: *limits,
+            delimiter: format!("\r\n--{}", boundary).into_bytes(),
+            // The very first delimiter may omit the leading line break.
+            buf: b"\r\n".to_vec(),
+            state: State::Preamble,
+            part: None,
+            operations: None,
+            map: None,
+            files: HashMap::new(),
+        })
+    }
+
+    /// Feeds the next `chunk` of the body to this [`MultipartParser`].
+    ///
+    /// # Errors
+    ///
+    /// If the body is malformed or exceeds the [`MultipartLimits`]. The rest of the body should
+    /// not be read then.
+    pub fn feed(&mut self, chunk: &[u8]) -> Result<(), MultipartError> {
+        self.buf.extend_from_slice(chunk);
+        loop {
+            match self.state {
+                State::Preamble => match find(&self.buf, &self.delimiter) {
+                    Some(pos) => {
+                        self.buf.drain(..pos + self.delimiter.len());
+                        self.state = State::Boundary;
+                    }
+                    None => {
+                        let keep = self.delimiter.len() - 1;
+                        if self.buf.len() > keep {
+                            self.buf.drain(..self.buf.len() - keep);
+                        }
+                        return Ok(());
+                    }
+                },
+                State::Boundary => {
+                    if self.buf.len() < 2 {
+                        return Ok(());
+                    }
+                    if self.buf.starts_with(b"--") {
+                        self.state = State::Epilogue;
+                    } else if self.buf.starts_with(b"\r\n") {
+                        self.buf.drain(..2);
+                        self.state = State::Headers;
+                    } else {
+                        return Err(MultipartError::Malformed(
+                            "missing line break after boundary",
+                        ));
+                    }
+                }
+                State::Headers => {
+                    let (headers_len, skip) = if self.buf.starts_with(b"\r\n") {
+                        (0, 2)
+                    } else {
+                        match find(&self.buf, b"\r\n\r\n") {
+                            Some(pos) => (pos, pos + 4),
+                            None if self.buf.len() > MAX_PART_HEADERS_SIZE => {
+                                return Err(MultipartError::Malformed("part headers are too large"))
+                            }
+                            None => return Ok(()),
+                        }
+                    };
+                    let headers = str::from_utf8(&self.buf[..headers_len]).map_err(|_| {
+                        MultipartError::Malformed("part headers are not valid UTF-8")
+                    })?;
+                    let part = self.start_part(headers)?;
+                    self.part = Some(part);
+                    self.buf.drain(..skip);
+                    self.state = State::Content;
+                }
+                State::Content => match find(&self.buf, &self.delimiter) {
+                    Some(pos) => {
+                        let mut part = self.part.take().expect("part is started");
+                        self.write(&mut part, pos)?;
+                        self.finish_part(part)?;
+                        self.buf.drain(..self.delimiter.len());
+                        self.state = State::Boundary;
+                    }
+                    None => {
+                        // The end of the buffer may be the beginning of a delimiter.
+                        let keep = self.delimiter.len() - 1;
+                        if self.buf.len() > keep {
+                            let mut part = self.part.take().expect("part is started");
+                            let res = self.write(&mut part, self.buf.len() - keep);
+                            self.part = Some(part);
+                            res?;
+                        }
+                        return Ok(());
+                    }
+                },
+                State::Epilogue => {
+                    self.buf.clear();
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    /// Finishes parsing the fed body, returning the requests of it.
+    ///
+    /// # Errors
+    ///
+    /// If the body is incomplete, or its `operations` and `map` fields are invalid.
+    pub fn finish<S>(mut self) -> Result<GraphQLBatchRequest<S>, MultipartError>
+    where
+        S: ScalarValue,
+    {
+        match self.state {
+            State::Epilogue => {}
+            State::Preamble => return Err(MultipartError::Malformed("missing opening boundary")),
+            _ => return Err(MultipartError::Malformed("missing closing boundary")),
+        }
+
+        let mut operations = self
+            .operations
+            .as_deref()
+            .ok_or(MultipartError::MissingOperations)
+            .and_then(|ops| serde_json::from_slice::<Json>(ops).map_err(MultipartError::Json))?;
+        let map = self
+            .map
+            .as_deref()
+            .ok_or(MultipartError::MissingMap)
+            .and_then(|map| {
+                serde_json::from_slice::<HashMap<String, Vec<String>>>(map)
+                    .map_err(MultipartError::Json)
+            })?;
+
+        let mut uploads = Uploads::new();
+        for (name, paths) in map {
+            let file = self
+                .files
+                .remove(&name)
+                .ok_or_else(|| MultipartError::MissingFile(name.clone()))?;
+            for path in paths {
+                let value = path
+                    .split('.')
+                    .try_fold(&mut operations, |value, segment| match value {
+                        Json::Object(fields) => fields.get_mut(segment),
+                        Json::Array(items) => segment
+                            .parse::<usize>()
+                            .ok()
+                            .and_then(move |i| items.get_mut(i)),
+                        _ => None,
+                    })
+                    .ok_or_else(|| MultipartError::InvalidPath(path.clone()))?;
+                *value = Json::String(name.clone());
+            }
+            uploads.insert(name, file);
+        }
+
+        let mut request = serde_json::from_value::<GraphQLBatchRequest<S>>(operations)
+            .map_err(MultipartError::Json)?;
+        match &mut request {
+            GraphQLBatchRequest::Single(req) => req.uploads = Some(uploads),
+            GraphQLBatchRequest::Batch(reqs) => {
+                for req in reqs {
+                    req.uploads = Some(uploads.clone());
+                }
+            }
+        }
+        Ok(request)
+    }
+
+    /// Starts a new [`Part`] with the given headers.
+    fn start_part(&self, headers: &str) -> Result<Part, MultipartError> {
+        let mut name = None;
+        let mut filename = None;
+        let mut content_type = None;
+        for header in headers.split("\r\n") {
+            let (header_name, value) = match header.find(':') {
+                Some(pos) => (header[..pos].trim(), header[pos + 1..].trim()),
+                None => continue,
+            };
+            if header_name.eq_ignore_ascii_case("content-disposition") {
+                for param in value.split(';').skip(1) {
+                    let param = param.trim();
+                    if let Some(v) = param.strip_prefix("name=") {
+                        name = Some(v.trim_matches('"'));
+                    } else if let Some(v) = param.strip_prefix("filename=") {
+                        filename = Some(v.trim_matches('"'));
+                    }
+                }
+            } else if header_name.eq_ignore_ascii_case("content-type") {
+                content_type = Some(value);
+            }
+        }
+        let name = name.ok_or(MultipartError::Malformed("part without a name"))?;
+
+        let sink = match name {
+            "operations" | "map" => Sink::Field(vec![]),
+            _ => {
+                if self.files.len() >= self.limits.max_files {
+                    return Err(MultipartError::TooManyFiles);
+                }
+                Sink::File {
+                    filename: filename.map(Into::into),
+                    content_type: content_type.map(Into::into),
+                    file: tempfile::NamedTempFile::new().map_err(MultipartError::Io)?,
+                    len: 0,
+                }
+            }
+        };
+        Ok(Part {
+            name: name.into(),
+            sink,
+        })
+    }
+
+    /// Writes the first `len` bytes of the buffer into the given [`Part`], removing them from the
+    /// buffer.
+    fn write(&mut self, part: &mut Part, len: usize) -> Result<(), MultipartError> {
+        let data = &self.buf[..len];
+        match &mut part.sink {
+            Sink::Field(content) => {
+                if content.len() + data.len() > self.limits.max_field_size {
+                    return Err(MultipartError::FieldTooLarge(part.name.clone()));
+                }
+                content.extend_from_slice(data);
+            }
+            Sink::File { file, len, .. } => {
+                *len += data.len() as u64;
+                if *len > self.limits.max_file_size {
+                    return Err(MultipartError::FileTooLarge(part.name.clone()));
+                }
+                file.write_all(data).map_err(MultipartError::Io)?;
+            }
+        }
+        self.buf.drain(..len);
+        Ok(())
+    }
+
+    /// Finishes the given fully written [`Part`].
+    fn finish_part(&mut self, part: Part) -> Result<(), MultipartError> {
+        match part.sink {
+            Sink::Field(content) => match part.name.as_str() {
+                "operations" => self.operations = Some(content),
+                _ => self.map = Some(content),
+            },
+            Sink::File {
+                filename,
+                content_type,
+                mut file,
+                len,
+            } => {
+                file.flush().map_err(MultipartError::Io)?;
+                let file = UploadedFile::spooled(filename, content_type, file, len);
+                self.files.insert(part.name, file);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Extracts the multipart boundary out of the given `Content-Type`.
+fn boundary(content_type: &str) -> Option<String> {
+    content_type.split(';').skip(1).find_map(|param| {
+        let (name, value) = param.trim().split_at(param.trim().find('=')?);
+        if !name.eq_ignore_ascii_case("boundary") {
+            return None;
+        }
+        let value = value[1..].trim_matches('"');
+        if value.is_empty() {
+            None
+        } else {
+            Some(value.to_owned())
+        }
+    })
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{http::GraphQLBatchRequest, DefaultScalarValue};
+
+    use super::{
+        check_preflight, parse_multipart_request, MultipartError, MultipartLimits, MultipartParser,
+    };
+
+    const CONTENT_TYPE: &str = "multipart/form-data; boundary=------------------------abc";
+
+    fn body(parts: &[(&str, Option<&str>, &str)]) -> Vec<u8> {
+        let mut body = String::new();
+        for (name, filename, content) in parts {
+            body.push_str("--------------------------abc\r\n");
+            match filename {
+                Some(filename) => body.push_str(&format!(
+                    "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n\
+                     Content-Type: text/plain\r\n",
+                    name, filename,
+                )),
+                None => body.push_str(&format!(
+                    "Content-Disposition: form-data; name=\"{}\"\r\n",
+                    name,
+                )),
+            }
+            body.push_str("\r\n");
+            body.push_str(content);
+            body.push_str("\r\n");
+        }
+        body.push_str("--------------------------abc--\r\n");
+        body.into_bytes()
+    }
+
+    fn parse(body: &[u8]) -> Result<GraphQLBatchRequest<DefaultScalarValue>, MultipartError> {
+        parse_multipart_request(CONTENT_TYPE, body, &MultipartLimits::default())
+    }
+
+    #[test]
+    fn parses_single_request() {
+        let body = body(&[
+            (
+                "operations",
+                None,
+                r#"{"query": "mutation ($file: Upload!) { upload(file: $file) }", "variables": {"file": null}}"#,
+            ),
+            ("map", None, r#"{"0": ["variables.file"]}"#),
+            ("0", Some("a.txt"), "Alpha file content."),
+        ]);
+
+        let req = match parse(&body).unwrap() {
+            GraphQLBatchRequest::Single(req) => req,
+            GraphQLBatchRequest::Batch(_) => panic!("expected single request"),
+        };
+
+        let uploads = req.uploads.as_ref().unwrap();
+        let file = &uploads["0"];
+        assert_eq!(file.filename(), Some("a.txt"));
+        assert_eq!(file.content_type(), Some("text/plain"));
+        assert_eq!(file.to_vec().unwrap(), b"Alpha file content.");
+        assert_eq!(
+            req.variables()
+                .get("file")
+                .and_then(|v| v.as_string_value()),
+            Some("0"),
+        );
+    }
+
+    #[test]
+    fn parses_batch_request() {
+        let body = body(&[
+            (
+                "operations",
+                None,
+                r#"[{"query": "{ a }", "variables": {"files": [null, null]}}, {"query": "{ b }"}]"#,
+            ),
+            ("map", None, r#"{"x": ["0.variables.files.1"]}"#),
+            ("x", Some("x.txt"), ""),
+        ]);
+
+        let reqs = match parse(&body).unwrap() {
+            GraphQLBatchRequest::Batch(reqs) => reqs,
+            GraphQLBatchRequest::Single(_) => panic!("expected batch request"),
+        };
+
+        assert_eq!(reqs.len(), 2);
+        assert!(reqs[0].uploads.as_ref().unwrap()["x"].is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_requests() {
+        let no_map = body(&[("operations", None, r#"{"query": "{ a }"}"#)]);
+        assert!(matches!(parse(&no_map), Err(MultipartError::MissingMap)));
+
+        let missing_file = body(&[
+            (
+                "operations",
+                None,
+                r#"{"query": "{ a }", "variables": {"f": null}}"#,
+            ),
+            ("map", None, r#"{"0": ["variables.f"]}"#),
+        ]);
+        assert!(matches!(
+            parse(&missing_file),
+            Err(MultipartError::MissingFile(name)) if name == "0",
+        ));
+
+        let invalid_path = body(&[
+            (
+                "operations",
+                None,
+                r#"{"query": "{ a }", "variables": {"f": null}}"#,
+            ),
+            ("map", None, r#"{"0": ["variables.g"]}"#),
+            ("0", Some("a.txt"), "a"),
+        ]);
+        assert!(matches!(
+            parse(&invalid_path),
+            Err(MultipartError::InvalidPath(path)) if path == "variables.g",
+        ));
+
+        assert!(matches!(
+            parse_multipart_request::<DefaultScalarValue>(
+                "multipart/form-data",
+                &invalid_path,
+                &MultipartLimits::default(),
+            ),
+            Err(MultipartError::MissingBoundary),
+        ));
+    }
+
+    #[test]
+    fn enforces_limits() {
+        let body = body(&[
+            (
+                "operations",
+                None,
+                r#"{"query": "{ a }", "variables": {"f": null}}"#,
+            ),
+            (
+                "map",
+                None,
+                r#"{"0": ["variables.f"], "1": ["variables.f"]}"#,
+            ),
+            ("0", Some("a.txt"), "aaaa"),
+            ("1", Some("b.txt"), "b"),
+        ]);
+
+        let limits = MultipartLimits {
+            max_file_size: 3,
+            ..MultipartLimits::default()
+        };
+        assert!(matches!(
+            parse_multipart_request::<DefaultScalarValue>(CONTENT_TYPE, &body, &limits),
+            Err(MultipartError::FileTooLarge(name)) if name == "0",
+        ));
+
+        let limits = MultipartLimits {
+            max_files: 1,
+            ..MultipartLimits::default()
+        };
+        assert!(matches!(
+            parse_multipart_request::<DefaultScalarValue>(CONTENT_TYPE, &body, &limits),
+            Err(MultipartError::TooManyFiles),
+        ));
+    }
+
+    #[test]
+    fn parses_body_fed_in_chunks() {
+        let body = body(&[
+            (
+                "operations",
+                None,
+                r#"{"query": "{ a }", "variables": {"f": null}}"#,
+            ),
+            ("map", None, r#"{"0": ["variables.f"]}"#),
+            ("0", Some("a.txt"), "--------------------------ab\r\n-"),
+        ]);
+
+        let mut parser = MultipartParser::new(CONTENT_TYPE, &MultipartLimits::default()).unwrap();
+        for byte in &body {
+            parser.feed(std::slice::from_ref(byte)).unwrap();
+        }
+        let req = match parser.finish::<DefaultScalarValue>().unwrap() {
+            GraphQLBatchRequest::Single(req) => req,
+            GraphQLBatchRequest::Batch(_) => panic!("expected single request"),
+        };
+
+        let file = &req.uploads.as_ref().unwrap()["0"];
+        assert_eq!(file.len(), 31);
+        assert_eq!(file.to_vec().unwrap(), b"--------------------------ab\r\n-",);
+    }
+
+    #[test]
+    fn rejects_oversized_file_before_reading_it_completely() {
+        let limits = MultipartLimits {
+            max_file_size: 3,
+            ..MultipartLimits::default()
+        };
+        let mut parser = MultipartParser::new(CONTENT_TYPE, &limits).unwrap();
+
+        let head = "--------------------------abc\r\n\
+                    Content-Disposition: form-data; name=\"0\"; filename=\"a.txt\"\r\n\r\n";
+        parser.feed(head.as_bytes()).unwrap();
+        assert!(matches!(
+            parser.feed(&[b'a'; 64]),
+            Err(MultipartError::FileTooLarge(name)) if name == "0",
+        ));
+    }
+
+    #[test]
+    fn requires_preflight_header() {
+        assert!(matches!(
+            check_preflight(|_| false),
+            Err(MultipartError::MissingPreflightHeader),
+        ));
+        assert!(check_preflight(|name| name == "apollo-require-preflight").is_ok());
+        assert!(check_preflight(|name| name == "x-apollo-operation-name").is_ok());
+    }
+}
@@ -1,7 +1,8 @@
 //! Utility module to generate a GraphQL Playground interface
 
+use super::assets::{js_headers, js_string, Asset, AssetSource};
+
 /// Generate the HTML source to show a GraphQL Playground interface
-// source: https://github.com/prisma/graphql-playground/blob/master/packages/graphql-playground-html/withAnimation.html
 pub fn playground_source(
     graphql_endpoint_url: &str,
     subscriptions_endpoint_url: Option<&str>,
@@ -12,7 +13,22 @@ pub fn playground_source(
         graphql_endpoint_url
     };
 
-    r##"
+    PAGE.replace("JUNIPER_PLAYGROUND_ASSETS", LEGACY_ASSETS)
+        .replace(
+            "JUNIPER_PLAYGROUND_OPTIONS",
+            "{ endpoint: 'JUNIPER_GRAPHQL_URL', subscriptionEndpoint: 'JUNIPER_SUBSCRIPTIONS_URL' }",
+        )
+        .replace("JUNIPER_GRAPHQL_URL", graphql_endpoint_url)
+        .replace("JUNIPER_SUBSCRIPTIONS_URL", subscriptions_endpoint)
+}
+
+const LEGACY_ASSETS: &str = r#"  <link rel="stylesheet" href="//cdn.jsdelivr.net/npm/graphql-playground-react@1.7.26/build/static/css/index.css" />
+  <link rel="shortcut icon" href="//cdn.jsdelivr.net/npm/graphql-playground-react@1.7.26/build/favicon.png" />
+  <script src="//cdn.jsdelivr.net/npm/graphql-playground-react@1.7.26/build/static/js/middleware.js"></script>
+"#;
+
+// source: https://github.com/prisma/graphql-playground/blob/master/packages/graphql-playground-html/withAnimation.html
+const PAGE: &str = r##"
 <!DOCTYPE html>
 
 <html>
@@ -21,10 +37,7 @@ pub fn playground_source(
   <meta charset=utf-8 />
   <meta name="viewport" content="user-scalable=no, initial-scale=1.0, minimum-scale=1.0, maximum-scale=1.0, minimal-ui">
   <title>GraphQL Playground</title>
-  <link rel="stylesheet" href="//cdn.jsdelivr.net/npm/graphql-playground-react@1.7.26/build/static/css/index.css" />
-  <link rel="shortcut icon" href="//cdn.jsdelivr.net/npm/graphql-playground-react@1.7.26/build/favicon.png" />
-  <script src="//cdn.jsdelivr.net/npm/graphql-playground-react@1.7.26/build/static/js/middleware.js"></script>
-
+JUNIPER_PLAYGROUND_ASSETS
 </head>
 
 <body>
@@ -546,11 +559,180 @@ pub fn playground_source(
       const root = document.getElementById('root');
       root.classList.add('playgroundIn');
 
-      GraphQLPlayground.init(root, { endpoint: 'JUNIPER_GRAPHQL_URL', subscriptionEndpoint: 'JUNIPER_SUBSCRIPTIONS_URL' })
+      GraphQLPlayground.init(root, JUNIPER_PLAYGROUND_OPTIONS)
     })
   </script>
 </body>
 </html>
-  "##.replace("JUNIPER_GRAPHQL_URL", graphql_endpoint_url)
-     .replace("JUNIPER_SUBSCRIPTIONS_URL", subscriptions_endpoint)
+  "##;
+
+/// Builder of the HTML source of a [GraphQL Playground][Playground] page.
+///
+/// Unlike [`playground_source()`], allows to set the headers sent with every request, the initial
+/// query and variables, and where the page loads its assets from (see the
+/// [`assets`](super::assets) module for serving it without network access).
+///
+/// ```
+/// # use juniper::http::playground::PlaygroundSource;
+/// let playground = PlaygroundSource::new("/graphql")
+///     .subscriptions_endpoint("/subscriptions")
+///     .header("Authorization", "Bearer token")
+///     .query("{ hero { name } }")
+///     .render();
+/// ```
+///
+/// [Playground]: https://github.com/graphql/graphql-playground
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlaygroundSource {
+    endpoint: String,
+    subscriptions_endpoint: Option<String>,
+    headers: Vec<(String, String)>,
+    query: Option<String>,
+    variables: Option<String>,
+    assets: AssetSource,
+}
+
+impl PlaygroundSource {
+    /// [`Asset`]s loaded by the page, relative to the CDN or the
+    /// [assets URL][`PlaygroundSource::assets_url()`].
+    pub const ASSETS: &'static [Asset] = &[
+        Asset::stylesheet("graphql-playground-react@1.7.26/build/static/css/index.css"),
+        Asset::script("graphql-playground-react@1.7.26/build/static/js/middleware.js"),
+    ];
+
+    /// Creates a new [`PlaygroundSource`] sending requests to the given `endpoint` URL.
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            subscriptions_endpoint: None,
+            headers: Vec::new(),
+            query: None,
+            variables: None,
+            assets: AssetSource::default(),
+        }
+    }
+
+    /// Sets the WebSocket URL to send subscriptions to.
+    ///
+    /// Defaults to the [endpoint][`PlaygroundSource::new()`] URL.
+    pub fn subscriptions_endpoint(mut self, url: impl Into<String>) -> Self {
+        self.subscriptions_endpoint = Some(url.into());
+        self
+    }
+
+    /// Adds a header sent with every request.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Sets the query shown in the editor initially.
+    pub fn query(mut self, query: impl Into<String>) -> Self {
+        self.query = Some(query.into());
+        self
+    }
+
+    /// Sets the JSON-encoded variables shown in the editor initially.
+    ///
+    /// Only shown if the [query][`PlaygroundSource::query()`] is set too.
+    pub fn variables(mut self, variables: impl Into<String>) -> Self {
+        self.variables = Some(variables.into());
+        self
+    }
+
+    /// Loads the [`PlaygroundSource::ASSETS`] from the given base URL, instead of the CDN.
+    pub fn assets_url(mut self, url: impl Into<String>) -> Self {
+        self.assets.set_base_url(url.into());
+        self
+    }
+
+    /// Inlines the given `contents` of the asset with the given `path` into the page, instead of
+    /// loading it.
+    ///
+    /// The `path` should be one of the [`PlaygroundSource::ASSETS`].
+    pub fn inline_asset(mut self, path: impl Into<String>, contents: impl Into<String>) -> Self {
+        self.assets.inline(path.into(), contents.into());
+        self
+    }
+
+    /// Inlines all the [`PlaygroundSource::ASSETS`] embedded with the `embedded-assets` feature into
+    /// the page, except the ones inlined with [`PlaygroundSource::inline_asset()`].
+    #[cfg(feature = "embedded-assets")]
+    pub fn embedded_assets(mut self) -> Self {
+        self.assets.inline_embedded();
+        self
+    }
+
+    /// Renders the HTML source of the page.
+    pub fn render(&self) -> String {
+        let endpoint = js_string(&self.endpoint);
+        let headers = js_headers(&self.headers);
+        let mut options = format!(
+            "{{ endpoint: {}, subscriptionEndpoint: {}, headers: {}",
+            endpoint,
+            js_string(
+                self.subscriptions_endpoint
+                    .as_ref()
+                    .unwrap_or(&self.endpoint)
+            ),
+            headers,
+        );
+        if let Some(query) = &self.query {
+            options.push_str(&format!(
+                ", tabs: [{{ endpoint: {}, query: {}, variables: {}, headers: {} }}]",
+                endpoint,
+                js_string(query),
+                js_string(self.variables.as_deref().unwrap_or("{}")),
+                headers,
+            ));
+        }
+        options.push_str(" }");
+
+        PAGE.replace(
+            "JUNIPER_PLAYGROUND_ASSETS",
+            &self.assets.render(Self::ASSETS, CDN_URL),
+        )
+        .replace("JUNIPER_PLAYGROUND_OPTIONS", &options)
+    }
+}
+
+const CDN_URL: &str = "https://cdn.jsdelivr.net/npm";
+
+#[cfg(test)]
+mod tests {
+    use super::{playground_source, PlaygroundSource};
+
+    #[test]
+    fn renders_legacy_playground_source() {
+        let source = playground_source("/graphql", None);
+
+        assert!(source.contains(
+            "GraphQLPlayground.init(root, \
+             { endpoint: '/graphql', subscriptionEndpoint: '/graphql' })",
+        ));
+        assert!(source.contains(
+            "<script src=\"//cdn.jsdelivr.net/npm/graphql-playground-react@1.7.26/build/static/js/middleware.js\"></script>",
+        ));
+    }
+
+    #[test]
+    fn renders_playground_source() {
+        let source = PlaygroundSource::new("/graphql")
+            .header("Authorization", "Bearer token")
+            .query("{ hero { name } }")
+            .assets_url("/static/")
+            .render();
+
+        assert!(source.contains(
+            "GraphQLPlayground.init(root, { endpoint: \"/graphql\", \
+             subscriptionEndpoint: \"/graphql\", \
+             headers: {\"Authorization\": \"Bearer token\"}, \
+             tabs: [{ endpoint: \"/graphql\", query: \"{ hero { name } }\", variables: \"{}\", \
+             headers: {\"Authorization\": \"Bearer token\"} }] })",
+        ));
+        assert!(source.contains(
+            "<script src=\"/static/graphql-playground-react@1.7.26/build/static/js/middleware.js\" crossorigin></script>",
+        ));
+        assert!(!source.contains("jsdelivr"));
+    }
 }
@@ -0,0 +1,32 @@
+//! Typed GraphQL operations sent to other GraphQL servers.
+
+use serde::de::DeserializeOwned;
+
+use crate::{ast::ToInputValue, value::DefaultScalarValue};
+
+use super::GraphQLRequest;
+
+/// GraphQL operation with typed variables and response data, as generated by the
+/// `#[derive(GraphQLQuery)]` macro of the `juniper_client_codegen` crate.
+pub trait GraphQLQuery {
+    /// Variables of the operation.
+    type Variables: ToInputValue<DefaultScalarValue>;
+
+    /// `data` of a response to the operation.
+    type ResponseData: DeserializeOwned;
+
+    /// Document containing the operation, along with the fragments it uses.
+    const QUERY: &'static str;
+
+    /// Name of the operation in the [`GraphQLQuery::QUERY`] document, unless it's anonymous.
+    const OPERATION_NAME: Option<&'static str>;
+
+    /// Builds a [`GraphQLRequest`] executing the operation with the given `variables`.
+    fn build_request(variables: Self::Variables) -> GraphQLRequest<DefaultScalarValue> {
+        GraphQLRequest::new(
+            Self::QUERY.to_owned(),
+            Self::OPERATION_NAME.map(ToOwned::to_owned),
+            Some(variables.to_input_value()),
+        )
+    }
+}
@@ -0,0 +1,146 @@
+//! Utility module to generate an [Apollo Sandbox][Sandbox] interface.
+//!
+//! [Sandbox]: https://www.apollographql.com/docs/graphos/explorer/sandbox
+
+use super::assets::{js_headers, js_string};
+
+/// Builder of the HTML source of an embedded [Apollo Sandbox][Sandbox] page.
+///
+/// Apollo Sandbox is rendered in an `<iframe>` served by Apollo, so unlike the other GraphQL IDE
+/// pages, it can't be served without network access.
+///
+/// ```
+/// # use juniper::http::sandbox::SandboxSource;
+/// let sandbox = SandboxSource::new("/graphql")
+///     .subscriptions_endpoint("/subscriptions")
+///     .header("Authorization", "Bearer token")
+///     .query("{ hero { name } }")
+///     .render();
+/// ```
+///
+/// [Sandbox]: https://www.apollographql.com/docs/graphos/explorer/sandbox
+#[derive(Clone, Debug, PartialEq)]
+pub struct SandboxSource {
+    endpoint: String,
+    subscriptions_endpoint: Option<String>,
+    headers: Vec<(String, String)>,
+    query: Option<String>,
+    variables: Option<String>,
+}
+
+impl SandboxSource {
+    /// URL of the script embedding the Apollo Sandbox.
+    pub const SCRIPT_URL: &'static str =
+        "https://embeddable-sandbox.cdn.apollographql.com/_latest/embeddable-sandbox.umd.production.min.js";
+
+    /// Creates a new [`SandboxSource`] sending requests to the given `endpoint` URL.
+    ///
+    /// Relative URLs are resolved against the location of the page.
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            subscriptions_endpoint: None,
+            headers: Vec::new(),
+            query: None,
+            variables: None,
+        }
+    }
+
+    /// Sets the WebSocket URL to send subscriptions to.
+    ///
+    /// Relative URLs are resolved against the location of the page.
+    pub fn subscriptions_endpoint(mut self, url: impl Into<String>) -> Self {
+        self.subscriptions_endpoint = Some(url.into());
+        self
+    }
+
+    /// Adds a header sent with every request.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Sets the query shown in the editor initially.
+    pub fn query(mut self, query: impl Into<String>) -> Self {
+        self.query = Some(query.into());
+        self
+    }
+
+    /// Sets the JSON-encoded variables shown in the editor initially.
+    pub fn variables(mut self, variables: impl Into<String>) -> Self {
+        self.variables = Some(variables.into());
+        self
+    }
+
+    /// Renders the HTML source of the page.
+    pub fn render(&self) -> String {
+        let subscriptions_endpoint = match &self.subscriptions_endpoint {
+            Some(url) => format!("wsUrl({})", js_string(url)),
+            None => "undefined".into(),
+        };
+
+        format!(
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8" />
+  <title>Apollo Sandbox</title>
+  <style>
+    body {{ height: 100%; margin: 0; width: 100%; overflow: hidden; }}
+    #embedded-sandbox {{ height: 100vh; }}
+  </style>
+  <script src="{script_url}"></script>
+</head>
+<body>
+  <div id="embedded-sandbox"></div>
+  <script>
+    function wsUrl(url) {{
+      if (url.startsWith('/')) {{
+        return (location.protocol === 'https:' ? 'wss://' : 'ws://') + location.host + url;
+      }}
+      return url.replace(/^http/, 'ws');
+    }}
+
+    new window.EmbeddedSandbox({{
+      target: '#embedded-sandbox',
+      initialEndpoint: new URL({endpoint}, location.href).href,
+      initialSubscriptionEndpoint: {subscriptions_endpoint},
+      initialState: {{
+        document: {query},
+        variables: JSON.parse({variables}),
+        headers: {headers},
+      }},
+    }});
+  </script>
+</body>
+</html>
+"#,
+            script_url = Self::SCRIPT_URL,
+            endpoint = js_string(&self.endpoint),
+            subscriptions_endpoint = subscriptions_endpoint,
+            query = js_string(self.query.as_deref().unwrap_or_default()),
+            variables = js_string(self.variables.as_deref().unwrap_or("{}")),
+            headers = js_headers(&self.headers),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SandboxSource;
+
+    #[test]
+    fn renders_sandbox_source() {
+        let source = SandboxSource::new("/graphql")
+            .subscriptions_endpoint("/subscriptions")
+            .header("Authorization", "Bearer token")
+            .query("{ hero { name } }")
+            .render();
+
+        assert!(source.contains(r#"initialEndpoint: new URL("/graphql", location.href).href,"#));
+        assert!(source.contains(r#"initialSubscriptionEndpoint: wsUrl("/subscriptions"),"#));
+        assert!(source.contains(r#"document: "{ hero { name } }","#));
+        assert!(source.contains(r#"variables: JSON.parse("{}"),"#));
+        assert!(source.contains(r#"headers: {"Authorization": "Bearer token"},"#));
+    }
+}
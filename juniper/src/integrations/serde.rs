@@ -78,6 +78,10 @@ impl<'a> ser::Serialize for GraphQLError<'a> {
                 message: "Expected subscription, got query",
             }]
             .serialize(serializer),
+            GraphQLError::IsMutation => [SerializeHelper {
+                message: "Mutations are not allowed in this request",
+            }]
+            .serialize(serializer),
         }
     }
 }
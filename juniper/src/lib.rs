@@ -111,7 +111,8 @@ pub use futures::future::{BoxFuture, LocalBoxFuture};
 // functionality automatically.
 pub use juniper_codegen::{
     graphql_interface, graphql_object, graphql_scalar, graphql_subscription, graphql_union,
-    GraphQLEnum, GraphQLInputObject, GraphQLObject, GraphQLScalarValue, GraphQLUnion,
+    GraphQLEnum, GraphQLInputObject, GraphQLMergedObject, GraphQLObject, GraphQLScalar,
+    GraphQLScalarValue, GraphQLUnion,
 };
 
 #[macro_use]
@@ -123,6 +124,8 @@ pub mod executor;
 mod introspection;
 pub mod parser;
 pub(crate) mod schema;
+#[cfg(feature = "schema-language")]
+pub mod snapshot;
 mod types;
 mod util;
 pub mod validation;
@@ -143,7 +146,7 @@ mod executor_tests;
 pub use crate::util::to_camel_case;
 
 use crate::{
-    executor::{execute_validated_query, get_operation},
+    executor::get_operation,
     introspection::{INTROSPECTION_QUERY, INTROSPECTION_QUERY_WITHOUT_DESCRIPTIONS},
     parser::parse_document_source,
     validation::{validate_input_values, visit_all_rules, ValidatorContext},
@@ -155,14 +158,16 @@ pub use crate::{
         ToInputValue, Type,
     },
     executor::{
-        Applies, Context, ExecutionError, ExecutionResult, Executor, FieldError, FieldResult,
-        FromContext, IntoFieldError, IntoResolvable, LookAheadArgument, LookAheadMethods,
-        LookAheadSelection, LookAheadValue, OwnedExecutor, Registry, ValuesStream, Variables,
+        Applies, CacheHint, CachePolicy, CacheScope, Context, ExecutionError, ExecutionResult,
+        Executor, FieldError, FieldResult, FromContext, Guard, IntoFieldError, IntoResolvable,
+        LookAheadArgument, LookAheadMethods, LookAheadSelection, LookAheadValue, OwnedExecutor,
+        Registry, ValuesStream, Variables,
     },
     introspection::IntrospectionFormat,
     macros::helper::{
         subscription::{ExtractTypeFromStream, IntoFieldResult},
-        AsDynGraphQLValue,
+        type_name_from_parts, AsDynGraphQLValue, ComplexObject, FieldNames, InputObjectFieldNames,
+        ObjectFieldNames,
     },
     parser::{ParseError, Spanning},
     schema::{
@@ -179,6 +184,7 @@ pub use crate::{
             ExecutionOutput, GraphQLSubscriptionType, GraphQLSubscriptionValue,
             SubscriptionConnection, SubscriptionCoordinator,
         },
+        upload::{Upload, UploadedFile},
     },
     validation::RuleError,
     value::{DefaultScalarValue, Object, ParseScalarResult, ParseScalarValue, ScalarValue, Value},
@@ -195,6 +201,7 @@ pub enum GraphQLError<'a> {
     UnknownOperationName,
     IsSubscription,
     NotSubscription,
+    IsMutation,
 }
 
 impl<'a> fmt::Display for GraphQLError<'a> {
@@ -212,6 +219,7 @@ impl<'a> fmt::Display for GraphQLError<'a> {
             GraphQLError::UnknownOperationName => write!(f, "Unknown operation name"),
             GraphQLError::IsSubscription => write!(f, "Operation is a subscription"),
             GraphQLError::NotSubscription => write!(f, "Operation is not a subscription"),
+            GraphQLError::IsMutation => write!(f, "Operation is a forbidden mutation"),
         }
     }
 }
@@ -226,6 +234,33 @@ pub fn execute_sync<'a, S, QueryT, MutationT, SubscriptionT>(
     variables: &Variables<S>,
     context: &QueryT::Context,
 ) -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+where
+    S: ScalarValue,
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S, Context = QueryT::Context>,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+{
+    execute_sync_with_meta(
+        document_source,
+        operation_name,
+        root_node,
+        variables,
+        context,
+        executor::RequestMeta::default(),
+    )
+    .map(|(value, errors, _)| (value, errors))
+}
+
+/// Execute a query synchronously in a provided schema with the given request `extensions`,
+/// additionally returning the metadata of the response.
+pub(crate) fn execute_sync_with_meta<'a, S, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    operation_name: Option<&str>,
+    root_node: &'a RootNode<QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+    request_meta: executor::RequestMeta<'_, S>,
+) -> executor::QueryResultWithMeta<'a, S>
 where
     S: ScalarValue,
     QueryT: GraphQLType<S>,
@@ -234,8 +269,10 @@ where
 {
     let document = parse_document_source(document_source, &root_node.schema)?;
 
+    let schema = root_node.schema_for(context);
+
     {
-        let mut ctx = ValidatorContext::new(&root_node.schema, &document);
+        let mut ctx = ValidatorContext::new(&schema, &document);
         visit_all_rules(&mut ctx, &document);
 
         let errors = ctx.into_errors();
@@ -247,14 +284,21 @@ where
     let operation = get_operation(&document, operation_name)?;
 
     {
-        let errors = validate_input_values(variables, operation, &root_node.schema);
+        let errors = validate_input_values(variables, operation, &schema);
 
         if !errors.is_empty() {
             return Err(GraphQLError::ValidationError(errors));
         }
     }
 
-    execute_validated_query(&document, operation, root_node, variables, context)
+    executor::execute_validated_query_with_meta(
+        &document,
+        operation,
+        root_node,
+        variables,
+        context,
+        request_meta,
+    )
 }
 
 /// Execute a query in a provided schema
@@ -265,6 +309,38 @@ pub async fn execute<'a, S, QueryT, MutationT, SubscriptionT>(
     variables: &Variables<S>,
     context: &QueryT::Context,
 ) -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+where
+    QueryT: GraphQLTypeAsync<S>,
+    QueryT::TypeInfo: Sync,
+    QueryT::Context: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLType<S, Context = QueryT::Context> + Sync,
+    SubscriptionT::TypeInfo: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    execute_with_meta(
+        document_source,
+        operation_name,
+        root_node,
+        variables,
+        context,
+        executor::RequestMeta::default(),
+    )
+    .await
+    .map(|(value, errors, _)| (value, errors))
+}
+
+/// Execute a query in a provided schema with the given request `extensions`, additionally
+/// returning the metadata of the response.
+pub(crate) async fn execute_with_meta<'a, S, QueryT, MutationT, SubscriptionT>(
+    document_source: &'a str,
+    operation_name: Option<&str>,
+    root_node: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    variables: &Variables<S>,
+    context: &QueryT::Context,
+    request_meta: executor::RequestMeta<'_, S>,
+) -> executor::QueryResultWithMeta<'a, S>
 where
     QueryT: GraphQLTypeAsync<S>,
     QueryT::TypeInfo: Sync,
@@ -277,8 +353,10 @@ where
 {
     let document = parse_document_source(document_source, &root_node.schema)?;
 
+    let schema = root_node.schema_for(context);
+
     {
-        let mut ctx = ValidatorContext::new(&root_node.schema, &document);
+        let mut ctx = ValidatorContext::new(&schema, &document);
         visit_all_rules(&mut ctx, &document);
 
         let errors = ctx.into_errors();
@@ -290,15 +368,22 @@ where
     let operation = get_operation(&document, operation_name)?;
 
     {
-        let errors = validate_input_values(variables, operation, &root_node.schema);
+        let errors = validate_input_values(variables, operation, &schema);
 
         if !errors.is_empty() {
             return Err(GraphQLError::ValidationError(errors));
         }
     }
 
-    executor::execute_validated_query_async(&document, operation, root_node, variables, context)
-        .await
+    executor::execute_validated_query_async_with_meta(
+        &document,
+        operation,
+        root_node,
+        variables,
+        context,
+        request_meta,
+    )
+    .await
 }
 
 /// Resolve subscription into `ValuesStream`
@@ -322,8 +407,10 @@ where
     let document: crate::ast::OwnedDocument<'a, S> =
         parse_document_source(document_source, &root_node.schema)?;
 
+    let schema = root_node.schema_for(context);
+
     {
-        let mut ctx = ValidatorContext::new(&root_node.schema, &document);
+        let mut ctx = ValidatorContext::new(&schema, &document);
         visit_all_rules(&mut ctx, &document);
 
         let errors = ctx.into_errors();
@@ -335,7 +422,7 @@ where
     let operation = get_operation(&document, operation_name)?;
 
     {
-        let errors = validate_input_values(&variables, operation, &root_node.schema);
+        let errors = validate_input_values(&variables, operation, &schema);
 
         if !errors.is_empty() {
             return Err(GraphQLError::ValidationError(errors));
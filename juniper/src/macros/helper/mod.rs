@@ -2,7 +2,16 @@
 
 pub mod subscription;
 
-use crate::{DefaultScalarValue, DynGraphQLValue, DynGraphQLValueAsync, ScalarValue};
+use std::{
+    any::TypeId,
+    collections::BTreeMap,
+    sync::{PoisonError, RwLock},
+};
+
+use crate::{
+    meta::Field, Arguments, BoxFuture, DefaultScalarValue, DynGraphQLValue, DynGraphQLValueAsync,
+    ExecutionResult, Executor, GraphQLValue, Registry, ScalarValue,
+};
 
 /// Conversion of a [`GraphQLValue`] to its [trait object][1].
 ///
@@ -32,3 +41,196 @@ pub trait AsDynGraphQLValue<S: ScalarValue = DefaultScalarValue> {
 }
 
 crate::sa::assert_obj_safe!(AsDynGraphQLValue<Context = (), TypeInfo = ()>);
+
+/// Additional fields of a GraphQL object derived with `#[graphql(complex)]`.
+///
+/// Implemented by a `#[graphql_object(complex)]` impl block of the same type, whose methods are
+/// resolved alongside the fields of the derived struct.
+pub trait ComplexObject<S: ScalarValue = DefaultScalarValue>: GraphQLValue<S> {
+    /// Names of the additional fields.
+    const FIELD_NAMES: &'static [&'static str];
+
+    /// Returns the meta information of the additional fields.
+    fn fields<'r>(info: &Self::TypeInfo, registry: &mut Registry<'r, S>) -> Vec<Field<'r, S>>
+    where
+        S: 'r;
+
+    /// Resolves the additional field with the given `field_name`.
+    ///
+    /// Returns [`None`] if there is no such additional field.
+    fn resolve_field(
+        &self,
+        info: &Self::TypeInfo,
+        field_name: &str,
+        arguments: &Arguments<S>,
+        executor: &Executor<Self::Context, S>,
+    ) -> Option<ExecutionResult<S>>;
+
+    /// Resolves the additional field with the given `field_name` asynchronously.
+    ///
+    /// Returns [`None`] if there is no such additional field.
+    fn resolve_field_async<'b>(
+        &'b self,
+        info: &'b Self::TypeInfo,
+        field_name: &'b str,
+        arguments: &'b Arguments<S>,
+        executor: &'b Executor<Self::Context, S>,
+    ) -> Option<BoxFuture<'b, ExecutionResult<S>>>
+    where
+        S: Send + Sync,
+        Self: Sync;
+}
+
+/// Names of the fields of a GraphQL object, known at compile time.
+#[derive(Clone, Copy, Debug)]
+pub enum FieldNames {
+    /// Plain list of field names.
+    List(&'static [&'static str]),
+
+    /// Field names of several objects merged together.
+    Merged(&'static [FieldNames]),
+}
+
+impl FieldNames {
+    /// Returns the number of field names.
+    pub const fn len(&self) -> usize {
+        match self {
+            Self::List(names) => names.len(),
+            Self::Merged(parts) => {
+                let mut len = 0;
+                let mut i = 0;
+                while i < parts.len() {
+                    len += parts[i].len();
+                    i += 1;
+                }
+                len
+            }
+        }
+    }
+
+    /// Indicates whether there are no field names.
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the field name at the given `index`.
+    ///
+    /// # Panics
+    ///
+    /// If the `index` is out of bounds.
+    pub const fn get(&self, mut index: usize) -> &'static str {
+        match *self {
+            Self::List(names) => names[index],
+            Self::Merged(parts) => {
+                let mut i = 0;
+                loop {
+                    let len = parts[i].len();
+                    if index < len {
+                        return parts[i].get(index);
+                    }
+                    index -= len;
+                    i += 1;
+                }
+            }
+        }
+    }
+
+    /// Indicates whether the given field `name` is contained.
+    pub const fn contains(&self, name: &str) -> bool {
+        let mut i = 0;
+        while i < self.len() {
+            if str_eq(self.get(i), name) {
+                return true;
+            }
+            i += 1;
+        }
+        false
+    }
+
+    /// Asserts that no field name is contained twice.
+    ///
+    /// # Panics
+    ///
+    /// With the duplicated field name, so that a compile-time evaluation of this function
+    /// fails with it.
+    pub const fn assert_no_duplicate_fields(&self) {
+        let len = self.len();
+        let mut i = 0;
+        while i < len {
+            let mut j = i + 1;
+            while j < len {
+                if str_eq(self.get(i), self.get(j)) {
+                    panic!("{}", self.get(i));
+                }
+                j += 1;
+            }
+            i += 1;
+        }
+    }
+}
+
+/// Compares the given strings in a `const` context.
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// GraphQL object with [`FieldNames`] known at compile time, so it can be merged into another
+/// object with `#[derive(GraphQLMergedObject)]`.
+pub trait ObjectFieldNames<S: ScalarValue = DefaultScalarValue> {
+    /// Names of the fields of this object.
+    const FIELD_NAMES: FieldNames;
+
+    /// Indicates whether this object implements any GraphQL interfaces, so can't be merged into
+    /// another object, as the interfaces don't list the merged object as their implementer.
+    const IMPLEMENTS_INTERFACES: bool = false;
+
+    /// Fails the compilation if [`ObjectFieldNames::FIELD_NAMES`] contain a duplicate, once
+    /// evaluated.
+    const NO_DUPLICATE_FIELDS: () = Self::FIELD_NAMES.assert_no_duplicate_fields();
+}
+
+/// GraphQL input object with [`FieldNames`] known at compile time, so its fields can be flattened
+/// into separate arguments of a field with `#[graphql(arguments(<arg>(flatten)))]`.
+pub trait InputObjectFieldNames<S: ScalarValue = DefaultScalarValue> {
+    /// Names of the fields of this input object.
+    const FIELD_NAMES: FieldNames;
+}
+
+/// Returns the GraphQL name of the generic type `T`, concatenated from the `parts` of its name
+/// template with the names of its type parameters already substituted.
+///
+/// The name is built once per monomorphized type `T`, keyed by its [`TypeId`], and leaked, so it
+/// can be returned from [`GraphQLType::name`] like a name known at compile time. Later calls only
+/// take a read lock.
+///
+/// [`GraphQLType::name`]: crate::GraphQLType::name
+pub fn type_name_from_parts<T: ?Sized + 'static>(
+    parts: impl FnOnce() -> Vec<&'static str>,
+) -> &'static str {
+    static NAMES: RwLock<BTreeMap<TypeId, &'static str>> = RwLock::new(BTreeMap::new());
+
+    let ty = TypeId::of::<T>();
+    if let Some(name) = NAMES
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&ty)
+    {
+        return name;
+    }
+    NAMES
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .entry(ty)
+        .or_insert_with(|| Box::leak(parts().concat().into_boxed_str()))
+}
@@ -16,6 +16,9 @@ pub enum ParseError<'a> {
 
     /// A scalar of unexpected type occurred in the source
     ExpectedScalarError(&'static str),
+
+    /// A scalar was rejected by its type, for the given reason
+    InvalidScalarError(String),
 }
 
 #[doc(hidden)]
@@ -200,6 +203,7 @@ impl<'a> fmt::Display for ParseError<'a> {
             ParseError::UnexpectedEndOfFile => write!(f, "Unexpected end of input"),
             ParseError::LexerError(ref err) => err.fmt(f),
             ParseError::ExpectedScalarError(err) => err.fmt(f),
+            ParseError::InvalidScalarError(ref err) => err.fmt(f),
         }
     }
 }
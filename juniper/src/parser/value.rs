@@ -60,9 +60,15 @@ where
                 end,
             } = parser.next_token()?
             {
+                // A scalar rejecting the literal knows better why than the inferred type does.
                 (s.parse_fn)(scalar)
                     .map(|s| Spanning::start_end(&start, &end, InputValue::Scalar(s)))
-                    .or_else(|_| parse_scalar_literal_by_infered_type(scalar, &start, &end, schema))
+                    .or_else(|e| match e {
+                        ParseError::InvalidScalarError(_) => {
+                            Err(Spanning::start_end(&start, &end, e))
+                        }
+                        _ => parse_scalar_literal_by_infered_type(scalar, &start, &end, schema),
+                    })
             } else {
                 unreachable!()
             }
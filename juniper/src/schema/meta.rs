@@ -1,12 +1,15 @@
 //! Types used to describe a `GraphQL` schema
 
 use std::{
+    any::Any,
     borrow::{Cow, ToOwned},
     fmt,
+    sync::Arc,
 };
 
 use crate::{
     ast::{FromInputValue, InputValue, Type},
+    executor::CacheHint,
     parser::{ParseError, ScalarToken},
     schema::model::SchemaType,
     types::base::TypeKind,
@@ -40,32 +43,79 @@ impl DeprecationStatus {
     }
 }
 
+/// Predicate deciding whether a schema item is visible to the current request.
+///
+/// Items with a [`Visibility`] are removed from introspection results and rejected by the
+/// validation of incoming queries, unless the predicate holds for the context of the request.
+/// Predicates are only evaluated if they were enabled on the schema with
+/// [`RootNode::enable_visibility`], otherwise such items are always hidden.
+///
+/// The predicate is always evaluated against the context of the whole request (the one of the
+/// query root type). If the context of the request is of a different type, the item is hidden.
+///
+/// [`RootNode::enable_visibility`]: crate::RootNode::enable_visibility
+#[derive(Clone)]
+pub struct Visibility(Arc<VisibilityPredicate>);
+
+type VisibilityPredicate = dyn Fn(&dyn Any) -> bool + Send + Sync;
+
+impl Visibility {
+    /// Creates a new [`Visibility`] out of the given `predicate` over the request context.
+    pub fn new<CtxT, F>(predicate: F) -> Self
+    where
+        CtxT: Any,
+        F: Fn(&CtxT) -> bool + Send + Sync + 'static,
+    {
+        Self(Arc::new(move |ctx: &dyn Any| {
+            ctx.downcast_ref::<CtxT>().map_or(false, &predicate)
+        }))
+    }
+
+    /// Checks whether the item is visible to the given request `context`.
+    ///
+    /// A missing `context` means that visibility predicates are not enabled on the schema, so
+    /// the item is considered hidden.
+    pub fn is_visible(&self, context: Option<&dyn Any>) -> bool {
+        context.map_or(false, |ctx| (self.0)(ctx))
+    }
+}
+
+impl fmt::Debug for Visibility {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str("Visibility")
+    }
+}
+
 /// Scalar type metadata
+#[derive(Clone)]
 pub struct ScalarMeta<'a, S> {
     #[doc(hidden)]
     pub name: Cow<'a, str>,
     #[doc(hidden)]
     pub description: Option<String>,
+    #[doc(hidden)]
+    pub visibility: Option<Visibility>,
     pub(crate) try_parse_fn: for<'b> fn(&'b InputValue<S>) -> bool,
+    pub(crate) parse_error_fn: for<'b> fn(&'b InputValue<S>) -> Option<String>,
     pub(crate) parse_fn: for<'b> fn(ScalarToken<'b>) -> Result<S, ParseError<'b>>,
 }
 
 /// List type metadata
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct ListMeta<'a> {
     #[doc(hidden)]
     pub of_type: Type<'a>,
 }
 
 /// Nullable type metadata
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct NullableMeta<'a> {
     #[doc(hidden)]
     pub of_type: Type<'a>,
 }
 
 /// Object type metadata
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct ObjectMeta<'a, S> {
     #[doc(hidden)]
     pub name: Cow<'a, str>,
@@ -75,9 +125,14 @@ pub struct ObjectMeta<'a, S> {
     pub fields: Vec<Field<'a, S>>,
     #[doc(hidden)]
     pub interface_names: Vec<String>,
+    #[doc(hidden)]
+    pub visibility: Option<Visibility>,
+    #[doc(hidden)]
+    pub cache_hint: Option<CacheHint>,
 }
 
 /// Enum type metadata
+#[derive(Clone)]
 pub struct EnumMeta<'a, S> {
     #[doc(hidden)]
     pub name: Cow<'a, str>,
@@ -85,11 +140,13 @@ pub struct EnumMeta<'a, S> {
     pub description: Option<String>,
     #[doc(hidden)]
     pub values: Vec<EnumValue>,
+    #[doc(hidden)]
+    pub visibility: Option<Visibility>,
     pub(crate) try_parse_fn: for<'b> fn(&'b InputValue<S>) -> bool,
 }
 
 /// Interface type metadata
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct InterfaceMeta<'a, S> {
     #[doc(hidden)]
     pub name: Cow<'a, str>,
@@ -97,10 +154,12 @@ pub struct InterfaceMeta<'a, S> {
     pub description: Option<String>,
     #[doc(hidden)]
     pub fields: Vec<Field<'a, S>>,
+    #[doc(hidden)]
+    pub visibility: Option<Visibility>,
 }
 
 /// Union type metadata
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct UnionMeta<'a> {
     #[doc(hidden)]
     pub name: Cow<'a, str>,
@@ -108,9 +167,12 @@ pub struct UnionMeta<'a> {
     pub description: Option<String>,
     #[doc(hidden)]
     pub of_type_names: Vec<String>,
+    #[doc(hidden)]
+    pub visibility: Option<Visibility>,
 }
 
 /// Input object metadata
+#[derive(Clone)]
 pub struct InputObjectMeta<'a, S> {
     #[doc(hidden)]
     pub name: Cow<'a, str>,
@@ -118,6 +180,8 @@ pub struct InputObjectMeta<'a, S> {
     pub description: Option<String>,
     #[doc(hidden)]
     pub input_fields: Vec<Argument<'a, S>>,
+    #[doc(hidden)]
+    pub visibility: Option<Visibility>,
     pub(crate) try_parse_fn: for<'b> fn(&'b InputValue<S>) -> bool,
 }
 
@@ -125,14 +189,14 @@ pub struct InputObjectMeta<'a, S> {
 ///
 /// After a type's `meta` method has been called but before it has returned, a placeholder type
 /// is inserted into a registry to indicate existence.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct PlaceholderMeta<'a> {
     #[doc(hidden)]
     pub of_type: Type<'a>,
 }
 
 /// Generic type metadata
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub enum MetaType<'a, S = DefaultScalarValue> {
     #[doc(hidden)]
     Scalar(ScalarMeta<'a, S>),
@@ -167,6 +231,10 @@ pub struct Field<'a, S> {
     pub field_type: Type<'a>,
     #[doc(hidden)]
     pub deprecation_status: DeprecationStatus,
+    #[doc(hidden)]
+    pub visibility: Option<Visibility>,
+    #[doc(hidden)]
+    pub cache_hint: Option<CacheHint>,
 }
 
 impl<'a, S> Field<'a, S> {
@@ -175,6 +243,21 @@ impl<'a, S> Field<'a, S> {
         // "used exclusively by GraphQL’s introspection system"
         self.name.starts_with("__")
     }
+
+    /// Converts this [`Field`] into one owning all of its type names.
+    pub(crate) fn into_static(self) -> Field<'static, S> {
+        Field {
+            name: self.name,
+            description: self.description,
+            arguments: self
+                .arguments
+                .map(|args| args.into_iter().map(Argument::into_static).collect()),
+            field_type: self.field_type.into_static(),
+            deprecation_status: self.deprecation_status,
+            visibility: self.visibility,
+            cache_hint: self.cache_hint,
+        }
+    }
 }
 
 /// Metadata for an argument to a field
@@ -188,6 +271,8 @@ pub struct Argument<'a, S> {
     pub arg_type: Type<'a>,
     #[doc(hidden)]
     pub default_value: Option<InputValue<S>>,
+    #[doc(hidden)]
+    pub visibility: Option<Visibility>,
 }
 
 impl<'a, S> Argument<'a, S> {
@@ -196,6 +281,17 @@ impl<'a, S> Argument<'a, S> {
         // "used exclusively by GraphQL’s introspection system"
         self.name.starts_with("__")
     }
+
+    /// Converts this [`Argument`] into one owning all of its type names.
+    pub(crate) fn into_static(self) -> Argument<'static, S> {
+        Argument {
+            name: self.name,
+            description: self.description,
+            arg_type: self.arg_type.into_static(),
+            default_value: self.default_value,
+            visibility: self.visibility,
+        }
+    }
 }
 
 /// Metadata for a single value in an enum
@@ -212,6 +308,8 @@ pub struct EnumValue {
     pub description: Option<String>,
     /// Whether the field is deprecated or not, with an optional reason.
     pub deprecation_status: DeprecationStatus,
+    /// Whether the enum value is visible only to some requests.
+    pub visibility: Option<Visibility>,
 }
 
 impl<'a, S> MetaType<'a, S> {
@@ -257,6 +355,21 @@ impl<'a, S> MetaType<'a, S> {
         }
     }
 
+    /// Access the visibility predicate of the type, if applicable
+    ///
+    /// Lists, nullable wrappers, and placeholders don't have visibility predicates.
+    pub fn visibility(&self) -> Option<&Visibility> {
+        match *self {
+            MetaType::Scalar(ScalarMeta { ref visibility, .. })
+            | MetaType::Object(ObjectMeta { ref visibility, .. })
+            | MetaType::Enum(EnumMeta { ref visibility, .. })
+            | MetaType::Interface(InterfaceMeta { ref visibility, .. })
+            | MetaType::Union(UnionMeta { ref visibility, .. })
+            | MetaType::InputObject(InputObjectMeta { ref visibility, .. }) => visibility.as_ref(),
+            _ => None,
+        }
+    }
+
     /// Construct a `TypeKind` for a given type
     ///
     /// # Panics
@@ -300,6 +413,70 @@ impl<'a, S> MetaType<'a, S> {
         }
     }
 
+    /// Converts this [`MetaType`] into one owning all of its names.
+    pub(crate) fn into_static(self) -> MetaType<'static, S> {
+        let owned = |name: Cow<'a, str>| Cow::Owned(name.into_owned());
+        let fields =
+            |fields: Vec<Field<'a, S>>| fields.into_iter().map(Field::into_static).collect();
+        match self {
+            MetaType::Scalar(m) => MetaType::Scalar(ScalarMeta {
+                name: owned(m.name),
+                description: m.description,
+                visibility: m.visibility,
+                try_parse_fn: m.try_parse_fn,
+                parse_error_fn: m.parse_error_fn,
+                parse_fn: m.parse_fn,
+            }),
+            MetaType::List(m) => MetaType::List(ListMeta {
+                of_type: m.of_type.into_static(),
+            }),
+            MetaType::Nullable(m) => MetaType::Nullable(NullableMeta {
+                of_type: m.of_type.into_static(),
+            }),
+            MetaType::Object(m) => MetaType::Object(ObjectMeta {
+                name: owned(m.name),
+                description: m.description,
+                fields: fields(m.fields),
+                interface_names: m.interface_names,
+                visibility: m.visibility,
+                cache_hint: m.cache_hint,
+            }),
+            MetaType::Enum(m) => MetaType::Enum(EnumMeta {
+                name: owned(m.name),
+                description: m.description,
+                values: m.values,
+                visibility: m.visibility,
+                try_parse_fn: m.try_parse_fn,
+            }),
+            MetaType::Interface(m) => MetaType::Interface(InterfaceMeta {
+                name: owned(m.name),
+                description: m.description,
+                fields: fields(m.fields),
+                visibility: m.visibility,
+            }),
+            MetaType::Union(m) => MetaType::Union(UnionMeta {
+                name: owned(m.name),
+                description: m.description,
+                of_type_names: m.of_type_names,
+                visibility: m.visibility,
+            }),
+            MetaType::InputObject(m) => MetaType::InputObject(InputObjectMeta {
+                name: owned(m.name),
+                description: m.description,
+                input_fields: m
+                    .input_fields
+                    .into_iter()
+                    .map(Argument::into_static)
+                    .collect(),
+                visibility: m.visibility,
+                try_parse_fn: m.try_parse_fn,
+            }),
+            MetaType::Placeholder(m) => MetaType::Placeholder(PlaceholderMeta {
+                of_type: m.of_type.into_static(),
+            }),
+        }
+    }
+
     /// Construct a `Type` literal instance based on the metadata
     pub fn as_type(&self) -> Type<'a> {
         match *self {
@@ -394,6 +571,54 @@ impl<'a, S> MetaType<'a, S> {
         }
     }
 
+    /// Returns true if the other type defines the same fields, values or members as this one, so
+    /// both can be registered with the same name.
+    ///
+    /// Placeholders of types still being built are assumed to match any type.
+    pub(crate) fn has_same_shape(&self, other: &Self) -> bool {
+        fn same_fields<S>(a: &[Field<S>], b: &[Field<S>]) -> bool {
+            a.len() == b.len()
+                && a.iter().zip(b).all(|(a, b)| {
+                    a.name == b.name
+                        && a.field_type == b.field_type
+                        && same_args(
+                            a.arguments.as_deref().unwrap_or_default(),
+                            b.arguments.as_deref().unwrap_or_default(),
+                        )
+                })
+        }
+
+        fn same_args<S>(a: &[Argument<S>], b: &[Argument<S>]) -> bool {
+            a.len() == b.len()
+                && a.iter()
+                    .zip(b)
+                    .all(|(a, b)| a.name == b.name && a.arg_type == b.arg_type)
+        }
+
+        match (self, other) {
+            (MetaType::Placeholder(_), _) | (_, MetaType::Placeholder(_)) => true,
+            (MetaType::Scalar(_), MetaType::Scalar(_)) => true,
+            (MetaType::List(a), MetaType::List(b)) => a.of_type == b.of_type,
+            (MetaType::Nullable(a), MetaType::Nullable(b)) => a.of_type == b.of_type,
+            (MetaType::Object(a), MetaType::Object(b)) => {
+                a.interface_names == b.interface_names && same_fields(&a.fields, &b.fields)
+            }
+            (MetaType::Interface(a), MetaType::Interface(b)) => same_fields(&a.fields, &b.fields),
+            (MetaType::Enum(a), MetaType::Enum(b)) => {
+                a.values.len() == b.values.len()
+                    && a.values
+                        .iter()
+                        .zip(&b.values)
+                        .all(|(a, b)| a.name == b.name)
+            }
+            (MetaType::Union(a), MetaType::Union(b)) => a.of_type_names == b.of_type_names,
+            (MetaType::InputObject(a), MetaType::InputObject(b)) => {
+                same_args(&a.input_fields, &b.input_fields)
+            }
+            _ => false,
+        }
+    }
+
     pub(crate) fn fields<'b>(&self, schema: &'b SchemaType<S>) -> Option<Vec<&'b Field<'b, S>>> {
         schema
             .lookup_type(&self.as_type())
@@ -425,7 +650,9 @@ where
         ScalarMeta {
             name,
             description: None,
+            visibility: None,
             try_parse_fn: try_parse_fn::<S, T>,
+            parse_error_fn: <T as FromInputValue<S>>::from_input_value_error,
             parse_fn: <T as ParseScalarValue<S>>::from_str,
         }
     }
@@ -438,6 +665,18 @@ where
         self
     }
 
+    /// Makes the scalar type visible only to requests whose context satisfies the given `predicate`.
+    ///
+    /// See [`Visibility`] for details.
+    pub fn visible_if<CtxT, F>(mut self, predicate: F) -> Self
+    where
+        CtxT: Any,
+        F: Fn(&CtxT) -> bool + Send + Sync + 'static,
+    {
+        self.visibility = Some(Visibility::new(predicate));
+        self
+    }
+
     /// Wrap the scalar in a generic meta type
     pub fn into_meta(self) -> MetaType<'a, S> {
         MetaType::Scalar(self)
@@ -477,6 +716,8 @@ where
         ObjectMeta {
             name,
             description: None,
+            visibility: None,
+            cache_hint: None,
             fields: fields.to_vec(),
             interface_names: vec![],
         }
@@ -502,6 +743,51 @@ where
         self
     }
 
+    /// Makes the object type visible only to requests whose context satisfies the given `predicate`.
+    ///
+    /// See [`Visibility`] for details.
+    pub fn visible_if<CtxT, F>(mut self, predicate: F) -> Self
+    where
+        CtxT: Any,
+        F: Fn(&CtxT) -> bool + Send + Sync + 'static,
+    {
+        self.visibility = Some(Visibility::new(predicate));
+        self
+    }
+
+    /// Sets the cache control hint of the object type.
+    ///
+    /// The cache policy of the whole response is restricted by it once the object type is resolved.
+    pub fn cache_control(mut self, hint: CacheHint) -> Self {
+        self.cache_hint = Some(hint);
+        self
+    }
+
+    /// Carries the object-level settings of the given `part` over to this object type, merging
+    /// it with `#[derive(GraphQLMergedObject)]`.
+    ///
+    /// Descriptions are joined, the object type is only visible if the `part` is, and its cache
+    /// control hint is restricted by the one of the `part`. Fields are not touched.
+    #[doc(hidden)]
+    pub fn merge_part(mut self, part: ObjectMeta<'a, S>) -> Self {
+        if let Some(description) = part.description {
+            self.description = Some(match self.description {
+                Some(own) => format!("{}\n\n{}", own, description),
+                None => description,
+            });
+        }
+        if let Some(Visibility(part)) = part.visibility {
+            self.visibility = Some(match self.visibility {
+                Some(Visibility(own)) => Visibility(Arc::new(move |ctx| own(ctx) && part(ctx))),
+                None => Visibility(part),
+            });
+        }
+        if let Some(hint) = part.cache_hint {
+            self.cache_hint.get_or_insert(hint).restrict(&hint);
+        }
+        self
+    }
+
     /// Wrap this object type in a generic meta type
     pub fn into_meta(self) -> MetaType<'a, S> {
         MetaType::Object(self)
@@ -520,6 +806,7 @@ where
         EnumMeta {
             name,
             description: None,
+            visibility: None,
             values: values.to_vec(),
             try_parse_fn: try_parse_fn::<S, T>,
         }
@@ -533,6 +820,18 @@ where
         self
     }
 
+    /// Makes the enum type visible only to requests whose context satisfies the given `predicate`.
+    ///
+    /// See [`Visibility`] for details.
+    pub fn visible_if<CtxT, F>(mut self, predicate: F) -> Self
+    where
+        CtxT: Any,
+        F: Fn(&CtxT) -> bool + Send + Sync + 'static,
+    {
+        self.visibility = Some(Visibility::new(predicate));
+        self
+    }
+
     /// Wrap this enum type in a generic meta type
     pub fn into_meta(self) -> MetaType<'a, S> {
         MetaType::Enum(self)
@@ -548,6 +847,7 @@ where
         InterfaceMeta {
             name,
             description: None,
+            visibility: None,
             fields: fields.to_vec(),
         }
     }
@@ -560,6 +860,18 @@ where
         self
     }
 
+    /// Makes the interface type visible only to requests whose context satisfies the given `predicate`.
+    ///
+    /// See [`Visibility`] for details.
+    pub fn visible_if<CtxT, F>(mut self, predicate: F) -> Self
+    where
+        CtxT: Any,
+        F: Fn(&CtxT) -> bool + Send + Sync + 'static,
+    {
+        self.visibility = Some(Visibility::new(predicate));
+        self
+    }
+
     /// Wrap this interface type in a generic meta type
     pub fn into_meta(self) -> MetaType<'a, S> {
         MetaType::Interface(self)
@@ -572,6 +884,7 @@ impl<'a> UnionMeta<'a> {
         UnionMeta {
             name,
             description: None,
+            visibility: None,
             of_type_names: of_types
                 .iter()
                 .map(|t| t.innermost_name().to_owned())
@@ -587,6 +900,18 @@ impl<'a> UnionMeta<'a> {
         self
     }
 
+    /// Makes the union type visible only to requests whose context satisfies the given `predicate`.
+    ///
+    /// See [`Visibility`] for details.
+    pub fn visible_if<CtxT, F>(mut self, predicate: F) -> Self
+    where
+        CtxT: Any,
+        F: Fn(&CtxT) -> bool + Send + Sync + 'static,
+    {
+        self.visibility = Some(Visibility::new(predicate));
+        self
+    }
+
     /// Wrap this union type in a generic meta type
     pub fn into_meta<S>(self) -> MetaType<'a, S> {
         MetaType::Union(self)
@@ -605,6 +930,7 @@ where
         InputObjectMeta {
             name,
             description: None,
+            visibility: None,
             input_fields: input_fields.to_vec(),
             try_parse_fn: try_parse_fn::<S, T>,
         }
@@ -618,6 +944,18 @@ where
         self
     }
 
+    /// Makes the input object type visible only to requests whose context satisfies the given `predicate`.
+    ///
+    /// See [`Visibility`] for details.
+    pub fn visible_if<CtxT, F>(mut self, predicate: F) -> Self
+    where
+        CtxT: Any,
+        F: Fn(&CtxT) -> bool + Send + Sync + 'static,
+    {
+        self.visibility = Some(Visibility::new(predicate));
+        self
+    }
+
     /// Wrap this union type in a generic meta type
     pub fn into_meta(self) -> MetaType<'a, S> {
         MetaType::InputObject(self)
@@ -636,12 +974,22 @@ impl<'a, S> Field<'a, S> {
     /// Add an argument to the field
     ///
     /// Arguments are unordered and can't contain duplicates by name.
+    ///
+    /// # Panics
+    ///
+    /// If the field already has an argument with the same name.
     pub fn argument(mut self, argument: Argument<'a, S>) -> Self {
         match self.arguments {
             None => {
                 self.arguments = Some(vec![argument]);
             }
             Some(ref mut args) => {
+                if args.iter().any(|arg| arg.name == argument.name) {
+                    panic!(
+                        "Field `{}` has several arguments named `{}`",
+                        self.name, argument.name,
+                    );
+                }
                 args.push(argument);
             }
         };
@@ -649,6 +997,21 @@ impl<'a, S> Field<'a, S> {
         self
     }
 
+    /// Add several arguments to the field
+    ///
+    /// Arguments are unordered and can't contain duplicates by name.
+    ///
+    /// # Panics
+    ///
+    /// If the field already has an argument with the same name as one of the `arguments`, or
+    /// the `arguments` contain a duplicate.
+    pub fn arguments<I>(self, arguments: I) -> Self
+    where
+        I: IntoIterator<Item = Argument<'a, S>>,
+    {
+        arguments.into_iter().fold(self, Self::argument)
+    }
+
     /// Set the field to be deprecated with an optional reason.
     ///
     /// This overwrites the deprecation reason if any was previously set.
@@ -656,6 +1019,25 @@ impl<'a, S> Field<'a, S> {
         self.deprecation_status = DeprecationStatus::Deprecated(reason.map(ToOwned::to_owned));
         self
     }
+    /// Makes the field visible only to requests whose context satisfies the given `predicate`.
+    ///
+    /// See [`Visibility`] for details.
+    pub fn visible_if<CtxT, F>(mut self, predicate: F) -> Self
+    where
+        CtxT: Any,
+        F: Fn(&CtxT) -> bool + Send + Sync + 'static,
+    {
+        self.visibility = Some(Visibility::new(predicate));
+        self
+    }
+
+    /// Sets the cache control hint of the field.
+    ///
+    /// The cache policy of the whole response is restricted by it once the field is resolved.
+    pub fn cache_control(mut self, hint: CacheHint) -> Self {
+        self.cache_hint = Some(hint);
+        self
+    }
 }
 
 impl<'a, S> Argument<'a, S> {
@@ -666,6 +1048,7 @@ impl<'a, S> Argument<'a, S> {
             description: None,
             arg_type,
             default_value: None,
+            visibility: None,
         }
     }
 
@@ -684,6 +1067,17 @@ impl<'a, S> Argument<'a, S> {
         self.default_value = Some(default_value);
         self
     }
+    /// Makes the argument visible only to requests whose context satisfies the given `predicate`.
+    ///
+    /// See [`Visibility`] for details.
+    pub fn visible_if<CtxT, F>(mut self, predicate: F) -> Self
+    where
+        CtxT: Any,
+        F: Fn(&CtxT) -> bool + Send + Sync + 'static,
+    {
+        self.visibility = Some(Visibility::new(predicate));
+        self
+    }
 }
 
 impl EnumValue {
@@ -693,6 +1087,7 @@ impl EnumValue {
             name: name.to_owned(),
             description: None,
             deprecation_status: DeprecationStatus::Current,
+            visibility: None,
         }
     }
 
@@ -711,6 +1106,17 @@ impl EnumValue {
         self.deprecation_status = DeprecationStatus::Deprecated(reason.map(ToOwned::to_owned));
         self
     }
+    /// Makes the enum value visible only to requests whose context satisfies the given `predicate`.
+    ///
+    /// See [`Visibility`] for details.
+    pub fn visible_if<CtxT, F>(mut self, predicate: F) -> Self
+    where
+        CtxT: Any,
+        F: Fn(&CtxT) -> bool + Send + Sync + 'static,
+    {
+        self.visibility = Some(Visibility::new(predicate));
+        self
+    }
 }
 
 impl<'a, S: fmt::Debug> fmt::Debug for ScalarMeta<'a, S> {
@@ -1,4 +1,10 @@
-use std::fmt;
+use std::{
+    any::Any,
+    borrow::Borrow,
+    fmt,
+    ops::Deref,
+    sync::{Arc, PoisonError, RwLock},
+};
 
 use fnv::FnvHashMap;
 #[cfg(feature = "graphql-parser-integration")]
@@ -7,8 +13,14 @@ use graphql_parser::schema::Document;
 use crate::{
     ast::Type,
     executor::{Context, Registry},
-    schema::meta::{Argument, InterfaceMeta, MetaType, ObjectMeta, PlaceholderMeta, UnionMeta},
-    types::{base::GraphQLType, name::Name},
+    schema::meta::{
+        Argument, EnumMeta, Field, InputObjectMeta, InterfaceMeta, MetaType, ObjectMeta,
+        PlaceholderMeta, UnionMeta, Visibility,
+    },
+    types::{
+        base::{GraphQLType, GraphQLValue},
+        name::Name,
+    },
     value::{DefaultScalarValue, ScalarValue},
     GraphQLEnum,
 };
@@ -44,16 +56,48 @@ pub struct RootNode<
     pub subscription_info: SubscriptionT::TypeInfo,
     #[doc(hidden)]
     pub schema: SchemaType<'a, S>,
+    #[doc(hidden)]
+    pub visibility_context: Option<VisibilityContextFn<<QueryT as GraphQLValue<S>>::Context>>,
+    visible_schemas: RwLock<FnvHashMap<Vec<bool>, Arc<SchemaType<'static, S>>>>,
 }
 
+/// Maximum number of distinct visibility sets whose filtered schemas are cached by a [`RootNode`].
+const MAX_VISIBLE_SCHEMAS: usize = 64;
+
+/// Function exposing the request context to [`Visibility`] predicates.
+///
+/// [`Visibility`]: crate::meta::Visibility
+#[doc(hidden)]
+pub type VisibilityContextFn<CtxT> = for<'c> fn(&'c CtxT) -> &'c dyn Any;
+
 /// Metadata for a schema
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct SchemaType<'a, S> {
     pub(crate) types: FnvHashMap<Name, MetaType<'a, S>>,
     pub(crate) query_type_name: String,
     pub(crate) mutation_type_name: Option<String>,
     pub(crate) subscription_type_name: Option<String>,
     directives: FnvHashMap<String, DirectiveType<'a, S>>,
+    visibilities: Vec<Visibility>,
+}
+
+/// [`SchemaType`] as seen by a single request, either the whole schema or its part visible to the
+/// request.
+#[doc(hidden)]
+pub enum SchemaRef<'r, 'a, S> {
+    Borrowed(&'r SchemaType<'a, S>),
+    Shared(Arc<SchemaType<'static, S>>),
+}
+
+impl<'r, 'a, S> Deref for SchemaRef<'r, 'a, S> {
+    type Target = SchemaType<'a, S>;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Borrowed(schema) => schema,
+            Self::Shared(schema) => schema,
+        }
+    }
 }
 
 impl<'a, S> Context for SchemaType<'a, S> {}
@@ -65,7 +109,7 @@ pub enum TypeType<'a, S: 'a> {
     List(Box<TypeType<'a, S>>),
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct DirectiveType<'a, S> {
     pub name: String,
     pub description: Option<String>,
@@ -150,7 +194,65 @@ where
             query_info,
             mutation_info,
             subscription_info,
+            visibility_context: None,
+            visible_schemas: RwLock::new(FnvHashMap::default()),
+        }
+    }
+
+    /// Enables [`Visibility`] predicates of this schema, evaluating them against the context of
+    /// each request.
+    ///
+    /// Until this is called, all the schema items having a [`Visibility`] predicate are hidden.
+    ///
+    /// [`Visibility`]: crate::meta::Visibility
+    pub fn enable_visibility(mut self) -> Self
+    where
+        QueryT::Context: Any,
+    {
+        self.visibility_context = Some(as_any::<QueryT::Context>);
+        self
+    }
+
+    /// Returns the schema as seen by the request with the given `context`, filtering out all the
+    /// items which are not visible to it.
+    ///
+    /// Borrows the whole schema if it doesn't contain any [`Visibility`] predicates. Otherwise,
+    /// the filtered schema is cached per set of predicates holding for the `context`, so it's
+    /// only built once for all the requests seeing the same items.
+    ///
+    /// [`Visibility`]: crate::meta::Visibility
+    #[doc(hidden)]
+    pub fn schema_for(&self, context: &QueryT::Context) -> SchemaRef<'_, 'a, S> {
+        if self.schema.visibilities.is_empty() {
+            return SchemaRef::Borrowed(&self.schema);
+        }
+
+        let context = self.visibility_context.map(|f| f(context));
+        let visible = self
+            .schema
+            .visibilities
+            .iter()
+            .map(|v| v.is_visible(context))
+            .collect::<Vec<_>>();
+
+        if let Some(schema) = self
+            .visible_schemas
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&visible)
+        {
+            return SchemaRef::Shared(Arc::clone(schema));
+        }
+
+        let schema = Arc::new(self.schema.visible_to(context));
+        let mut cache = self
+            .visible_schemas
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        if cache.len() < MAX_VISIBLE_SCHEMAS {
+            cache.insert(visible, Arc::clone(&schema));
         }
+        SchemaRef::Shared(schema)
     }
 
     #[cfg(feature = "schema-language")]
@@ -215,7 +317,7 @@ impl<'a, S> SchemaType<'a, S> {
         let mut meta_fields = vec![
             registry.field::<SchemaType<S>>("__schema", &()),
             registry
-                .field::<TypeType<S>>("__type", &())
+                .field::<Option<TypeType<S>>>("__type", &())
                 .argument(registry.arg::<String>("name", &())),
         ];
 
@@ -229,11 +331,36 @@ impl<'a, S> SchemaType<'a, S> {
             panic!("Root type not found");
         }
 
+        registry.assert_valid_type_names();
+
         for meta_type in registry.types.values() {
             if let MetaType::Placeholder(PlaceholderMeta { ref of_type }) = *meta_type {
                 panic!("Type {:?} is still a placeholder type", of_type);
             }
         }
+        let mut visibilities = vec![];
+        for t in registry.types.values() {
+            visibilities.extend(t.visibility().cloned());
+            match t {
+                MetaType::Object(ObjectMeta { fields, .. })
+                | MetaType::Interface(InterfaceMeta { fields, .. }) => {
+                    for f in fields {
+                        visibilities.extend(f.visibility.iter().cloned());
+                        for a in f.arguments.iter().flatten() {
+                            visibilities.extend(a.visibility.iter().cloned());
+                        }
+                    }
+                }
+                MetaType::Enum(EnumMeta { values, .. }) => {
+                    visibilities.extend(values.iter().filter_map(|v| v.visibility.clone()));
+                }
+                MetaType::InputObject(InputObjectMeta { input_fields, .. }) => {
+                    visibilities.extend(input_fields.iter().filter_map(|f| f.visibility.clone()));
+                }
+                _ => {}
+            }
+        }
+
         SchemaType {
             types: registry.types,
             query_type_name,
@@ -248,6 +375,102 @@ impl<'a, S> SchemaType<'a, S> {
                 None
             },
             directives,
+            visibilities,
+        }
+    }
+
+    /// Returns an owned copy of this schema containing only the items visible to the given request
+    /// `context`.
+    ///
+    /// Along with the hidden types themselves, all the fields, arguments and input fields of
+    /// hidden types are omitted, so the returned schema remains consistent. The root types are
+    /// never hidden.
+    pub(crate) fn visible_to(&self, context: Option<&dyn Any>) -> SchemaType<'static, S>
+    where
+        S: Clone,
+    {
+        let is_root = |name: &str| {
+            name == self.query_type_name
+                || self.mutation_type_name.as_deref() == Some(name)
+                || self.subscription_type_name.as_deref() == Some(name)
+        };
+        let hidden = self
+            .types
+            .iter()
+            .map(|(name, t)| (Borrow::<str>::borrow(name), t))
+            .filter(|(name, t)| {
+                !is_root(name) && t.visibility().map_or(false, |v| !v.is_visible(context))
+            })
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>();
+        let is_shown_name = |name: &String| !hidden.contains(&name.as_str());
+        let is_shown = |ty: &Type| !hidden.contains(&ty.innermost_name());
+
+        let filter_args = |args: &mut Vec<Argument<'a, S>>| {
+            args.retain(|a| {
+                a.visibility
+                    .as_ref()
+                    .map_or(true, |v| v.is_visible(context))
+                    && is_shown(&a.arg_type)
+            })
+        };
+        let filter_fields = |fields: &mut Vec<Field<'a, S>>| {
+            fields.retain(|f| {
+                f.visibility
+                    .as_ref()
+                    .map_or(true, |v| v.is_visible(context))
+                    && is_shown(&f.field_type)
+            });
+            for f in fields {
+                if let Some(args) = f.arguments.as_mut() {
+                    filter_args(args);
+                }
+            }
+        };
+        let types = self
+            .types
+            .iter()
+            .filter(|(name, _)| !hidden.contains(&Borrow::<str>::borrow(*name)))
+            .map(|(name, t)| {
+                let mut t = t.clone();
+                match &mut t {
+                    MetaType::Object(ObjectMeta {
+                        fields,
+                        interface_names,
+                        ..
+                    }) => {
+                        filter_fields(fields);
+                        interface_names.retain(is_shown_name);
+                    }
+                    MetaType::Interface(InterfaceMeta { fields, .. }) => filter_fields(fields),
+                    MetaType::Union(UnionMeta { of_type_names, .. }) => {
+                        of_type_names.retain(is_shown_name)
+                    }
+                    MetaType::Enum(EnumMeta { values, .. }) => values.retain(|v| {
+                        v.visibility
+                            .as_ref()
+                            .map_or(true, |v| v.is_visible(context))
+                    }),
+                    MetaType::InputObject(InputObjectMeta { input_fields, .. }) => {
+                        filter_args(input_fields)
+                    }
+                    _ => {}
+                }
+                (name.clone(), t.into_static())
+            })
+            .collect();
+
+        SchemaType {
+            types,
+            query_type_name: self.query_type_name.clone(),
+            mutation_type_name: self.mutation_type_name.clone(),
+            subscription_type_name: self.subscription_type_name.clone(),
+            directives: self
+                .directives
+                .iter()
+                .map(|(name, d)| (name.clone(), d.clone().into_static()))
+                .collect(),
+            visibilities: self.visibilities.clone(),
         }
     }
 
@@ -479,6 +702,10 @@ impl<'a, S> TypeType<'a, S> {
     }
 }
 
+fn as_any<T: Any>(val: &T) -> &dyn Any {
+    val
+}
+
 impl<'a, S> DirectiveType<'a, S>
 where
     S: ScalarValue + 'a,
@@ -532,6 +759,22 @@ where
     }
 }
 
+impl<'a, S> DirectiveType<'a, S> {
+    /// Converts this [`DirectiveType`] into one owning all of its type names.
+    fn into_static(self) -> DirectiveType<'static, S> {
+        DirectiveType {
+            name: self.name,
+            description: self.description,
+            locations: self.locations,
+            arguments: self
+                .arguments
+                .into_iter()
+                .map(Argument::into_static)
+                .collect(),
+        }
+    }
+}
+
 impl fmt::Display for DirectiveLocation {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.write_str(match *self {
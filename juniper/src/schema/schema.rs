@@ -63,14 +63,16 @@ where
         executor: &Executor<Self::Context, S>,
     ) -> ExecutionResult<S> {
         match field {
-            "__schema" => executor
-                .replaced_context(&self.schema)
-                .resolve(&(), &self.schema),
+            "__schema" => {
+                let schema = self.schema_for(executor.context());
+                executor.replaced_context(&*schema).resolve(&(), &*schema)
+            }
             "__type" => {
+                let schema = self.schema_for(executor.context());
                 let type_name: String = args.get("name").unwrap();
                 executor
-                    .replaced_context(&self.schema)
-                    .resolve(&(), &self.schema.type_by_name(&type_name))
+                    .replaced_context(&*schema)
+                    .resolve(&(), &schema.type_by_name(&type_name))
             }
             _ => self.query_type.resolve_field(info, field, args, executor),
         }
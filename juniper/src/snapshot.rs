@@ -0,0 +1,293 @@
+//! Snapshots of a schema in the [GraphQL Schema Language][1], checked in alongside the code
+//! defining it.
+//!
+//! A test asserts that the schema still matches its snapshot with [`assert_schema_snapshot`],
+//! failing on any change of it until the snapshot is updated by running the test with the
+//! [`UPDATE_ENV_VAR`] environment variable set:
+//!
+//! ```rust,no_run
+//! # use juniper::{graphql_object, EmptyMutation, EmptySubscription, RootNode};
+//! struct Query;
+//!
+//! #[graphql_object]
+//! impl Query {
+//!     fn api_version() -> &'static str {
+//!         "1.0"
+//!     }
+//! }
+//!
+//! #[test]
+//! fn schema_is_unchanged() {
+//!     let schema = RootNode::new(Query, EmptyMutation::<()>::new(), EmptySubscription::<()>::new());
+//!     juniper::snapshot::assert_schema_snapshot(&schema, "schema.graphql");
+//! }
+//! ```
+//!
+//! Alternatively, a build script may emit the schema with [`write_schema`], e.g. into a
+//! directory served to clients.
+//!
+//! Type definitions are sorted by name, so that the snapshot changes only along with the
+//! schema itself.
+//!
+//! [1]: https://graphql.org/learn/schema/#type-language
+
+use std::{env, fs, io, path::Path};
+
+use graphql_parser::schema::{Definition, TypeDefinition};
+
+use crate::{GraphQLType, RootNode, ScalarValue};
+
+/// Environment variable which makes [`assert_schema_snapshot`] update the snapshot instead of
+/// comparing the schema against it, when set to anything but `0`.
+pub const UPDATE_ENV_VAR: &str = "JUNIPER_UPDATE_SNAPSHOTS";
+
+/// Returns the schema of the given `root` in the GraphQL Schema Language, with its type
+/// definitions sorted by name.
+pub fn schema_language<'a, QueryT, MutationT, SubscriptionT, S>(
+    root: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+) -> String
+where
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S>,
+    SubscriptionT: GraphQLType<S>,
+    S: ScalarValue + 'a,
+{
+    let mut doc = root.as_parser_document();
+    doc.definitions
+        .sort_by(|a, b| sort_key(a).cmp(&sort_key(b)));
+    doc.to_string()
+}
+
+/// Asserts that the schema of the given `root` matches the snapshot stored at the given `path`.
+///
+/// Writes the snapshot instead, if the [`UPDATE_ENV_VAR`] environment variable is set.
+///
+/// # Panics
+///
+/// If the schema differs from the snapshot, listing the differing lines, or if the snapshot
+/// can't be read or written.
+pub fn assert_schema_snapshot<'a, QueryT, MutationT, SubscriptionT, S>(
+    root: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    path: impl AsRef<Path>,
+) where
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S>,
+    SubscriptionT: GraphQLType<S>,
+    S: ScalarValue + 'a,
+{
+    let update = env::var_os(UPDATE_ENV_VAR).is_some_and(|v| v != "0");
+    assert_snapshot(path.as_ref(), &schema_language(root), update);
+}
+
+/// Asserts that the `actual` schema matches the snapshot stored at the given `path`, or writes it
+/// there if `update` is set.
+fn assert_snapshot(path: &Path, actual: &str, update: bool) {
+    if update {
+        if let Err(e) = write_if_changed(path, actual) {
+            panic!(
+                "Failed to write schema snapshot `{}`: {}",
+                path.display(),
+                e
+            );
+        }
+        return;
+    }
+
+    let expected = match fs::read_to_string(path) {
+        Ok(expected) => expected.replace("\r\n", "\n"),
+        Err(e) => panic!(
+            "Failed to read schema snapshot `{}`: {}\n\
+             Run with `{}=1` to create it.",
+            path.display(),
+            e,
+            UPDATE_ENV_VAR,
+        ),
+    };
+    if expected != actual {
+        panic!(
+            "Schema differs from its snapshot `{}`:\n{}\n\
+             Run with `{}=1` to update it.",
+            path.display(),
+            diff_lines(&expected, actual),
+            UPDATE_ENV_VAR,
+        );
+    }
+}
+
+/// Writes the schema of the given `root` to the given `path`, creating its parent directories.
+///
+/// Leaves the file untouched if it already contains the schema, so it can be called from a build
+/// script without triggering rebuilds of anything watching the file.
+///
+/// Returns whether the file has been written.
+pub fn write_schema<'a, QueryT, MutationT, SubscriptionT, S>(
+    root: &'a RootNode<'a, QueryT, MutationT, SubscriptionT, S>,
+    path: impl AsRef<Path>,
+) -> io::Result<bool>
+where
+    QueryT: GraphQLType<S>,
+    MutationT: GraphQLType<S>,
+    SubscriptionT: GraphQLType<S>,
+    S: ScalarValue + 'a,
+{
+    write_if_changed(path.as_ref(), &schema_language(root))
+}
+
+fn write_if_changed(path: &Path, contents: &str) -> io::Result<bool> {
+    match fs::read_to_string(path) {
+        Ok(existing) if existing == contents => return Ok(false),
+        Ok(_) => {}
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)?;
+    Ok(true)
+}
+
+/// Orders the `schema` definition first, followed by the type definitions sorted by name.
+fn sort_key<'a>(def: &Definition<'a, &'a str>) -> (u8, &'a str) {
+    match def {
+        Definition::SchemaDefinition(_) => (0, ""),
+        Definition::TypeDefinition(def) => (
+            1,
+            match def {
+                TypeDefinition::Scalar(t) => t.name,
+                TypeDefinition::Object(t) => t.name,
+                TypeDefinition::Interface(t) => t.name,
+                TypeDefinition::Union(t) => t.name,
+                TypeDefinition::Enum(t) => t.name,
+                TypeDefinition::InputObject(t) => t.name,
+            },
+        ),
+        Definition::TypeExtension(_) => (2, ""),
+        Definition::DirectiveDefinition(d) => (3, d.name),
+    }
+}
+
+/// Lists the lines of `old` differing from `new` with a `-` and the ones replacing them in `new`
+/// with a `+`, along with their line numbers, skipping the lines both start and end with.
+fn diff_lines(old: &str, new: &str) -> String {
+    let (old, new): (Vec<_>, Vec<_>) = (old.lines().collect(), new.lines().collect());
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let removed = (prefix..old.len() - suffix).map(|i| format!("{:>4} - {}\n", i + 1, old[i]));
+    let added = (prefix..new.len() - suffix).map(|i| format!("{:>4} + {}\n", i + 1, new[i]));
+    removed.chain(added).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use crate::{
+        graphql_object, EmptyMutation, EmptySubscription, GraphQLEnum, GraphQLInputObject, RootNode,
+    };
+
+    use super::{assert_snapshot, diff_lines, schema_language, write_schema};
+
+    /// Temporary directory, removed on drop even if the test panics.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            Self(std::env::temp_dir().join(format!("juniper-{}-{}", name, std::process::id())))
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[derive(GraphQLEnum)]
+    enum Zone {
+        North,
+    }
+
+    #[derive(GraphQLInputObject)]
+    struct Area {
+        zone: Zone,
+    }
+
+    struct Query;
+
+    #[graphql_object]
+    impl Query {
+        fn count(_area: Area) -> i32 {
+            0
+        }
+    }
+
+    fn schema() -> RootNode<'static, Query, EmptyMutation, EmptySubscription> {
+        RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new())
+    }
+
+    #[test]
+    fn sorts_type_definitions() {
+        let schema = schema();
+        assert_eq!(
+            schema_language(&schema),
+            "schema {\n  query: Query\n}\n\n\
+             input Area {\n  zone: Zone!\n}\n\n\
+             type Query {\n  count(area: Area!): Int!\n}\n\n\
+             enum Zone {\n  NORTH\n}\n",
+        );
+    }
+
+    #[test]
+    fn writes_and_asserts_snapshot() {
+        let dir = TempDir::new("snapshot");
+        let path = dir.0.join("nested").join("schema.graphql");
+        let schema = schema();
+
+        assert!(write_schema(&schema, &path).unwrap());
+        assert!(!write_schema(&schema, &path).unwrap());
+        assert_snapshot(&path, &schema_language(&schema), false);
+    }
+
+    #[test]
+    #[should_panic(expected = "Schema differs from its snapshot")]
+    fn panics_on_changed_schema() {
+        let dir = TempDir::new("snapshot-old");
+        let path = dir.0.join("schema.graphql");
+        fs::create_dir_all(&dir.0).unwrap();
+        fs::write(&path, "type Query {\n  count: Int!\n}\n").unwrap();
+
+        assert_snapshot(&path, &schema_language(&schema()), false);
+    }
+
+    #[test]
+    fn updates_changed_snapshot() {
+        let dir = TempDir::new("snapshot-update");
+        let path = dir.0.join("schema.graphql");
+        fs::create_dir_all(&dir.0).unwrap();
+        fs::write(&path, "type Query {\n  count: Int!\n}\n").unwrap();
+
+        let actual = schema_language(&schema());
+        assert_snapshot(&path, &actual, true);
+        assert_eq!(fs::read_to_string(&path).unwrap(), actual);
+    }
+
+    #[test]
+    fn lists_changed_lines() {
+        assert_eq!(
+            diff_lines("a\nb\nc\n", "a\nc\nd\n"),
+            "   2 - b\n   3 - c\n   2 + c\n   3 + d\n",
+        );
+        assert_eq!(diff_lines("", "a\n"), "   1 + a\n");
+        assert_eq!(
+            diff_lines("a\nb\nc\nd\n", "a\nx\nd\n"),
+            "   2 - b\n   3 - c\n   2 + x\n",
+        );
+    }
+}
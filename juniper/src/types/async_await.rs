@@ -2,6 +2,7 @@ use crate::{
     ast::Selection,
     executor::{ExecutionResult, Executor},
     parser::Spanning,
+    schema::meta::{MetaType, ObjectMeta},
     value::{DefaultScalarValue, Object, ScalarValue, Value},
 };
 
@@ -209,6 +210,14 @@ where
         )
         .expect("Type not found in schema");
 
+    if let MetaType::Object(ObjectMeta {
+        cache_hint: Some(ref hint),
+        ..
+    }) = *meta_type
+    {
+        executor.restrict_cache_policy(hint);
+    }
+
     for selection in selection_set {
         match *selection {
             Selection::Field(Spanning {
@@ -238,6 +247,8 @@ where
                     )
                 });
 
+                executor.restrict_cache_policy_by_field(meta_type, meta_field);
+
                 let exec_vars = executor.variables();
 
                 let sub_exec = executor.field_sub_executor(
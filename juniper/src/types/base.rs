@@ -3,8 +3,9 @@ use indexmap::IndexMap;
 use crate::{
     ast::{Directive, FromInputValue, InputValue, Selection},
     executor::{ExecutionResult, Executor, Registry, Variables},
+    macros::helper::InputObjectFieldNames,
     parser::Spanning,
-    schema::meta::{Argument, MetaType},
+    schema::meta::{Argument, MetaType, ObjectMeta},
     value::{DefaultScalarValue, Object, ScalarValue, Value},
     GraphQLEnum,
 };
@@ -115,6 +116,26 @@ where
             .and_then(|args| args.get(key))
             .and_then(InputValue::convert)
     }
+
+    /// Convert the arguments named after the fields of the input object `T`, as fields of a
+    /// single input object, into the desired type.
+    ///
+    /// This is the counterpart of [`Registry::flattened_args`], returning `Some` if the type
+    /// conversion succeeds.
+    ///
+    /// [`Registry::flattened_args`]: crate::Registry::flattened_args
+    pub fn get_flattened<T>(&self) -> Option<T>
+    where
+        T: FromInputValue<S> + InputObjectFieldNames<S>,
+    {
+        let args = self.args.as_ref().map_or_else(IndexMap::new, |args| {
+            args.iter()
+                .filter(|(k, _)| T::FIELD_NAMES.contains(k))
+                .map(|(k, v)| (*k, v.clone()))
+                .collect()
+        });
+        InputValue::object(args).convert()
+    }
 }
 
 /// Primary trait used to resolve GraphQL values.
@@ -427,6 +448,14 @@ where
         )
         .expect("Type not found in schema");
 
+    if let MetaType::Object(ObjectMeta {
+        cache_hint: Some(ref hint),
+        ..
+    }) = *meta_type
+    {
+        executor.restrict_cache_policy(hint);
+    }
+
     for selection in selection_set {
         match *selection {
             Selection::Field(Spanning {
@@ -456,6 +485,8 @@ where
                     )
                 });
 
+                executor.restrict_cache_policy_by_field(meta_type, meta_field);
+
                 let exec_vars = executor.variables();
 
                 let sub_exec = executor.field_sub_executor(
@@ -0,0 +1,245 @@
+//! File uploads via the [GraphQL multipart request spec][1].
+//!
+//! [1]: https://github.com/jaydenseric/graphql-multipart-request-spec
+
+use std::{collections::HashMap, fmt, io, sync::Arc};
+
+use futures::{stream, Stream};
+
+use crate::{
+    parser::{ParseError, ScalarToken, Token},
+    value::{ParseScalarResult, Value},
+};
+
+/// Files of a single multipart request, keyed by their names in its `map` field.
+pub(crate) type Uploads = HashMap<String, UploadedFile>;
+
+/// Reference to a file uploaded along with a GraphQL multipart request.
+///
+/// This is the `Upload` scalar, accepted as an argument or an input object field. It only
+/// references the uploaded file, so use [`Executor::upload`] to access it while resolving.
+///
+/// ```rust
+/// use juniper::{graphql_object, DefaultScalarValue, Executor, FieldResult, Upload};
+///
+/// struct Mutation;
+///
+/// #[graphql_object]
+/// impl Mutation {
+///     fn upload_size(
+///         executor: &Executor<'_, '_, (), DefaultScalarValue>,
+///         file: Upload,
+///     ) -> FieldResult<i32> {
+///         let file = executor.upload(&file).ok_or("file is missing")?;
+///         Ok(file.len() as i32)
+///     }
+/// }
+/// ```
+///
+/// [`Executor::upload`]: crate::Executor::upload
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Upload(String);
+
+impl Upload {
+    /// Returns the name of the referenced file in the `map` field of the multipart request.
+    pub fn key(&self) -> &str {
+        &self.0
+    }
+}
+
+#[crate::graphql_scalar(
+    name = "Upload",
+    description = "The `Upload` scalar type represents a file uploaded via a GraphQL multipart \
+                   request."
+)]
+impl<S> GraphQLScalar for Upload
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> Value {
+        Value::scalar(self.0.clone())
+    }
+
+    fn from_input_value(v: &InputValue) -> Option<Upload> {
+        v.as_string_value().map(|s| Upload(s.into()))
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        match value {
+            ScalarToken::String(value) => Ok(S::from(value.to_owned())),
+            _ => Err(ParseError::UnexpectedToken(Token::Scalar(value))),
+        }
+    }
+}
+
+/// File uploaded along with a GraphQL multipart request.
+///
+/// Files parsed by [`http::multipart`] are spooled to a temporary file while the request body is
+/// being read, so their content is never buffered in memory as a whole. The temporary file is
+/// removed once the last clone of the [`UploadedFile`] is dropped.
+///
+/// [`http::multipart`]: crate::http::multipart
+#[derive(Clone, PartialEq)]
+pub struct UploadedFile {
+    filename: Option<String>,
+    content_type: Option<String>,
+    len: u64,
+    content: Content,
+}
+
+/// Storage of the content of an [`UploadedFile`].
+#[derive(Clone)]
+enum Content {
+    Memory(Arc<[u8]>),
+    #[cfg(feature = "multipart")]
+    Spooled(Arc<tempfile::NamedTempFile>),
+}
+
+impl PartialEq for Content {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Memory(a), Self::Memory(b)) => a == b,
+            #[cfg(feature = "multipart")]
+            (Self::Spooled(a), Self::Spooled(b)) => Arc::ptr_eq(a, b),
+            #[cfg(feature = "multipart")]
+            _ => false,
+        }
+    }
+}
+
+impl UploadedFile {
+    /// Creates a new [`UploadedFile`] out of the given parts, keeping its content in memory.
+    pub fn new(filename: Option<String>, content_type: Option<String>, content: Vec<u8>) -> Self {
+        Self {
+            filename,
+            content_type,
+            len: content.len() as u64,
+            content: Content::Memory(content.into()),
+        }
+    }
+
+    /// Creates a new [`UploadedFile`] out of the given parts, backed by the given temporary file
+    /// of `len` bytes.
+    #[cfg(feature = "multipart")]
+    pub(crate) fn spooled(
+        filename: Option<String>,
+        content_type: Option<String>,
+        file: tempfile::NamedTempFile,
+        len: u64,
+    ) -> Self {
+        Self {
+            filename,
+            content_type,
+            len,
+            content: Content::Spooled(Arc::new(file)),
+        }
+    }
+
+    /// Returns the name of this file provided by the client, if any.
+    pub fn filename(&self) -> Option<&str> {
+        self.filename.as_deref()
+    }
+
+    /// Returns the MIME type of this file provided by the client, if any.
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// Returns the size of this file in bytes.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Indicates whether this file is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns an [`io::Read`]er over the content of this file, starting from its beginning.
+    ///
+    /// # Errors
+    ///
+    /// If the temporary file backing this [`UploadedFile`] cannot be reopened.
+    pub fn reader(&self) -> io::Result<impl io::Read + Send + 'static> {
+        Ok(match &self.content {
+            Content::Memory(bytes) => Reader::Memory(io::Cursor::new(Arc::clone(bytes))),
+            #[cfg(feature = "multipart")]
+            Content::Spooled(file) => Reader::Spooled(file.reopen()?),
+        })
+    }
+
+    /// Reads the whole content of this file into memory.
+    ///
+    /// # Errors
+    ///
+    /// If the temporary file backing this [`UploadedFile`] cannot be read.
+    pub fn to_vec(&self) -> io::Result<Vec<u8>> {
+        let mut content = Vec::with_capacity(self.len as usize);
+        io::Read::read_to_end(&mut self.reader()?, &mut content)?;
+        Ok(content)
+    }
+
+    /// Returns a [`Stream`] yielding the content of this file in chunks of at most the given
+    /// size.
+    ///
+    /// Chunks are read lazily, one per poll, so at most `chunk_size` bytes of the file are held in
+    /// memory at once. Reading is blocking, but only touches a local temporary file.
+    ///
+    /// # Panics
+    ///
+    /// If `chunk_size` is zero.
+    pub fn stream(
+        &self,
+        chunk_size: usize,
+    ) -> impl Stream<Item = io::Result<Vec<u8>>> + Send + 'static {
+        assert!(chunk_size > 0, "chunk size must be positive");
+        stream::unfold(Some(self.reader()), move |reader| async move {
+            let mut reader = match reader? {
+                Ok(reader) => reader,
+                Err(e) => return Some((Err(e), None)),
+            };
+            let mut chunk = vec![0; chunk_size];
+            let mut filled = 0;
+            while filled < chunk_size {
+                match io::Read::read(&mut reader, &mut chunk[filled..]) {
+                    Ok(0) => break,
+                    Ok(n) => filled += n,
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                    Err(e) => return Some((Err(e), None)),
+                }
+            }
+            if filled == 0 {
+                return None;
+            }
+            chunk.truncate(filled);
+            Some((Ok(chunk), Some(Ok(reader))))
+        })
+    }
+}
+
+impl fmt::Debug for UploadedFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UploadedFile")
+            .field("filename", &self.filename)
+            .field("content_type", &self.content_type)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+/// [`io::Read`]er over the content of an [`UploadedFile`].
+enum Reader {
+    Memory(io::Cursor<Arc<[u8]>>),
+    #[cfg(feature = "multipart")]
+    Spooled(std::fs::File),
+}
+
+impl io::Read for Reader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Self::Memory(cursor) => cursor.read(buf),
+            #[cfg(feature = "multipart")]
+            Self::Spooled(file) => file.read(buf),
+        }
+    }
+}
@@ -39,6 +39,16 @@ where
                 return false;
             }
 
+            // Enum values may be hidden from the schema, so they should be checked against its
+            // metadata too.
+            if let (&InputValue::Enum(ref name), &MetaType::Enum(EnumMeta { ref values, .. })) =
+                (arg_value, t)
+            {
+                if !values.iter().any(|v| &v.name == name) {
+                    return false;
+                }
+            }
+
             match *arg_value {
                 InputValue::Null | InputValue::Variable(_) => true,
                 ref v @ InputValue::Scalar(_) | ref v @ InputValue::Enum(_) => {
@@ -184,12 +184,11 @@ where
     let mut errors: Vec<RuleError> = vec![];
 
     if !(meta.try_parse_fn)(value) {
-        return vec![unification_error(
-            var_name,
-            var_pos,
-            path,
-            &format!(r#"Expected "{}""#, meta.name),
-        )];
+        let message = match (meta.parse_error_fn)(value) {
+            Some(reason) => format!(r#"Expected "{}": {}"#, meta.name, reason),
+            None => format!(r#"Expected "{}""#, meta.name),
+        };
+        return vec![unification_error(var_name, var_pos, path, &message)];
     }
 
     match *value {
@@ -41,13 +41,19 @@ Check the LICENSE file for details.
 #![doc(html_root_url = "https://docs.rs/juniper_actix/0.1.0")]
 
 use actix_web::{
-    error::JsonPayloadError, http::Method, web, Error, FromRequest, HttpMessage, HttpRequest,
-    HttpResponse,
+    error::JsonPayloadError,
+    http::{
+        header::{ACCEPT, ALLOW, CACHE_CONTROL},
+        Method, StatusCode,
+    },
+    web, Error, FromRequest, HttpMessage, HttpRequest, HttpResponse,
 };
+#[cfg(feature = "multipart")]
+use juniper::http::multipart::{self, MultipartLimits, MultipartParser};
 use juniper::{
     http::{
-        graphiql::graphiql_source, playground::playground_source, GraphQLBatchRequest,
-        GraphQLRequest,
+        graphiql::graphiql_source, playground::playground_source, BatchConfig, ContextError,
+        ContextFactory, GraphQLBatchRequest, GraphQLRequest, RequestParts, ResponseMediaType,
     },
     ScalarValue,
 };
@@ -60,6 +66,7 @@ struct GetGraphQLRequest {
     #[serde(rename = "operationName")]
     operation_name: Option<String>,
     variables: Option<String>,
+    extensions: Option<String>,
 }
 
 impl<S> From<GetGraphQLRequest> for GraphQLRequest<S>
@@ -71,9 +78,11 @@ where
             query,
             operation_name,
             variables,
+            extensions,
         } = get_req;
         let variables = variables.map(|s| serde_json::from_str(&s).unwrap());
-        Self::new(query, operation_name, variables)
+        let extensions = extensions.map(|s| serde_json::from_str(&s).unwrap());
+        Self::new(query, operation_name, variables).with_extensions(extensions)
     }
 }
 
@@ -100,6 +109,69 @@ where
         _ => Err(actix_web::error::UrlGenerationError::ResourceNotFound.into()),
     }
 }
+
+/// Actix Web GraphQL Handler for GET and POST requests, with the context created by the given
+/// [`ContextFactory`].
+///
+/// Responds with the status code of the [`ContextError`] if the context can't be created. See
+/// [`create_context()`] for what the [`RequestParts`] are filled with.
+pub async fn graphql_handler_with_context_factory<Query, Mutation, Subscription, CtxT, S, F>(
+    schema: &juniper::RootNode<'static, Query, Mutation, Subscription, S>,
+    context_factory: &F,
+    req: HttpRequest,
+    payload: actix_web::web::Payload,
+) -> Result<HttpResponse, Error>
+where
+    Query: juniper::GraphQLTypeAsync<S, Context = CtxT>,
+    Query::TypeInfo: Sync,
+    Mutation: juniper::GraphQLTypeAsync<S, Context = CtxT>,
+    Mutation::TypeInfo: Sync,
+    Subscription: juniper::GraphQLSubscriptionType<S, Context = CtxT>,
+    Subscription::TypeInfo: Sync,
+    CtxT: Sync,
+    S: ScalarValue + Send + Sync,
+    F: ContextFactory<CtxT> + ?Sized,
+{
+    match create_context(context_factory, &req).await {
+        Ok(context) => graphql_handler(schema, &context, req, payload).await,
+        Err(e) => {
+            let status =
+                StatusCode::from_u16(e.status_code()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+            let body = serde_json::to_string(&e.into_response::<S>())?;
+            Ok(HttpResponse::build(status)
+                .content_type("application/json")
+                .body(body))
+        }
+    }
+}
+
+/// Creates the context of the given request with the given [`ContextFactory`].
+///
+/// The [`RequestParts::remote_addr`] is the peer address of the request, and the
+/// [`RequestParts::extensions`] are left empty, as the extensions of an [`HttpRequest`] are not
+/// thread-safe.
+pub async fn create_context<CtxT, F>(
+    context_factory: &F,
+    req: &HttpRequest,
+) -> Result<CtxT, ContextError>
+where
+    F: ContextFactory<CtxT> + ?Sized,
+{
+    let mut parts = RequestParts::new(req.method().as_str(), req.uri().to_string());
+    for (name, value) in req.headers() {
+        if let Ok(value) = value.to_str() {
+            parts.append_header(name, value);
+        }
+    }
+    parts.remote_addr = req.peer_addr();
+    context_factory.create(&parts).await
+}
+
+fn response_media_type(req: &HttpRequest) -> ResponseMediaType {
+    let accept = req.headers().get(ACCEPT);
+    ResponseMediaType::negotiate(accept.and_then(|v| v.to_str().ok()))
+}
+
 /// Actix GraphQL Handler for GET requests
 pub async fn get_graphql_handler<Query, Mutation, Subscription, CtxT, S>(
     schema: &juniper::RootNode<'static, Query, Mutation, Subscription, S>,
@@ -116,20 +188,34 @@ where
     CtxT: Sync,
     S: ScalarValue + Send + Sync,
 {
+    let media_type = response_media_type(&req);
     let get_req = web::Query::<GetGraphQLRequest>::from_query(req.query_string())?;
-    let req = GraphQLRequest::from(get_req.into_inner());
+    let req = GraphQLRequest::from(get_req.into_inner()).forbid_mutations();
     let gql_response = req.execute(schema, context).await;
+    if gql_response.is_rejected_mutation() {
+        return Ok(HttpResponse::MethodNotAllowed()
+            .insert_header((ALLOW, "POST"))
+            .body("mutations are not allowed over GET"));
+    }
     let body_response = serde_json::to_string(&gql_response)?;
-    let mut response = match gql_response.is_ok() {
-        true => HttpResponse::Ok(),
-        false => HttpResponse::BadRequest(),
-    };
+    let mut response =
+        HttpResponse::build(StatusCode::from_u16(gql_response.status_code()).unwrap());
+    if let Some(cache_control) = gql_response.cache_policy().and_then(|p| p.header_value()) {
+        response.insert_header((CACHE_CONTROL, cache_control));
+    }
     Ok(response
-        .content_type("application/json")
+        .content_type(media_type.as_str())
         .body(body_response))
 }
 
 /// Actix GraphQL Handler for POST requests
+///
+/// Executes batch requests with the `juniper::http::BatchConfig` provided as `app_data`, if any.
+/// With the `multipart` feature, also accepts [GraphQL multipart requests][1] carrying an
+/// `Apollo-Require-Preflight` or `X-Apollo-Operation-Name` header, limited by the
+/// `juniper::http::multipart::MultipartLimits` provided as `app_data`, if any.
+///
+/// [1]: https://github.com/jaydenseric/graphql-multipart-request-spec
 pub async fn post_graphql_handler<Query, Mutation, Subscription, CtxT, S>(
     schema: &juniper::RootNode<'static, Query, Mutation, Subscription, S>,
     context: &CtxT,
@@ -146,6 +232,8 @@ where
     CtxT: Sync,
     S: ScalarValue + Send + Sync,
 {
+    let media_type = response_media_type(&req);
+    let batch_config = req.app_data::<BatchConfig>().copied().unwrap_or_default();
     let req = match req.content_type() {
         "application/json" => {
             let body = String::from_request(&req, &mut payload.into_inner()).await?;
@@ -158,15 +246,69 @@ where
                 body, None, None,
             )))
         }
+        #[cfg(feature = "multipart")]
+        "multipart/form-data" => Ok(parse_multipart(&req, payload).await?),
         _ => Err(JsonPayloadError::ContentType),
     }?;
-    let gql_batch_response = req.execute(schema, context).await;
+    let gql_batch_response = req
+        .execute_with_batch_config(schema, context, batch_config)
+        .await;
     let gql_response = serde_json::to_string(&gql_batch_response)?;
-    let mut response = match gql_batch_response.is_ok() {
-        true => HttpResponse::Ok(),
-        false => HttpResponse::BadRequest(),
+    let mut response =
+        HttpResponse::build(StatusCode::from_u16(gql_batch_response.status_code()).unwrap());
+    if let Some(cache_control) = gql_batch_response
+        .cache_policy()
+        .and_then(|p| p.header_value())
+    {
+        response.insert_header((CACHE_CONTROL, cache_control));
+    }
+    Ok(response
+        .content_type(media_type.as_str())
+        .body(gql_response))
+}
+
+/// Parses a GraphQL multipart request while its `payload` is being received, with the
+/// [`MultipartLimits`] provided as `app_data`, if any.
+#[cfg(feature = "multipart")]
+async fn parse_multipart<S: ScalarValue>(
+    req: &HttpRequest,
+    mut payload: web::Payload,
+) -> Result<GraphQLBatchRequest<S>, Error> {
+    use actix_web::{
+        error::{ErrorBadRequest, ErrorInternalServerError},
+        http::header::CONTENT_TYPE,
     };
-    Ok(response.content_type("application/json").body(gql_response))
+    use futures::StreamExt as _;
+
+    let into_error = |e: multipart::MultipartError| {
+        if e.is_server_error() {
+            ErrorInternalServerError(e)
+        } else {
+            ErrorBadRequest(e)
+        }
+    };
+
+    multipart::check_preflight(|name| {
+        req.headers()
+            .get(name)
+            .map_or(false, |v| !v.as_bytes().is_empty())
+    })
+    .map_err(into_error)?;
+    let content_type = req
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    let limits = req
+        .app_data::<MultipartLimits>()
+        .copied()
+        .unwrap_or_default();
+
+    let mut parser = MultipartParser::new(content_type, &limits).map_err(into_error)?;
+    while let Some(chunk) = payload.next().await {
+        parser.feed(&chunk?).map_err(into_error)?;
+    }
+    parser.finish().map_err(into_error)
 }
 
 /// Create a handler that replies with an HTML page containing GraphiQL. This does not handle routing, so you can mount it on any endpoint
@@ -202,6 +344,112 @@ pub async fn playground_handler(
         .body(html))
 }
 
+/// `juniper_actix` [GraphQL over SSE][1] handler implementation.
+///
+/// [1]: https://github.com/enisdenjo/graphql-sse/blob/master/PROTOCOL.md
+#[cfg(feature = "sse")]
+pub mod sse {
+    use std::{convert::Infallible, sync::Arc};
+
+    use actix_web::{
+        error::{ErrorBadRequest, JsonPayloadError},
+        http::{header::CACHE_CONTROL, Method, StatusCode},
+        web, Error, FromRequest, HttpRequest, HttpResponse,
+    };
+    use juniper::{
+        futures::stream::{BoxStream, StreamExt as _},
+        http::GraphQLRequest,
+        GraphQLSubscriptionType, GraphQLTypeAsync, ScalarValue,
+    };
+    use juniper_subscriptions::sse::{self, EventStreams, SseError};
+
+    use super::GetGraphQLRequest;
+
+    /// Serves [GraphQL over SSE][1] requests.
+    ///
+    /// Requests carrying an event stream token, either in the `x-graphql-event-stream-token`
+    /// header or in the `token` query parameter, are handled in the "single connection" mode:
+    /// - `PUT` reserves a new event stream, responding with its token;
+    /// - `GET` opens the reserved event stream;
+    /// - `POST` executes an operation over the event stream, responding with `202 Accepted`;
+    /// - `DELETE` cancels the operation specified in the `operationId` query parameter.
+    ///
+    /// Any other `GET` or `POST` request is executed over its own event stream, as of the
+    /// "distinct connections" mode.
+    ///
+    /// [1]: https://github.com/enisdenjo/graphql-sse/blob/master/PROTOCOL.md
+    pub async fn sse_handler<Query, Mutation, Subscription, CtxT, S>(
+        req: HttpRequest,
+        payload: web::Payload,
+        event_streams: Arc<EventStreams<Query, Mutation, Subscription, CtxT, S>>,
+        context: Arc<CtxT>,
+    ) -> Result<HttpResponse, Error>
+    where
+        Query: GraphQLTypeAsync<S, Context = CtxT> + Send + Sync + 'static,
+        Query::TypeInfo: Send + Sync,
+        Mutation: GraphQLTypeAsync<S, Context = CtxT> + Send + Sync + 'static,
+        Mutation::TypeInfo: Send + Sync,
+        Subscription: GraphQLSubscriptionType<S, Context = CtxT> + Send + Sync + 'static,
+        Subscription::TypeInfo: Send + Sync,
+        CtxT: Send + Sync + 'static,
+        S: ScalarValue + Send + Sync + 'static,
+    {
+        let token = sse::stream_token(
+            req.headers()
+                .get(sse::TOKEN_HEADER)
+                .and_then(|v| v.to_str().ok()),
+            Some(req.query_string()),
+        )
+        .map(str::to_owned);
+
+        Ok(match (req.method().clone(), token) {
+            (Method::PUT, _) => HttpResponse::Created()
+                .content_type("text/plain; charset=utf-8")
+                .body(event_streams.reserve()),
+            (Method::GET, Some(token)) => match event_streams.open(&token) {
+                Ok(events) => sse_response(events),
+                Err(e) => sse_error_response(e),
+            },
+            (Method::DELETE, Some(token)) => match sse::operation_id(Some(req.query_string())) {
+                Some(id) => match event_streams.cancel(&token, id) {
+                    Ok(()) => HttpResponse::Ok().finish(),
+                    Err(e) => sse_error_response(e),
+                },
+                None => return Err(ErrorBadRequest("'operationId' parameter is missing")),
+            },
+            (method @ Method::GET, token @ None) | (method @ Method::POST, token) => {
+                let request = if method == Method::GET {
+                    let get_req = web::Query::<GetGraphQLRequest>::from_query(req.query_string())?;
+                    GraphQLRequest::from(get_req.into_inner()).forbid_mutations()
+                } else {
+                    let body = String::from_request(&req, &mut payload.into_inner()).await?;
+                    serde_json::from_str::<GraphQLRequest<S>>(&body)
+                        .map_err(JsonPayloadError::Deserialize)?
+                };
+                match token {
+                    Some(token) => match event_streams.execute(&token, request, context) {
+                        Ok(()) => HttpResponse::Accepted().finish(),
+                        Err(e) => sse_error_response(e),
+                    },
+                    None => sse_response(event_streams.subscribe(request, context)),
+                }
+            }
+            _ => HttpResponse::MethodNotAllowed().finish(),
+        })
+    }
+
+    fn sse_response(events: BoxStream<'static, sse::Event>) -> HttpResponse {
+        HttpResponse::Ok()
+            .content_type(sse::CONTENT_TYPE)
+            .insert_header((CACHE_CONTROL, "no-cache"))
+            .streaming(events.map(|event| Ok::<_, Infallible>(web::Bytes::from(event.to_string()))))
+    }
+
+    fn sse_error_response(err: SseError) -> HttpResponse {
+        HttpResponse::build(StatusCode::from_u16(err.status_code()).unwrap()).body(err.to_string())
+    }
+}
+
 /// `juniper_actix` subscriptions handler implementation.
 /// Cannot be merged to `juniper_actix` yet as GraphQL over WS[1]
 /// is not fully supported in current implementation.
@@ -498,6 +746,46 @@ mod tests {
         graphql_handler(&schema, &context, req, payload).await
     }
 
+    #[actix_web::rt::test]
+    async fn context_factory_creates_context() {
+        async fn index(
+            req: HttpRequest,
+            payload: actix_web::web::Payload,
+            schema: web::Data<Schema>,
+        ) -> Result<HttpResponse, Error> {
+            let context_factory = |parts: &RequestParts| {
+                futures::future::ready(match parts.header("authorization") {
+                    Some("secret") => Ok(Database::new()),
+                    _ => Err(ContextError::unauthorized("invalid token")),
+                })
+            };
+            graphql_handler_with_context_factory(&schema, &context_factory, req, payload).await
+        }
+
+        let schema = Schema::new(Query, EmptyMutation::new(), EmptySubscription::new());
+        let mut app = test::init_service(
+            App::new()
+                .app_data(Data::new(schema))
+                .route("/", web::get().to(index)),
+        )
+        .await;
+        let req = |token| {
+            test::TestRequest::get()
+                .uri("/?query=%7Bhero%7Bname%7D%7D")
+                .append_header(("authorization", token))
+                .to_request()
+        };
+
+        let mut resp = test::call_service(&mut app, req("wrong")).await;
+        assert_eq!(resp.status(), http::StatusCode::UNAUTHORIZED);
+        assert!(take_response_body_string(&mut resp)
+            .await
+            .contains("invalid token"));
+
+        let resp = test::call_service(&mut app, req("secret")).await;
+        assert_eq!(resp.status(), http::StatusCode::OK);
+    }
+
     #[actix_web::rt::test]
     async fn graphiql_response_does_not_panic() {
         let result = graphiql_handler("/abcd", None).await;
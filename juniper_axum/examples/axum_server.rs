@@ -0,0 +1,53 @@
+#![deny(warnings)]
+
+use std::{net::SocketAddr, sync::Arc};
+
+use axum::{
+    response::{Html, Response},
+    routing::get,
+    Extension, Router, Server,
+};
+use juniper::{
+    tests::fixtures::starwars::schema::{Database, Query},
+    EmptyMutation, EmptySubscription, RootNode,
+};
+use juniper_axum::extract::JuniperRequest;
+
+type Schema = RootNode<'static, Query, EmptyMutation<Database>, EmptySubscription<Database>>;
+
+async fn homepage() -> Html<&'static str> {
+    Html("<html><h1>juniper_axum</h1><div>visit <a href=\"/graphiql\">/graphiql</a></html>")
+}
+
+async fn graphql(Extension(schema): Extension<Arc<Schema>>, req: JuniperRequest) -> Response {
+    req.execute(&schema, &Database::new()).await
+}
+
+#[tokio::main]
+async fn main() {
+    let schema = Schema::new(
+        Query,
+        EmptyMutation::<Database>::new(),
+        EmptySubscription::<Database>::new(),
+    );
+
+    let app = Router::new()
+        .route("/", get(homepage))
+        .route("/graphql", get(graphql).post(graphql))
+        .route(
+            "/graphiql",
+            get(|| juniper_axum::graphiql("/graphql", None)),
+        )
+        .route(
+            "/playground",
+            get(|| juniper_axum::playground("/graphql", None)),
+        )
+        .layer(Extension(Arc::new(schema)));
+
+    let addr = SocketAddr::from(([127, 0, 0, 1], 8080));
+    println!("Listening on {}", addr);
+    Server::bind(&addr)
+        .serve(app.into_make_service())
+        .await
+        .unwrap();
+}
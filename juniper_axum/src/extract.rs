@@ -0,0 +1,407 @@
+//! Extraction of GraphQL requests from [`Request`]s.
+
+use std::{convert::TryFrom, mem, net::SocketAddr, sync::Arc};
+
+use axum::{
+    async_trait,
+    body::{Bytes, HttpBody},
+    extract::{ConnectInfo, FromRequest, FromRequestParts, Query},
+    http::{
+        header::{ACCEPT, ALLOW, CONTENT_TYPE},
+        request::Parts,
+        Extensions, Method, Request, StatusCode,
+    },
+    response::{IntoResponse as _, Response},
+    BoxError, Json,
+};
+#[cfg(feature = "multipart")]
+use juniper::http::multipart::{self, MultipartError, MultipartLimits, MultipartParser};
+use juniper::{
+    http::{
+        BatchConfig, ContextError, ContextFactory, GraphQLBatchRequest, GraphQLRequest,
+        RequestParts, ResponseMediaType,
+    },
+    DefaultScalarValue, GraphQLSubscriptionType, GraphQLType, GraphQLTypeAsync, RootNode,
+    ScalarValue,
+};
+use serde::Deserialize;
+
+use crate::response::JuniperResponse;
+
+/// Extractor of a [`GraphQLBatchRequest`] from a [`Request`].
+///
+/// Accepts:
+/// - `GET` requests specifying the operation in the `query`, `operationName`, `variables` and
+///   `extensions` query parameters;
+/// - `POST` requests with an `application/json` or `application/graphql` body;
+/// - with the `multipart` feature, `POST` [GraphQL multipart requests][2] carrying an
+///   `Apollo-Require-Preflight` or `X-Apollo-Operation-Name` header, limited by the
+///   `Extension<MultipartLimits>`, if any.
+///
+/// Batch requests are executed with the `Extension<BatchConfig>`, if any. Also negotiates the
+/// [`ResponseMediaType`] against the `Accept` header, so [`JuniperRequest::execute()`] responds
+/// as of the [GraphQL over HTTP spec][1].
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use axum::{response::Response, routing::get, Extension, Router};
+/// # use juniper::{
+/// #     tests::fixtures::starwars::schema::{Database, Query},
+/// #     EmptyMutation, EmptySubscription, RootNode,
+/// # };
+/// # use juniper_axum::extract::JuniperRequest;
+/// #
+/// type Schema = RootNode<'static, Query, EmptyMutation<Database>, EmptySubscription<Database>>;
+///
+/// async fn graphql(Extension(schema): Extension<Arc<Schema>>, req: JuniperRequest) -> Response {
+///     req.execute(&schema, &Database::new()).await
+/// }
+///
+/// let schema = Schema::new(Query, EmptyMutation::new(), EmptySubscription::new());
+/// let app: Router = Router::new()
+///     .route("/graphql", get(graphql).post(graphql))
+///     .layer(Extension(Arc::new(schema)));
+/// ```
+///
+/// [1]: https://github.com/graphql/graphql-over-http
+/// [2]: https://github.com/jaydenseric/graphql-multipart-request-spec
+#[derive(Debug, PartialEq)]
+pub struct JuniperRequest<S = DefaultScalarValue>
+where
+    S: ScalarValue,
+{
+    /// Extracted GraphQL request.
+    pub request: GraphQLBatchRequest<S>,
+
+    /// Media type of the response negotiated against the `Accept` header.
+    pub media_type: ResponseMediaType,
+
+    /// [`BatchConfig`] of executing the [`request`](Self::request).
+    pub batch_config: BatchConfig,
+}
+
+impl<S> JuniperRequest<S>
+where
+    S: ScalarValue + Send + Sync,
+{
+    /// Executes this request against the given `root_node` with the given `context`.
+    ///
+    /// Responds with `405 Method Not Allowed` to mutations sent over `GET`.
+    pub async fn execute<QueryT, MutationT, SubscriptionT>(
+        &self,
+        root_node: &RootNode<'_, QueryT, MutationT, SubscriptionT, S>,
+        context: &QueryT::Context,
+    ) -> Response
+    where
+        QueryT: GraphQLTypeAsync<S>,
+        QueryT::TypeInfo: Sync,
+        QueryT::Context: Sync,
+        MutationT: GraphQLTypeAsync<S, Context = QueryT::Context>,
+        MutationT::TypeInfo: Sync,
+        SubscriptionT: GraphQLSubscriptionType<S, Context = QueryT::Context>,
+        SubscriptionT::TypeInfo: Sync,
+    {
+        let response = self
+            .request
+            .execute_with_batch_config(root_node, context, self.batch_config)
+            .await;
+        if response.is_rejected_mutation() {
+            return mutation_over_get_response();
+        }
+        JuniperResponse::new(response)
+            .with_media_type(self.media_type)
+            .into_response()
+    }
+
+    /// Executes this request synchronously against the given `root_node` with the given
+    /// `context`.
+    ///
+    /// Responds with `405 Method Not Allowed` to mutations sent over `GET`.
+    pub fn execute_sync<QueryT, MutationT, SubscriptionT>(
+        &self,
+        root_node: &RootNode<'_, QueryT, MutationT, SubscriptionT, S>,
+        context: &QueryT::Context,
+    ) -> Response
+    where
+        QueryT: GraphQLType<S>,
+        MutationT: GraphQLType<S, Context = QueryT::Context>,
+        SubscriptionT: GraphQLType<S, Context = QueryT::Context>,
+    {
+        let response =
+            self.request
+                .execute_sync_with_batch_config(root_node, context, self.batch_config);
+        if response.is_rejected_mutation() {
+            return mutation_over_get_response();
+        }
+        JuniperResponse::new(response)
+            .with_media_type(self.media_type)
+            .into_response()
+    }
+}
+
+#[async_trait]
+impl<S, St, B> FromRequest<St, B> for JuniperRequest<S>
+where
+    S: ScalarValue,
+    St: Send + Sync,
+    B: HttpBody + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request<B>, state: &St) -> Result<Self, Self::Rejection> {
+        let media_type =
+            ResponseMediaType::negotiate(req.headers().get(ACCEPT).and_then(|v| v.to_str().ok()));
+        let batch_config = req
+            .extensions()
+            .get::<BatchConfig>()
+            .copied()
+            .unwrap_or_default();
+        let content_type = req
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_owned);
+
+        let request = match (req.method().clone(), content_type.as_deref()) {
+            (Method::GET, _) => {
+                let Query(get_req) =
+                    Query::<GetRequest>::try_from_uri(req.uri()).map_err(|e| e.into_response())?;
+                GraphQLBatchRequest::Single(
+                    GraphQLRequest::try_from(get_req)
+                        .map_err(|e| (StatusCode::BAD_REQUEST, e).into_response())?
+                        .forbid_mutations(),
+                )
+            }
+            (Method::POST, Some(ct)) if is_graphql_content_type(ct) => {
+                let query = String::from_request(req, state)
+                    .await
+                    .map_err(|e| e.into_response())?;
+                GraphQLBatchRequest::Single(GraphQLRequest::new(query, None, None))
+            }
+            #[cfg(feature = "multipart")]
+            (Method::POST, Some(ct)) if multipart::is_multipart_request(ct) => {
+                parse_multipart(ct, req).await?
+            }
+            (Method::POST, _) => {
+                let body = Bytes::from_request(req, state)
+                    .await
+                    .map_err(|e| e.into_response())?;
+                serde_json::from_slice(&body).map_err(|e| {
+                    (StatusCode::BAD_REQUEST, format!("invalid JSON body: {}", e)).into_response()
+                })?
+            }
+            _ => {
+                return Err((
+                    StatusCode::METHOD_NOT_ALLOWED,
+                    [(ALLOW, "GET, POST")],
+                    "GraphQL requests can only be sent with GET or POST",
+                )
+                    .into_response())
+            }
+        };
+
+        Ok(Self {
+            request,
+            media_type,
+            batch_config,
+        })
+    }
+}
+
+/// Indicates whether the given `Content-Type` is `application/graphql`, ignoring its parameters.
+fn is_graphql_content_type(content_type: &str) -> bool {
+    let mime = content_type.split(';').next().unwrap_or_default();
+    mime.trim().eq_ignore_ascii_case("application/graphql")
+}
+
+/// Parses a GraphQL multipart request while its body is being received, with the
+/// [`MultipartLimits`] provided as an `Extension<MultipartLimits>`, if any.
+#[cfg(feature = "multipart")]
+async fn parse_multipart<S, B>(
+    content_type: &str,
+    req: Request<B>,
+) -> Result<GraphQLBatchRequest<S>, Response>
+where
+    S: ScalarValue,
+    B: HttpBody + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+{
+    use bytes::Buf as _;
+
+    multipart::check_preflight(|name| {
+        req.headers()
+            .get(name)
+            .map_or(false, |v| !v.as_bytes().is_empty())
+    })
+    .map_err(multipart_error_response)?;
+    let limits = req
+        .extensions()
+        .get::<MultipartLimits>()
+        .copied()
+        .unwrap_or_default();
+
+    let mut parser =
+        MultipartParser::new(content_type, &limits).map_err(multipart_error_response)?;
+    let body = req.into_body();
+    futures::pin_mut!(body);
+    while let Some(chunk) = body.data().await {
+        let mut chunk = chunk.map_err(|e| {
+            let e: BoxError = e.into();
+            (StatusCode::BAD_REQUEST, e.to_string()).into_response()
+        })?;
+        while chunk.has_remaining() {
+            let bytes = chunk.chunk();
+            let len = bytes.len();
+            parser.feed(bytes).map_err(multipart_error_response)?;
+            chunk.advance(len);
+        }
+    }
+    parser.finish().map_err(multipart_error_response)
+}
+
+#[cfg(feature = "multipart")]
+fn multipart_error_response(err: MultipartError) -> Response {
+    let status = if err.is_server_error() {
+        StatusCode::INTERNAL_SERVER_ERROR
+    } else {
+        StatusCode::BAD_REQUEST
+    };
+    (status, err.to_string()).into_response()
+}
+
+/// Extractor of a context created by the [`ContextFactory`] provided as an
+/// `Extension<Arc<dyn ContextFactory<CtxT>>>`.
+///
+/// The [`RequestParts::extensions`] hold the [`Extensions`] of the request, and the
+/// [`RequestParts::remote_addr`] is taken from the [`ConnectInfo`] of the request, if any.
+///
+/// Rejects the request with the status code of the [`ContextError`] if the context can't be
+/// created, or with `500 Internal Server Error` if no [`ContextFactory`] is provided.
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use axum::{response::Response, routing::get, Extension, Router};
+/// # use futures::future;
+/// # use juniper::{
+/// #     http::{ContextError, ContextFactory, RequestParts},
+/// #     tests::fixtures::starwars::schema::{Database, Query},
+/// #     EmptyMutation, EmptySubscription, RootNode,
+/// # };
+/// # use juniper_axum::extract::{JuniperContext, JuniperRequest};
+/// #
+/// type Schema = RootNode<'static, Query, EmptyMutation<Database>, EmptySubscription<Database>>;
+///
+/// async fn graphql(
+///     Extension(schema): Extension<Arc<Schema>>,
+///     JuniperContext(context): JuniperContext<Database>,
+///     req: JuniperRequest,
+/// ) -> Response {
+///     req.execute(&schema, &context).await
+/// }
+///
+/// let context_factory: Arc<dyn ContextFactory<Database>> = Arc::new(|parts: &RequestParts| {
+///     future::ready(match parts.header("authorization") {
+///         Some(_) => Ok(Database::new()),
+///         None => Err(ContextError::unauthorized("missing `Authorization` header")),
+///     })
+/// });
+/// let schema = Schema::new(Query, EmptyMutation::new(), EmptySubscription::new());
+/// let app: Router = Router::new()
+///     .route("/graphql", get(graphql).post(graphql))
+///     .layer(Extension(Arc::new(schema)))
+///     .layer(Extension(context_factory));
+/// ```
+#[derive(Debug)]
+pub struct JuniperContext<CtxT>(pub CtxT);
+
+#[async_trait]
+impl<CtxT, St> FromRequestParts<St> for JuniperContext<CtxT>
+where
+    CtxT: 'static,
+    St: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, _: &St) -> Result<Self, Self::Rejection> {
+        let context_factory = parts
+            .extensions
+            .get::<Arc<dyn ContextFactory<CtxT>>>()
+            .cloned()
+            .ok_or_else(|| {
+                context_error_response(ContextError::internal(
+                    "no `ContextFactory` extension is provided",
+                ))
+            })?;
+
+        let mut request_parts = RequestParts::new(parts.method.as_str(), parts.uri.to_string());
+        for (name, value) in &parts.headers {
+            if let Ok(value) = value.to_str() {
+                request_parts.append_header(name, value);
+            }
+        }
+        request_parts.remote_addr = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| *addr);
+        request_parts
+            .extensions
+            .insert(mem::take(&mut parts.extensions));
+
+        let context = context_factory.create(&request_parts).await;
+
+        if let Some(extensions) = request_parts.extensions.remove::<Extensions>() {
+            parts.extensions = extensions;
+        }
+        context.map(Self).map_err(context_error_response)
+    }
+}
+
+fn context_error_response(err: ContextError) -> Response {
+    let status =
+        StatusCode::from_u16(err.status_code()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    (status, Json(err.into_response::<DefaultScalarValue>())).into_response()
+}
+
+/// Query parameters of a GraphQL `GET` request.
+#[derive(Deserialize, Debug)]
+struct GetRequest {
+    query: String,
+    #[serde(rename = "operationName")]
+    operation_name: Option<String>,
+    variables: Option<String>,
+    extensions: Option<String>,
+}
+
+impl<S: ScalarValue> TryFrom<GetRequest> for GraphQLRequest<S> {
+    type Error = String;
+
+    fn try_from(req: GetRequest) -> Result<Self, Self::Error> {
+        let GetRequest {
+            query,
+            operation_name,
+            variables,
+            extensions,
+        } = req;
+        let variables = variables
+            .map(|v| serde_json::from_str(&v))
+            .transpose()
+            .map_err(|e| format!("invalid `variables`: {}", e))?;
+        let extensions = extensions
+            .map(|e| serde_json::from_str(&e))
+            .transpose()
+            .map_err(|e| format!("invalid `extensions`: {}", e))?;
+        Ok(Self::new(query, operation_name, variables).with_extensions(extensions))
+    }
+}
+
+fn mutation_over_get_response() -> Response {
+    (
+        StatusCode::METHOD_NOT_ALLOWED,
+        [(ALLOW, "POST")],
+        "mutations are not allowed over GET",
+    )
+        .into_response()
+}
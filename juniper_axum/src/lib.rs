@@ -0,0 +1,410 @@
+/*!
+
+# juniper_axum
+
+This repository contains the [axum][axum] web server integration for
+[Juniper][Juniper], a [GraphQL][GraphQL] implementation for Rust.
+
+## Documentation
+
+For documentation, including guides and examples, check out [Juniper][Juniper].
+
+A basic usage example can also be found in the [Api documentation][documentation].
+
+## Examples
+
+Check [examples/axum_server.rs][example] for example code of a working axum
+server with GraphQL handlers.
+
+## Links
+
+* [Juniper][Juniper]
+* [Api Reference][documentation]
+* [axum][axum]
+
+## License
+
+This project is under the BSD-2 license.
+
+Check the LICENSE file for details.
+
+[axum]: https://github.com/tokio-rs/axum
+[Juniper]: https://github.com/graphql-rust/juniper
+[GraphQL]: http://graphql.org
+[documentation]: https://docs.rs/juniper_axum
+[example]: https://github.com/graphql-rust/juniper/blob/master/juniper_axum/examples/axum_server.rs
+
+*/
+
+#![deny(missing_docs)]
+#![deny(warnings)]
+#![doc(html_root_url = "https://docs.rs/juniper_axum/0.1.0")]
+
+pub mod extract;
+pub mod response;
+#[cfg(feature = "subscriptions")]
+pub mod subscriptions;
+
+use axum::response::Html;
+
+/// Creates a handler response serving GraphiQL.
+///
+/// The `graphql_endpoint_url` argument should be the URL to send GraphQL requests to from
+/// GraphiQL, and the `subscriptions_endpoint_url` one should be the URL of the WebSocket
+/// subscriptions endpoint, if any.
+///
+/// ```
+/// # use axum::{routing::get, Router};
+/// #
+/// let app: Router = Router::new().route(
+///     "/graphiql",
+///     get(|| juniper_axum::graphiql("/graphql", Some("/subscriptions"))),
+/// );
+/// ```
+pub async fn graphiql(
+    graphql_endpoint_url: &str,
+    subscriptions_endpoint_url: Option<&str>,
+) -> Html<String> {
+    Html(juniper::http::graphiql::graphiql_source(
+        graphql_endpoint_url,
+        subscriptions_endpoint_url,
+    ))
+}
+
+/// Creates a handler response serving GraphQL Playground.
+///
+/// The `graphql_endpoint_url` argument should be the URL to send GraphQL requests to from
+/// GraphQL Playground, and the `subscriptions_endpoint_url` one should be the URL of the
+/// WebSocket subscriptions endpoint, if any.
+///
+/// ```
+/// # use axum::{routing::get, Router};
+/// #
+/// let app: Router = Router::new().route(
+///     "/playground",
+///     get(|| juniper_axum::playground("/graphql", Some("/subscriptions"))),
+/// );
+/// ```
+pub async fn playground(
+    graphql_endpoint_url: &str,
+    subscriptions_endpoint_url: Option<&str>,
+) -> Html<String> {
+    Html(juniper::http::playground::playground_source(
+        graphql_endpoint_url,
+        subscriptions_endpoint_url,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{net::TcpListener, sync::Arc};
+
+    use axum::{
+        body::Body,
+        http::{
+            header::{AUTHORIZATION, CONTENT_TYPE},
+            Request, StatusCode,
+        },
+        response::Response,
+        routing::{get, post},
+        Extension, Router, Server,
+    };
+    use juniper::{
+        http::{tests as http_tests, ContextError, ContextFactory, RequestParts},
+        tests::fixtures::starwars::schema::{Database, Query},
+        EmptyMutation, EmptySubscription, RootNode,
+    };
+    use reqwest::blocking::Response as ReqwestResponse;
+    use tower::ServiceExt as _;
+
+    use crate::extract::{JuniperContext, JuniperRequest};
+
+    type Schema = RootNode<'static, Query, EmptyMutation<Database>, EmptySubscription<Database>>;
+
+    struct TestAxumIntegration {
+        port: u16,
+    }
+
+    impl http_tests::HttpIntegration for TestAxumIntegration {
+        fn get(&self, url: &str) -> http_tests::TestResponse {
+            let url = format!("http://127.0.0.1:{}/graphql{}", self.port, url);
+            make_test_response(
+                reqwest::blocking::get(&url).unwrap_or_else(|_| panic!("failed GET {}", url)),
+            )
+        }
+
+        fn post_json(&self, url: &str, body: &str) -> http_tests::TestResponse {
+            self.post(url, "application/json", body)
+        }
+
+        fn post_graphql(&self, url: &str, body: &str) -> http_tests::TestResponse {
+            self.post(url, "application/graphql", body)
+        }
+    }
+
+    impl TestAxumIntegration {
+        fn post(&self, url: &str, content_type: &str, body: &str) -> http_tests::TestResponse {
+            let url = format!("http://127.0.0.1:{}/graphql{}", self.port, url);
+            let res = reqwest::blocking::Client::new()
+                .post(&url)
+                .header(reqwest::header::CONTENT_TYPE, content_type)
+                .body(body.to_owned())
+                .send()
+                .unwrap_or_else(|_| panic!("failed POST {}", url));
+            make_test_response(res)
+        }
+    }
+
+    fn make_test_response(response: ReqwestResponse) -> http_tests::TestResponse {
+        let status_code = response.status().as_u16() as i32;
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .map(|ct| ct.to_str().unwrap().to_owned())
+            .unwrap_or_default();
+        let body = response.text().unwrap();
+
+        http_tests::TestResponse {
+            status_code,
+            body: Some(body),
+            content_type,
+        }
+    }
+
+    async fn graphql(Extension(schema): Extension<Arc<Schema>>, req: JuniperRequest) -> Response {
+        req.execute(&schema, &Database::new()).await
+    }
+
+    async fn graphql_sync(
+        Extension(schema): Extension<Arc<Schema>>,
+        req: JuniperRequest,
+    ) -> Response {
+        req.execute_sync(&schema, &Database::new())
+    }
+
+    async fn run_axum_integration(is_sync: bool) {
+        let schema = Arc::new(Schema::new(
+            Query,
+            EmptyMutation::new(),
+            EmptySubscription::new(),
+        ));
+        let route = if is_sync {
+            get(graphql_sync).post(graphql_sync)
+        } else {
+            get(graphql).post(graphql)
+        };
+        let app = Router::new()
+            .route("/graphql/", route)
+            .layer(Extension(schema));
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = Server::from_tcp(listener)
+            .unwrap()
+            .serve(app.into_make_service());
+        let server = tokio::spawn(server);
+
+        tokio::task::spawn_blocking(move || {
+            http_tests::run_http_test_suite(&TestAxumIntegration { port });
+        })
+        .await
+        .unwrap();
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_axum_integration() {
+        run_axum_integration(false).await
+    }
+
+    #[tokio::test]
+    async fn test_sync_axum_integration() {
+        run_axum_integration(true).await
+    }
+
+    #[tokio::test]
+    async fn test_context_factory() {
+        // `JuniperContext` goes first to check the request extensions are put back.
+        async fn handler(
+            JuniperContext(context): JuniperContext<Database>,
+            Extension(schema): Extension<Arc<Schema>>,
+            req: JuniperRequest,
+        ) -> Response {
+            req.execute(&schema, &context).await
+        }
+
+        let schema = Arc::new(Schema::new(
+            Query,
+            EmptyMutation::new(),
+            EmptySubscription::new(),
+        ));
+        let context_factory: Arc<dyn ContextFactory<Database>> =
+            Arc::new(|parts: &RequestParts| {
+                futures::future::ready(match parts.header("authorization") {
+                    Some("secret") => Ok(Database::new()),
+                    _ => Err(ContextError::unauthorized("invalid token")),
+                })
+            });
+        let app = Router::new()
+            .route("/graphql", get(handler))
+            .layer(Extension(schema))
+            .layer(Extension(context_factory));
+        let req = |token| {
+            Request::get("/graphql?query=%7Bhero%7Bname%7D%7D")
+                .header(AUTHORIZATION, token)
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let resp = app.clone().oneshot(req("wrong")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        let body = hyper::body::to_bytes(resp.into_body()).await.unwrap();
+        assert!(String::from_utf8_lossy(&body).contains("invalid token"));
+
+        let resp = app.oneshot(req("secret")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_graphql_content_type_with_parameters() {
+        let schema = Arc::new(Schema::new(
+            Query,
+            EmptyMutation::new(),
+            EmptySubscription::new(),
+        ));
+        let app = Router::new()
+            .route("/graphql", post(graphql))
+            .layer(Extension(schema));
+        let req = Request::post("/graphql")
+            .header(CONTENT_TYPE, "application/graphql; charset=utf-8")
+            .body(Body::from("{ hero { name } }"))
+            .unwrap();
+
+        let resp = app.oneshot(req).await.unwrap();
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = hyper::body::to_bytes(resp.into_body()).await.unwrap();
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+            serde_json::json!({"data": {"hero": {"name": "R2-D2"}}}),
+        );
+    }
+
+    #[cfg(feature = "subscriptions")]
+    struct TestAxumWsIntegration {
+        port: u16,
+    }
+
+    #[cfg(feature = "subscriptions")]
+    impl TestAxumWsIntegration {
+        async fn run_async(
+            &self,
+            messages: Vec<http_tests::WsIntegrationMessage>,
+        ) -> Result<(), anyhow::Error> {
+            use std::time::Duration;
+
+            use futures::{SinkExt as _, StreamExt as _};
+            use tokio_tungstenite::tungstenite::{client::IntoClientRequest as _, Message};
+
+            let mut req = format!("ws://127.0.0.1:{}/subscriptions", self.port)
+                .into_client_request()
+                .map_err(|e| anyhow::anyhow!("WS error: {:?}", e))?;
+            req.headers_mut().insert(
+                "sec-websocket-protocol",
+                crate::subscriptions::PROTOCOL.parse().unwrap(),
+            );
+            let (mut websocket, _) = tokio_tungstenite::connect_async(req)
+                .await
+                .map_err(|e| anyhow::anyhow!("WS error: {:?}", e))?;
+
+            for message in messages {
+                match message {
+                    http_tests::WsIntegrationMessage::Send(body) => {
+                        websocket
+                            .send(Message::Text(body))
+                            .await
+                            .map_err(|e| anyhow::anyhow!("WS error: {:?}", e))?;
+                    }
+                    http_tests::WsIntegrationMessage::Expect(body, message_timeout) => {
+                        let message = tokio::time::timeout(
+                            Duration::from_millis(message_timeout),
+                            websocket.next(),
+                        )
+                        .await
+                        .map_err(|_| anyhow::anyhow!("Timed-out waiting for message"))?
+                        .ok_or_else(|| anyhow::anyhow!("Empty message received"))?
+                        .map_err(|e| anyhow::anyhow!("WS error: {:?}", e))?;
+
+                        let text = match message {
+                            Message::Text(text) => text,
+                            _ => return Err(anyhow::anyhow!("Received non-text message")),
+                        };
+                        let expected_value = serde_json::from_str::<serde_json::Value>(&body)
+                            .map_err(|e| anyhow::anyhow!("Serde error: {:?}", e))?;
+                        let value = serde_json::from_str::<serde_json::Value>(&text)
+                            .map_err(|e| anyhow::anyhow!("Serde error: {:?}", e))?;
+                        if value != expected_value {
+                            return Err(anyhow::anyhow!(
+                                "Expected message: {}. Received message: {}",
+                                expected_value,
+                                value,
+                            ));
+                        }
+                    }
+                }
+            }
+
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "subscriptions")]
+    impl http_tests::WsIntegration for TestAxumWsIntegration {
+        fn run(
+            &self,
+            messages: Vec<http_tests::WsIntegrationMessage>,
+        ) -> juniper::LocalBoxFuture<'_, Result<(), anyhow::Error>> {
+            Box::pin(self.run_async(messages))
+        }
+    }
+
+    #[cfg(feature = "subscriptions")]
+    #[tokio::test]
+    async fn test_axum_ws_integration() {
+        use axum::extract::WebSocketUpgrade;
+        use juniper::tests::fixtures::starwars::schema::Subscription;
+        use juniper_graphql_ws::ConnectionConfig;
+
+        type WsSchema = RootNode<'static, Query, EmptyMutation<Database>, Subscription>;
+
+        async fn graphql_ws(
+            Extension(schema): Extension<Arc<WsSchema>>,
+            ws: WebSocketUpgrade,
+        ) -> Response {
+            ws.protocols([crate::subscriptions::PROTOCOL])
+                .on_upgrade(move |socket| async move {
+                    let config = ConnectionConfig::new(Database::new());
+                    crate::subscriptions::serve_graphql_ws(socket, schema, config)
+                        .await
+                        .unwrap();
+                })
+        }
+
+        let schema = Arc::new(WsSchema::new(Query, EmptyMutation::new(), Subscription));
+        let app = Router::new()
+            .route("/subscriptions", get(graphql_ws))
+            .layer(Extension(schema));
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = Server::from_tcp(listener)
+            .unwrap()
+            .serve(app.into_make_service());
+        let server = tokio::spawn(server);
+
+        http_tests::run_ws_test_suite(&TestAxumWsIntegration { port }).await;
+
+        server.abort();
+    }
+}
@@ -0,0 +1,77 @@
+//! Responses of executed GraphQL requests.
+
+use axum::{
+    http::{
+        header::{CACHE_CONTROL, CONTENT_TYPE},
+        HeaderValue, StatusCode,
+    },
+    response::{IntoResponse, Response},
+};
+use juniper::{
+    http::{GraphQLBatchResponse, ResponseMediaType},
+    DefaultScalarValue, ScalarValue,
+};
+
+/// Wrapper of a [`GraphQLBatchResponse`] implementing [`IntoResponse`].
+///
+/// Responds with the status code of the wrapped response and its `Cache-Control` header, if it
+/// may be cached.
+pub struct JuniperResponse<'a, S = DefaultScalarValue>
+where
+    S: ScalarValue,
+{
+    response: GraphQLBatchResponse<'a, S>,
+    media_type: ResponseMediaType,
+}
+
+impl<'a, S> JuniperResponse<'a, S>
+where
+    S: ScalarValue,
+{
+    /// Wraps the given `response`, sending it with the legacy `application/json` media type.
+    pub fn new(response: GraphQLBatchResponse<'a, S>) -> Self {
+        Self {
+            response,
+            media_type: ResponseMediaType::Json,
+        }
+    }
+
+    /// Sets the [`ResponseMediaType`] to send the wrapped response with.
+    pub fn with_media_type(mut self, media_type: ResponseMediaType) -> Self {
+        self.media_type = media_type;
+        self
+    }
+}
+
+impl<'a, S> IntoResponse for JuniperResponse<'a, S>
+where
+    S: ScalarValue,
+{
+    fn into_response(self) -> Response {
+        let body = match serde_json::to_vec(&self.response) {
+            Ok(body) => body,
+            Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        };
+        let status = StatusCode::from_u16(self.response.status_code())
+            .expect("GraphQL response status code is valid");
+
+        let mut resp = (
+            status,
+            [(
+                CONTENT_TYPE,
+                HeaderValue::from_static(self.media_type.as_str()),
+            )],
+            body,
+        )
+            .into_response();
+        if let Some(cache_control) = self
+            .response
+            .cache_policy()
+            .and_then(|p| p.header_value())
+            .and_then(|v| HeaderValue::from_str(&v).ok())
+        {
+            resp.headers_mut().insert(CACHE_CONTROL, cache_control);
+        }
+        resp
+    }
+}
@@ -0,0 +1,145 @@
+//! GraphQL subscriptions over WebSocket, as of the [graphql-ws protocol][1].
+//!
+//! [1]: https://github.com/apollographql/subscriptions-transport-ws/blob/master/PROTOCOL.md
+
+use std::{convert::Infallible, fmt, sync::Arc};
+
+use axum::extract::ws::{self, WebSocket};
+use futures::{
+    future::{self, Either},
+    SinkExt as _, StreamExt as _,
+};
+use juniper::{GraphQLSubscriptionType, GraphQLTypeAsync, RootNode, ScalarValue};
+use juniper_graphql_ws::{ArcSchema, ClientMessage, Connection, Init};
+
+/// Sub-protocol of WebSocket connections served by [`serve_graphql_ws()`].
+///
+/// Should be passed to [`WebSocketUpgrade::protocols()`][1], so clients requesting it get the
+/// connection upgraded.
+///
+/// [1]: axum::extract::ws::WebSocketUpgrade::protocols
+pub const PROTOCOL: &str = "graphql-ws";
+
+struct Message(ws::Message);
+
+impl<S: ScalarValue> std::convert::TryFrom<Message> for ClientMessage<S> {
+    type Error = Error;
+
+    fn try_from(msg: Message) -> Result<Self, Self::Error> {
+        match msg.0 {
+            ws::Message::Text(text) => serde_json::from_str(&text),
+            msg => serde_json::from_slice(&msg.into_data()),
+        }
+        .map_err(Error::Serde)
+    }
+}
+
+/// Errors that can happen while serving a connection.
+#[derive(Debug)]
+pub enum Error {
+    /// Errors that can happen in axum while serving a connection.
+    Axum(axum::Error),
+
+    /// Errors that can happen while serializing outgoing messages. Note that errors that occur
+    /// while deserializing incoming messages are handled internally by the protocol.
+    Serde(serde_json::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Axum(e) => write!(f, "axum error: {}", e),
+            Self::Serde(e) => write!(f, "serde error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<axum::Error> for Error {
+    fn from(err: axum::Error) -> Self {
+        Self::Axum(err)
+    }
+}
+
+impl From<Infallible> for Error {
+    fn from(_err: Infallible) -> Self {
+        unreachable!()
+    }
+}
+
+/// Serves the graphql-ws protocol over a WebSocket connection.
+///
+/// The `init` argument is used to provide the context and additional configuration for
+/// connections. This can be a `juniper_graphql_ws::ConnectionConfig` if the context and
+/// configuration are already known, or it can be a closure that gets executed asynchronously
+/// when the client sends the ConnectionInit message. Using a closure allows you to perform
+/// authentication based on the parameters provided by the client.
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use axum::{extract::WebSocketUpgrade, response::Response, routing::get, Extension, Router};
+/// # use juniper::{
+/// #     tests::fixtures::starwars::schema::{Database, Query},
+/// #     EmptyMutation, EmptySubscription, RootNode,
+/// # };
+/// # use juniper_axum::subscriptions;
+/// # use juniper_graphql_ws::ConnectionConfig;
+/// #
+/// type Schema = RootNode<'static, Query, EmptyMutation<Database>, EmptySubscription<Database>>;
+///
+/// async fn graphql_ws(Extension(schema): Extension<Arc<Schema>>, ws: WebSocketUpgrade) -> Response {
+///     ws.protocols([subscriptions::PROTOCOL]).on_upgrade(move |socket| async move {
+///         let config = ConnectionConfig::new(Database::new());
+///         if let Err(e) = subscriptions::serve_graphql_ws(socket, schema, config).await {
+///             eprintln!("WebSocket error: {}", e);
+///         }
+///     })
+/// }
+///
+/// let schema = Schema::new(Query, EmptyMutation::new(), EmptySubscription::new());
+/// let app: Router = Router::new()
+///     .route("/subscriptions", get(graphql_ws))
+///     .layer(Extension(Arc::new(schema)));
+/// ```
+pub async fn serve_graphql_ws<Query, Mutation, Subscription, CtxT, S, I>(
+    socket: WebSocket,
+    root_node: Arc<RootNode<'static, Query, Mutation, Subscription, S>>,
+    init: I,
+) -> Result<(), Error>
+where
+    Query: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Query::TypeInfo: Send + Sync,
+    Mutation: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Mutation::TypeInfo: Send + Sync,
+    Subscription: GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+    Subscription::TypeInfo: Send + Sync,
+    CtxT: Unpin + Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+    I: Init<S, CtxT> + Send,
+{
+    let (ws_tx, ws_rx) = socket.split();
+    let (s_tx, s_rx) = Connection::new(ArcSchema(root_node), init).split();
+
+    // Control frames are answered by axum itself, so only data frames are passed further.
+    let ws_rx = ws_rx.filter_map(|r| {
+        future::ready(match r {
+            Ok(ws::Message::Ping(_)) | Ok(ws::Message::Pong(_)) | Ok(ws::Message::Close(_)) => None,
+            r => Some(r.map(Message).map_err(Error::Axum)),
+        })
+    });
+    let s_rx = s_rx.map(|msg| {
+        serde_json::to_string(&msg)
+            .map(ws::Message::Text)
+            .map_err(Error::Serde)
+    });
+
+    match future::select(
+        ws_rx.forward(s_tx.sink_err_into()),
+        s_rx.forward(ws_tx.sink_err_into()),
+    )
+    .await
+    {
+        Either::Left((r, _)) | Either::Right((r, _)) => r,
+    }
+}
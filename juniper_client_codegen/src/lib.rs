@@ -0,0 +1,193 @@
+//! This crate supplies the `#[derive(GraphQLQuery)]` macro generating typed operations, sent to
+//! [Juniper](https://github.com/graphql-rust/juniper) servers, or any other GraphQL servers, with
+//! the [`juniper::http::GraphQLQuery`] trait.
+//!
+//! Queries are validated against the schema of the server at compile time, with the same
+//! validation rules Juniper applies to incoming requests, so a query broken by a change of the
+//! schema fails the compilation.
+
+#![doc(html_root_url = "https://docs.rs/juniper_client_codegen/0.1.0")]
+#![recursion_limit = "1024"]
+
+mod query;
+mod schema;
+
+use std::{fs, path::PathBuf, rc::Rc};
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use proc_macro_error::{abort, abort_if_dirty, emit_error, proc_macro_error};
+use syn::{Lit, LitStr, Meta, NestedMeta};
+
+use self::schema::Schema;
+
+/// `#[derive(GraphQLQuery)]` macro for generating a typed GraphQL operation, implementing the
+/// [`juniper::http::GraphQLQuery`] trait for the deriving struct.
+///
+/// The schema of the server is read from its definition in the [GraphQL Schema Language][1],
+/// given by the `schema_path` attribute argument. For schemas defined with Juniper, it's the
+/// snapshot written by `juniper::snapshot::write_schema()` or kept up to date by
+/// `juniper::snapshot::assert_schema_snapshot()`. The operation is given either inline with the
+/// `query` attribute argument, or by the `query_path` one. Paths are relative to the directory
+/// of the crate's `Cargo.toml`, and the crate is rebuilt whenever the files change.
+///
+/// ```ignore
+/// use juniper::http::GraphQLQuery as _;
+/// use juniper_client_codegen::GraphQLQuery;
+///
+/// #[derive(GraphQLQuery)]
+/// #[graphql(
+///     schema_path = "schema.graphql",
+///     query = "query Hero($episode: Episode) { hero(episode: $episode) { name } }",
+/// )]
+/// struct Hero;
+///
+/// let request = Hero::build_request(hero::Variables {
+///     episode: Some(hero::Episode::NewHope),
+/// });
+/// ```
+///
+/// The query is validated against the schema, failing the compilation on any violated validation
+/// rule. The `__schema` and `__type` introspection fields aren't supported, as the schema
+/// definition doesn't contain the introspection types.
+///
+/// ## Generated types
+///
+/// The types of the operation are generated in a module named after the deriving struct in
+/// `snake_case`, containing:
+/// - `QUERY` constant with the query document;
+/// - `Variables` struct with a field for each variable of the operation;
+/// - `ResponseData` struct deserialized from the `data` of a response, with a field for each
+///   selected field, named after its alias, if any, in `snake_case`;
+/// - a struct for each selection of fields of an object, interface or union, named after the path
+///   of the selecting field, like `HeroFriends` for `hero { friends { name } }`;
+/// - an enum for each enum type, and a struct for each input object type the operation uses.
+///
+/// Nullable types are represented as [`Option`]s, and lists as [`Vec`]s. The `Int`, `Float`,
+/// `String`, `Boolean` and `ID` scalars are represented as [`i32`], [`f64`], [`String`], [`bool`]
+/// and [`juniper::ID`]. Other scalars are represented as types of the same name, which should be
+/// in scope of the deriving struct, implementing [`juniper::ToInputValue`] and `Deserialize`.
+///
+/// Fields of fragments on narrower types than the one they're spread into, and fields having the
+/// `@skip` or `@include` directives, may be missing from responses, so they're [`Option`]s too.
+///
+/// ## Operation
+///
+/// A document containing several operations should specify the one to generate with the
+/// `operation` attribute argument. It defaults to the only operation of the document, or to the
+/// one named like the deriving struct.
+///
+/// [1]: https://graphql.org/learn/schema/#type-language
+#[proc_macro_error]
+#[proc_macro_derive(GraphQLQuery, attributes(graphql))]
+pub fn derive_query(input: TokenStream) -> TokenStream {
+    let ast = syn::parse_macro_input!(input as syn::DeriveInput);
+    if !matches!(ast.data, syn::Data::Struct(_)) {
+        abort!(ast.ident, "GraphQLQuery can only be derived for structs");
+    }
+    let attrs = Attributes::parse(&ast);
+
+    let schema_path = match &attrs.schema_path {
+        Some(path) => path,
+        None => abort!(ast.ident, "Missing `schema_path` attribute argument"),
+    };
+    let (schema_file, sdl) = read(schema_path);
+    let schema = Schema::parse(&sdl).unwrap_or_else(|e| {
+        abort!(
+            schema_path,
+            "Invalid schema `{}`: {}",
+            schema_path.value(),
+            e
+        )
+    });
+
+    let (query_lit, query_file, query) = match (&attrs.query, &attrs.query_path) {
+        (Some(query), None) => (query, None, query.value()),
+        (None, Some(path)) => {
+            let (file, query) = read(path);
+            (path, Some(file), query)
+        }
+        (Some(query), Some(_)) => abort!(
+            query,
+            "`query` and `query_path` attribute arguments are mutually exclusive",
+        ),
+        (None, None) => abort!(
+            ast.ident,
+            "Missing either `query` or `query_path` attribute argument",
+        ),
+    };
+
+    let schema = Rc::new(schema);
+    if let Err(errors) = schema.validate(&query) {
+        for e in errors {
+            emit_error!(query_lit, "Query doesn't match the schema: {}", e);
+        }
+        abort_if_dirty();
+    }
+
+    query::generate(query::Input {
+        schema: &schema,
+        query: &query,
+        query_lit,
+        schema_file,
+        query_file,
+        operation: attrs.operation.as_ref(),
+        ident: &ast.ident,
+        vis: &ast.vis,
+    })
+    .into()
+}
+
+/// Arguments of the `#[graphql]` attribute of a struct deriving `GraphQLQuery`.
+#[derive(Default)]
+struct Attributes {
+    schema_path: Option<LitStr>,
+    query: Option<LitStr>,
+    query_path: Option<LitStr>,
+    operation: Option<LitStr>,
+}
+
+impl Attributes {
+    fn parse(ast: &syn::DeriveInput) -> Self {
+        let mut attrs = Self::default();
+        for attr in ast.attrs.iter().filter(|a| a.path.is_ident("graphql")) {
+            let list = match attr.parse_meta() {
+                Ok(Meta::List(list)) => list,
+                Ok(meta) => abort!(meta, "Expected `#[graphql(...)]`"),
+                Err(e) => abort!(e.span(), "{}", e),
+            };
+            for nested in list.nested {
+                let (name, value) = match &nested {
+                    NestedMeta::Meta(Meta::NameValue(nv)) => match (nv.path.get_ident(), &nv.lit) {
+                        (Some(name), Lit::Str(value)) => (name.to_string(), value.clone()),
+                        (_, lit) => abort!(lit, "Expected a string literal"),
+                    },
+                    _ => abort!(nested, "Expected `name = \"value\"`"),
+                };
+                let slot = match name.as_str() {
+                    "schema_path" => &mut attrs.schema_path,
+                    "query" => &mut attrs.query,
+                    "query_path" => &mut attrs.query_path,
+                    "operation" => &mut attrs.operation,
+                    _ => abort!(nested, "Unknown attribute argument `{}`", name),
+                };
+                if slot.replace(value).is_some() {
+                    abort!(nested, "Duplicated attribute argument `{}`", name);
+                }
+            }
+        }
+        attrs
+    }
+}
+
+/// Reads the file at the given `path`, relative to the directory of the crate's `Cargo.toml`,
+/// returning its absolute path and contents.
+fn read(path: &LitStr) -> (String, String) {
+    let dir = std::env::var_os("CARGO_MANIFEST_DIR")
+        .unwrap_or_else(|| abort!(Span::call_site(), "`CARGO_MANIFEST_DIR` is not set"));
+    let file = PathBuf::from(dir).join(path.value());
+    match fs::read_to_string(&file) {
+        Ok(contents) => (file.display().to_string(), contents),
+        Err(e) => abort!(path, "Failed to read `{}`: {}", file.display(), e),
+    }
+}
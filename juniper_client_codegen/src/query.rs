@@ -0,0 +1,591 @@
+//! Code generation of the types of an operation.
+
+use std::collections::{HashMap, HashSet};
+
+use graphql_parser::query::{
+    parse_query, Definition, Directive, FragmentDefinition, OperationDefinition, Selection,
+    SelectionSet, TypeCondition, VariableDefinition,
+};
+use proc_macro2::{Ident, Span, TokenStream};
+use proc_macro_error::abort;
+use quote::{format_ident, quote};
+use syn::{LitStr, Visibility};
+
+use crate::schema::{InputFieldDef, Schema, TypeDef, TypeRef};
+
+/// Input of the code generation of an operation.
+pub(crate) struct Input<'a> {
+    /// Schema the operation is validated against.
+    pub(crate) schema: &'a Schema,
+
+    /// Document containing the operation.
+    pub(crate) query: &'a str,
+
+    /// Literal the document is given by, either inline or as the path of its file.
+    pub(crate) query_lit: &'a LitStr,
+
+    /// Absolute path of the file the schema is read from.
+    pub(crate) schema_file: String,
+
+    /// Absolute path of the file the document is read from, if not given inline.
+    pub(crate) query_file: Option<String>,
+
+    /// Name of the operation to generate, if specified.
+    pub(crate) operation: Option<&'a LitStr>,
+
+    /// Identifier of the deriving struct.
+    pub(crate) ident: &'a Ident,
+
+    /// Visibility of the deriving struct.
+    pub(crate) vis: &'a Visibility,
+}
+
+/// Generates the module with the types of the operation, and the `GraphQLQuery` implementation
+/// referring to them.
+pub(crate) fn generate(input: Input<'_>) -> TokenStream {
+    let doc = parse_query::<String>(input.query)
+        .unwrap_or_else(|e| abort!(input.query_lit, "Invalid query: {}", e));
+
+    let mut operations = vec![];
+    let mut fragments = HashMap::new();
+    for def in &doc.definitions {
+        match def {
+            Definition::Operation(op) => operations.push(op),
+            Definition::Fragment(f) => {
+                fragments.insert(f.name.as_str(), f);
+            }
+        }
+    }
+    let op = select_operation(&operations, &input);
+
+    let (name, root, variables, selection_set) = match op {
+        OperationDefinition::SelectionSet(s) => (None, Some(&input.schema.query), &[][..], s),
+        OperationDefinition::Query(q) => (
+            q.name.as_ref(),
+            Some(&input.schema.query),
+            &q.variable_definitions[..],
+            &q.selection_set,
+        ),
+        OperationDefinition::Mutation(m) => (
+            m.name.as_ref(),
+            input.schema.mutation.as_ref(),
+            &m.variable_definitions[..],
+            &m.selection_set,
+        ),
+        OperationDefinition::Subscription(s) => (
+            s.name.as_ref(),
+            input.schema.subscription.as_ref(),
+            &s.variable_definitions[..],
+            &s.selection_set,
+        ),
+    };
+    let root = root.unwrap_or_else(|| {
+        abort!(
+            input.query_lit,
+            "Schema doesn't support the operation type of the query",
+        )
+    });
+
+    let mut gen = Generator {
+        schema: input.schema,
+        query_lit: input.query_lit,
+        fragments,
+        items: vec![],
+        generated: HashSet::new(),
+    };
+    gen.variables(variables);
+    gen.object(
+        "ResponseData",
+        "",
+        root.as_str(),
+        vec![(selection_set, false)],
+    );
+    let items = gen.items;
+
+    let ident = input.ident;
+    let vis = input.vis;
+    let module = format_ident!("{}", to_snake_case(&ident.to_string()));
+    let schema_file = &input.schema_file;
+    let query = match &input.query_file {
+        Some(file) => quote! { include_str!(#file) },
+        None => {
+            let query = input.query_lit;
+            quote! { #query }
+        }
+    };
+    let operation_name = match name {
+        Some(name) => quote! { ::std::option::Option::Some(#name) },
+        None => quote! { ::std::option::Option::None },
+    };
+
+    quote! {
+        #[automatically_derived]
+        #vis mod #module {
+            #[allow(unused_imports)]
+            use super::*;
+
+            const _: &[u8] = include_bytes!(#schema_file);
+
+            /// Document containing the operation.
+            pub const QUERY: &str = #query;
+
+            #( #items )*
+        }
+
+        #[automatically_derived]
+        impl ::juniper::http::GraphQLQuery for #ident {
+            type Variables = #module::Variables;
+            type ResponseData = #module::ResponseData;
+
+            const QUERY: &'static str = #module::QUERY;
+            const OPERATION_NAME: ::std::option::Option<&'static str> = #operation_name;
+        }
+    }
+}
+
+/// Selects the operation to generate out of the `operations` of the document.
+fn select_operation<'q>(
+    operations: &[&'q OperationDefinition<'q, String>],
+    input: &Input<'_>,
+) -> &'q OperationDefinition<'q, String> {
+    let find = |name: &str| {
+        operations
+            .iter()
+            .copied()
+            .find(|op| operation_name(op) == Some(name))
+    };
+    match (input.operation, operations) {
+        (Some(name), _) => find(&name.value()).unwrap_or_else(|| {
+            abort!(
+                name,
+                "Query doesn't contain an operation named `{}`",
+                name.value(),
+            )
+        }),
+        (None, [op]) => op,
+        (None, []) => abort!(input.query_lit, "Query doesn't contain any operation"),
+        (None, _) => find(&input.ident.to_string()).unwrap_or_else(|| {
+            abort!(
+                input.query_lit,
+                "Query contains several operations, specify the one to generate with the \
+                 `operation` attribute argument",
+            )
+        }),
+    }
+}
+
+fn operation_name<'q>(op: &'q OperationDefinition<'q, String>) -> Option<&'q str> {
+    match op {
+        OperationDefinition::SelectionSet(_) => None,
+        OperationDefinition::Query(q) => q.name.as_deref(),
+        OperationDefinition::Mutation(m) => m.name.as_deref(),
+        OperationDefinition::Subscription(s) => s.name.as_deref(),
+    }
+}
+
+/// Field selected in a selection set, along with the fields of the same response key it's merged
+/// with.
+struct Selected<'q> {
+    /// Key of the field in the response, being its alias, if any.
+    key: &'q str,
+
+    /// Name of the field in the schema.
+    name: &'q str,
+
+    /// Name of the type the field is selected on.
+    owner: &'q str,
+
+    /// Whether the field may be missing from the response.
+    optional: bool,
+
+    /// Selection sets of the merged fields, along with whether each of them may be missing.
+    selection_sets: Vec<(&'q SelectionSet<'q, String>, bool)>,
+}
+
+/// Generator of the items of an operation.
+struct Generator<'a, 'q> {
+    schema: &'a Schema,
+
+    /// Literal the document is given by, which errors are reported on.
+    query_lit: &'a LitStr,
+
+    fragments: HashMap<&'q str, &'q FragmentDefinition<'q, String>>,
+    items: Vec<TokenStream>,
+
+    /// Names of the already generated enums and input objects.
+    generated: HashSet<String>,
+}
+
+impl<'a, 'q> Generator<'a, 'q>
+where
+    'a: 'q,
+{
+    /// Generates the `Variables` struct out of the variable definitions of the operation.
+    fn variables(&mut self, defs: &'q [VariableDefinition<'q, String>]) {
+        let fields = defs
+            .iter()
+            .map(|def| (def.name.as_str(), TypeRef::from_ast(&def.var_type)))
+            .collect::<Vec<_>>();
+        self.input_struct("Variables", &fields);
+    }
+
+    /// Generates the struct named `name` out of the given `selection_sets` on the type named
+    /// `owner`, naming the structs of its fields with the given `prefix`.
+    fn object(
+        &mut self,
+        name: &str,
+        prefix: &str,
+        owner: &'q str,
+        selection_sets: Vec<(&'q SelectionSet<'q, String>, bool)>,
+    ) {
+        let mut selected = vec![];
+        for (set, optional) in selection_sets {
+            self.collect(owner, set, optional, &mut selected);
+        }
+
+        let fields = selected
+            .into_iter()
+            .map(|sel| {
+                let key = sel.key;
+                let ident = field_ident(key);
+                let (ty, is_option) = self.field_type(sel.owner, &sel, prefix);
+                let (ty, default) = if !sel.optional {
+                    (ty, None)
+                } else if is_option {
+                    (ty, Some(quote! { #[serde(default)] }))
+                } else {
+                    (
+                        quote! { ::std::option::Option<#ty> },
+                        Some(quote! { #[serde(default)] }),
+                    )
+                };
+                quote! {
+                    #[serde(rename = #key)]
+                    #default
+                    pub #ident: #ty,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let ident = format_ident!("{}", name);
+        self.items.push(quote! {
+            #[derive(Clone, Debug, PartialEq, ::juniper::serde::Deserialize)]
+            #[serde(crate = "::juniper::serde")]
+            pub struct #ident {
+                #( #fields )*
+            }
+        });
+    }
+
+    /// Collects the fields of the given selection `set` on the type named `owner` into
+    /// `selected`, merging the ones of the same response key.
+    fn collect(
+        &self,
+        owner: &'q str,
+        set: &'q SelectionSet<'q, String>,
+        optional: bool,
+        selected: &mut Vec<Selected<'q>>,
+    ) {
+        for item in &set.items {
+            match item {
+                Selection::Field(f) => {
+                    let key = f.alias.as_ref().unwrap_or(&f.name).as_str();
+                    let optional = optional || is_conditional(&f.directives);
+                    if let Some(sel) = selected.iter_mut().find(|s| s.key == key) {
+                        sel.optional &= optional;
+                        sel.selection_sets.push((&f.selection_set, optional));
+                    } else {
+                        selected.push(Selected {
+                            key,
+                            name: &f.name,
+                            owner,
+                            optional,
+                            selection_sets: vec![(&f.selection_set, optional)],
+                        });
+                    }
+                }
+                Selection::FragmentSpread(s) => {
+                    let fragment = self.fragments[s.fragment_name.as_str()];
+                    let TypeCondition::On(on) = &fragment.type_condition;
+                    let optional =
+                        optional || is_conditional(&s.directives) || self.narrows(owner, on);
+                    self.collect(on, &fragment.selection_set, optional, selected);
+                }
+                Selection::InlineFragment(f) => {
+                    let on = match &f.type_condition {
+                        Some(TypeCondition::On(on)) => on.as_str(),
+                        None => owner,
+                    };
+                    let optional =
+                        optional || is_conditional(&f.directives) || self.narrows(owner, on);
+                    self.collect(on, &f.selection_set, optional, selected);
+                }
+            }
+        }
+    }
+
+    /// Indicates whether a fragment on the type named `on` applies only to some of the values of
+    /// the type named `owner`.
+    fn narrows(&self, owner: &str, on: &str) -> bool {
+        owner != on && self.schema.types[owner].is_abstract()
+    }
+
+    /// Returns the Rust type of the `selected` field of the type named `owner`, generating the
+    /// types it consists of, along with whether it's an [`Option`].
+    fn field_type(
+        &mut self,
+        owner: &'q str,
+        selected: &Selected<'q>,
+        prefix: &str,
+    ) -> (TokenStream, bool) {
+        match selected.name {
+            "__typename" => return (quote! { ::std::string::String }, false),
+            // The introspection types aren't part of the schema definition the types of the
+            // response are generated from.
+            "__schema" | "__type" => abort!(
+                self.query_lit,
+                "Introspection field `{}` is not supported in queries deriving `GraphQLQuery`",
+                selected.name,
+            ),
+            _ => {}
+        }
+        let schema = self.schema;
+        let def = schema.types[owner]
+            .fields()
+            .iter()
+            .find(|f| f.name == selected.name)
+            .unwrap_or_else(|| {
+                abort!(
+                    self.query_lit,
+                    "Field `{}.{}` not found",
+                    owner,
+                    selected.name,
+                )
+            });
+
+        let type_name = def.ty.innermost_name();
+        let inner = match schema.types.get_key_value(type_name) {
+            None => builtin_scalar(type_name),
+            Some((_, TypeDef::Scalar)) => {
+                let ident = format_ident!("{}", type_name);
+                quote! { #ident }
+            }
+            Some((_, TypeDef::Enum { values })) => self.enumeration(type_name, values),
+            Some((type_name, _)) => {
+                let name = format!("{}{}", prefix, to_pascal_case(selected.key));
+                // Fields of merged selection sets which may be missing unlike the field itself
+                // remain optional.
+                let selection_sets = selected
+                    .selection_sets
+                    .iter()
+                    .map(|(set, optional)| (*set, *optional && !selected.optional))
+                    .collect();
+                self.object(&name, &name, type_name, selection_sets);
+                let ident = format_ident!("{}", name);
+                quote! { #ident }
+            }
+        };
+        (wrap(&def.ty, inner), !matches!(def.ty, TypeRef::NonNull(_)))
+    }
+
+    /// Returns the Rust type of the input type referred to by `ty`, generating the types it
+    /// consists of.
+    fn input_type(&mut self, ty: &TypeRef) -> TokenStream {
+        let schema = self.schema;
+        let name = ty.innermost_name();
+        let inner = match schema.types.get(name) {
+            None => builtin_scalar(name),
+            Some(TypeDef::Enum { values }) => self.enumeration(name, values),
+            Some(TypeDef::InputObject { fields }) => self.input_object(name, fields),
+            Some(_) => {
+                let ident = format_ident!("{}", name);
+                quote! { #ident }
+            }
+        };
+        wrap(ty, inner)
+    }
+
+    /// Generates the struct of the input object type `name` with the given `fields`, unless
+    /// already generated.
+    fn input_object(&mut self, name: &str, fields: &[InputFieldDef]) -> TokenStream {
+        let ident = format_ident!("{}", name);
+        if self.generated.insert(name.to_owned()) {
+            let fields = fields
+                .iter()
+                .map(|f| (f.name.as_str(), f.ty.clone()))
+                .collect::<Vec<_>>();
+            self.input_struct(name, &fields);
+        }
+        quote! { #ident }
+    }
+
+    /// Generates the struct named `name` with the given input `fields`, converted into an input
+    /// object.
+    fn input_struct(&mut self, name: &str, fields: &[(&str, TypeRef)]) {
+        let ident = format_ident!("{}", name);
+        let keys = fields.iter().map(|(key, _)| key).collect::<Vec<_>>();
+        let idents = fields
+            .iter()
+            .map(|(key, _)| field_ident(key))
+            .collect::<Vec<_>>();
+        let types = fields
+            .iter()
+            .map(|(_, ty)| self.input_type(ty))
+            .collect::<Vec<_>>();
+
+        self.items.push(quote! {
+            #[derive(Clone, Debug, PartialEq)]
+            pub struct #ident {
+                #( pub #idents: #types, )*
+            }
+
+            impl ::juniper::ToInputValue for #ident {
+                fn to_input_value(&self) -> ::juniper::InputValue {
+                    let fields: ::std::vec::Vec<(&str, ::juniper::InputValue)> = vec![#(
+                        (#keys, ::juniper::ToInputValue::to_input_value(&self.#idents)),
+                    )*];
+                    ::juniper::InputValue::object(fields.into_iter().collect())
+                }
+            }
+        });
+    }
+
+    /// Generates the enum of the enum type `name` with the given `values`, unless already
+    /// generated.
+    fn enumeration(&mut self, name: &str, values: &[String]) -> TokenStream {
+        let ident = format_ident!("{}", name);
+        if self.generated.insert(name.to_owned()) {
+            let variants = values
+                .iter()
+                .map(|v| format_ident!("{}", to_pascal_case(v)))
+                .collect::<Vec<_>>();
+
+            self.items.push(quote! {
+                #[derive(
+                    Clone, Copy, Debug, Eq, Hash, PartialEq, ::juniper::serde::Deserialize,
+                )]
+                #[serde(crate = "::juniper::serde")]
+                pub enum #ident {
+                    #(
+                        #[serde(rename = #values)]
+                        #variants,
+                    )*
+                }
+
+                impl ::juniper::ToInputValue for #ident {
+                    fn to_input_value(&self) -> ::juniper::InputValue {
+                        match self {
+                            #( Self::#variants => ::juniper::InputValue::enum_value(#values), )*
+                        }
+                    }
+                }
+            });
+        }
+        quote! { #ident }
+    }
+}
+
+/// Indicates whether the given `directives` may exclude a selection from the response.
+fn is_conditional(directives: &[Directive<'_, String>]) -> bool {
+    directives
+        .iter()
+        .any(|d| d.name == "skip" || d.name == "include")
+}
+
+/// Returns the Rust type of the built-in scalar `name`.
+fn builtin_scalar(name: &str) -> TokenStream {
+    match name {
+        "Int" => quote! { i32 },
+        "Float" => quote! { f64 },
+        "String" => quote! { ::std::string::String },
+        "Boolean" => quote! { bool },
+        "ID" => quote! { ::juniper::ID },
+        _ => unreachable!("Undefined type `{}`", name),
+    }
+}
+
+/// Wraps the Rust type `inner` of the named type referred to by `ty` into the [`Option`]s and
+/// [`Vec`]s of its nullable and list types.
+fn wrap(ty: &TypeRef, inner: TokenStream) -> TokenStream {
+    match ty {
+        TypeRef::NonNull(of) => wrap_non_null(of, inner),
+        nullable => {
+            let ty = wrap_non_null(nullable, inner);
+            quote! { ::std::option::Option<#ty> }
+        }
+    }
+}
+
+fn wrap_non_null(ty: &TypeRef, inner: TokenStream) -> TokenStream {
+    match ty {
+        TypeRef::Named(_) => inner,
+        TypeRef::List(of) => {
+            let ty = wrap(of, inner);
+            quote! { ::std::vec::Vec<#ty> }
+        }
+        TypeRef::NonNull(_) => unreachable!("Non-null type wrapping another one"),
+    }
+}
+
+/// Returns the identifier of the struct field representing the GraphQL field or variable `name`.
+fn field_ident(name: &str) -> Ident {
+    let name = to_snake_case(name.trim_start_matches('_'));
+    match name.as_str() {
+        "crate" | "self" | "super" | "Self" => format_ident!("{}_", name),
+        _ if syn::parse_str::<Ident>(&name).is_err() => Ident::new_raw(&name, Span::call_site()),
+        _ => format_ident!("{}", name),
+    }
+}
+
+/// Converts `camelCase` and `SCREAMING_SNAKE_CASE` names into `snake_case`.
+fn to_snake_case(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 4);
+    let mut prev_lower = false;
+    for c in s.chars() {
+        if c.is_uppercase() && prev_lower {
+            out.push('_');
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        out.extend(c.to_lowercase());
+    }
+    out
+}
+
+/// Converts `camelCase`, `snake_case` and `SCREAMING_SNAKE_CASE` names into `PascalCase`.
+fn to_pascal_case(s: &str) -> String {
+    s.split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let all_upper = !word.chars().any(char::is_lowercase);
+            let mut chars = word.chars();
+            let first = chars.next().unwrap().to_uppercase();
+            if all_upper {
+                first.chain(chars.flat_map(char::to_lowercase)).collect()
+            } else {
+                first.chain(chars).collect::<String>()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{field_ident, to_pascal_case, to_snake_case};
+
+    #[test]
+    fn converts_case() {
+        assert_eq!(to_snake_case("homePlanet"), "home_planet");
+        assert_eq!(to_snake_case("ID"), "id");
+        assert_eq!(to_snake_case("UserQuery"), "user_query");
+        assert_eq!(to_pascal_case("homePlanet"), "HomePlanet");
+        assert_eq!(to_pascal_case("NEW_HOPE"), "NewHope");
+        assert_eq!(to_pascal_case("first_name"), "FirstName");
+    }
+
+    #[test]
+    fn escapes_keywords() {
+        assert_eq!(field_ident("__typename").to_string(), "typename");
+        assert_eq!(field_ident("type").to_string(), "r#type");
+        assert_eq!(field_ident("self").to_string(), "self_");
+    }
+}
@@ -0,0 +1,559 @@
+//! Schema read from its definition in the GraphQL Schema Language, and validation of queries
+//! against it with the rules of Juniper.
+
+use std::{collections::HashMap, convert::TryFrom as _, iter, rc::Rc};
+
+use graphql_parser::{
+    query::{Type as AstType, Value as AstValue},
+    schema::{self as ast, Definition, TypeDefinition},
+};
+use juniper::{
+    meta::{Argument, EnumValue, Field, MetaType},
+    parser::{parse_document_source, ScalarToken},
+    validation::{visit_all_rules, ValidatorContext},
+    DefaultScalarValue, FromInputValue, GraphQLType, GraphQLValue, InputValue, ParseScalarResult,
+    ParseScalarValue, Registry, SchemaType, ID,
+};
+
+/// Reference to a type, as used by fields, arguments and variables.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum TypeRef {
+    Named(String),
+    List(Box<TypeRef>),
+    NonNull(Box<TypeRef>),
+}
+
+impl TypeRef {
+    pub(crate) fn from_ast<'a>(ty: &AstType<'a, String>) -> Self {
+        match ty {
+            AstType::NamedType(name) => Self::Named(name.clone()),
+            AstType::ListType(of) => Self::List(Box::new(Self::from_ast(of))),
+            AstType::NonNullType(of) => Self::NonNull(Box::new(Self::from_ast(of))),
+        }
+    }
+
+    fn non_null(name: &str) -> Self {
+        Self::NonNull(Box::new(Self::Named(name.to_owned())))
+    }
+
+    /// Returns the name of the named type this reference wraps.
+    pub(crate) fn innermost_name(&self) -> &str {
+        match self {
+            Self::Named(name) => name,
+            Self::List(of) | Self::NonNull(of) => of.innermost_name(),
+        }
+    }
+}
+
+/// Field of an object or interface type.
+#[derive(Debug)]
+pub(crate) struct FieldDef {
+    pub(crate) name: String,
+    pub(crate) args: Vec<InputFieldDef>,
+    pub(crate) ty: TypeRef,
+}
+
+/// Argument of a field, or field of an input object type.
+#[derive(Debug)]
+pub(crate) struct InputFieldDef {
+    pub(crate) name: String,
+    pub(crate) ty: TypeRef,
+    pub(crate) default: Option<InputValue>,
+}
+
+/// Definition of a named type.
+#[derive(Debug)]
+pub(crate) enum TypeDef {
+    Scalar,
+    Object {
+        fields: Vec<FieldDef>,
+        interfaces: Vec<String>,
+    },
+    Interface {
+        fields: Vec<FieldDef>,
+    },
+    Union {
+        members: Vec<String>,
+    },
+    Enum {
+        values: Vec<String>,
+    },
+    InputObject {
+        fields: Vec<InputFieldDef>,
+    },
+}
+
+impl TypeDef {
+    pub(crate) fn fields(&self) -> &[FieldDef] {
+        match self {
+            Self::Object { fields, .. } | Self::Interface { fields } => fields,
+            _ => &[],
+        }
+    }
+
+    pub(crate) fn is_abstract(&self) -> bool {
+        matches!(self, Self::Interface { .. } | Self::Union { .. })
+    }
+}
+
+/// Schema defined in the GraphQL Schema Language.
+#[derive(Debug)]
+pub(crate) struct Schema {
+    pub(crate) types: HashMap<String, TypeDef>,
+    pub(crate) query: String,
+    pub(crate) mutation: Option<String>,
+    pub(crate) subscription: Option<String>,
+}
+
+/// Names of the scalars built into every schema.
+const BUILTIN_SCALARS: &[&str] = &["Int", "Float", "String", "Boolean", "ID"];
+
+/// Name Juniper gives to the mutation type of schemas without mutations.
+const EMPTY_MUTATION: &str = "_EmptyMutation";
+
+/// Name Juniper gives to the subscription type of schemas without subscriptions.
+const EMPTY_SUBSCRIPTION: &str = "_EmptySubscription";
+
+impl Schema {
+    /// Parses the schema definition `sdl`, checking that all the types it refers to are defined.
+    pub(crate) fn parse(sdl: &str) -> Result<Self, String> {
+        let doc = ast::parse_schema::<String>(sdl).map_err(|e| e.to_string())?;
+
+        let mut types = HashMap::new();
+        let mut roots = (None, None, None);
+        for def in &doc.definitions {
+            let def = match def {
+                Definition::SchemaDefinition(s) => {
+                    roots = (s.query.clone(), s.mutation.clone(), s.subscription.clone());
+                    continue;
+                }
+                Definition::TypeDefinition(def) => def,
+                Definition::TypeExtension(_) | Definition::DirectiveDefinition(_) => continue,
+            };
+            let (name, ty) = match def {
+                TypeDefinition::Scalar(t) => (&t.name, TypeDef::Scalar),
+                TypeDefinition::Object(t) => (
+                    &t.name,
+                    TypeDef::Object {
+                        fields: t.fields.iter().map(field_def).collect(),
+                        interfaces: t.implements_interfaces.clone(),
+                    },
+                ),
+                TypeDefinition::Interface(t) => (
+                    &t.name,
+                    TypeDef::Interface {
+                        fields: t.fields.iter().map(field_def).collect(),
+                    },
+                ),
+                TypeDefinition::Union(t) => (
+                    &t.name,
+                    TypeDef::Union {
+                        members: t.types.clone(),
+                    },
+                ),
+                TypeDefinition::Enum(t) => (
+                    &t.name,
+                    TypeDef::Enum {
+                        values: t.values.iter().map(|v| v.name.clone()).collect(),
+                    },
+                ),
+                TypeDefinition::InputObject(t) => (
+                    &t.name,
+                    TypeDef::InputObject {
+                        fields: t.fields.iter().map(input_field_def).collect(),
+                    },
+                ),
+            };
+            if BUILTIN_SCALARS.contains(&name.as_str()) {
+                continue;
+            }
+            if types.insert(name.clone(), ty).is_some() {
+                return Err(format!("Type `{}` is defined more than once", name));
+            }
+        }
+
+        let root = |name: Option<String>, default: &str| {
+            name.or_else(|| Some(default.to_owned()).filter(|n| types.contains_key(n)))
+        };
+        let schema = Self {
+            query: root(roots.0, "Query").unwrap_or_else(|| "Query".to_owned()),
+            mutation: root(roots.1, "Mutation"),
+            subscription: root(roots.2, "Subscription"),
+            types,
+        };
+        schema.check()?;
+        Ok(schema)
+    }
+
+    /// Checks that all the referenced types are defined, and that the root types are objects.
+    fn check(&self) -> Result<(), String> {
+        let defined = |name: &str, by: &str| {
+            if BUILTIN_SCALARS.contains(&name) || self.types.contains_key(name) {
+                Ok(())
+            } else {
+                Err(format!("Type `{}` used by `{}` is not defined", name, by))
+            }
+        };
+        for (name, ty) in &self.types {
+            let refs: Vec<&str> = match ty {
+                TypeDef::Object { fields, interfaces } => interfaces
+                    .iter()
+                    .map(String::as_str)
+                    .chain(field_refs(fields))
+                    .collect(),
+                TypeDef::Interface { fields } => field_refs(fields).collect(),
+                TypeDef::Union { members } => members.iter().map(String::as_str).collect(),
+                TypeDef::InputObject { fields } => {
+                    fields.iter().map(|f| f.ty.innermost_name()).collect()
+                }
+                TypeDef::Scalar | TypeDef::Enum { .. } => vec![],
+            };
+            for r in refs {
+                defined(r, name)?;
+            }
+        }
+
+        let roots = Some(&self.query)
+            .into_iter()
+            .chain(&self.mutation)
+            .chain(&self.subscription);
+        for root in roots {
+            match self.types.get(root) {
+                Some(TypeDef::Object { .. }) => {}
+                Some(_) => return Err(format!("Root type `{}` is not an object", root)),
+                None => return Err(format!("Root type `{}` is not defined", root)),
+            }
+        }
+        Ok(())
+    }
+
+    /// Validates the `query` document against this schema with the validation rules of Juniper,
+    /// returning the messages of the failed rules.
+    pub(crate) fn validate(self: &Rc<Self>, query: &str) -> Result<(), Vec<String>> {
+        let info = |name: Option<&String>, empty: &str| SchemaInfo {
+            schema: self.clone(),
+            ty: TypeRef::non_null(name.map_or(empty, String::as_str)),
+        };
+        let schema = SchemaType::new::<SchemaNode, SchemaNode, SchemaNode>(
+            &info(Some(&self.query), ""),
+            &info(self.mutation.as_ref(), EMPTY_MUTATION),
+            &info(self.subscription.as_ref(), EMPTY_SUBSCRIPTION),
+        );
+
+        let document = parse_document_source(query, &schema).map_err(|e| {
+            vec![format!(
+                "{} at {}:{}",
+                e.item,
+                e.start.line() + 1,
+                e.start.column() + 1,
+            )]
+        })?;
+        let mut ctx = ValidatorContext::new(&schema, &document);
+        visit_all_rules(&mut ctx, &document);
+        let errors = ctx.into_errors();
+        if errors.is_empty() {
+            return Ok(());
+        }
+        Err(errors
+            .iter()
+            .map(|e| match e.locations().first() {
+                Some(pos) => format!("{} at {}:{}", e.message(), pos.line() + 1, pos.column() + 1),
+                None => e.message().to_owned(),
+            })
+            .collect())
+    }
+}
+
+/// Returns the names of the types the given `fields` and their arguments refer to.
+fn field_refs(fields: &[FieldDef]) -> impl Iterator<Item = &str> {
+    fields.iter().flat_map(|f| {
+        iter::once(f.ty.innermost_name()).chain(f.args.iter().map(|a| a.ty.innermost_name()))
+    })
+}
+
+fn field_def<'a>(field: &ast::Field<'a, String>) -> FieldDef {
+    FieldDef {
+        name: field.name.clone(),
+        args: field.arguments.iter().map(input_field_def).collect(),
+        ty: TypeRef::from_ast(&field.field_type),
+    }
+}
+
+fn input_field_def<'a>(value: &ast::InputValue<'a, String>) -> InputFieldDef {
+    InputFieldDef {
+        name: value.name.clone(),
+        ty: TypeRef::from_ast(&value.value_type),
+        default: value.default_value.as_ref().map(input_value),
+    }
+}
+
+fn input_value<'a>(value: &AstValue<'a, String>) -> InputValue {
+    match value {
+        AstValue::Variable(name) => InputValue::variable(name),
+        AstValue::Int(n) => n
+            .as_i64()
+            .and_then(|n| i32::try_from(n).ok())
+            .map_or(InputValue::Null, InputValue::scalar),
+        AstValue::Float(f) => InputValue::scalar(*f),
+        AstValue::String(s) => InputValue::scalar(s.clone()),
+        AstValue::Boolean(b) => InputValue::scalar(*b),
+        AstValue::Null => InputValue::Null,
+        AstValue::Enum(name) => InputValue::enum_value(name),
+        AstValue::List(items) => InputValue::list(items.iter().map(input_value).collect()),
+        AstValue::Object(fields) => InputValue::object(
+            fields
+                .iter()
+                .map(|(name, value)| (name.as_str(), input_value(value)))
+                .collect(),
+        ),
+    }
+}
+
+/// Node of a [`Schema`], registering the type it's referred to with into a Juniper [`Registry`].
+struct SchemaNode;
+
+/// Type of a [`SchemaNode`].
+struct SchemaInfo {
+    schema: Rc<Schema>,
+    ty: TypeRef,
+}
+
+impl SchemaInfo {
+    fn of(&self, ty: TypeRef) -> Self {
+        Self {
+            schema: self.schema.clone(),
+            ty,
+        }
+    }
+}
+
+impl GraphQLValue for SchemaNode {
+    type Context = ();
+    type TypeInfo = SchemaInfo;
+
+    fn type_name<'i>(&self, info: &'i Self::TypeInfo) -> Option<&'i str> {
+        <Self as GraphQLType>::name(info)
+    }
+}
+
+impl GraphQLType for SchemaNode {
+    fn name(info: &Self::TypeInfo) -> Option<&str> {
+        match &info.ty {
+            TypeRef::NonNull(of) => match &**of {
+                TypeRef::Named(name) => Some(name),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn meta<'r>(info: &Self::TypeInfo, registry: &mut Registry<'r>) -> MetaType<'r>
+    where
+        DefaultScalarValue: 'r,
+    {
+        let name = match &info.ty {
+            TypeRef::NonNull(of) => match &**of {
+                TypeRef::Named(name) => name,
+                TypeRef::List(item) => {
+                    return registry
+                        .build_list_type::<Self>(&info.of((**item).clone()))
+                        .into_meta()
+                }
+                TypeRef::NonNull(_) => unreachable!("Non-null type wrapping another one"),
+            },
+            nullable => {
+                return registry
+                    .build_nullable_type::<Self>(
+                        &info.of(TypeRef::NonNull(Box::new(nullable.clone()))),
+                    )
+                    .into_meta()
+            }
+        };
+
+        if *name == info.schema.query {
+            // Literals are parsed into the built-in scalars, even if the schema doesn't use them.
+            registry.get_type::<i32>(&());
+            registry.get_type::<f64>(&());
+            registry.get_type::<String>(&());
+            registry.get_type::<bool>(&());
+            registry.get_type::<ID>(&());
+        }
+
+        match name.as_str() {
+            "Int" => return <i32 as GraphQLType>::meta(&(), registry),
+            "Float" => return <f64 as GraphQLType>::meta(&(), registry),
+            "String" => return <String as GraphQLType>::meta(&(), registry),
+            "Boolean" => return <bool as GraphQLType>::meta(&(), registry),
+            "ID" => return <ID as GraphQLType>::meta(&(), registry),
+            _ => {}
+        }
+
+        match info.schema.types.get(name) {
+            Some(TypeDef::Scalar) => registry.build_scalar_type::<Self>(info).into_meta(),
+            Some(TypeDef::Object {
+                fields: defs,
+                interfaces,
+            }) => {
+                let fields = fields(defs, info, registry);
+                let interfaces = interfaces
+                    .iter()
+                    .map(|name| registry.get_type::<Self>(&info.of(TypeRef::non_null(name))))
+                    .collect::<Vec<_>>();
+                registry
+                    .build_object_type::<Self>(info, &fields)
+                    .interfaces(&interfaces)
+                    .into_meta()
+            }
+            Some(TypeDef::Interface { fields: defs }) => {
+                let fields = fields(defs, info, registry);
+                registry
+                    .build_interface_type::<Self>(info, &fields)
+                    .into_meta()
+            }
+            Some(TypeDef::Union { members }) => {
+                let members = members
+                    .iter()
+                    .map(|name| registry.get_type::<Self>(&info.of(TypeRef::non_null(name))))
+                    .collect::<Vec<_>>();
+                registry
+                    .build_union_type::<Self>(info, &members)
+                    .into_meta()
+            }
+            Some(TypeDef::Enum { values }) => {
+                let values = values.iter().map(|v| EnumValue::new(v)).collect::<Vec<_>>();
+                registry.build_enum_type::<Self>(info, &values).into_meta()
+            }
+            Some(TypeDef::InputObject { fields: defs }) => {
+                let args = defs
+                    .iter()
+                    .map(|def| argument(def, info, registry))
+                    .collect::<Vec<_>>();
+                registry
+                    .build_input_object_type::<Self>(info, &args)
+                    .into_meta()
+            }
+            // Mutation or subscription type of a schema without mutations or subscriptions.
+            None => registry.build_object_type::<Self>(info, &[]).into_meta(),
+        }
+    }
+}
+
+fn fields<'r>(
+    defs: &[FieldDef],
+    info: &SchemaInfo,
+    registry: &mut Registry<'r>,
+) -> Vec<Field<'r, DefaultScalarValue>> {
+    defs.iter()
+        .map(|def| {
+            let field = registry.field::<SchemaNode>(&def.name, &info.of(def.ty.clone()));
+            def.args.iter().fold(field, |field, arg| {
+                field.argument(argument(arg, info, registry))
+            })
+        })
+        .collect()
+}
+
+fn argument<'r>(
+    def: &InputFieldDef,
+    info: &SchemaInfo,
+    registry: &mut Registry<'r>,
+) -> Argument<'r, DefaultScalarValue> {
+    let arg = registry.arg::<SchemaNode>(&def.name, &info.of(def.ty.clone()));
+    match &def.default {
+        Some(default) => arg.default_value(default.clone()),
+        None => arg,
+    }
+}
+
+/// Values of custom scalars, enums and input objects are checked by the validation rules against
+/// the schema only.
+impl FromInputValue for SchemaNode {
+    fn from_input_value(_: &InputValue) -> Option<Self> {
+        Some(Self)
+    }
+}
+
+impl ParseScalarValue for SchemaNode {
+    fn from_str(value: ScalarToken<'_>) -> ParseScalarResult<'_, DefaultScalarValue> {
+        <String as ParseScalarValue>::from_str(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::Schema;
+
+    const SDL: &str = r#"
+        type Query {
+            hero(episode: Episode): Character!
+            search(text: String!): [SearchResult!]!
+        }
+
+        type Mutation {
+            rate(episode: Episode!, stars: Int = 5): Boolean
+        }
+
+        enum Episode { NEW_HOPE EMPIRE JEDI }
+
+        interface Character { name: String! }
+
+        type Human implements Character { name: String! homePlanet: String }
+
+        type Droid implements Character { name: String! primaryFunction: String }
+
+        union SearchResult = Human | Droid
+    "#;
+
+    fn schema() -> Rc<Schema> {
+        Rc::new(Schema::parse(SDL).unwrap())
+    }
+
+    #[test]
+    fn accepts_valid_queries() {
+        let schema = schema();
+        assert_eq!(schema.mutation.as_deref(), Some("Mutation"));
+        assert_eq!(schema.subscription, None);
+
+        schema
+            .validate(
+                "query Hero($e: Episode) { \
+                     hero(episode: $e) { name ... on Human { homePlanet } } \
+                     search(text: \"r\") { __typename ... on Droid { primaryFunction } } \
+                 }",
+            )
+            .unwrap();
+        schema.validate("mutation { rate(episode: JEDI) }").unwrap();
+    }
+
+    #[test]
+    fn rejects_queries_violating_rules() {
+        let errors = schema()
+            .validate("{ hero(episode: FIFTH) { name nickname } search { name } }")
+            .unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![
+                "Invalid value for argument \"episode\", expected type \"Episode\" at 1:17",
+                "Unknown field \"nickname\" on type \"Character\" at 1:31",
+                "Field \"search\" argument \"text\" of type \"String!\" is required but not \
+                 provided at 1:42",
+                "Unknown field \"name\" on type \"SearchResult\" at 1:51",
+            ],
+        );
+    }
+
+    #[test]
+    fn rejects_undefined_types() {
+        assert_eq!(
+            Schema::parse("type Query { hero: Hero }").unwrap_err(),
+            "Type `Hero` used by `Query` is not defined",
+        );
+        assert_eq!(
+            Schema::parse("type Mutation { rate: Int }").unwrap_err(),
+            "Root type `Query` is not defined",
+        );
+    }
+}
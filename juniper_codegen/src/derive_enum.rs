@@ -1,6 +1,6 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{ext::IdentExt, spanned::Spanned, Data, Fields};
+use syn::{ext::IdentExt, parse_quote, spanned::Spanned, Data, Fields};
 
 use crate::{
     result::{GraphQLScope, UnsupportedAttribute},
@@ -10,10 +10,6 @@ use crate::{
 pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<TokenStream> {
     let ast_span = ast.span();
 
-    if !ast.generics.params.is_empty() {
-        return Err(error.custom_error(ast_span, "does not support generics or lifetimes"));
-    }
-
     let variants = match ast.data {
         Data::Enum(enum_data) => enum_data.variants,
         _ => return Err(error.custom_error(ast_span, "can only be applied to enums")),
@@ -28,6 +24,8 @@ pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<Toke
         .map(SpanContainer::into_inner)
         .unwrap_or_else(|| ident.unraw().to_string());
 
+    let mut skipped = Vec::new();
+
     let fields = variants
         .into_iter()
         .filter_map(|field| {
@@ -55,24 +53,51 @@ pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<Toke
                         .apply(&field_name.unraw().to_string())
                 });
 
-            let resolver_code = quote!( #ident::#field_name );
-
-            let _type = match field.fields {
-                Fields::Unit => syn::parse_str(&field_name.to_string()).unwrap(),
-                _ => {
-                    error.emit_custom(
-                        field.fields.span(),
-                        "all fields of the enum must be unnamed, e.g., None",
-                    );
-                    return None;
-                }
+            // Data of variants is ignored, so they're matched regardless of it.
+            let resolver_code = match field.fields {
+                Fields::Unit => quote!( #ident::#field_name ),
+                _ => quote!( #ident::#field_name { .. } ),
             };
 
-            if let Some(skip) = field_attrs.skip {
-                error.unsupported_attribute(skip.span(), UnsupportedAttribute::Skip);
+            if field_attrs.skip.is_some() {
+                skipped.push(resolver_code);
                 return None;
             }
 
+            let _type = syn::parse_str(&field_name.to_string()).unwrap();
+
+            // Variants carrying data are constructed from the `default` expression, if any, or
+            // from the `Default` values of their fields otherwise.
+            let default = match &field.fields {
+                Fields::Unit => {
+                    if let Some(default) = &field_attrs.default {
+                        error.unsupported_attribute_within(
+                            default.span_ident(),
+                            UnsupportedAttribute::Default,
+                        );
+                    }
+                    None
+                }
+                Fields::Named(fields) => {
+                    let names = fields.named.iter().map(|f| &f.ident);
+                    Some(quote!(#ident::#field_name {
+                        #( #names: ::std::default::Default::default() ),*
+                    }))
+                }
+                Fields::Unnamed(fields) => {
+                    let values = fields
+                        .unnamed
+                        .iter()
+                        .map(|_| quote!(::std::default::Default::default()));
+                    Some(quote!(#ident::#field_name( #( #values ),* )))
+                }
+            };
+            let default = field_attrs
+                .default
+                .and_then(SpanContainer::into_inner)
+                .map(|expr| quote!(#expr))
+                .or(default);
+
             if name.starts_with("__") {
                 error.no_double_underscore(if let Some(name) = field_attrs.name {
                     name.span_ident()
@@ -81,10 +106,14 @@ pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<Toke
                 });
             }
 
-            if let Some(default) = field_attrs.default {
+            if let Some(guard) = field_attrs.guard {
+                error.unsupported_attribute_within(guard.span_ident(), UnsupportedAttribute::Guard);
+            }
+
+            if let Some(cache_control) = field_attrs.cache_control {
                 error.unsupported_attribute_within(
-                    default.span_ident(),
-                    UnsupportedAttribute::Default,
+                    cache_control.span_ident(),
+                    UnsupportedAttribute::CacheControl,
                 );
             }
 
@@ -97,7 +126,10 @@ pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<Toke
                 resolver_code,
                 is_type_inferred: true,
                 is_async: false,
-                default: None,
+                default,
+                guard: None,
+                visible: field_attrs.visible.map(SpanContainer::into_inner),
+                cache_control: None,
                 span,
             })
         })
@@ -120,6 +152,13 @@ pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<Toke
         });
     }
 
+    if let Some(cache_control) = &attrs.cache_control {
+        error.unsupported_attribute(
+            cache_control.span_ident(),
+            UnsupportedAttribute::CacheControl,
+        );
+    }
+
     if let Some(scalar) = attrs.scalar {
         error.unsupported_attribute(scalar.span_ident(), UnsupportedAttribute::Scalar);
     }
@@ -134,20 +173,23 @@ pub fn impl_enum(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<Toke
 
     proc_macro_error::abort_if_dirty();
 
+    let (_, ty_generics, _) = ast.generics.split_for_impl();
     let definition = util::GraphQLTypeDefiniton {
         name,
-        _type: syn::parse_str(&ast.ident.to_string()).unwrap(),
+        _type: parse_quote!(#ident #ty_generics),
         context: attrs.context.map(SpanContainer::into_inner),
         scalar: None,
         description: attrs.description.map(SpanContainer::into_inner),
+        visible: attrs.visible.map(SpanContainer::into_inner),
+        cache_control: None,
         fields,
-        // NOTICE: only unit variants allow -> no generics possible
-        generics: syn::Generics::default(),
+        generics: ast.generics.clone(),
         interfaces: vec![],
         include_type_generics: true,
         generic_scalar: true,
         no_async: attrs.no_async.is_some(),
+        complex: false,
     };
 
-    Ok(definition.into_enum_tokens())
+    Ok(definition.into_enum_tokens(skipped))
 }
@@ -56,10 +56,22 @@ pub fn impl_input_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Res
                     .apply(&field_ident.unraw().to_string()),
             };
 
-            if let Some(span) = field_attrs.skip {
+            if let Some(span) = field_attrs
+                .skip
+                .as_ref()
+                .or_else(|| field_attrs.skip_input.as_ref())
+                .or_else(|| field_attrs.skip_output.as_ref())
+            {
                 error.unsupported_attribute_within(span.span(), UnsupportedAttribute::Skip)
             }
 
+            if let Some(input_type) = &field_attrs.input_type {
+                error.unsupported_attribute_within(
+                    input_type.span_ident(),
+                    UnsupportedAttribute::InputObject,
+                )
+            }
+
             if let Some(span) = field_attrs.deprecation {
                 error.unsupported_attribute_within(
                     span.span_ident(),
@@ -75,6 +87,17 @@ pub fn impl_input_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Res
                 });
             }
 
+            if let Some(guard) = field_attrs.guard {
+                error.unsupported_attribute_within(guard.span_ident(), UnsupportedAttribute::Guard);
+            }
+
+            if let Some(cache_control) = field_attrs.cache_control {
+                error.unsupported_attribute_within(
+                    cache_control.span_ident(),
+                    UnsupportedAttribute::CacheControl,
+                );
+            }
+
             let resolver_code = quote!(#field_ident);
 
             let default = field_attrs
@@ -94,6 +117,9 @@ pub fn impl_input_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Res
                 is_type_inferred: true,
                 is_async: false,
                 default,
+                guard: None,
+                visible: field_attrs.visible.map(SpanContainer::into_inner),
+                cache_control: None,
                 span,
             })
         })
@@ -117,12 +143,27 @@ pub fn impl_input_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Res
         });
     }
 
+    if let Some(input_object) = &attrs.input_object {
+        error.unsupported_attribute(input_object.span_ident(), UnsupportedAttribute::InputObject);
+    }
+
+    if let Some(cache_control) = &attrs.cache_control {
+        error.unsupported_attribute(
+            cache_control.span_ident(),
+            UnsupportedAttribute::CacheControl,
+        );
+    }
+
     if let Some(duplicates) =
         crate::util::duplicate::Duplicate::find_by_key(&fields, |field| field.name.as_str())
     {
         error.duplicate(duplicates.iter());
     }
 
+    if let Some(name) = &attrs.name {
+        util::check_name_template(name, &ast.generics, &error);
+    }
+
     if !attrs.is_internal && name.starts_with("__") {
         error.no_double_underscore(if let Some(name) = attrs.name {
             name.span_ident()
@@ -139,12 +180,15 @@ pub fn impl_input_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Res
         context: attrs.context.map(SpanContainer::into_inner),
         scalar: attrs.scalar.map(SpanContainer::into_inner),
         description: attrs.description.map(SpanContainer::into_inner),
+        visible: attrs.visible.map(SpanContainer::into_inner),
+        cache_control: None,
         fields,
         generics: ast.generics,
         interfaces: vec![],
         include_type_generics: true,
         generic_scalar: true,
         no_async: attrs.no_async.is_some(),
+        complex: false,
     };
 
     Ok(definition.into_input_object_tokens())
@@ -0,0 +1,314 @@
+use crate::{
+    result::GraphQLScope,
+    util::{self, span_container::SpanContainer, CacheControlAttr},
+};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{ext::IdentExt, parse_quote, spanned::Spanned, Data, Fields};
+
+/// Generate code for the `juniper::GraphQLMergedObject` derive.
+pub fn build_derive_merged_object(
+    ast: syn::DeriveInput,
+    error: GraphQLScope,
+) -> syn::Result<TokenStream> {
+    let ast_span = ast.span();
+    let parts = match ast.data {
+        Data::Struct(data) => match data.fields {
+            Fields::Named(fields) => fields.named,
+            Fields::Unnamed(fields) => fields.unnamed,
+            Fields::Unit => return Err(error.custom_error(ast_span, "expects objects to merge")),
+        },
+        _ => return Err(error.custom_error(ast_span, "can only be applied to structs")),
+    };
+
+    let attrs = util::ObjectAttributes::from_attrs(&ast.attrs)?;
+
+    // Fields come from the merged objects, so attributes shaping them are meaningless here.
+    let unsupported = attrs
+        .interfaces
+        .iter()
+        .map(SpanContainer::span_ident)
+        .chain(attrs.no_async.as_ref().map(SpanContainer::span_ident))
+        .chain(attrs.complex.as_ref().map(SpanContainer::span_ident));
+    for span in unsupported {
+        error.emit_custom(span, "attribute is not supported on merged objects");
+    }
+    if attrs.rename.is_some() {
+        error.emit_custom(
+            ast_span,
+            "`rename` is not supported on merged objects, set it on the merged objects instead",
+        );
+    }
+
+    let ident = &ast.ident;
+    let name = attrs
+        .name
+        .clone()
+        .map(SpanContainer::into_inner)
+        .unwrap_or_else(|| ident.unraw().to_string());
+
+    if !attrs.is_internal && name.starts_with("__") {
+        error.no_double_underscore(if let Some(name) = attrs.name {
+            name.span_ident()
+        } else {
+            ident.span()
+        });
+    }
+
+    if parts.is_empty() {
+        error.not_empty(ast_span);
+    }
+
+    proc_macro_error::abort_if_dirty();
+
+    let parts = parts
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let accessor = field
+                .ident
+                .as_ref()
+                .map(|ident| quote!(#ident))
+                .unwrap_or_else(|| {
+                    let index = syn::Index::from(i);
+                    quote!(#index)
+                });
+            (accessor, &field.ty)
+        })
+        .collect::<Vec<_>>();
+
+    let context = attrs
+        .context
+        .map(SpanContainer::into_inner)
+        .map(|ctx| quote!(#ctx))
+        .unwrap_or_else(|| quote!(()));
+    let scalar = attrs
+        .scalar
+        .clone()
+        .map(SpanContainer::into_inner)
+        .map(|scalar| quote!(#scalar))
+        .unwrap_or_else(|| quote!(__S));
+    // Overrides the descriptions of the merged objects, so is applied after merging them.
+    let description = attrs
+        .description
+        .map(SpanContainer::into_inner)
+        .map(|description| quote!( .description(#description) ));
+    let visible = attrs
+        .visible
+        .map(SpanContainer::into_inner)
+        .map(|pred| quote!( .visible_if(#pred) ));
+    let cache_control = attrs
+        .cache_control
+        .map(SpanContainer::into_inner)
+        .as_ref()
+        .map(CacheControlAttr::meta_tokens);
+
+    let mut generics = ast.generics.clone();
+    let (_, ty_generics, _) = ast.generics.split_for_impl();
+    if attrs.scalar.is_none() {
+        generics.params.push(parse_quote!(__S));
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(__S: ::juniper::ScalarValue));
+    }
+    // All the merged objects are resolved with the context and type info of the merged object.
+    let (_, first_ty) = &parts[0];
+    let type_info = quote!( <#first_ty as ::juniper::GraphQLValue<#scalar>>::TypeInfo );
+    generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!( #first_ty: ::juniper::GraphQLValue<#scalar, Context = #context> ));
+    for (_, ty) in &parts[1..] {
+        generics.make_where_clause().predicates.push(parse_quote! {
+            #ty: ::juniper::GraphQLValue<#scalar, Context = #context, TypeInfo = #type_info>
+        });
+    }
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    let mut where_async = where_clause.cloned().unwrap_or_else(|| parse_quote!(where));
+    where_async
+        .predicates
+        .push(parse_quote!( #scalar: Send + Sync ));
+    where_async.predicates.push(parse_quote!(Self: Sync));
+    where_async
+        .predicates
+        .push(parse_quote!( #type_info: Sync ));
+
+    let part_tys = parts.iter().map(|(_, ty)| ty).collect::<Vec<_>>();
+    let part_metas = parts.iter().map(|(_, ty)| {
+        quote! {
+            match <#ty as ::juniper::GraphQLType<#scalar>>::meta(info, registry) {
+                ::juniper::meta::MetaType::Object(meta) => meta,
+                _ => panic!(
+                    "Merged type {:?} is not a GraphQL object",
+                    <#ty as ::juniper::GraphQLType<#scalar>>::name(info),
+                ),
+            }
+        }
+    });
+    let no_interfaces = part_tys.iter().map(|ty| {
+        let msg = format!(
+            "`{}` implements GraphQL interfaces, so can't be merged into `{}`",
+            quote!(#ty).to_string().replace(' ', ""),
+            ident,
+        );
+        quote! {
+            if <#ty as ::juniper::ObjectFieldNames<#scalar>>::IMPLEMENTS_INTERFACES {
+                panic!("{}", #msg);
+            }
+        }
+    });
+    let resolve_parts = parts.iter().map(|(accessor, ty)| {
+        quote! {
+            if <#ty as ::juniper::ObjectFieldNames<#scalar>>::FIELD_NAMES.contains(field) {
+                return <#ty as ::juniper::GraphQLValue<#scalar>>::resolve_field(
+                    &self.#accessor, info, field, args, executor,
+                );
+            }
+        }
+    });
+    let resolve_parts_async = parts.iter().map(|(accessor, ty)| {
+        quote! {
+            if <#ty as ::juniper::ObjectFieldNames<#scalar>>::FIELD_NAMES.contains(field) {
+                return <#ty as ::juniper::GraphQLValueAsync<#scalar>>::resolve_field_async(
+                    &self.#accessor, info, field, args, executor,
+                );
+            }
+        }
+    });
+
+    // Generic structs can only be checked once the merged object is used, so non-generic ones
+    // are checked eagerly, as even `cargo check` evaluates free constants.
+    let eager_check = if ast.generics.params.is_empty() {
+        let scalar = attrs
+            .scalar
+            .map(SpanContainer::into_inner)
+            .map(|scalar| quote!(#scalar))
+            .unwrap_or_else(|| quote!(::juniper::DefaultScalarValue));
+        Some(quote! {
+            const _: () = <#ident as ::juniper::ObjectFieldNames<#scalar>>::NO_DUPLICATE_FIELDS;
+        })
+    } else {
+        None
+    };
+
+    Ok(quote! {
+        #eager_check
+
+        #[automatically_derived]
+        impl#impl_generics ::juniper::marker::IsOutputType<#scalar> for #ident #ty_generics
+            #where_clause
+        {
+            fn mark() {
+                #( <#part_tys as ::juniper::marker::IsOutputType<#scalar>>::mark(); )*
+            }
+        }
+
+        #[automatically_derived]
+        impl#impl_generics ::juniper::marker::GraphQLObjectType<#scalar> for #ident #ty_generics
+            #where_clause
+        {}
+
+        #[automatically_derived]
+        impl#impl_generics ::juniper::ObjectFieldNames<#scalar> for #ident #ty_generics
+            #where_clause
+        {
+            const FIELD_NAMES: ::juniper::FieldNames = {
+                #( #no_interfaces )*
+                ::juniper::FieldNames::Merged(&[
+                    #( <#part_tys as ::juniper::ObjectFieldNames<#scalar>>::FIELD_NAMES ),*
+                ])
+            };
+        }
+
+        #[automatically_derived]
+        impl#impl_generics ::juniper::GraphQLType<#scalar> for #ident #ty_generics
+            #where_clause
+        {
+            fn name(_: &Self::TypeInfo) -> Option<&'static str> {
+                Some(#name)
+            }
+
+            fn meta<'r>(
+                info: &Self::TypeInfo,
+                registry: &mut ::juniper::Registry<'r, #scalar>,
+            ) -> ::juniper::meta::MetaType<'r, #scalar>
+            where
+                #scalar: 'r,
+            {
+                // Fails the compilation if the merged objects have fields in common or implement
+                // interfaces.
+                let () = <Self as ::juniper::ObjectFieldNames<#scalar>>::NO_DUPLICATE_FIELDS;
+
+                let parts = vec![ #( #part_metas ),* ];
+                let fields = parts
+                    .iter()
+                    .flat_map(|meta| meta.fields.iter())
+                    .filter(|f| &*f.name != "__typename")
+                    .cloned()
+                    .collect::<Vec<_>>();
+                let meta = registry
+                    .build_object_type::<Self>(info, &fields)
+                    #visible
+                    #cache_control;
+                parts
+                    .into_iter()
+                    .fold(meta, ::juniper::meta::ObjectMeta::merge_part)
+                    #description
+                    .into_meta()
+            }
+        }
+
+        #[automatically_derived]
+        impl#impl_generics ::juniper::GraphQLValue<#scalar> for #ident #ty_generics
+            #where_clause
+        {
+            type Context = #context;
+            type TypeInfo = #type_info;
+
+            fn type_name<'__i>(&self, info: &'__i Self::TypeInfo) -> Option<&'__i str> {
+                <Self as ::juniper::GraphQLType<#scalar>>::name(info)
+            }
+
+            fn resolve_field(
+                &self,
+                info: &Self::TypeInfo,
+                field: &str,
+                args: &::juniper::Arguments<#scalar>,
+                executor: &::juniper::Executor<Self::Context, #scalar>,
+            ) -> ::juniper::ExecutionResult<#scalar> {
+                #( #resolve_parts )*
+                panic!(
+                    "Field {} not found on type {:?}",
+                    field,
+                    <Self as ::juniper::GraphQLType<#scalar>>::name(info),
+                );
+            }
+
+            fn concrete_type_name(&self, _: &Self::Context, _: &Self::TypeInfo) -> String {
+                #name.to_string()
+            }
+        }
+
+        #[automatically_derived]
+        impl#impl_generics ::juniper::GraphQLValueAsync<#scalar> for #ident #ty_generics
+            #where_async
+        {
+            fn resolve_field_async<'b>(
+                &'b self,
+                info: &'b Self::TypeInfo,
+                field: &'b str,
+                args: &'b ::juniper::Arguments<#scalar>,
+                executor: &'b ::juniper::Executor<Self::Context, #scalar>,
+            ) -> ::juniper::BoxFuture<'b, ::juniper::ExecutionResult<#scalar>> {
+                #( #resolve_parts_async )*
+                panic!(
+                    "Field {} not found on type {:?}",
+                    field,
+                    <Self as ::juniper::GraphQLType<#scalar>>::name(info),
+                );
+            }
+        }
+    })
+}
@@ -1,11 +1,20 @@
+use std::collections::HashSet;
+
 use crate::{
     result::{GraphQLScope, UnsupportedAttribute},
     util::{self, span_container::SpanContainer, RenameRule},
 };
-use proc_macro2::TokenStream;
-use quote::quote;
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
 use syn::{self, ext::IdentExt, spanned::Spanned, Data, Fields};
 
+/// Field of the input object derived alongside a GraphQL object.
+struct InputField {
+    definition: util::GraphQLTypeDefinitionField,
+    declaration: TokenStream,
+    is_converted: bool,
+}
+
 pub fn build_derive_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::Result<TokenStream> {
     let ast_span = ast.span();
     let struct_fields = match ast.data {
@@ -26,6 +35,9 @@ pub fn build_derive_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::R
         .map(SpanContainer::into_inner)
         .unwrap_or_else(|| ident.unraw().to_string());
 
+    let mut input_fields = Vec::new();
+    let mut has_skipped_input = false;
+
     let fields = struct_fields
         .into_iter()
         .filter_map(|field| {
@@ -42,10 +54,11 @@ pub fn build_derive_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::R
             };
 
             if field_attrs.skip.is_some() {
+                has_skipped_input = true;
                 return None;
             }
 
-            let field_name = &field.ident.unwrap();
+            let field_name = field.ident.as_ref().unwrap();
             let name = field_attrs
                 .name
                 .clone()
@@ -58,18 +71,35 @@ pub fn build_derive_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::R
                 });
 
             if name.starts_with("__") {
-                error.no_double_underscore(if let Some(name) = field_attrs.name {
+                error.no_double_underscore(if let Some(name) = &field_attrs.name {
                     name.span_ident()
                 } else {
                     field_name.span()
                 });
             }
 
-            if let Some(default) = field_attrs.default {
-                error.unsupported_attribute_within(
-                    default.span_ident(),
-                    UnsupportedAttribute::Default,
-                );
+            if attrs.input_object.is_none() {
+                if let Some(default) = &field_attrs.default {
+                    error.unsupported_attribute_within(
+                        default.span_ident(),
+                        UnsupportedAttribute::Default,
+                    );
+                }
+                let input_attrs = field_attrs.skip_input.as_ref().map(|a| a.span_ident());
+                let input_attrs = input_attrs
+                    .or_else(|| field_attrs.skip_output.as_ref().map(|a| a.span_ident()))
+                    .or_else(|| field_attrs.input_type.as_ref().map(|a| a.span_ident()));
+                if let Some(span) = input_attrs {
+                    error.emit_custom(span, "requires the `input_object` attribute on the struct");
+                }
+            } else if field_attrs.skip_input.is_some() {
+                has_skipped_input = true;
+            } else {
+                input_fields.push(input_field(&field, &field_attrs, &name));
+            }
+
+            if field_attrs.skip_output.is_some() {
+                return None;
             }
 
             let resolver_code = quote!(
@@ -84,6 +114,9 @@ pub fn build_derive_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::R
                 deprecation: field_attrs.deprecation.map(SpanContainer::into_inner),
                 resolver_code,
                 default: None,
+                guard: field_attrs.guard.map(SpanContainer::into_inner),
+                visible: field_attrs.visible.map(SpanContainer::into_inner),
+                cache_control: field_attrs.cache_control.map(SpanContainer::into_inner),
                 is_type_inferred: true,
                 is_async: false,
                 span,
@@ -100,27 +133,68 @@ pub fn build_derive_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::R
         error.duplicate(duplicates.iter());
     }
 
+    if let Some(name) = &attrs.name {
+        util::check_name_template(name, &ast.generics, &error);
+    }
+
     if !attrs.is_internal && name.starts_with("__") {
-        error.no_double_underscore(if let Some(name) = attrs.name {
+        error.no_double_underscore(if let Some(name) = &attrs.name {
             name.span_ident()
         } else {
             ident.span()
         });
     }
 
-    if fields.is_empty() {
+    if fields.is_empty() && attrs.complex.is_none() {
         error.not_empty(ast_span);
     }
 
+    let input_generics = used_generics(
+        &ast.generics,
+        input_fields.iter().map(|field| &field.definition._type),
+    );
+    let input_name = match &attrs.input_object {
+        Some(input_object) => {
+            if input_fields.is_empty() {
+                GraphQLScope::DeriveInputObject.not_empty(input_object.span_ident());
+            }
+            let input_name = match input_object.as_ref() {
+                Some(input_name) => input_name.clone(),
+                None => format!("{}Input", name),
+            };
+            let input_name = SpanContainer::new(input_object.span_ident(), None, input_name);
+            util::check_name_template(&input_name, &input_generics, &error);
+            Some(input_name.into_inner())
+        }
+        None => None,
+    };
+
     // Early abort after GraphQL properties
     proc_macro_error::abort_if_dirty();
 
+    let input_object = match input_name {
+        Some(input_name) => Some(build_input_object(
+            ident,
+            &ast.vis,
+            &ast.attrs,
+            &ast.generics,
+            &input_generics,
+            &attrs,
+            input_name,
+            input_fields,
+            has_skipped_input,
+        )),
+        None => None,
+    };
+
     let definition = util::GraphQLTypeDefiniton {
         name,
         _type: syn::parse_str(&ast.ident.to_string()).unwrap(),
         context: attrs.context.map(SpanContainer::into_inner),
         scalar: attrs.scalar.map(SpanContainer::into_inner),
         description: attrs.description.map(SpanContainer::into_inner),
+        visible: attrs.visible.map(SpanContainer::into_inner),
+        cache_control: attrs.cache_control.map(SpanContainer::into_inner),
         fields,
         generics: ast.generics,
         interfaces: attrs
@@ -131,7 +205,183 @@ pub fn build_derive_object(ast: syn::DeriveInput, error: GraphQLScope) -> syn::R
         include_type_generics: true,
         generic_scalar: true,
         no_async: attrs.no_async.is_some(),
+        complex: attrs.complex.is_some(),
     };
 
-    Ok(definition.into_tokens())
+    let output_tokens = definition.into_tokens();
+    Ok(quote! {
+        #output_tokens
+        #input_object
+    })
+}
+
+/// Returns the field of the input object derived alongside a GraphQL object for the given struct
+/// `field`.
+fn input_field(field: &syn::Field, attrs: &util::FieldAttributes, name: &str) -> InputField {
+    let ident = field.ident.as_ref().unwrap();
+    let ty = attrs
+        .input_type
+        .as_ref()
+        .map(|ty| ty.as_ref().clone())
+        .unwrap_or_else(|| field.ty.clone());
+    let docs = field.attrs.iter().filter(|attr| attr.path.is_ident("doc"));
+    let vis = &field.vis;
+
+    InputField {
+        declaration: quote! {
+            #( #docs )*
+            #vis #ident: #ty
+        },
+        definition: util::GraphQLTypeDefinitionField {
+            name: name.to_owned(),
+            _type: ty,
+            args: Vec::new(),
+            description: attrs.description.clone().map(SpanContainer::into_inner),
+            deprecation: None,
+            resolver_code: quote!(#ident),
+            default: attrs
+                .default
+                .clone()
+                .map(|default| match default.into_inner() {
+                    Some(expr) => expr.into_token_stream(),
+                    None => quote!(Default::default()),
+                }),
+            guard: None,
+            visible: attrs.visible.clone().map(SpanContainer::into_inner),
+            cache_control: None,
+            is_type_inferred: true,
+            is_async: false,
+            span: field.span(),
+        },
+        is_converted: attrs.input_type.is_some(),
+    }
+}
+
+/// Generates the input object derived alongside the GraphQL object of the given struct, along
+/// with its conversion into the struct, if it has all of its fields.
+fn build_input_object(
+    ident: &syn::Ident,
+    vis: &syn::Visibility,
+    struct_attrs: &[syn::Attribute],
+    generics: &syn::Generics,
+    input_generics: &syn::Generics,
+    attrs: &util::ObjectAttributes,
+    name: String,
+    fields: Vec<InputField>,
+    has_skipped_field: bool,
+) -> TokenStream {
+    let input_ident = format_ident!("{}Input", ident);
+    let docs = struct_attrs.iter().filter(|attr| attr.path.is_ident("doc"));
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+    let (_, input_type_generics, input_where_clause) = input_generics.split_for_impl();
+    let params = &input_generics.params;
+
+    let from_input = if has_skipped_field || fields.iter().any(|field| field.is_converted) {
+        None
+    } else {
+        let field_idents = fields.iter().map(|field| &field.definition.resolver_code);
+        Some(quote! {
+            #[automatically_derived]
+            impl#impl_generics ::std::convert::From<#input_ident #input_type_generics>
+                for #ident #type_generics
+                #where_clause
+            {
+                fn from(input: #input_ident #input_type_generics) -> Self {
+                    Self { #( #field_idents: input.#field_idents, )* }
+                }
+            }
+        })
+    };
+
+    let (declarations, fields): (Vec<_>, Vec<_>) = fields
+        .into_iter()
+        .map(|field| (field.declaration, field.definition))
+        .unzip();
+
+    let definition = util::GraphQLTypeDefiniton {
+        name,
+        _type: syn::parse_str(&input_ident.to_string()).unwrap(),
+        context: attrs.context.clone().map(SpanContainer::into_inner),
+        scalar: attrs.scalar.clone().map(SpanContainer::into_inner),
+        description: attrs.description.clone().map(SpanContainer::into_inner),
+        visible: attrs.visible.clone().map(SpanContainer::into_inner),
+        cache_control: None,
+        fields,
+        generics: input_generics.clone(),
+        interfaces: vec![],
+        include_type_generics: true,
+        generic_scalar: true,
+        no_async: attrs.no_async.is_some(),
+        complex: false,
+    };
+    let input_tokens = definition.into_input_object_tokens();
+
+    quote! {
+        #( #docs )*
+        #vis struct #input_ident<#params> #input_where_clause {
+            #( #declarations, )*
+        }
+
+        #input_tokens
+
+        #from_input
+    }
+}
+
+/// Returns the parameters of the given `generics` used by the given field `types`, along with the
+/// where predicates bounding only them, as the input object derived alongside a GraphQL object
+/// can't declare parameters used only by its skipped fields.
+fn used_generics<'a>(
+    generics: &syn::Generics,
+    types: impl IntoIterator<Item = &'a syn::Type>,
+) -> syn::Generics {
+    fn collect_idents(tokens: TokenStream, idents: &mut HashSet<String>) {
+        for token in tokens {
+            match token {
+                TokenTree::Ident(ident) => {
+                    idents.insert(ident.unraw().to_string());
+                }
+                TokenTree::Group(group) => collect_idents(group.stream(), idents),
+                TokenTree::Punct(_) | TokenTree::Literal(_) => {}
+            }
+        }
+    }
+
+    let param_ident = |param: &syn::GenericParam| match param {
+        syn::GenericParam::Type(ty) => ty.ident.unraw().to_string(),
+        syn::GenericParam::Lifetime(lt) => lt.lifetime.ident.unraw().to_string(),
+        syn::GenericParam::Const(c) => c.ident.unraw().to_string(),
+    };
+
+    let mut used = HashSet::new();
+    for ty in types {
+        collect_idents(ty.to_token_stream(), &mut used);
+    }
+    let unused = generics
+        .params
+        .iter()
+        .map(param_ident)
+        .filter(|ident| !used.contains(ident))
+        .collect::<HashSet<_>>();
+
+    let mut input_generics = generics.clone();
+    input_generics.params = generics
+        .params
+        .iter()
+        .filter(|param| !unused.contains(&param_ident(param)))
+        .cloned()
+        .collect();
+    if let Some(where_clause) = &mut input_generics.where_clause {
+        where_clause.predicates = where_clause
+            .predicates
+            .iter()
+            .filter(|predicate| {
+                let mut idents = HashSet::new();
+                collect_idents(predicate.to_token_stream(), &mut idents);
+                idents.is_disjoint(&unused)
+            })
+            .cloned()
+            .collect();
+    }
+    input_generics
 }
@@ -3,14 +3,15 @@ use crate::{
     result::GraphQLScope,
     util::{self, span_container::SpanContainer},
 };
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use quote::quote;
-use syn::{spanned::Spanned, token, Data, Fields, Ident, Variant};
+use syn::{ext::IdentExt, parse_quote, spanned::Spanned, token, Data, Fields, Ident, Variant};
 
 #[derive(Debug, Default)]
 struct TransparentAttributes {
-    transparent: Option<bool>,
-    name: Option<String>,
+    transparent: Option<Span>,
+    with_str: Option<Span>,
+    name: Option<SpanContainer<String>>,
     description: Option<String>,
     scalar: Option<syn::Type>,
 }
@@ -19,6 +20,7 @@ impl syn::parse::Parse for TransparentAttributes {
     fn parse(input: syn::parse::ParseStream) -> syn::parse::Result<Self> {
         let mut output = Self {
             transparent: None,
+            with_str: None,
             name: None,
             description: None,
             scalar: None,
@@ -30,7 +32,11 @@ impl syn::parse::Parse for TransparentAttributes {
                 "name" => {
                     input.parse::<token::Eq>()?;
                     let val = input.parse::<syn::LitStr>()?;
-                    output.name = Some(val.value());
+                    output.name = Some(SpanContainer::new(
+                        ident.span(),
+                        Some(val.span()),
+                        val.value(),
+                    ));
                 }
                 "description" => {
                     input.parse::<token::Eq>()?;
@@ -38,7 +44,10 @@ impl syn::parse::Parse for TransparentAttributes {
                     output.description = Some(val.value());
                 }
                 "transparent" => {
-                    output.transparent = Some(true);
+                    output.transparent = Some(ident.span());
+                }
+                "with_str" => {
+                    output.with_str = Some(ident.span());
                 }
                 "scalar" | "Scalar" => {
                     input.parse::<token::Eq>()?;
@@ -56,95 +65,214 @@ impl syn::parse::Parse for TransparentAttributes {
 
 impl TransparentAttributes {
     fn from_attrs(attrs: &[syn::Attribute]) -> syn::parse::Result<Self> {
-        match util::find_graphql_attr(attrs) {
-            Some(attr) => {
-                let mut parsed: TransparentAttributes = attr.parse_args()?;
-                if parsed.description.is_none() {
-                    parsed.description =
-                        util::get_doc_comment(attrs).map(SpanContainer::into_inner);
-                }
-                Ok(parsed)
-            }
-            None => Ok(Default::default()),
+        let mut parsed: TransparentAttributes = match util::find_graphql_attr(attrs) {
+            Some(attr) => attr.parse_args()?,
+            None => Default::default(),
+        };
+        if parsed.description.is_none() {
+            parsed.description = util::get_doc_comment(attrs).map(SpanContainer::into_inner);
         }
+        Ok(parsed)
     }
 }
 
+/// How a scalar is converted from and into the underlying GraphQL scalar.
+enum Mode<'a> {
+    /// Delegates to the single field of a newtype, being the default for structs or set
+    /// explicitly with the `transparent` attribute argument.
+    Transparent(&'a syn::Field),
+
+    /// Goes through a string, using the `Display` and `FromStr` implementations, as set with the
+    /// `with_str` attribute argument.
+    WithStr,
+}
+
+/// Generate code for the `juniper::GraphQLScalarValue` derive.
 pub fn impl_scalar_value(ast: &syn::DeriveInput, error: GraphQLScope) -> syn::Result<TokenStream> {
     let ident = &ast.ident;
 
     match ast.data {
         Data::Enum(ref enum_data) => impl_scalar_enum(ident, enum_data, error),
-        Data::Struct(ref struct_data) => impl_scalar_struct(ast, struct_data, error),
+        Data::Struct(_) => impl_scalar_type(ast, error),
         Data::Union(_) => Err(error.custom_error(ast.span(), "may not be applied to unions")),
     }
 }
 
-fn impl_scalar_struct(
-    ast: &syn::DeriveInput,
-    data: &syn::DataStruct,
-    error: GraphQLScope,
-) -> syn::Result<TokenStream> {
-    let field = match data.fields {
-        syn::Fields::Unnamed(ref fields) if fields.unnamed.len() == 1 => {
-            fields.unnamed.first().unwrap()
+/// Generate code for the `juniper::GraphQLScalar` derive.
+pub fn impl_scalar(ast: &syn::DeriveInput, error: GraphQLScope) -> syn::Result<TokenStream> {
+    impl_scalar_type(ast, error)
+}
+
+/// Generates the GraphQL scalar implementations of a transparent newtype, or of a struct or enum
+/// represented as a string with the `with_str` attribute argument.
+fn impl_scalar_type(ast: &syn::DeriveInput, error: GraphQLScope) -> syn::Result<TokenStream> {
+    let ast_span = ast.span();
+    let attrs = TransparentAttributes::from_attrs(&ast.attrs)?;
+
+    if !ast.generics.params.is_empty() {
+        return Err(error.custom_error(ast.generics.span(), "does not support generics"));
+    }
+
+    if let (Some(transparent), Some(_)) = (attrs.transparent, attrs.with_str) {
+        return Err(error.custom_error(
+            transparent,
+            "`transparent` and `with_str` are mutually exclusive",
+        ));
+    }
+
+    let mode = match (&ast.data, attrs.with_str) {
+        (Data::Union(_), _) => {
+            return Err(error.custom_error(ast_span, "may not be applied to unions"))
         }
-        _ => {
+        (_, Some(_)) => Mode::WithStr,
+        (Data::Struct(data), None) => match &data.fields {
+            Fields::Named(fields) if fields.named.len() == 1 => {
+                Mode::Transparent(fields.named.first().unwrap())
+            }
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                Mode::Transparent(fields.unnamed.first().unwrap())
+            }
+            fields => {
+                return Err(error.custom_error(
+                    fields.span(),
+                    "requires exact one field, e.g., Test(i32), or the `with_str` attribute",
+                ))
+            }
+        },
+        (Data::Enum(_), None) => {
             return Err(error.custom_error(
-                data.fields.span(),
-                "requires exact one field, e.g., Test(i32)",
+                ast_span,
+                "can only be applied to enums with the `with_str` attribute",
             ))
         }
     };
+
     let ident = &ast.ident;
-    let attrs = TransparentAttributes::from_attrs(&ast.attrs)?;
-    let inner_ty = &field.ty;
-    let name = attrs.name.unwrap_or_else(|| ident.to_string());
+    let name = attrs
+        .name
+        .clone()
+        .map(SpanContainer::into_inner)
+        .unwrap_or_else(|| ident.unraw().to_string());
+    if name.starts_with("__") {
+        error.no_double_underscore(if let Some(name) = attrs.name {
+            name.span_ident()
+        } else {
+            ident.span()
+        });
+    }
 
-    let description = match attrs.description {
-        Some(val) => quote!( .description( #val ) ),
-        None => quote!(),
-    };
+    proc_macro_error::abort_if_dirty();
+
+    let description = attrs
+        .description
+        .map(|description| quote!( .description(#description) ));
 
     let scalar = attrs
         .scalar
         .as_ref()
-        .map(|s| quote!( #s ))
+        .map(|scalar| quote!(#scalar))
         .unwrap_or_else(|| quote!(__S));
 
-    let impl_generics = attrs
-        .scalar
-        .as_ref()
-        .map(|_| quote!())
-        .unwrap_or_else(|| quote!(<__S>));
+    let mut generics = ast.generics.clone();
+    if attrs.scalar.is_none() {
+        generics.params.push(parse_quote!(__S));
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(__S: ::juniper::ScalarValue));
+    }
 
-    let _async = quote!(
-        impl#impl_generics ::juniper::GraphQLValueAsync<#scalar> for #ident
-        where
-            Self: Sync,
-            Self::TypeInfo: Sync,
-            Self::Context: Sync,
-            #scalar: ::juniper::ScalarValue + Send + Sync,
-        {
-            fn resolve_async<'a>(
-                &'a self,
-                info: &'a Self::TypeInfo,
-                selection_set: Option<&'a [::juniper::Selection<#scalar>]>,
-                executor: &'a ::juniper::Executor<Self::Context, #scalar>,
-            ) -> ::juniper::BoxFuture<'a, ::juniper::ExecutionResult<#scalar>> {
-                use ::juniper::futures::future;
-                let v = ::juniper::GraphQLValue::<#scalar>::resolve(self, info, selection_set, executor);
-                Box::pin(future::ready(v))
-            }
+    let (resolve, to_input_value, from_input_value, from_input_value_error, from_str) = match mode {
+        Mode::Transparent(field) => {
+            let inner_ty = &field.ty;
+            generics.make_where_clause().predicates.push(parse_quote! {
+                #inner_ty: ::juniper::GraphQLValue<#scalar, Context = (), TypeInfo = ()>
+                    + ::juniper::ToInputValue<#scalar>
+                    + ::juniper::FromInputValue<#scalar>
+                    + ::juniper::ParseScalarValue<#scalar>
+            });
+
+            let (accessor, constructor) = match &field.ident {
+                Some(field_ident) => (quote!(#field_ident), quote!(Self { #field_ident: inner })),
+                None => (quote!(0), quote!(Self(inner))),
+            };
+            (
+                quote! {
+                    ::juniper::GraphQLValue::<#scalar>::resolve(
+                        &self.#accessor, info, selection, executor,
+                    )
+                },
+                quote! {
+                    ::juniper::ToInputValue::<#scalar>::to_input_value(&self.#accessor)
+                },
+                quote! {
+                    let inner: #inner_ty = ::juniper::FromInputValue::<#scalar>::from_input_value(v)?;
+                    Some(#constructor)
+                },
+                quote! {
+                    <#inner_ty as ::juniper::FromInputValue<#scalar>>::from_input_value_error(v)
+                },
+                quote! {
+                    <#inner_ty as ::juniper::ParseScalarValue<#scalar>>::from_str(value)
+                },
+            )
         }
-    );
+        Mode::WithStr => (
+            quote! {
+                Ok(::juniper::Value::scalar(::std::string::ToString::to_string(self)))
+            },
+            quote! {
+                ::juniper::InputValue::scalar(::std::string::ToString::to_string(self))
+            },
+            quote! {
+                v.as_string_value()
+                    .and_then(|s| <Self as ::std::str::FromStr>::from_str(s).ok())
+            },
+            quote! {
+                let s = v.as_string_value()?;
+                <Self as ::std::str::FromStr>::from_str(s)
+                    .err()
+                    .map(|e| ::std::string::ToString::to_string(&e))
+            },
+            // String literals are unescaped the same way as for the `String` scalar and then
+            // validated with `FromStr`, while any other literal is rejected as an unexpected token.
+            quote! {
+                let parsed =
+                    <::std::string::String as ::juniper::ParseScalarValue<#scalar>>::from_str(value)?;
+                if let Some(s) = ::juniper::ScalarValue::as_str(&parsed) {
+                    if let Err(e) = <Self as ::std::str::FromStr>::from_str(s) {
+                        return Err(::juniper::ParseError::InvalidScalarError(
+                            ::std::string::ToString::to_string(&e),
+                        ));
+                    }
+                }
+                Ok(parsed)
+            },
+        ),
+    };
 
-    let content = quote!(
-        #_async
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
 
+    let mut where_async = where_clause.cloned().unwrap_or_else(|| parse_quote!(where));
+    where_async
+        .predicates
+        .push(parse_quote!( #scalar: Send + Sync ));
+    where_async.predicates.push(parse_quote!(Self: Sync));
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl#impl_generics ::juniper::marker::IsInputType<#scalar> for #ident
+            #where_clause
+        {}
+
+        #[automatically_derived]
+        impl#impl_generics ::juniper::marker::IsOutputType<#scalar> for #ident
+            #where_clause
+        {}
+
+        #[automatically_derived]
         impl#impl_generics ::juniper::GraphQLType<#scalar> for #ident
-        where
-            #scalar: ::juniper::ScalarValue,
+            #where_clause
         {
             fn name(_: &Self::TypeInfo) -> Option<&'static str> {
                 Some(#name)
@@ -163,9 +291,9 @@ fn impl_scalar_struct(
             }
         }
 
+        #[automatically_derived]
         impl#impl_generics ::juniper::GraphQLValue<#scalar> for #ident
-        where
-            #scalar: ::juniper::ScalarValue,
+            #where_clause
         {
             type Context = ();
             type TypeInfo = ();
@@ -180,49 +308,61 @@ fn impl_scalar_struct(
                 selection: Option<&[::juniper::Selection<#scalar>]>,
                 executor: &::juniper::Executor<Self::Context, #scalar>,
             ) -> ::juniper::ExecutionResult<#scalar> {
-                ::juniper::GraphQLValue::<#scalar>::resolve(&self.0, info, selection, executor)
+                #resolve
             }
         }
 
+        #[automatically_derived]
+        impl#impl_generics ::juniper::GraphQLValueAsync<#scalar> for #ident
+            #where_async
+        {
+            fn resolve_async<'a>(
+                &'a self,
+                info: &'a Self::TypeInfo,
+                selection_set: Option<&'a [::juniper::Selection<#scalar>]>,
+                executor: &'a ::juniper::Executor<Self::Context, #scalar>,
+            ) -> ::juniper::BoxFuture<'a, ::juniper::ExecutionResult<#scalar>> {
+                use ::juniper::futures::future;
+                let v = ::juniper::GraphQLValue::<#scalar>::resolve(
+                    self, info, selection_set, executor,
+                );
+                Box::pin(future::ready(v))
+            }
+        }
+
+        #[automatically_derived]
         impl#impl_generics ::juniper::ToInputValue<#scalar> for #ident
-        where
-            #scalar: ::juniper::ScalarValue,
+            #where_clause
         {
             fn to_input_value(&self) -> ::juniper::InputValue<#scalar> {
-                ::juniper::ToInputValue::<#scalar>::to_input_value(&self.0)
+                #to_input_value
             }
         }
 
+        #[automatically_derived]
         impl#impl_generics ::juniper::FromInputValue<#scalar> for #ident
-        where
-            #scalar: ::juniper::ScalarValue,
+            #where_clause
         {
-            fn from_input_value(v: &::juniper::InputValue<#scalar>) -> Option<#ident> {
-                let inner: #inner_ty = ::juniper::FromInputValue::<#scalar>::from_input_value(v)?;
-                Some(#ident(inner))
+            fn from_input_value(v: &::juniper::InputValue<#scalar>) -> Option<Self> {
+                #from_input_value
+            }
+
+            fn from_input_value_error(v: &::juniper::InputValue<#scalar>) -> Option<String> {
+                #from_input_value_error
             }
         }
 
+        #[automatically_derived]
         impl#impl_generics ::juniper::ParseScalarValue<#scalar> for #ident
-        where
-            #scalar: ::juniper::ScalarValue,
+            #where_clause
         {
             fn from_str<'a>(
                 value: ::juniper::parser::ScalarToken<'a>,
             ) -> ::juniper::ParseScalarResult<'a, #scalar> {
-                <#inner_ty as ::juniper::ParseScalarValue<#scalar>>::from_str(value)
+                #from_str
             }
         }
-
-        impl#impl_generics ::juniper::marker::IsOutputType<#scalar> for #ident
-            where #scalar: ::juniper::ScalarValue,
-        { }
-        impl#impl_generics ::juniper::marker::IsInputType<#scalar> for #ident
-            where #scalar: ::juniper::ScalarValue,
-        { }
-    );
-
-    Ok(content)
+    })
 }
 
 fn impl_scalar_enum(
@@ -12,7 +12,7 @@ use crate::{
         ScalarValueType,
     },
     result::GraphQLScope,
-    util::{path_eq_single, span_container::SpanContainer, to_camel_case},
+    util::{path_eq_single, span_container::SpanContainer, RenameRule},
 };
 
 use super::{
@@ -113,10 +113,16 @@ pub fn expand_on_trait(
 
     proc_macro_error::abort_if_dirty();
 
+    let rename_all = meta
+        .rename_all
+        .as_ref()
+        .map(|rule| *rule.inner())
+        .unwrap_or(RenameRule::CamelCase);
+
     let mut fields = vec![];
     for item in &mut ast.items {
         if let syn::TraitItem::Method(m) = item {
-            match TraitMethod::parse(m) {
+            match TraitMethod::parse(m, rename_all) {
                 Some(TraitMethod::Field(f)) => fields.push(f),
                 Some(TraitMethod::Downcast(d)) => {
                     match implementers.iter_mut().find(|i| i.ty == d.ty) {
@@ -363,7 +369,7 @@ impl TraitMethod {
     /// Returns [`None`] if the trait method marked with `#[graphql(ignore)]` attribute,
     /// or parsing fails.
     #[must_use]
-    fn parse(method: &mut syn::TraitItemMethod) -> Option<Self> {
+    fn parse(method: &mut syn::TraitItemMethod, rename_all: RenameRule) -> Option<Self> {
         let method_attrs = method.attrs.clone();
 
         // Remove repeated attributes from the method, to omit incorrect expansion.
@@ -384,7 +390,7 @@ impl TraitMethod {
             return Some(Self::Downcast(Box::new(Self::parse_downcast(method)?)));
         }
 
-        Some(Self::Field(Self::parse_field(method, meta)?))
+        Some(Self::Field(Self::parse_field(method, meta, rename_all)?))
     }
 
     /// Parses [`TraitMethod::Downcast`] from the given trait method definition.
@@ -435,14 +441,18 @@ impl TraitMethod {
     ///
     /// Returns [`None`] if parsing fails.
     #[must_use]
-    fn parse_field(method: &mut syn::TraitItemMethod, meta: MethodMeta) -> Option<Field> {
+    fn parse_field(
+        method: &mut syn::TraitItemMethod,
+        meta: MethodMeta,
+        rename_all: RenameRule,
+    ) -> Option<Field> {
         let method_ident = &method.sig.ident;
 
         let name = meta
             .name
             .as_ref()
             .map(|m| m.as_ref().value())
-            .unwrap_or_else(|| to_camel_case(&method_ident.unraw().to_string()));
+            .unwrap_or_else(|| rename_all.apply(&method_ident.unraw().to_string()));
         if name.starts_with("__") {
             ERR.no_double_underscore(
                 meta.name
@@ -476,7 +486,7 @@ impl TraitMethod {
             args_iter
                 .filter_map(|arg| match arg {
                     syn::FnArg::Receiver(_) => None,
-                    syn::FnArg::Typed(arg) => Self::parse_field_argument(arg),
+                    syn::FnArg::Typed(arg) => Self::parse_field_argument(arg, rename_all),
                 })
                 .collect()
         };
@@ -501,6 +511,8 @@ impl TraitMethod {
             method: method_ident.clone(),
             arguments,
             is_async: method.sig.asyncness.is_some(),
+            guard: meta.guard.map(SpanContainer::into_inner),
+            visible: meta.visible.map(SpanContainer::into_inner),
         })
     }
 
@@ -508,7 +520,10 @@ impl TraitMethod {
     ///
     /// Returns [`None`] if parsing fails.
     #[must_use]
-    fn parse_field_argument(argument: &mut syn::PatType) -> Option<MethodArgument> {
+    fn parse_field_argument(
+        argument: &mut syn::PatType,
+        rename_all: RenameRule,
+    ) -> Option<MethodArgument> {
         let argument_attrs = argument.attrs.clone();
 
         // Remove repeated attributes from the method, to omit incorrect expansion.
@@ -544,7 +559,7 @@ impl TraitMethod {
         let name = if let Some(name) = meta.name.as_ref() {
             name.as_ref().value()
         } else if let syn::Pat::Ident(name) = &*argument.pat {
-            to_camel_case(&name.ident.unraw().to_string())
+            rename_all.apply(&name.ident.unraw().to_string())
         } else {
             ERR.custom(
                 argument.pat.span(),
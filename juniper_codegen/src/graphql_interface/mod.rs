@@ -24,7 +24,10 @@ use crate::{
         },
         ScalarValueType,
     },
-    util::{filter_attrs, get_deprecated, get_doc_comment, span_container::SpanContainer},
+    util::{
+        filter_attrs, get_deprecated, get_doc_comment, parse_lit_or_expr,
+        span_container::SpanContainer, RenameRule,
+    },
 };
 
 /// Available metadata (arguments) behind `#[graphql_interface]` attribute placed on a trait
@@ -116,6 +119,14 @@ struct TraitMeta {
     /// [2]: https://spec.graphql.org/June2018/#sec-Objects
     external_downcasts: HashMap<syn::Type, SpanContainer<syn::ExprPath>>,
 
+    /// Explicitly specified rule of renaming the Rust names of trait methods and their arguments
+    /// into names of [GraphQL interface][1] fields and their arguments.
+    ///
+    /// If absent, then `camelCase` rule is used by default.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Interfaces
+    rename_all: Option<SpanContainer<RenameRule>>,
+
     /// Indicator whether the generated code is intended to be used only inside the [`juniper`]
     /// library.
     is_internal: bool,
@@ -214,6 +225,18 @@ impl Parse for TraitMeta {
                         .insert(ty, dwncst_spanned)
                         .none_or_else(|_| err::dup_arg(dwncst_span))?
                 }
+                "rename_all" => {
+                    input.parse::<token::Eq>()?;
+                    let val = input.parse::<syn::LitStr>()?;
+                    let rule = val
+                        .value()
+                        .parse::<RenameRule>()
+                        .map_err(|_| syn::Error::new(val.span(), "unknown rename rule"))?;
+                    output
+                        .rename_all
+                        .replace(SpanContainer::new(ident.span(), Some(val.span()), rule))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
                 "internal" => {
                     output.is_internal = true;
                 }
@@ -243,6 +266,7 @@ impl TraitMeta {
             external_downcasts: try_merge_hashmap!(
                 external_downcasts: self, another => span_joined
             ),
+            rename_all: try_merge_opt!(rename_all: self, another),
             is_internal: self.is_internal || another.is_internal,
         })
     }
@@ -412,6 +436,19 @@ struct MethodMeta {
     /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
     /// [2]: https://spec.graphql.org/June2018/#sec-Objects
     downcast: Option<SpanContainer<syn::Ident>>,
+
+    /// Explicitly specified [`Guard`] checked before resolving this [GraphQL field][1] through
+    /// the interface.
+    ///
+    /// [`Guard`]: juniper::Guard
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    guard: Option<SpanContainer<syn::Expr>>,
+
+    /// Explicitly specified predicate over the request context deciding whether this
+    /// [GraphQL field][1] is visible.
+    ///
+    /// [1]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    visible: Option<SpanContainer<syn::Expr>>,
 }
 
 impl Parse for MethodMeta {
@@ -460,6 +497,22 @@ impl Parse for MethodMeta {
                     .downcast
                     .replace(SpanContainer::new(ident.span(), None, ident.clone()))
                     .none_or_else(|_| err::dup_arg(&ident))?,
+                "guard" => {
+                    input.parse::<token::Eq>()?;
+                    let (expr, span) = parse_lit_or_expr(input)?;
+                    output
+                        .guard
+                        .replace(SpanContainer::new(ident.span(), Some(span), expr))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
+                "visible" => {
+                    input.parse::<token::Eq>()?;
+                    let (expr, span) = parse_lit_or_expr(input)?;
+                    output
+                        .visible
+                        .replace(SpanContainer::new(ident.span(), Some(span), expr))
+                        .none_or_else(|_| err::dup_arg(&ident))?
+                }
                 name => {
                     return Err(err::unknown_arg(&ident, name));
                 }
@@ -480,6 +533,8 @@ impl MethodMeta {
             deprecated: try_merge_opt!(deprecated: self, another),
             ignore: try_merge_opt!(ignore: self, another),
             downcast: try_merge_opt!(downcast: self, another),
+            guard: try_merge_opt!(guard: self, another),
+            visible: try_merge_opt!(visible: self, another),
         })
     }
 
@@ -495,6 +550,8 @@ impl MethodMeta {
                 || meta.description.is_some()
                 || meta.deprecated.is_some()
                 || meta.downcast.is_some()
+                || meta.guard.is_some()
+                || meta.visible.is_some()
             {
                 return Err(syn::Error::new(
                     ignore.span(),
@@ -508,6 +565,8 @@ impl MethodMeta {
                 || meta.description.is_some()
                 || meta.deprecated.is_some()
                 || meta.ignore.is_some()
+                || meta.guard.is_some()
+                || meta.visible.is_some()
             {
                 return Err(syn::Error::new(
                     downcast.span(),
@@ -845,7 +904,7 @@ impl Definition {
         let fields_resolvers = self
             .fields
             .iter()
-            .filter_map(|f| f.method_resolve_field_tokens(&trait_ty));
+            .filter_map(|f| f.method_resolve_field_tokens(scalar, &trait_ty));
         let async_fields_panic = {
             let names = self
                 .fields
@@ -959,7 +1018,7 @@ impl Definition {
         let fields_resolvers = self
             .fields
             .iter()
-            .map(|f| f.method_resolve_field_async_tokens(&trait_ty));
+            .map(|f| f.method_resolve_field_async_tokens(scalar, &trait_ty));
         let no_field_panic = self.panic_no_field_tokens();
 
         let custom_downcasts = self
@@ -1272,6 +1331,18 @@ struct Field {
     ///
     /// [2]: https://spec.graphql.org/June2018/#sec-Language.Fields
     is_async: bool,
+
+    /// [`Guard`] to check before resolving this [GraphQL field][2] through the interface, if any.
+    ///
+    /// [`Guard`]: juniper::Guard
+    /// [2]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    guard: Option<syn::Expr>,
+
+    /// Predicate over the request context deciding whether this [GraphQL field][2] is visible,
+    /// if any.
+    ///
+    /// [2]: https://spec.graphql.org/June2018/#sec-Language.Fields
+    visible: Option<syn::Expr>,
 }
 
 impl Field {
@@ -1297,6 +1368,11 @@ impl Field {
             quote! { .deprecated(#reason) }
         });
 
+        let visible = self
+            .visible
+            .as_ref()
+            .map(|pred| quote! { .visible_if(#pred) });
+
         let arguments = self
             .arguments
             .iter()
@@ -1307,6 +1383,7 @@ impl Field {
                 #( #arguments )*
                 #description
                 #deprecated
+                #visible
         }
     }
 
@@ -1317,7 +1394,11 @@ impl Field {
     ///
     /// [`GraphQLValue::resolve_field`]: juniper::GraphQLValue::resolve_field
     #[must_use]
-    fn method_resolve_field_tokens(&self, trait_ty: &syn::Type) -> Option<TokenStream> {
+    fn method_resolve_field_tokens(
+        &self,
+        scalar: &ScalarValueType,
+        trait_ty: &syn::Type,
+    ) -> Option<TokenStream> {
         if self.is_async {
             return None;
         }
@@ -1329,10 +1410,21 @@ impl Field {
             .iter()
             .map(MethodArgument::method_resolve_field_tokens);
 
+        let guard_check = self.guard.as_ref().map(|guard| {
+            quote! {
+                ::juniper::Guard::<Self::Context, #scalar>::check(
+                    &(#guard),
+                    executor.context(),
+                    args,
+                )?;
+            }
+        });
+
         let resolving_code = gen::sync_resolving_code();
 
         Some(quote! {
             #name => {
+                #guard_check
                 let res: #ty = <Self as #trait_ty>::#method(self #( , #arguments )*);
                 #resolving_code
             }
@@ -1344,7 +1436,11 @@ impl Field {
     ///
     /// [`GraphQLValueAsync::resolve_field_async`]: juniper::GraphQLValueAsync::resolve_field_async
     #[must_use]
-    fn method_resolve_field_async_tokens(&self, trait_ty: &syn::Type) -> TokenStream {
+    fn method_resolve_field_async_tokens(
+        &self,
+        scalar: &ScalarValueType,
+        trait_ty: &syn::Type,
+    ) -> TokenStream {
         let (name, ty, method) = (&self.name, &self.ty, &self.method);
 
         let arguments = self
@@ -1357,10 +1453,23 @@ impl Field {
             fut = quote! { ::juniper::futures::future::ready(#fut) };
         }
 
+        let guard_check = self.guard.as_ref().map(|guard| {
+            quote! {
+                if let Err(e) = ::juniper::Guard::<Self::Context, #scalar>::check(
+                    &(#guard),
+                    executor.context(),
+                    args,
+                ) {
+                    return Box::pin(::juniper::futures::future::ready(Err(e)));
+                }
+            }
+        });
+
         let resolving_code = gen::async_resolving_code(Some(ty));
 
         quote! {
             #name => {
+                #guard_check
                 let fut = #fut;
                 #resolving_code
             }
@@ -14,7 +14,11 @@ pub fn build_object(args: TokenStream, body: TokenStream, error: GraphQLScope) -
         Ok(definition) => definition,
         Err(err) => return err.to_compile_error(),
     };
-    definition.into_tokens()
+    if definition.complex {
+        definition.into_complex_object_tokens()
+    } else {
+        definition.into_tokens()
+    }
 }
 
 /// Generate code for the juniper::graphql_subscription macro.
@@ -27,6 +31,13 @@ pub fn build_subscription(
         Ok(definition) => definition,
         Err(err) => return err.to_compile_error(),
     };
+    if definition.complex {
+        return syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "`complex` is only supported on `#[graphql_object]` impl blocks",
+        )
+        .to_compile_error();
+    }
     definition.into_subscription_tokens()
 }
 
@@ -84,6 +95,36 @@ fn create(
                     let arg_name = arg_ident.unraw().to_string();
                     let ty = &captured.ty;
 
+                    if let Some(arg) = attrs.argument(&arg_name).filter(|arg| arg.flatten.is_some()) {
+                        if arg.rename.is_some()
+                            || arg.default.is_some()
+                            || arg.description.is_some()
+                            || arg.visible.is_some()
+                        {
+                            return Err(syn::Error::new(
+                                arg.name.span(),
+                                "flattened arguments take their names, defaults and descriptions \
+                                 from the fields of their input object",
+                            ));
+                        }
+
+                        let mut_modifier = if is_mut { quote!(mut) } else { quote!() };
+                        let resolver = quote!(
+                            let #mut_modifier #arg_ident = args
+                                .get_flattened::<#ty>()
+                                .expect("Flattened arguments must be an input object");
+                        );
+                        let field_type = util::GraphQLTypeDefinitionFieldArg {
+                            name: arg_name,
+                            description: None,
+                            default: None,
+                            visible: None,
+                            _type: ty.clone(),
+                            flatten: true,
+                        };
+                        return Ok((resolver, field_type));
+                    }
+
                     let final_name = attrs
                         .argument(&arg_name)
                         .and_then(|attrs| attrs.rename.clone().map(|ident| ident.value()))
@@ -122,8 +163,12 @@ fn create(
                         default: attrs
                             .argument(&arg_name)
                             .and_then(|arg| arg.default.clone()),
+                        visible: attrs
+                            .argument(&arg_name)
+                            .and_then(|arg| arg.visible.clone()),
                         _type: ty.clone(),
                         name: final_name,
+                        flatten: false,
                     };
                     Ok((resolver, field_type))
                 });
@@ -179,6 +224,9 @@ fn create(
                 is_type_inferred: false,
                 is_async,
                 default: None,
+                guard: attrs.guard.map(SpanContainer::into_inner),
+                visible: attrs.visible.map(SpanContainer::into_inner),
+                cache_control: attrs.cache_control.map(SpanContainer::into_inner),
                 span,
             })
         })
@@ -193,6 +241,35 @@ fn create(
         error.duplicate(duplicates.iter())
     }
 
+    if _impl.attrs.complex.is_some() {
+        // Everything besides the fields is defined by the derived object.
+        let misplaced = _impl
+            .attrs
+            .name
+            .as_ref()
+            .map(SpanContainer::span_ident)
+            .into_iter()
+            .chain(_impl.attrs.interfaces.iter().map(SpanContainer::span_ident))
+            .chain(_impl.attrs.visible.as_ref().map(SpanContainer::span_ident))
+            .chain(
+                _impl
+                    .attrs
+                    .cache_control
+                    .as_ref()
+                    .map(SpanContainer::span_ident),
+            );
+        for span in misplaced {
+            error.emit_custom(
+                span,
+                "not allowed on a `complex` impl block, set it on the derived object instead",
+            );
+        }
+    }
+
+    if let Some(name) = &_impl.attrs.name {
+        util::check_name_template(name, &_impl.generics, &error);
+    }
+
     if !_impl.attrs.is_internal && name.starts_with("__") {
         error.no_double_underscore(if let Some(name) = _impl.attrs.name {
             name.span_ident()
@@ -201,7 +278,8 @@ fn create(
         });
     }
 
-    if fields.is_empty() {
+    // A `complex` impl block may be empty, as the derived object provides the other fields.
+    if fields.is_empty() && _impl.attrs.complex.is_none() {
         error.not_empty(body_span);
     }
 
@@ -214,6 +292,8 @@ fn create(
         scalar: _impl.attrs.scalar.map(SpanContainer::into_inner),
         context: _impl.attrs.context.map(SpanContainer::into_inner),
         description: _impl.description,
+        visible: _impl.attrs.visible.map(SpanContainer::into_inner),
+        cache_control: _impl.attrs.cache_control.map(SpanContainer::into_inner),
         fields,
         generics: _impl.generics.clone(),
         interfaces: _impl
@@ -225,6 +305,7 @@ fn create(
         include_type_generics: false,
         generic_scalar: true,
         no_async: _impl.attrs.no_async.is_some(),
+        complex: _impl.attrs.complex.is_some(),
     };
 
     Ok(definition)
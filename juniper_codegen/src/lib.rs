@@ -108,6 +108,7 @@ macro_rules! try_merge_hashset {
 
 mod derive_enum;
 mod derive_input_object;
+mod derive_merged_object;
 mod derive_object;
 mod derive_scalar_value;
 mod impl_object;
@@ -154,6 +155,142 @@ pub fn derive_object(input: TokenStream) -> TokenStream {
     }
 }
 
+/// Merges several GraphQL objects into a single one, so that its fields can be defined in
+/// separate files.
+///
+/// Each field of the struct holds one of the merged objects, which must be defined with
+/// `#[derive(GraphQLObject)]`, `#[graphql_object]` or `#[derive(GraphQLMergedObject)]` and share
+/// the same `context` and type info. Fields of the merged objects with the same name fail the
+/// compilation, once the merged object is used in a schema, as do merged objects implementing
+/// interfaces.
+///
+/// ```rust
+/// # use juniper::{graphql_object, GraphQLMergedObject};
+/// struct UserQuery;
+///
+/// #[graphql_object]
+/// impl UserQuery {
+///     fn user_count() -> i32 {
+///         42
+///     }
+/// }
+///
+/// struct BillingQuery;
+///
+/// #[graphql_object]
+/// impl BillingQuery {
+///     fn invoice_count() -> i32 {
+///         7
+///     }
+/// }
+///
+/// /// Has both the `userCount` and `invoiceCount` fields.
+/// #[derive(GraphQLMergedObject)]
+/// #[graphql(name = "Query")]
+/// struct Query(UserQuery, BillingQuery);
+/// ```
+///
+/// The `name`, `description`, `context`, `scalar`, `visible` and `cache_control` attributes are
+/// supported, like on `#[derive(GraphQLObject)]`. Interfaces are not supported.
+///
+/// The descriptions of the merged objects are joined, unless the `description` is set. The merged
+/// object is only visible if all the merged objects are, and its cache control hint is the most
+/// restrictive one of the merged objects.
+#[proc_macro_error]
+#[proc_macro_derive(GraphQLMergedObject, attributes(graphql))]
+pub fn derive_merged_object(input: TokenStream) -> TokenStream {
+    let ast = syn::parse::<syn::DeriveInput>(input).unwrap();
+    let gen = derive_merged_object::build_derive_merged_object(ast, GraphQLScope::DeriveObject);
+    match gen {
+        Ok(gen) => gen.into(),
+        Err(err) => proc_macro_error::abort!(err),
+    }
+}
+
+/// `#[derive(GraphQLScalar)]` macro for deriving a custom [GraphQL scalar][1] from a Rust type,
+/// without writing a [`graphql_scalar`] implementation by hand.
+///
+/// ## Transparent newtypes
+///
+/// A struct with exactly one field is resolved, parsed and converted exactly as its inner type,
+/// while being exposed as a distinct scalar in the schema. The `transparent` attribute argument
+/// states it explicitly, and can't be combined with `with_str`:
+///
+/// ```rust
+/// /// Doc comments are used for the GraphQL type description.
+/// #[derive(juniper::GraphQLScalar)]
+/// struct UserId(String);
+///
+/// #[derive(juniper::GraphQLScalar)]
+/// #[graphql(
+///     // Set a custom GraphQL name.
+///     name = "Cents",
+///     // A description can also be specified in the attribute.
+///     // This will override the doc comment, if one exists.
+///     description = "Amount of money in cents.",
+/// )]
+/// struct Money {
+///     cents: i32,
+/// }
+/// ```
+///
+/// ## String-encoded types
+///
+/// With the `with_str` attribute argument any struct or enum is represented as a GraphQL string,
+/// serialized via its [`Display`] implementation and parsed via its [`FromStr`] one. Strings
+/// failing to parse are rejected as invalid values of the scalar, both as literals and as
+/// variables, reporting the [`Display`]ed [`FromStr::Err`]:
+///
+/// ```rust
+/// use std::{fmt, str::FromStr};
+///
+/// #[derive(juniper::GraphQLScalar)]
+/// #[graphql(with_str)]
+/// struct Semver {
+///     major: u32,
+///     minor: u32,
+/// }
+///
+/// impl fmt::Display for Semver {
+///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+///         write!(f, "{}.{}", self.major, self.minor)
+///     }
+/// }
+///
+/// impl FromStr for Semver {
+///     type Err = String;
+///
+///     fn from_str(s: &str) -> Result<Self, Self::Err> {
+///         let (major, minor) = s.split_once('.').ok_or("expected `major.minor`")?;
+///         Ok(Self {
+///             major: major.parse().map_err(|_| "invalid major version")?,
+///             minor: minor.parse().map_err(|_| "invalid minor version")?,
+///         })
+///     }
+/// }
+/// ```
+///
+/// ## Custom `ScalarValue`
+///
+/// By default, the scalar is generic over all [`ScalarValue`]s. A concrete one can be specified
+/// with the `scalar` attribute argument, e.g. `#[graphql(scalar = MyScalarValue)]`.
+///
+/// [`Display`]: std::fmt::Display
+/// [`FromStr`]: std::str::FromStr
+/// [`FromStr::Err`]: std::str::FromStr::Err
+/// [`ScalarValue`]: juniper::ScalarValue
+/// [1]: https://spec.graphql.org/June2018/#sec-Scalars
+#[proc_macro_error]
+#[proc_macro_derive(GraphQLScalar, attributes(graphql))]
+pub fn derive_scalar(input: TokenStream) -> TokenStream {
+    let ast = syn::parse::<syn::DeriveInput>(input).unwrap();
+    let gen = derive_scalar_value::impl_scalar(&ast, GraphQLScope::DeriveScalar);
+    match gen {
+        Ok(gen) => gen.into(),
+        Err(err) => proc_macro_error::abort!(err),
+    }
+}
+
 /// This custom derive macro implements the #[derive(GraphQLScalarValue)]
 /// derive.
 ///
@@ -192,6 +329,9 @@ pub fn derive_object(input: TokenStream) -> TokenStream {
 /// struct UserId(String);
 /// ```
 ///
+/// Structs are derived the same way as with `#[derive(GraphQLScalar)]`, so the `with_str`
+/// attribute argument is supported too.
+///
 /// ### Base ScalarValue Enum
 ///
 /// TODO: write documentation.
@@ -351,6 +491,8 @@ struct InternalQuery;
 /// More info for GraphQL users....
 #[juniper::graphql_object(
     // You can rename the type for GraphQL by specifying the name here.
+    // The name of a generic type may contain `{T}` placeholders of its type
+    // parameters, replaced with their GraphQL names, e.g. `name = "{T}Page"`.
     name = "Query",
     // You can also specify a description here.
     // If present, doc comments will be ignored.
@@ -402,6 +544,104 @@ impl InternalQuery {
 }
 ```
 
+## Guards
+
+A field can be protected by a [`Guard`](juniper::Guard), which is checked against
+the context and the field's arguments before the resolver is called. If the guard
+fails, the field resolves to `null` and the error is added to the response.
+
+```
+# use juniper::{Arguments, FieldResult, ScalarValue};
+struct Context {
+    is_admin: bool,
+}
+
+impl juniper::Context for Context {}
+
+fn admin_only<S: ScalarValue>(ctx: &Context, _: &Arguments<S>) -> FieldResult<(), S> {
+    if ctx.is_admin { Ok(()) } else { Err("Forbidden".into()) }
+}
+
+struct Query;
+
+#[juniper::graphql_object(Context = Context)]
+impl Query {
+    #[graphql(guard = "admin_only")]
+    fn secret() -> &'static str {
+        "42"
+    }
+}
+```
+
+## Visibility
+
+Types, fields and arguments may be hidden from introspection and validation with the
+`visible` attribute, accepting a predicate over the request context. Such predicates are
+only evaluated once enabled with `RootNode::enable_visibility`, otherwise the items are hidden.
+
+```
+# use juniper::{graphql_object, EmptyMutation, EmptySubscription, RootNode};
+struct Context {
+    is_staff: bool,
+}
+
+impl juniper::Context for Context {}
+
+fn is_staff(ctx: &Context) -> bool {
+    ctx.is_staff
+}
+
+struct Query;
+
+#[graphql_object(Context = Context)]
+impl Query {
+    #[graphql(visible = "is_staff")]
+    fn internal() -> &'static str {
+        "internal"
+    }
+
+    #[graphql(arguments(verbose(visible = "is_staff")))]
+    fn status(verbose: Option<bool>) -> &'static str {
+        if verbose.unwrap_or_default() { "all good" } else { "ok" }
+    }
+}
+
+let schema = RootNode::new(
+    Query,
+    EmptyMutation::<Context>::new(),
+    EmptySubscription::<Context>::new(),
+)
+.enable_visibility();
+```
+
+## Cache control
+
+Object types and fields may declare how long their values may be cached with the
+`cache_control` attribute. The `CachePolicy` of a whole query response is the most restrictive
+hint among all the resolved object types and fields, and is sent as the `Cache-Control` header
+by the integration crates.
+
+A field without a hint makes the whole response uncacheable, unless its parent object type
+declares a hint, it returns an object type declaring a hint, or it returns a scalar or an enum
+and isn't a root field (inheriting the hint of its parent field then).
+
+```
+# use juniper::graphql_object;
+struct Query;
+
+#[graphql_object(cache_control(max_age = 60))]
+impl Query {
+    fn news() -> &'static str {
+        "nothing new"
+    }
+
+    #[graphql(cache_control(max_age = 10, scope = "PRIVATE"))]
+    fn me() -> &'static str {
+        "me"
+    }
+}
+```
+
 ## Lifetimes, Generics and custom Scalars
 
 Lifetimes work just like you'd expect.
@@ -57,6 +57,9 @@ pub enum UnsupportedAttribute {
     Scalar,
     Deprecation,
     Default,
+    Guard,
+    CacheControl,
+    InputObject,
 }
 
 impl GraphQLScope {
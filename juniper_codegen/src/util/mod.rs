@@ -19,7 +19,13 @@ use syn::{
     token, Attribute, Ident, Lit, Meta, MetaList, MetaNameValue, NestedMeta,
 };
 
-use crate::common::parse::ParseBufferExt as _;
+use crate::{
+    common::parse::{
+        attr::{err, OptionExt as _},
+        ParseBufferExt as _,
+    },
+    result::GraphQLScope,
+};
 
 /// Returns the name of a type.
 /// If the type does not end in a simple ident, `None` is returned.
@@ -294,13 +300,39 @@ pub fn is_valid_name(field_name: &str) -> bool {
     chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
 }
 
+/// Parses an expression given either directly or as a string literal containing it, returning it
+/// along with its span.
+pub fn parse_lit_or_expr(input: ParseStream) -> syn::Result<(syn::Expr, Span)> {
+    if let Ok(lit) = input.parse::<syn::LitStr>() {
+        Ok((lit.parse::<syn::Expr>()?, lit.span()))
+    } else {
+        let expr = input.parse::<syn::Expr>()?;
+        let span = expr.span();
+        Ok((expr, span))
+    }
+}
+
 /// The different possible ways to change case of fields in a struct, or variants in an enum.
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub enum RenameRule {
     /// Don't apply a default rename rule.
     None,
-    /// Rename to "camelCase" style.
+    /// Rename to "lowercase" style.
+    LowerCase,
+    /// Rename to "UPPERCASE" style.
+    UpperCase,
+    /// Rename to "PascalCase" style.
+    PascalCase,
+    /// Rename to "camelCase" style, keeping the first character as is.
+    ///
+    /// Used by default, so Rust names like `ID` or `URL` are kept as is.
     CamelCase,
+    /// Rename to "camelCase" style, lowercasing the first character too.
+    ///
+    /// Used when `rename_all = "camelCase"` is given explicitly.
+    LowerCamelCase,
+    /// Rename to "snake_case" style.
+    SnakeCase,
     /// Rename to "SCREAMING_SNAKE_CASE" style
     ScreamingSnakeCase,
 }
@@ -309,7 +341,26 @@ impl RenameRule {
     pub fn apply(&self, field: &str) -> String {
         match self {
             Self::None => field.to_owned(),
+            Self::LowerCase => field.to_lowercase(),
+            Self::UpperCase => field.to_uppercase(),
+            Self::PascalCase => {
+                let camel = to_camel_case(field);
+                let mut chars = camel.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_uppercase().chain(chars).collect())
+                    .unwrap_or_default()
+            }
             Self::CamelCase => to_camel_case(field),
+            Self::LowerCamelCase => {
+                let camel = to_camel_case(field);
+                let mut chars = camel.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_lowercase().chain(chars).collect())
+                    .unwrap_or_default()
+            }
+            Self::SnakeCase => to_upper_snake_case(field).to_lowercase(),
             Self::ScreamingSnakeCase => to_upper_snake_case(field),
         }
     }
@@ -320,14 +371,82 @@ impl FromStr for RenameRule {
 
     fn from_str(rule: &str) -> Result<Self, Self::Err> {
         match rule {
-            "none" => Ok(Self::None),
-            "camelCase" => Ok(Self::CamelCase),
+            "none" | "verbatim" => Ok(Self::None),
+            "lowercase" => Ok(Self::LowerCase),
+            "UPPERCASE" => Ok(Self::UpperCase),
+            "PascalCase" => Ok(Self::PascalCase),
+            "camelCase" => Ok(Self::LowerCamelCase),
+            "snake_case" => Ok(Self::SnakeCase),
             "SCREAMING_SNAKE_CASE" => Ok(Self::ScreamingSnakeCase),
             _ => Err(()),
         }
     }
 }
 
+/// Arguments of the `#[graphql(cache_control(max_age = 60, scope = "PRIVATE"))]` attribute.
+#[derive(Debug, Default)]
+pub struct CacheControlAttr {
+    pub max_age: Option<u32>,
+    pub scope: Option<CacheScopeAttr>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum CacheScopeAttr {
+    Public,
+    Private,
+}
+
+impl Parse for CacheControlAttr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut output = Self::default();
+
+        while !input.is_empty() {
+            let ident = input.parse::<syn::Ident>()?;
+            match ident.to_string().as_str() {
+                "max_age" => {
+                    input.parse::<token::Eq>()?;
+                    let lit = input.parse::<syn::LitInt>()?;
+                    output.max_age = Some(lit.base10_parse()?);
+                }
+                "scope" => {
+                    input.parse::<token::Eq>()?;
+                    let lit = input.parse::<syn::LitStr>()?;
+                    output.scope = Some(match lit.value().as_str() {
+                        "PUBLIC" => CacheScopeAttr::Public,
+                        "PRIVATE" => CacheScopeAttr::Private,
+                        _ => {
+                            return Err(syn::Error::new(
+                                lit.span(),
+                                "unknown cache scope, expected `PUBLIC` or `PRIVATE`",
+                            ))
+                        }
+                    });
+                }
+                _ => return Err(syn::Error::new(ident.span(), "unknown attribute")),
+            }
+            input.try_parse::<token::Comma>()?;
+        }
+
+        Ok(output)
+    }
+}
+
+impl CacheControlAttr {
+    /// Returns generated code of the `.cache_control()` call on a type or a field metadata.
+    pub(crate) fn meta_tokens(&self) -> TokenStream {
+        let max_age = match self.max_age {
+            Some(age) => quote! { Some(#age) },
+            None => quote! { None },
+        };
+        let scope = match self.scope {
+            Some(CacheScopeAttr::Public) => quote! { Some(::juniper::CacheScope::Public) },
+            Some(CacheScopeAttr::Private) => quote! { Some(::juniper::CacheScope::Private) },
+            None => quote! { None },
+        };
+        quote! { .cache_control(::juniper::CacheHint::new(#max_age, #scope)) }
+    }
+}
+
 #[derive(Default, Debug)]
 pub struct ObjectAttributes {
     pub name: Option<SpanContainer<String>>,
@@ -338,6 +457,11 @@ pub struct ObjectAttributes {
     pub no_async: Option<SpanContainer<()>>,
     pub is_internal: bool,
     pub rename: Option<RenameRule>,
+    pub visible: Option<SpanContainer<syn::Expr>>,
+    pub cache_control: Option<SpanContainer<CacheControlAttr>>,
+    pub complex: Option<SpanContainer<()>>,
+    /// Only relevant for GraphQLObject derive, holding the custom name of the input object.
+    pub input_object: Option<SpanContainer<Option<String>>>,
 }
 
 impl Parse for ObjectAttributes {
@@ -399,7 +523,34 @@ impl Parse for ObjectAttributes {
                 "internal" => {
                     output.is_internal = true;
                 }
-                "rename" => {
+                "complex" => {
+                    output.complex = Some(SpanContainer::new(ident.span(), None, ()));
+                }
+                "input_object" => {
+                    let name = if input.peek(token::Eq) {
+                        input.parse::<token::Eq>()?;
+                        Some(input.parse::<syn::LitStr>()?)
+                    } else {
+                        None
+                    };
+                    output.input_object = Some(SpanContainer::new(
+                        ident.span(),
+                        name.as_ref().map(|val| val.span()),
+                        name.map(|val| val.value()),
+                    ));
+                }
+                "visible" => {
+                    input.parse::<token::Eq>()?;
+                    let (expr, span) = parse_lit_or_expr(input)?;
+                    output.visible = Some(SpanContainer::new(ident.span(), Some(span), expr));
+                }
+                "cache_control" => {
+                    let content;
+                    syn::parenthesized!(content in input);
+                    let attr = content.parse::<CacheControlAttr>()?;
+                    output.cache_control = Some(SpanContainer::new(ident.span(), None, attr));
+                }
+                "rename" | "rename_all" => {
                     input.parse::<syn::Token![=]>()?;
                     let val = input.parse::<syn::LitStr>()?;
                     if let Ok(rename) = RenameRule::from_str(&val.value()) {
@@ -446,6 +597,8 @@ pub struct FieldAttributeArgument {
     pub rename: Option<SpanContainer<syn::LitStr>>,
     pub default: Option<syn::Expr>,
     pub description: Option<syn::LitStr>,
+    pub visible: Option<syn::Expr>,
+    pub flatten: Option<SpanContainer<syn::Ident>>,
 }
 
 impl Parse for FieldAttributeArgument {
@@ -457,12 +610,19 @@ impl Parse for FieldAttributeArgument {
             rename: None,
             default: None,
             description: None,
+            visible: None,
+            flatten: None,
         };
 
         let content;
         syn::parenthesized!(content in input);
         while !content.is_empty() {
             let name = content.parse::<syn::Ident>()?;
+            if name == "flatten" {
+                arg.flatten = Some(SpanContainer::new(name.span(), None, name));
+                content.parse::<token::Comma>().ok();
+                continue;
+            }
             content.parse::<token::Eq>()?;
 
             match name.to_string().as_str() {
@@ -476,6 +636,9 @@ impl Parse for FieldAttributeArgument {
                 "default" => {
                     arg.default = Some(content.parse()?);
                 }
+                "visible" => {
+                    arg.visible = Some(parse_lit_or_expr(&content)?.0);
+                }
                 _ => return Err(syn::Error::new(name.span(), "unknown attribute")),
             }
 
@@ -498,8 +661,14 @@ enum FieldAttribute {
     Description(SpanContainer<syn::LitStr>),
     Deprecation(SpanContainer<DeprecationAttr>),
     Skip(SpanContainer<syn::Ident>),
+    SkipInput(SpanContainer<syn::Ident>),
+    SkipOutput(SpanContainer<syn::Ident>),
+    InputType(Box<SpanContainer<syn::Type>>),
     Arguments(HashMap<String, FieldAttributeArgument>),
     Default(Box<SpanContainer<Option<syn::Expr>>>),
+    Guard(Box<SpanContainer<syn::Expr>>),
+    Visible(Box<SpanContainer<syn::Expr>>),
+    CacheControl(SpanContainer<CacheControlAttr>),
 }
 
 impl Parse for FieldAttribute {
@@ -550,6 +719,25 @@ impl Parse for FieldAttribute {
                 None,
                 ident,
             ))),
+            "skip_input" => Ok(FieldAttribute::SkipInput(SpanContainer::new(
+                ident.span(),
+                None,
+                ident,
+            ))),
+            "skip_output" => Ok(FieldAttribute::SkipOutput(SpanContainer::new(
+                ident.span(),
+                None,
+                ident,
+            ))),
+            "input_type" => {
+                input.parse::<token::Eq>()?;
+                let ty = input.parse::<syn::Type>()?;
+                Ok(FieldAttribute::InputType(Box::new(SpanContainer::new(
+                    ident.span(),
+                    Some(ty.span()),
+                    ty,
+                ))))
+            }
             "arguments" => {
                 let arg_content;
                 syn::parenthesized!(arg_content in input);
@@ -574,6 +762,34 @@ impl Parse for FieldAttribute {
 
                 Ok(FieldAttribute::Default(Box::new(default_expr)))
             }
+            "guard" => {
+                input.parse::<token::Eq>()?;
+                let (expr, span) = parse_lit_or_expr(input)?;
+                Ok(FieldAttribute::Guard(Box::new(SpanContainer::new(
+                    ident.span(),
+                    Some(span),
+                    expr,
+                ))))
+            }
+            "visible" => {
+                input.parse::<token::Eq>()?;
+                let (expr, span) = parse_lit_or_expr(input)?;
+                Ok(FieldAttribute::Visible(Box::new(SpanContainer::new(
+                    ident.span(),
+                    Some(span),
+                    expr,
+                ))))
+            }
+            "cache_control" => {
+                let content;
+                syn::parenthesized!(content in input);
+                let attr = content.parse::<CacheControlAttr>()?;
+                Ok(FieldAttribute::CacheControl(SpanContainer::new(
+                    ident.span(),
+                    None,
+                    attr,
+                )))
+            }
             _ => Err(syn::Error::new(ident.span(), "unknown attribute")),
         }
     }
@@ -586,10 +802,21 @@ pub struct FieldAttributes {
     pub deprecation: Option<SpanContainer<DeprecationAttr>>,
     // Only relevant for GraphQLObject derive.
     pub skip: Option<SpanContainer<syn::Ident>>,
+    /// Only relevant for GraphQLObject derive with an input object.
+    pub skip_input: Option<SpanContainer<syn::Ident>>,
+    /// Only relevant for GraphQLObject derive with an input object.
+    pub skip_output: Option<SpanContainer<syn::Ident>>,
+    /// Only relevant for GraphQLObject derive with an input object.
+    pub input_type: Option<SpanContainer<syn::Type>>,
     /// Only relevant for object macro.
     pub arguments: HashMap<String, FieldAttributeArgument>,
     /// Only relevant for object input objects.
     pub default: Option<SpanContainer<Option<syn::Expr>>>,
+    /// Only relevant for object fields.
+    pub guard: Option<SpanContainer<syn::Expr>>,
+    pub visible: Option<SpanContainer<syn::Expr>>,
+    /// Only relevant for object fields.
+    pub cache_control: Option<SpanContainer<CacheControlAttr>>,
 }
 
 impl Parse for FieldAttributes {
@@ -612,12 +839,42 @@ impl Parse for FieldAttributes {
                 FieldAttribute::Skip(ident) => {
                     output.skip = Some(ident);
                 }
+                FieldAttribute::SkipInput(ident) => {
+                    output.skip_input = Some(ident);
+                }
+                FieldAttribute::SkipOutput(ident) => {
+                    output.skip_output = Some(ident);
+                }
+                FieldAttribute::InputType(ty) => {
+                    output.input_type = Some(*ty);
+                }
                 FieldAttribute::Arguments(args) => {
                     output.arguments = args;
                 }
                 FieldAttribute::Default(expr) => {
                     output.default = Some(*expr);
                 }
+                FieldAttribute::Guard(expr) => {
+                    let span = expr.span_ident();
+                    output
+                        .guard
+                        .replace(*expr)
+                        .none_or_else(|_| err::dup_arg(span))?;
+                }
+                FieldAttribute::Visible(expr) => {
+                    let span = expr.span_ident();
+                    output
+                        .visible
+                        .replace(*expr)
+                        .none_or_else(|_| err::dup_arg(span))?;
+                }
+                FieldAttribute::CacheControl(attr) => {
+                    let span = attr.span_ident();
+                    output
+                        .cache_control
+                        .replace(attr)
+                        .none_or_else(|_| err::dup_arg(span))?;
+                }
             }
         }
 
@@ -665,7 +922,10 @@ pub struct GraphQLTypeDefinitionFieldArg {
     pub name: String,
     pub description: Option<String>,
     pub default: Option<syn::Expr>,
+    pub visible: Option<syn::Expr>,
     pub _type: Box<syn::Type>,
+    /// Whether the fields of the `_type` input object are flattened into separate arguments.
+    pub flatten: bool,
 }
 
 #[derive(Debug)]
@@ -679,9 +939,45 @@ pub struct GraphQLTypeDefinitionField {
     pub is_type_inferred: bool,
     pub is_async: bool,
     pub default: Option<TokenStream>,
+    pub guard: Option<syn::Expr>,
+    pub visible: Option<syn::Expr>,
+    pub cache_control: Option<CacheControlAttr>,
     pub span: Span,
 }
 
+impl GraphQLTypeDefinitionField {
+    /// Returns generated code checking the guard of this field, if any, before resolving it.
+    ///
+    /// The failed check is propagated from the enclosing resolver via `?`.
+    fn guard_check_tokens(&self, scalar: &TokenStream) -> Option<TokenStream> {
+        let guard = self.guard.as_ref()?;
+        Some(quote!(
+            ::juniper::Guard::<Self::Context, #scalar>::check(
+                &(#guard),
+                executor.context(),
+                &args,
+            )?;
+        ))
+    }
+
+    /// Returns generated code checking the guard of this field, if any, before resolving it
+    /// inside a `resolve_field_async` implementation.
+    ///
+    /// The failed check is returned as a ready future.
+    fn guard_check_async_tokens(&self, scalar: &TokenStream) -> Option<TokenStream> {
+        let guard = self.guard.as_ref()?;
+        Some(quote!(
+            if let Err(e) = ::juniper::Guard::<Self::Context, #scalar>::check(
+                &(#guard),
+                executor.context(),
+                args,
+            ) {
+                return Box::pin(::juniper::futures::future::ready(Err(e)));
+            }
+        ))
+    }
+}
+
 impl syn::spanned::Spanned for GraphQLTypeDefinitionField {
     fn span(&self) -> Span {
         self.span
@@ -694,6 +990,67 @@ impl<'a> syn::spanned::Spanned for &'a GraphQLTypeDefinitionField {
     }
 }
 
+/// Part of a GraphQL type name template, like `{T}Page`.
+#[derive(Debug)]
+pub enum NamePart {
+    /// Literal part of the name.
+    Literal(String),
+
+    /// Placeholder of a type parameter, substituted with the GraphQL name of its type.
+    Param(syn::Ident),
+}
+
+/// Splits the given GraphQL type `name` into literal parts and `{T}` placeholders of type
+/// parameters.
+pub fn parse_name_template(name: &str) -> Result<Vec<NamePart>, String> {
+    let mut parts = Vec::new();
+    let mut rest = name;
+    while let Some(start) = rest.find('{') {
+        if start > 0 {
+            parts.push(NamePart::Literal(rest[..start].to_owned()));
+        }
+        let end = rest[start..]
+            .find('}')
+            .map(|end| start + end)
+            .ok_or_else(|| format!("unclosed `{{` in type name `{}`", name))?;
+        let param = syn::parse_str::<syn::Ident>(&rest[start + 1..end]).map_err(|_| {
+            format!(
+                "expected type parameter within `{{}}` in type name `{}`",
+                name
+            )
+        })?;
+        parts.push(NamePart::Param(param));
+        rest = &rest[end + 1..];
+    }
+    if !rest.is_empty() {
+        parts.push(NamePart::Literal(rest.to_owned()));
+    }
+    Ok(parts)
+}
+
+/// Checks that all the placeholders of the given GraphQL type `name` template are type parameters
+/// of the given `generics`.
+pub fn check_name_template(
+    name: &SpanContainer<String>,
+    generics: &syn::Generics,
+    error: &GraphQLScope,
+) {
+    let parts = match parse_name_template(name.as_str()) {
+        Ok(parts) => parts,
+        Err(msg) => return error.emit_custom(name.span_ident(), msg),
+    };
+    for part in parts {
+        if let NamePart::Param(param) = part {
+            if !generics.type_params().any(|p| p.ident == param) {
+                error.emit_custom(
+                    name.span_ident(),
+                    format!("`{}` is not a type parameter of this type", param),
+                );
+            }
+        }
+    }
+}
+
 /// Definition of a graphql type based on information extracted
 /// by various macros.
 /// The definition can be rendered to Rust code.
@@ -704,6 +1061,8 @@ pub struct GraphQLTypeDefiniton {
     pub context: Option<syn::Type>,
     pub scalar: Option<syn::Type>,
     pub description: Option<String>,
+    pub visible: Option<syn::Expr>,
+    pub cache_control: Option<CacheControlAttr>,
     pub fields: Vec<GraphQLTypeDefinitionField>,
     pub generics: syn::Generics,
     pub interfaces: Vec<syn::Type>,
@@ -721,6 +1080,9 @@ pub struct GraphQLTypeDefiniton {
     pub generic_scalar: bool,
     // FIXME: make this redundant.
     pub no_async: bool,
+    // This flag indicates if the object has additional fields resolved by
+    // a `#[graphql_object(complex)]` impl block.
+    pub complex: bool,
 }
 
 impl GraphQLTypeDefiniton {
@@ -729,72 +1091,92 @@ impl GraphQLTypeDefiniton {
         self.fields.iter().any(|field| field.is_async)
     }
 
-    pub fn into_tokens(self) -> TokenStream {
-        let name = &self.name;
-        let ty = &self._type;
-        let context = self
-            .context
-            .as_ref()
-            .map(|ctx| quote!( #ctx ))
-            .unwrap_or_else(|| quote!(()));
+    fn field_definition_tokens(&self) -> Vec<TokenStream> {
+        self.fields
+            .iter()
+            .map(|field| {
+                let args = field.args.iter().map(|arg| {
+                    let arg_type = &arg._type;
+                    let arg_name = &arg.name;
 
-        let field_definitions = self.fields.iter().map(|field| {
-            let args = field.args.iter().map(|arg| {
-                let arg_type = &arg._type;
-                let arg_name = &arg.name;
+                    if arg.flatten {
+                        return quote!(
+                            .arguments(registry.flattened_args::<#arg_type>(info))
+                        );
+                    }
 
-                let description = match arg.description.as_ref() {
-                    Some(value) => quote!( .description( #value ) ),
+                    let description = match arg.description.as_ref() {
+                        Some(value) => quote!( .description( #value ) ),
+                        None => quote!(),
+                    };
+                    let visible = arg
+                        .visible
+                        .as_ref()
+                        .map(|pred| quote!( .visible_if(#pred) ));
+
+                    // Code.
+                    match arg.default.as_ref() {
+                        Some(value) => quote!(
+                            .argument(
+                                registry.arg_with_default::<#arg_type>(#arg_name, &#value, info)
+                                    #description
+                                    #visible
+                            )
+                        ),
+                        None => quote!(
+                            .argument(
+                                registry.arg::<#arg_type>(#arg_name, info)
+                                    #description
+                                    #visible
+                            )
+                        ),
+                    }
+                });
+
+                let description = match field.description.as_ref() {
+                    Some(description) => quote!( .description(#description) ),
                     None => quote!(),
                 };
 
-                // Code.
-                match arg.default.as_ref() {
-                    Some(value) => quote!(
-                        .argument(
-                            registry.arg_with_default::<#arg_type>(#arg_name, &#value, info)
-                                #description
-                        )
-                    ),
-                    None => quote!(
-                        .argument(
-                            registry.arg::<#arg_type>(#arg_name, info)
-                                #description
-                        )
-                    ),
-                }
-            });
+                let deprecation = match field.deprecation.as_ref() {
+                    Some(deprecation) => {
+                        if let Some(reason) = deprecation.reason.as_ref() {
+                            quote!( .deprecated(Some(#reason)) )
+                        } else {
+                            quote!( .deprecated(None) )
+                        }
+                    }
+                    None => quote!(),
+                };
 
-            let description = match field.description.as_ref() {
-                Some(description) => quote!( .description(#description) ),
-                None => quote!(),
-            };
+                let visible = field
+                    .visible
+                    .as_ref()
+                    .map(|pred| quote!( .visible_if(#pred) ));
 
-            let deprecation = match field.deprecation.as_ref() {
-                Some(deprecation) => {
-                    if let Some(reason) = deprecation.reason.as_ref() {
-                        quote!( .deprecated(Some(#reason)) )
-                    } else {
-                        quote!( .deprecated(None) )
-                    }
-                }
-                None => quote!(),
-            };
+                let cache_control = field
+                    .cache_control
+                    .as_ref()
+                    .map(CacheControlAttr::meta_tokens);
 
-            let field_name = &field.name;
+                let field_name = &field.name;
 
-            let _type = &field._type;
-            quote! {
-                registry
-                    .field_convert::<#_type, _, Self::Context>(#field_name, info)
-                    #(#args)*
-                    #description
-                    #deprecation
-            }
-        });
+                let _type = &field._type;
+                quote! {
+                    registry
+                        .field_convert::<#_type, _, Self::Context>(#field_name, info)
+                        #(#args)*
+                        #description
+                        #deprecation
+                        #visible
+                        #cache_control
+                }
+            })
+            .collect()
+    }
 
-        let scalar = self
-            .scalar
+    fn scalar_tokens(&self) -> TokenStream {
+        self.scalar
             .as_ref()
             .map(|s| quote!( #s ))
             .unwrap_or_else(|| {
@@ -805,9 +1187,123 @@ impl GraphQLTypeDefiniton {
                 } else {
                     quote!(::juniper::DefaultScalarValue)
                 }
-            });
+            })
+    }
+
+    /// Returns the type parameters substituted into the name of this type.
+    fn name_params(&self) -> Vec<syn::Ident> {
+        parse_name_template(&self.name)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|part| match part {
+                NamePart::Param(param) => Some(param),
+                NamePart::Literal(_) => None,
+            })
+            .collect()
+    }
+
+    /// Returns the expression of the `&'static str` name of this type, composed from the names of
+    /// its type parameters, if its name is a template.
+    fn name_tokens(&self, scalar: &TokenStream) -> TokenStream {
+        let parts = parse_name_template(&self.name).unwrap_or_default();
+        if self.name_params().is_empty() {
+            let name = &self.name;
+            return quote!(#name);
+        }
+
+        // Type parameters without a name are reported by `meta()` once the schema is built.
+        let parts = parts.into_iter().map(|part| match part {
+            NamePart::Literal(lit) => quote!(#lit),
+            NamePart::Param(param) => quote! {
+                <#param as ::juniper::GraphQLType<#scalar>>::name(&()).unwrap_or("Unnamed")
+            },
+        });
+        quote! {
+            ::juniper::type_name_from_parts::<Self>(|| vec![ #( #parts ),* ])
+        }
+    }
+
+    /// Returns the statements of `meta()` recording the type parameters substituted into the name
+    /// of this type which have no GraphQL name, so building the schema fails.
+    fn unnamed_params_check_tokens(&self, scalar: &TokenStream) -> TokenStream {
+        let params = self.name_params();
+        quote! {
+            #(
+                if <#params as ::juniper::GraphQLType<#scalar>>::name(&()).is_none() {
+                    registry.add_unnamed_type_param::<Self, #params>();
+                }
+            )*
+        }
+    }
+
+    /// Generates the compile-time check that the arguments flattened from input objects don't
+    /// collide with each other or with the regular arguments of the same field.
+    ///
+    /// Free constants can't refer to generic parameters, so arguments of generic types are only
+    /// checked once their `meta()` is built.
+    fn flattened_args_check_tokens(&self) -> Option<TokenStream> {
+        if !self.generics.params.is_empty() {
+            return None;
+        }
+
+        let scalar = self
+            .scalar
+            .as_ref()
+            .map(|s| quote!( #s ))
+            .unwrap_or_else(|| quote!(::juniper::DefaultScalarValue));
+
+        let checks = self
+            .fields
+            .iter()
+            .filter(|field| field.args.iter().any(|arg| arg.flatten))
+            .map(|field| {
+                let names = field
+                    .args
+                    .iter()
+                    .filter(|arg| !arg.flatten)
+                    .map(|arg| &arg.name);
+                let flattened = field.args.iter().filter(|arg| arg.flatten).map(|arg| {
+                    let ty = &arg._type;
+                    quote!( <#ty as ::juniper::InputObjectFieldNames<#scalar>>::FIELD_NAMES )
+                });
+                quote! {
+                    const _: () = ::juniper::FieldNames::Merged(&[
+                        ::juniper::FieldNames::List(&[ #( #names ),* ]),
+                        #( #flattened ),*
+                    ])
+                    .assert_no_duplicate_fields();
+                }
+            })
+            .collect::<Vec<_>>();
 
-        let resolve_matches = self.fields.iter().map(|field| {
+        if checks.is_empty() {
+            None
+        } else {
+            Some(quote!( #( #checks )* ))
+        }
+    }
+
+    /// Adds the bounds required to compose the name of this type to the given `generics`.
+    ///
+    /// Composed names are cached by the [`TypeId`] of the type, so it must be `'static`.
+    ///
+    /// [`TypeId`]: std::any::TypeId
+    fn add_name_bounds(&self, generics: &mut syn::Generics, scalar: &TokenStream) {
+        let params = self.name_params();
+        if params.is_empty() {
+            return;
+        }
+        let where_clause = generics.make_where_clause();
+        where_clause.predicates.push(parse_quote!(Self: 'static));
+        for param in params {
+            where_clause
+                .predicates
+                .push(parse_quote!(#param: ::juniper::GraphQLType<#scalar, TypeInfo = ()>));
+        }
+    }
+
+    fn resolve_match_tokens(&self, scalar: &TokenStream) -> Vec<TokenStream> {
+        self.fields.iter().map(|field| {
             let name = &field.name;
             let code = &field.resolver_code;
 
@@ -827,8 +1323,10 @@ impl GraphQLTypeDefiniton {
                     let _type = &field._type;
                     quote!(: #_type)
                 };
+                let guard_check = field.guard_check_tokens(scalar);
                 quote!(
                     #name => {
+                        #guard_check
                         let res #_type = (|| { #code })();
                         ::juniper::IntoResolvable::into(
                             res,
@@ -843,12 +1341,189 @@ impl GraphQLTypeDefiniton {
                     },
                 )
             }
-        });
+        })
+        .collect()
+    }
+
+    fn resolve_match_async_tokens(&self, scalar: &TokenStream) -> Vec<TokenStream> {
+        self.fields.iter().map(|field| {
+            let name = &field.name;
+            let code = &field.resolver_code;
+            let _type = if field.is_type_inferred {
+                quote!()
+            } else {
+                let _type = &field._type;
+                quote!(: #_type)
+            };
+
+            if field.is_async {
+                let guard_check = field.guard_check_tokens(scalar);
+                quote!(
+                    #name => {
+                        let f = async move {
+                            #guard_check
+                            let res #_type = async move { #code }.await;
+
+                            let inner_res = ::juniper::IntoResolvable::into(
+                                res,
+                                executor.context()
+                            );
+                            match inner_res {
+                                Ok(Some((ctx, r))) => {
+                                    let subexec = executor
+                                        .replaced_context(ctx);
+                                    subexec.resolve_with_ctx_async(&(), &r)
+                                        .await
+                                },
+                                Ok(None) => Ok(::juniper::Value::null()),
+                                Err(e) => Err(e),
+                            }
+                        };
+                        Box::pin(f)
+                    },
+                )
+            } else {
+                let inner = if !self.no_async {
+                    quote!(
+                        let f = async move {
+                            match res2 {
+                                Ok(Some((ctx, r))) => {
+                                    let sub = executor.replaced_context(ctx);
+                                    sub.resolve_with_ctx_async(&(), &r).await
+                                },
+                                Ok(None) => Ok(::juniper::Value::null()),
+                                Err(e) => Err(e),
+                            }
+                        };
+                        use ::juniper::futures::future;
+                        future::FutureExt::boxed(f)
+                    )
+                } else {
+                    quote!(
+                        let v = match res2 {
+                            Ok(Some((ctx, r))) => executor.replaced_context(ctx).resolve_with_ctx(&(), &r),
+                            Ok(None) => Ok(::juniper::Value::null()),
+                            Err(e) => Err(e),
+                        };
+                        use ::juniper::futures::future;
+                        Box::pin(future::ready(v))
+                    )
+                };
+
+                let guard_check = field.guard_check_async_tokens(scalar);
+                quote!(
+                    #name => {
+                        #guard_check
+                        let res #_type = (||{ #code })();
+                        let res2 = ::juniper::IntoResolvable::into(
+                            res,
+                            executor.context()
+                        );
+                        #inner
+                    },
+                )
+            }
+        })
+        .collect()
+    }
+
+    /// Returns the generics of the generated impls, including the generic scalar, if needed.
+    fn impl_generics(&self) -> syn::Generics {
+        let mut generics = self.generics.clone();
+
+        if self.scalar.is_none() && self.generic_scalar {
+            // No custom scalar specified, but always generic specified.
+            // Therefore we inject the generic scalar.
+            generics.params.push(parse_quote!(__S));
+            generics
+                .make_where_clause()
+                .predicates
+                .push(parse_quote!(__S: ::juniper::ScalarValue));
+        }
+        self.add_name_bounds(&mut generics, &self.scalar_tokens());
+
+        generics
+    }
+
+    pub fn into_tokens(self) -> TokenStream {
+        let ty = &self._type;
+        let context = self
+            .context
+            .as_ref()
+            .map(|ctx| quote!( #ctx ))
+            .unwrap_or_else(|| quote!(()));
+
+        let field_definitions = self.field_definition_tokens();
+        let scalar = self.scalar_tokens();
+        let name = self.name_tokens(&scalar);
+        let unnamed_params_check = self.unnamed_params_check_tokens(&scalar);
+        let resolve_matches = self.resolve_match_tokens(&scalar);
+
+        let (fields, complex_resolve, complex_resolve_async) = if self.complex {
+            (
+                quote! {
+                    let () = <Self as ::juniper::ObjectFieldNames<#scalar>>::NO_DUPLICATE_FIELDS;
+                    let mut fields = vec![
+                        #( #field_definitions ),*
+                    ];
+                    fields.extend(
+                        <Self as ::juniper::ComplexObject<#scalar>>::fields(info, registry),
+                    );
+                },
+                Some(quote! {
+                    if let Some(res) = <Self as ::juniper::ComplexObject<#scalar>>::resolve_field(
+                        self, _info, field, args, executor,
+                    ) {
+                        return res;
+                    }
+                }),
+                Some(quote! {
+                    if let Some(f) = <Self as ::juniper::ComplexObject<#scalar>>::resolve_field_async(
+                        self, info, field, args, executor,
+                    ) {
+                        return f;
+                    }
+                }),
+            )
+        } else {
+            (
+                quote! {
+                    let fields = [
+                        #( #field_definitions ),*
+                    ];
+                },
+                None,
+                None,
+            )
+        };
+
+        let field_names = {
+            let names = self.fields.iter().map(|field| &field.name);
+            let names = quote!( ::juniper::FieldNames::List(&[ #( #names ),* ]) );
+            if self.complex {
+                quote!(::juniper::FieldNames::Merged(&[
+                    #names,
+                    ::juniper::FieldNames::List(
+                        <Self as ::juniper::ComplexObject<#scalar>>::FIELD_NAMES,
+                    ),
+                ]))
+            } else {
+                names
+            }
+        };
 
         let description = self
             .description
             .as_ref()
             .map(|description| quote!( .description(#description) ));
+        let visible = self
+            .visible
+            .as_ref()
+            .map(|pred| quote!( .visible_if(#pred) ));
+        let cache_control = self
+            .cache_control
+            .as_ref()
+            .map(CacheControlAttr::meta_tokens);
 
         let interfaces = if !self.interfaces.is_empty() {
             let interfaces_ty = &self.interfaces;
@@ -867,100 +1542,17 @@ impl GraphQLTypeDefiniton {
         // is specified.
         let (_, type_generics, _) = self.generics.split_for_impl();
 
-        let mut generics = self.generics.clone();
-
-        if self.scalar.is_none() && self.generic_scalar {
-            // No custom scalar specified, but always generic specified.
-            // Therefore we inject the generic scalar.
-            generics.params.push(parse_quote!(__S));
-            generics
-                .make_where_clause()
-                .predicates
-                .push(parse_quote!(__S: ::juniper::ScalarValue));
-        }
-
         let type_generics_tokens = if self.include_type_generics {
             Some(type_generics)
         } else {
             None
         };
+
+        let generics = self.impl_generics();
         let (impl_generics, _, where_clause) = generics.split_for_impl();
 
         let resolve_field_async = {
-            let resolve_matches_async = self.fields.iter().map(|field| {
-                let name = &field.name;
-                let code = &field.resolver_code;
-                let _type = if field.is_type_inferred {
-                    quote!()
-                } else {
-                    let _type = &field._type;
-                    quote!(: #_type)
-                };
-
-                if field.is_async {
-                    quote!(
-                        #name => {
-                            let f = async move {
-                                let res #_type = async move { #code }.await;
-
-                                let inner_res = ::juniper::IntoResolvable::into(
-                                    res,
-                                    executor.context()
-                                );
-                                match inner_res {
-                                    Ok(Some((ctx, r))) => {
-                                        let subexec = executor
-                                            .replaced_context(ctx);
-                                        subexec.resolve_with_ctx_async(&(), &r)
-                                            .await
-                                    },
-                                    Ok(None) => Ok(::juniper::Value::null()),
-                                    Err(e) => Err(e),
-                                }
-                            };
-                            Box::pin(f)
-                        },
-                    )
-                } else {
-                    let inner = if !self.no_async {
-                        quote!(
-                            let f = async move {
-                                match res2 {
-                                    Ok(Some((ctx, r))) => {
-                                        let sub = executor.replaced_context(ctx);
-                                        sub.resolve_with_ctx_async(&(), &r).await
-                                    },
-                                    Ok(None) => Ok(::juniper::Value::null()),
-                                    Err(e) => Err(e),
-                                }
-                            };
-                            use ::juniper::futures::future;
-                            future::FutureExt::boxed(f)
-                        )
-                    } else {
-                        quote!(
-                            let v = match res2 {
-                                Ok(Some((ctx, r))) => executor.replaced_context(ctx).resolve_with_ctx(&(), &r),
-                                Ok(None) => Ok(::juniper::Value::null()),
-                                Err(e) => Err(e),
-                            };
-                            use ::juniper::futures::future;
-                            Box::pin(future::ready(v))
-                        )
-                    };
-
-                    quote!(
-                        #name => {
-                            let res #_type = (||{ #code })();
-                            let res2 = ::juniper::IntoResolvable::into(
-                                res,
-                                executor.context()
-                            );
-                            #inner
-                        },
-                    )
-                }
-            });
+            let resolve_matches_async = self.resolve_match_async_tokens(&scalar);
 
             let mut where_async = where_clause.cloned().unwrap_or_else(|| parse_quote!(where));
 
@@ -1011,6 +1603,7 @@ impl GraphQLTypeDefiniton {
                         match field {
                             #( #resolve_matches_async )*
                             _ => {
+                                #complex_resolve_async
                                 panic!("Field {} not found on type {:?}",
                                     field,
                                     <Self as ::juniper::GraphQLType<#scalar>>::name(info)
@@ -1043,7 +1636,29 @@ impl GraphQLTypeDefiniton {
             }
         });
 
+        // Fields of the complex impl block can only be checked against the struct fields once both
+        // are known, so the check is deferred to the `meta()` of generic types, while non-generic
+        // ones are checked eagerly, as even `cargo check` evaluates free constants.
+        let eager_check = if self.complex && self.generics.params.is_empty() {
+            let scalar = self
+                .scalar
+                .as_ref()
+                .map(|s| quote!( #s ))
+                .unwrap_or_else(|| quote!(::juniper::DefaultScalarValue));
+            Some(quote! {
+                const _: () = <#ty as ::juniper::ObjectFieldNames<#scalar>>::NO_DUPLICATE_FIELDS;
+            })
+        } else {
+            None
+        };
+
+        let flattened_args_check = self.flattened_args_check_tokens();
+        let implements_interfaces = !self.interfaces.is_empty();
+
         let output = quote!(
+            #eager_check
+            #flattened_args_check
+
             impl#impl_generics ::juniper::marker::IsOutputType<#scalar> for #ty #type_generics_tokens #where_clause {
                 fn mark() {
                     #( #marks )*
@@ -1053,6 +1668,12 @@ impl GraphQLTypeDefiniton {
             impl#impl_generics ::juniper::marker::GraphQLObjectType<#scalar> for #ty #type_generics_tokens #where_clause
             { }
 
+            impl#impl_generics ::juniper::ObjectFieldNames<#scalar> for #ty #type_generics_tokens #where_clause
+            {
+                const FIELD_NAMES: ::juniper::FieldNames = #field_names;
+                const IMPLEMENTS_INTERFACES: bool = #implements_interfaces;
+            }
+
         impl#impl_generics ::juniper::GraphQLType<#scalar> for #ty #type_generics_tokens
             #where_clause
         {
@@ -1066,11 +1687,12 @@ impl GraphQLTypeDefiniton {
                 ) -> ::juniper::meta::MetaType<'r, #scalar>
                     where #scalar : 'r,
                 {
-                    let fields = [
-                        #( #field_definitions ),*
-                    ];
-                    let meta = registry.build_object_type::<#ty>(info, &fields)
+                    #unnamed_params_check
+                    #fields
+                    let meta = registry.build_object_type::<Self>(info, &fields)
                         #description
+                        #visible
+                        #cache_control
                         #interfaces;
                     meta.into_meta()
                 }
@@ -1098,6 +1720,7 @@ impl GraphQLTypeDefiniton {
                     match field {
                         #( #resolve_matches )*
                         _ => {
+                            #complex_resolve
                             panic!("Field {} not found on type {:?}",
                                 field,
                                 <Self as ::juniper::GraphQLType<#scalar>>::name(_info)
@@ -1118,6 +1741,115 @@ impl GraphQLTypeDefiniton {
         output
     }
 
+    /// Generates an implementation of `juniper::ComplexObject`, providing the fields of an impl
+    /// block annotated with `#[graphql_object(complex)]` to the `GraphQLObject` derived with
+    /// `#[graphql(complex)]` on the same type.
+    pub fn into_complex_object_tokens(self) -> TokenStream {
+        let ty = &self._type;
+
+        let scalar = self.scalar_tokens();
+        let field_definitions = self.field_definition_tokens();
+        let field_names = self
+            .fields
+            .iter()
+            .map(|field| &field.name)
+            .collect::<Vec<_>>();
+        let resolve_matches = self.resolve_match_tokens(&scalar);
+        let resolve_matches_async = self.resolve_match_async_tokens(&scalar);
+
+        let (_, type_generics, _) = self.generics.split_for_impl();
+        let type_generics_tokens = if self.include_type_generics {
+            Some(type_generics)
+        } else {
+            None
+        };
+        let generics = self.impl_generics();
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+        // Resolvers are run inside closures, so guards can bail out of them the same way they do
+        // in the `GraphQLValue` implementations.
+        let (resolve_field, resolve_field_async) = if field_names.is_empty() {
+            (quote!(None), quote!(None))
+        } else {
+            (
+                quote! {
+                    match field {
+                        #( #field_names )|* => {}
+                        _ => return None,
+                    }
+                    let resolve = move || -> ::juniper::ExecutionResult<#scalar> {
+                        match field {
+                            #( #resolve_matches )*
+                            _ => unreachable!(),
+                        }
+                    };
+                    Some(resolve())
+                },
+                quote! {
+                    use ::juniper::futures::future;
+                    use ::juniper::GraphQLType;
+                    match field {
+                        #( #field_names )|* => {}
+                        _ => return None,
+                    }
+                    let resolve = move || -> ::juniper::BoxFuture<'b, ::juniper::ExecutionResult<#scalar>> {
+                        match field {
+                            #( #resolve_matches_async )*
+                            _ => unreachable!(),
+                        }
+                    };
+                    Some(resolve())
+                },
+            )
+        };
+
+        quote!(
+            #[automatically_derived]
+            impl#impl_generics ::juniper::ComplexObject<#scalar> for #ty #type_generics_tokens
+                #where_clause
+            {
+                const FIELD_NAMES: &'static [&'static str] = &[ #( #field_names ),* ];
+
+                fn fields<'r>(
+                    info: &Self::TypeInfo,
+                    registry: &mut ::juniper::Registry<'r, #scalar>,
+                ) -> Vec<::juniper::meta::Field<'r, #scalar>>
+                    where #scalar: 'r,
+                {
+                    vec![
+                        #( #field_definitions ),*
+                    ]
+                }
+
+                #[allow(unused_variables)]
+                #[allow(unused_mut)]
+                fn resolve_field(
+                    &self,
+                    _info: &Self::TypeInfo,
+                    field: &str,
+                    args: &::juniper::Arguments<#scalar>,
+                    executor: &::juniper::Executor<Self::Context, #scalar>,
+                ) -> Option<::juniper::ExecutionResult<#scalar>> {
+                    #resolve_field
+                }
+
+                #[allow(unused_variables)]
+                fn resolve_field_async<'b>(
+                    &'b self,
+                    info: &'b Self::TypeInfo,
+                    field: &'b str,
+                    args: &'b ::juniper::Arguments<#scalar>,
+                    executor: &'b ::juniper::Executor<Self::Context, #scalar>,
+                ) -> Option<::juniper::BoxFuture<'b, ::juniper::ExecutionResult<#scalar>>>
+                    where #scalar: Send + Sync,
+                          Self: Sync,
+                {
+                    #resolve_field_async
+                }
+            }
+        )
+    }
+
     pub fn into_subscription_tokens(self) -> TokenStream {
         let name = &self.name;
         let ty = &self._type;
@@ -1146,22 +1878,34 @@ impl GraphQLTypeDefiniton {
                 let arg_type = &arg._type;
                 let arg_name = &arg.name;
 
+                if arg.flatten {
+                    return quote!(
+                        .arguments(registry.flattened_args::<#arg_type>(info))
+                    );
+                }
+
                 let description = match arg.description.as_ref() {
                     Some(value) => quote!( .description( #value ) ),
                     None => quote!(),
                 };
+                let visible = arg
+                    .visible
+                    .as_ref()
+                    .map(|pred| quote!( .visible_if(#pred) ));
 
                 match arg.default.as_ref() {
                     Some(value) => quote!(
                         .argument(
                             registry.arg_with_default::<#arg_type>(#arg_name, &#value, info)
                                 #description
+                                #visible
                         )
                     ),
                     None => quote!(
                         .argument(
                             registry.arg::<#arg_type>(#arg_name, info)
                                 #description
+                                #visible
                         )
                     ),
                 }
@@ -1183,6 +1927,13 @@ impl GraphQLTypeDefiniton {
                 None => quote!(),
             };
 
+            let visible = field
+                .visible
+                .as_ref()
+                .map(|pred| quote!( .visible_if(#pred) ));
+
+            let cache_control = field.cache_control.as_ref().map(CacheControlAttr::meta_tokens);
+
             let field_name = &field.name;
 
             let type_name = &field._type;
@@ -1201,6 +1952,8 @@ impl GraphQLTypeDefiniton {
                     #(#args)*
                     #description
                     #deprecation
+                    #visible
+                    #cache_control
             }
         });
 
@@ -1208,6 +1961,14 @@ impl GraphQLTypeDefiniton {
             .description
             .as_ref()
             .map(|description| quote!( .description(#description) ));
+        let visible = self
+            .visible
+            .as_ref()
+            .map(|pred| quote!( .visible_if(#pred) ));
+        let cache_control = self
+            .cache_control
+            .as_ref()
+            .map(CacheControlAttr::meta_tokens);
 
         let interfaces = if !self.interfaces.is_empty() {
             let interfaces_ty = &self.interfaces;
@@ -1268,9 +2029,11 @@ impl GraphQLTypeDefiniton {
                     let _type_name = &field._type;
                     _type = quote!(: #_type_name);
                 };
+                let guard_check = field.guard_check_tokens(&scalar);
                 quote!(
                     #name => {
                         ::juniper::futures::FutureExt::boxed(async move {
+                            #guard_check
                             let res #_type = async { #code }.await;
                             let res = ::juniper::IntoFieldResult::<_, #scalar>::into_result(res)?;
                             let executor= executor.as_owned_executor();
@@ -1352,6 +2115,8 @@ impl GraphQLTypeDefiniton {
                         ];
                         let meta = registry.build_object_type::<#ty>(info, &fields)
                             #description
+                            #visible
+                            #cache_control
                             #interfaces;
                         meta.into_meta()
                     }
@@ -1427,13 +2192,20 @@ impl GraphQLTypeDefiniton {
             }
         );
 
+        let flattened_args_check = self.flattened_args_check_tokens();
+
         quote!(
+            #flattened_args_check
             #graphql_implementation
             #subscription_implementation
         )
     }
 
-    pub fn into_enum_tokens(self) -> TokenStream {
+    /// Generates the implementation of a GraphQL enum.
+    ///
+    /// Values of the `skipped` variants, given as patterns, can't be resolved, and are converted to
+    /// a `null` input value.
+    pub fn into_enum_tokens(self, skipped: Vec<TokenStream>) -> TokenStream {
         let name = &self.name;
         let ty = &self._type;
         let context = self
@@ -1460,6 +2232,10 @@ impl GraphQLTypeDefiniton {
             .description
             .as_ref()
             .map(|description| quote!( .description(#description) ));
+        let visible = self
+            .visible
+            .as_ref()
+            .map(|pred| quote!( .visible_if(#pred) ));
 
         let values = self.fields.iter().map(|variant| {
             let variant_name = &variant.name;
@@ -1479,11 +2255,18 @@ impl GraphQLTypeDefiniton {
                 })
                 .unwrap_or_else(|| quote!(::juniper::meta::DeprecationStatus::Current));
 
+            let visibility = variant
+                .visible
+                .as_ref()
+                .map(|pred| quote!(Some(::juniper::meta::Visibility::new(#pred))))
+                .unwrap_or_else(|| quote!(None));
+
             quote!(
                 ::juniper::meta::EnumValue {
                     name: #variant_name.to_string(),
                     description: #descr,
                     deprecation_status: #depr,
+                    visibility: #visibility,
                 },
             )
         });
@@ -1497,9 +2280,17 @@ impl GraphQLTypeDefiniton {
             )
         });
 
+        let resolves_skipped = skipped.iter().map(|pattern| {
+            quote!(
+                &#pattern => return Err(::juniper::FieldError::from(format!(
+                    "Variant of enum {} not present in the schema", #name,
+                ))),
+            )
+        });
+
         let from_inputs = self.fields.iter().map(|variant| {
             let variant_name = &variant.name;
-            let resolver_code = &variant.resolver_code;
+            let resolver_code = variant.default.as_ref().unwrap_or(&variant.resolver_code);
 
             quote!(
                 Some(#variant_name) => Some(#resolver_code),
@@ -1516,6 +2307,12 @@ impl GraphQLTypeDefiniton {
             )
         });
 
+        let to_inputs_skipped = skipped.iter().map(|pattern| {
+            quote!(
+                &#pattern => ::juniper::InputValue::null(),
+            )
+        });
+
         let mut generics = self.generics.clone();
 
         if self.scalar.is_none() && self.generic_scalar {
@@ -1580,6 +2377,7 @@ impl GraphQLTypeDefiniton {
                         #( #values )*
                     ])
                     #description
+                    #visible
                     .into_meta()
                 }
             }
@@ -1602,6 +2400,7 @@ impl GraphQLTypeDefiniton {
                 ) -> ::juniper::ExecutionResult<#scalar> {
                     let v = match self {
                         #( #resolves )*
+                        #( #resolves_skipped )*
                     };
                     Ok(v)
                 }
@@ -1627,6 +2426,7 @@ impl GraphQLTypeDefiniton {
                 fn to_input_value(&self) -> ::juniper::InputValue<#scalar> {
                     match self {
                         #( #to_inputs )*
+                        #( #to_inputs_skipped )*
                     }
                 }
             }
@@ -1640,7 +2440,6 @@ impl GraphQLTypeDefiniton {
     }
 
     pub fn into_input_object_tokens(self) -> TokenStream {
-        let name = &self.name;
         let ty = &self._type;
         let context = self
             .context
@@ -1661,6 +2460,8 @@ impl GraphQLTypeDefiniton {
                     quote!(::juniper::DefaultScalarValue)
                 }
             });
+        let name = self.name_tokens(&scalar);
+        let unnamed_params_check = self.unnamed_params_check_tokens(&scalar);
 
         let meta_fields = self
             .fields
@@ -1699,11 +2500,17 @@ impl GraphQLTypeDefiniton {
                     }
                 };
 
+                let visible = field
+                    .visible
+                    .as_ref()
+                    .map(|pred| quote!( .visible_if(#pred) ));
+
                 quote!(
                     {
                         #create_meta_field
                         #description
                         #deprecation
+                        #visible
                     },
                 )
             })
@@ -1752,10 +2559,16 @@ impl GraphQLTypeDefiniton {
             })
             .collect::<Vec<_>>();
 
+        let field_names = self.fields.iter().map(|field| &field.name);
+
         let description = self
             .description
             .as_ref()
             .map(|description| quote!( .description(#description) ));
+        let visible = self
+            .visible
+            .as_ref()
+            .map(|pred| quote!( .visible_if(#pred) ));
 
         // Preserve the original type_generics before modification,
         // since alteration makes them invalid if self.generic_scalar
@@ -1776,6 +2589,7 @@ impl GraphQLTypeDefiniton {
                 .predicates
                 .push(parse_quote!(__S: ::juniper::ScalarValue));
         }
+        self.add_name_bounds(&mut generics, &scalar);
 
         let type_generics_tokens = if self.include_type_generics {
             Some(type_generics)
@@ -1824,11 +2638,13 @@ impl GraphQLTypeDefiniton {
                 ) -> ::juniper::meta::MetaType<'r, #scalar>
                 where #scalar: 'r
                 {
+                    #unnamed_params_check
                     let fields = &[
                         #( #meta_fields )*
                     ];
-                    registry.build_input_object_type::<#ty>(&(), fields)
+                    registry.build_input_object_type::<Self>(&(), fields)
                     #description
+                    #visible
                     .into_meta()
                 }
             }
@@ -1870,6 +2686,14 @@ impl GraphQLTypeDefiniton {
                     ].into_iter().collect())
                 }
             }
+
+            impl#impl_generics ::juniper::InputObjectFieldNames<#scalar> for #ty #type_generics_tokens
+                #where_clause
+            {
+                const FIELD_NAMES: ::juniper::FieldNames = ::juniper::FieldNames::List(&[
+                    #( #field_names ),*
+                ]);
+            }
         );
 
         if !self.no_async {
@@ -2017,6 +2841,39 @@ mod test {
         assert_eq!(to_upper_snake_case("some_INpuT"), "SOME_INPU_T");
     }
 
+    #[test]
+    fn test_rename_rules() {
+        let cases = [
+            ("none", "first_second", "first_second"),
+            ("verbatim", "FirstSecond", "FirstSecond"),
+            ("lowercase", "first_second", "first_second"),
+            ("lowercase", "FirstSecond", "firstsecond"),
+            ("UPPERCASE", "first_second", "FIRST_SECOND"),
+            ("UPPERCASE", "FirstSecond", "FIRSTSECOND"),
+            ("PascalCase", "first_second", "FirstSecond"),
+            ("PascalCase", "FirstSecond", "FirstSecond"),
+            ("PascalCase", "", ""),
+            ("camelCase", "first_second", "firstSecond"),
+            ("camelCase", "FirstSecond", "firstSecond"),
+            ("camelCase", "", ""),
+            ("snake_case", "first_second", "first_second"),
+            ("snake_case", "FirstSecond", "first_second"),
+            ("snake_case", "firstSecond", "first_second"),
+            ("SCREAMING_SNAKE_CASE", "FirstSecond", "FIRST_SECOND"),
+        ];
+        for (rule, input, expected) in &cases {
+            let rule = RenameRule::from_str(rule).unwrap();
+            assert_eq!(rule.apply(input), *expected, "{:?} of {}", rule, input);
+        }
+
+        assert!(RenameRule::from_str("kebab-case").is_err());
+
+        // The default rule keeps the first character as is.
+        assert_eq!(RenameRule::CamelCase.apply("first_second"), "firstSecond");
+        assert_eq!(RenameRule::CamelCase.apply("ID"), "ID");
+        assert_eq!(RenameRule::CamelCase.apply("URL"), "URL");
+    }
+
     #[test]
     fn test_is_valid_name() {
         assert_eq!(is_valid_name("yesItIs"), true);
@@ -1,15 +1,32 @@
 #![doc(html_root_url = "https://docs.rs/juniper_hyper/0.2.0")]
 
-use std::{error::Error, fmt, string::FromUtf8Error, sync::Arc};
+#[cfg(feature = "tower")]
+pub mod service;
+#[cfg(feature = "subscriptions")]
+pub mod subscriptions;
+
+#[cfg(feature = "sse")]
+use std::convert::Infallible;
+use std::{error::Error, fmt, mem, net::SocketAddr, string::FromUtf8Error, sync::Arc};
+
+#[cfg(feature = "sse")]
+use futures::stream::{BoxStream, StreamExt as _};
 
 use hyper::{
     header::{self, HeaderValue},
     Body, Method, Request, Response, StatusCode,
 };
+#[cfg(feature = "multipart")]
+use juniper::http::multipart::{self, MultipartError, MultipartLimits};
 use juniper::{
-    http::{GraphQLBatchRequest, GraphQLRequest as JuniperGraphQLRequest, GraphQLRequest},
+    http::{
+        BatchConfig, ContextError, ContextFactory, GraphQLBatchRequest,
+        GraphQLRequest as JuniperGraphQLRequest, GraphQLRequest, RequestParts, ResponseMediaType,
+    },
     GraphQLSubscriptionType, GraphQLType, GraphQLTypeAsync, InputValue, RootNode, ScalarValue,
 };
+#[cfg(feature = "sse")]
+use juniper_subscriptions::sse;
 use serde_json::error::Error as SerdeError;
 use url::form_urlencoded;
 
@@ -28,8 +45,14 @@ where
     CtxT: Sync,
     S: ScalarValue + Send + Sync,
 {
+    let is_get = req.method() == Method::GET;
+    let media_type = response_media_type(&req);
+    let batch_config = batch_config(&req);
     match parse_req(req).await {
-        Ok(req) => execute_request_sync(root_node, context, req).await,
+        Ok(req) => {
+            let req = if is_get { req.forbid_mutations() } else { req };
+            execute_request_sync(root_node, context, req, media_type, batch_config).await
+        }
         Err(resp) => resp,
     }
 }
@@ -49,12 +72,197 @@ where
     CtxT: Sync,
     S: ScalarValue + Send + Sync,
 {
+    let is_get = req.method() == Method::GET;
+    let media_type = response_media_type(&req);
+    let batch_config = batch_config(&req);
     match parse_req(req).await {
-        Ok(req) => execute_request(root_node, context, req).await,
+        Ok(req) => {
+            let req = if is_get { req.forbid_mutations() } else { req };
+            execute_request(root_node, context, req, media_type, batch_config).await
+        }
         Err(resp) => resp,
     }
 }
 
+/// Executes the given request against the given `root_node`, with the context created by the
+/// given [`ContextFactory`].
+///
+/// Responds with the status code of the [`ContextError`] if the context can't be created. See
+/// [`create_context()`] for what the [`RequestParts`] are filled with.
+pub async fn graphql_with_context_factory<CtxT, QueryT, MutationT, SubscriptionT, S, F>(
+    root_node: Arc<RootNode<'static, QueryT, MutationT, SubscriptionT, S>>,
+    context_factory: &F,
+    mut req: Request<Body>,
+) -> Response<Body>
+where
+    QueryT: GraphQLTypeAsync<S, Context = CtxT>,
+    QueryT::TypeInfo: Sync,
+    MutationT: GraphQLTypeAsync<S, Context = CtxT>,
+    MutationT::TypeInfo: Sync,
+    SubscriptionT: GraphQLSubscriptionType<S, Context = CtxT>,
+    SubscriptionT::TypeInfo: Sync,
+    CtxT: Sync,
+    S: ScalarValue + Send + Sync,
+    F: ContextFactory<CtxT> + ?Sized,
+{
+    match create_context(context_factory, &mut req).await {
+        Ok(context) => graphql(root_node, Arc::new(context), req).await,
+        Err(e) => context_error_response::<S>(e),
+    }
+}
+
+/// Creates the context of the given request with the given [`ContextFactory`].
+///
+/// The [`RequestParts::extensions`] hold the [`http::Extensions`] of the request, which are put
+/// back into the request afterwards, and the [`RequestParts::remote_addr`] is taken from the
+/// [`SocketAddr`] extension of the request, if any.
+///
+/// [`http::Extensions`]: hyper::http::Extensions
+pub async fn create_context<CtxT, F, B>(
+    context_factory: &F,
+    req: &mut Request<B>,
+) -> Result<CtxT, ContextError>
+where
+    F: ContextFactory<CtxT> + ?Sized,
+{
+    let mut parts = RequestParts::new(req.method().as_str(), req.uri().to_string());
+    for (name, value) in req.headers() {
+        if let Ok(value) = value.to_str() {
+            parts.append_header(name, value);
+        }
+    }
+    parts.remote_addr = req.extensions().get::<SocketAddr>().copied();
+    parts.extensions.insert(mem::take(req.extensions_mut()));
+
+    let context = context_factory.create(&parts).await;
+
+    if let Some(extensions) = parts.extensions.remove() {
+        *req.extensions_mut() = extensions;
+    }
+    context
+}
+
+/// Handles a [GraphQL over SSE][1] request, streaming the results of the requested operation as
+/// server-sent events.
+///
+/// Requests carrying an event stream token, either in the [`sse::TOKEN_HEADER`] or in the `token`
+/// query parameter, are handled in the "single connection" mode:
+/// - `PUT` reserves a new event stream, responding with its token;
+/// - `GET` opens the reserved event stream;
+/// - `POST` executes an operation over the event stream, responding with `202 Accepted`;
+/// - `DELETE` cancels the operation specified in the `operationId` query parameter.
+///
+/// Any other request is executed over its own event stream, as of the "distinct connections" mode.
+///
+/// [1]: https://github.com/enisdenjo/graphql-sse/blob/master/PROTOCOL.md
+#[cfg(feature = "sse")]
+pub async fn graphql_sse<CtxT, QueryT, MutationT, SubscriptionT, S>(
+    event_streams: Arc<sse::EventStreams<QueryT, MutationT, SubscriptionT, CtxT, S>>,
+    context: Arc<CtxT>,
+    req: Request<Body>,
+) -> Response<Body>
+where
+    QueryT: GraphQLTypeAsync<S, Context = CtxT> + Send + Sync + 'static,
+    QueryT::TypeInfo: Send + Sync,
+    MutationT: GraphQLTypeAsync<S, Context = CtxT> + Send + Sync + 'static,
+    MutationT::TypeInfo: Send + Sync,
+    SubscriptionT: GraphQLSubscriptionType<S, Context = CtxT> + Send + Sync + 'static,
+    SubscriptionT::TypeInfo: Send + Sync,
+    CtxT: Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+{
+    let token = sse::stream_token(
+        req.headers()
+            .get(sse::TOKEN_HEADER)
+            .and_then(|v| v.to_str().ok()),
+        req.uri().query(),
+    )
+    .map(str::to_owned);
+
+    match (req.method(), token) {
+        (&Method::PUT, _) => {
+            let mut resp = new_response(StatusCode::CREATED);
+            resp.headers_mut().insert(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("text/plain; charset=utf-8"),
+            );
+            *resp.body_mut() = Body::from(event_streams.reserve());
+            resp
+        }
+        (&Method::GET, Some(token)) => match event_streams.open(&token) {
+            Ok(events) => sse_response(events),
+            Err(e) => sse_error_response(e),
+        },
+        (&Method::DELETE, Some(token)) => match sse::operation_id(req.uri().query()) {
+            Some(id) => match event_streams.cancel(&token, id) {
+                Ok(()) => new_response(StatusCode::OK),
+                Err(e) => sse_error_response(e),
+            },
+            None => render_error(GraphQLRequestError::Invalid(
+                "'operationId' parameter is missing".to_string(),
+            )),
+        },
+        (_, token) => {
+            let is_get = req.method() == Method::GET;
+            let request = match parse_req(req).await {
+                Ok(GraphQLBatchRequest::Single(request)) => request,
+                Ok(GraphQLBatchRequest::Batch(_)) => {
+                    return render_error(GraphQLRequestError::Invalid(
+                        "batch requests are not supported over SSE".to_string(),
+                    ))
+                }
+                Err(resp) => return resp,
+            };
+            let request = if is_get {
+                request.forbid_mutations()
+            } else {
+                request
+            };
+            match token {
+                Some(token) => match event_streams.execute(&token, request, context) {
+                    Ok(()) => new_response(StatusCode::ACCEPTED),
+                    Err(e) => sse_error_response(e),
+                },
+                None => sse_response(event_streams.subscribe(request, context)),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "sse")]
+fn sse_response(events: BoxStream<'static, sse::Event>) -> Response<Body> {
+    let mut resp = new_response(StatusCode::OK);
+    resp.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static(sse::CONTENT_TYPE),
+    );
+    resp.headers_mut()
+        .insert(header::CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+    *resp.body_mut() =
+        Body::wrap_stream(events.map(|event| Ok::<_, Infallible>(event.to_string())));
+    resp
+}
+
+#[cfg(feature = "sse")]
+fn sse_error_response(err: sse::SseError) -> Response<Body> {
+    let mut resp = new_response(StatusCode::from_u16(err.status_code()).unwrap());
+    *resp.body_mut() = Body::from(err.to_string());
+    resp
+}
+
+fn response_media_type(req: &Request<Body>) -> ResponseMediaType {
+    let accept = req.headers().get(header::ACCEPT);
+    ResponseMediaType::negotiate(accept.and_then(|v| v.to_str().ok()))
+}
+
+/// Returns the [`BatchConfig`] from the extensions of the `req`, if any.
+fn batch_config(req: &Request<Body>) -> BatchConfig {
+    req.extensions()
+        .get::<BatchConfig>()
+        .copied()
+        .unwrap_or_default()
+}
+
 async fn parse_req<S: ScalarValue>(
     req: Request<Body>,
 ) -> Result<GraphQLBatchRequest<S>, Response<Body>> {
@@ -68,6 +276,11 @@ async fn parse_req<S: ScalarValue>(
             match content_type {
                 Some(Ok("application/json")) => parse_post_json_req(req.into_body()).await,
                 Some(Ok("application/graphql")) => parse_post_graphql_req(req.into_body()).await,
+                #[cfg(feature = "multipart")]
+                Some(Ok(ct)) if multipart::is_multipart_request(ct) => {
+                    let ct = ct.to_owned();
+                    parse_post_multipart_req(&ct, req).await
+                }
                 _ => return Err(new_response(StatusCode::BAD_REQUEST)),
             }
         }
@@ -118,6 +331,39 @@ async fn parse_post_graphql_req<S: ScalarValue>(
     )))
 }
 
+/// Parses a GraphQL multipart request while its body is being received, with the
+/// [`MultipartLimits`] from the extensions of the `req`, if any.
+#[cfg(feature = "multipart")]
+async fn parse_post_multipart_req<S: ScalarValue>(
+    content_type: &str,
+    req: Request<Body>,
+) -> Result<GraphQLBatchRequest<S>, GraphQLRequestError> {
+    use hyper::body::HttpBody as _;
+
+    multipart::check_preflight(|name| {
+        req.headers()
+            .get(name)
+            .map_or(false, |v| !v.as_bytes().is_empty())
+    })
+    .map_err(GraphQLRequestError::Multipart)?;
+    let limits = req
+        .extensions()
+        .get::<MultipartLimits>()
+        .copied()
+        .unwrap_or_default();
+
+    let mut parser = multipart::MultipartParser::new(content_type, &limits)
+        .map_err(GraphQLRequestError::Multipart)?;
+    let mut body = req.into_body();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.map_err(GraphQLRequestError::BodyHyper)?;
+        parser
+            .feed(&chunk)
+            .map_err(GraphQLRequestError::Multipart)?;
+    }
+    parser.finish().map_err(GraphQLRequestError::Multipart)
+}
+
 pub async fn graphiql(
     graphql_endpoint: &str,
     subscriptions_endpoint: Option<&str>,
@@ -143,9 +389,35 @@ pub async fn playground(
     resp
 }
 
+fn context_error_response<S: ScalarValue>(err: ContextError) -> Response<Body> {
+    let code = StatusCode::from_u16(err.status_code()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    let mut resp = new_response(code);
+    resp.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+    *resp.body_mut() = Body::from(serde_json::to_string_pretty(&err.into_response::<S>()).unwrap());
+    resp
+}
+
+fn mutation_over_get_response() -> Response<Body> {
+    let mut resp = new_response(StatusCode::METHOD_NOT_ALLOWED);
+    resp.headers_mut()
+        .insert(header::ALLOW, HeaderValue::from_static("POST"));
+    *resp.body_mut() = Body::from("mutations are not allowed over GET");
+    resp
+}
+
 fn render_error(err: GraphQLRequestError) -> Response<Body> {
     let message = format!("{}", err);
-    let mut resp = new_response(StatusCode::BAD_REQUEST);
+    let status = match err {
+        #[cfg(feature = "multipart")]
+        GraphQLRequestError::Multipart(ref e) if e.is_server_error() => {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+        _ => StatusCode::BAD_REQUEST,
+    };
+    let mut resp = new_response(status);
     *resp.body_mut() = Body::from(message);
     resp
 }
@@ -154,6 +426,8 @@ async fn execute_request_sync<CtxT, QueryT, MutationT, SubscriptionT, S>(
     root_node: Arc<RootNode<'static, QueryT, MutationT, SubscriptionT, S>>,
     context: Arc<CtxT>,
     request: GraphQLBatchRequest<S>,
+    media_type: ResponseMediaType,
+    batch_config: BatchConfig,
 ) -> Response<Body>
 where
     QueryT: GraphQLType<S, Context = CtxT>,
@@ -165,18 +439,23 @@ where
     CtxT: Sync,
     S: ScalarValue + Send + Sync,
 {
-    let res = request.execute_sync(&*root_node, &context);
+    let res = request.execute_sync_with_batch_config(&*root_node, &context, batch_config);
+    if res.is_rejected_mutation() {
+        return mutation_over_get_response();
+    }
     let body = Body::from(serde_json::to_string_pretty(&res).unwrap());
-    let code = if res.is_ok() {
-        StatusCode::OK
-    } else {
-        StatusCode::BAD_REQUEST
-    };
+    let code = StatusCode::from_u16(res.status_code()).unwrap();
     let mut resp = new_response(code);
     resp.headers_mut().insert(
         header::CONTENT_TYPE,
-        HeaderValue::from_static("application/json"),
+        HeaderValue::from_static(media_type.as_str()),
     );
+    if let Some(cache_control) = res.cache_policy().and_then(|p| p.header_value()) {
+        resp.headers_mut().insert(
+            header::CACHE_CONTROL,
+            HeaderValue::from_str(&cache_control).unwrap(),
+        );
+    }
     *resp.body_mut() = body;
     resp
 }
@@ -185,6 +464,8 @@ async fn execute_request<CtxT, QueryT, MutationT, SubscriptionT, S>(
     root_node: Arc<RootNode<'static, QueryT, MutationT, SubscriptionT, S>>,
     context: Arc<CtxT>,
     request: GraphQLBatchRequest<S>,
+    media_type: ResponseMediaType,
+    batch_config: BatchConfig,
 ) -> Response<Body>
 where
     QueryT: GraphQLTypeAsync<S, Context = CtxT>,
@@ -196,18 +477,25 @@ where
     CtxT: Sync,
     S: ScalarValue + Send + Sync,
 {
-    let res = request.execute(&*root_node, &context).await;
+    let res = request
+        .execute_with_batch_config(&*root_node, &context, batch_config)
+        .await;
+    if res.is_rejected_mutation() {
+        return mutation_over_get_response();
+    }
     let body = Body::from(serde_json::to_string_pretty(&res).unwrap());
-    let code = if res.is_ok() {
-        StatusCode::OK
-    } else {
-        StatusCode::BAD_REQUEST
-    };
+    let code = StatusCode::from_u16(res.status_code()).unwrap();
     let mut resp = new_response(code);
     resp.headers_mut().insert(
         header::CONTENT_TYPE,
-        HeaderValue::from_static("application/json"),
+        HeaderValue::from_static(media_type.as_str()),
     );
+    if let Some(cache_control) = res.cache_policy().and_then(|p| p.header_value()) {
+        resp.headers_mut().insert(
+            header::CACHE_CONTROL,
+            HeaderValue::from_str(&cache_control).unwrap(),
+        );
+    }
     *resp.body_mut() = body;
     resp
 }
@@ -219,6 +507,7 @@ where
     let mut query = None;
     let operation_name = None;
     let mut variables = None;
+    let mut extensions = None;
     for (key, value) in form_urlencoded::parse(input.as_bytes()).into_owned() {
         match key.as_ref() {
             "query" => {
@@ -243,11 +532,23 @@ where
                     Err(e) => return Err(e),
                 }
             }
+            "extensions" => {
+                if extensions.is_some() {
+                    return Err(invalid_err("extensions"));
+                }
+                match serde_json::from_str::<InputValue<S>>(&value)
+                    .map_err(GraphQLRequestError::Extensions)
+                {
+                    Ok(parsed_extensions) => extensions = Some(parsed_extensions),
+                    Err(e) => return Err(e),
+                }
+            }
             _ => continue,
         }
     }
     match query {
-        Some(query) => Ok(JuniperGraphQLRequest::new(query, operation_name, variables)),
+        Some(query) => Ok(JuniperGraphQLRequest::new(query, operation_name, variables)
+            .with_extensions(extensions)),
         None => Err(GraphQLRequestError::Invalid(
             "'query' parameter is missing".to_string(),
         )),
@@ -282,6 +583,9 @@ enum GraphQLRequestError {
     BodyUtf8(FromUtf8Error),
     BodyJSONError(SerdeError),
     Variables(SerdeError),
+    Extensions(SerdeError),
+    #[cfg(feature = "multipart")]
+    Multipart(MultipartError),
     Invalid(String),
 }
 
@@ -292,6 +596,9 @@ impl fmt::Display for GraphQLRequestError {
             GraphQLRequestError::BodyUtf8(ref err) => fmt::Display::fmt(err, &mut f),
             GraphQLRequestError::BodyJSONError(ref err) => fmt::Display::fmt(err, &mut f),
             GraphQLRequestError::Variables(ref err) => fmt::Display::fmt(err, &mut f),
+            GraphQLRequestError::Extensions(ref err) => fmt::Display::fmt(err, &mut f),
+            #[cfg(feature = "multipart")]
+            GraphQLRequestError::Multipart(ref err) => fmt::Display::fmt(err, &mut f),
             GraphQLRequestError::Invalid(ref err) => fmt::Display::fmt(err, &mut f),
         }
     }
@@ -304,6 +611,9 @@ impl Error for GraphQLRequestError {
             GraphQLRequestError::BodyUtf8(ref err) => Some(err),
             GraphQLRequestError::BodyJSONError(ref err) => Some(err),
             GraphQLRequestError::Variables(ref err) => Some(err),
+            GraphQLRequestError::Extensions(ref err) => Some(err),
+            #[cfg(feature = "multipart")]
+            GraphQLRequestError::Multipart(ref err) => Some(err),
             GraphQLRequestError::Invalid(_) => None,
         }
     }
@@ -312,12 +622,13 @@ impl Error for GraphQLRequestError {
 #[cfg(test)]
 mod tests {
     use hyper::{
+        header,
         server::Server,
         service::{make_service_fn, service_fn},
-        Body, Method, Response, StatusCode,
+        Body, Method, Request, Response, StatusCode,
     };
     use juniper::{
-        http::tests as http_tests,
+        http::{tests as http_tests, BatchConfig, ContextError, RequestParts},
         tests::fixtures::starwars::schema::{Database, Query},
         EmptyMutation, EmptySubscription, RootNode,
     };
@@ -452,4 +763,379 @@ mod tests {
     async fn test_sync_hyper_integration() {
         run_hyper_integration(true).await
     }
+
+    #[cfg(feature = "tower")]
+    async fn run_tower_integration(with_layer: bool) {
+        use super::service::{GraphQLLayer, GraphQLService};
+        use futures::FutureExt as _;
+        use hyper::service::service_fn;
+        use tower_layer::Layer as _;
+
+        let port = if with_layer { 3004 } else { 3003 };
+        let addr: SocketAddr = ([127, 0, 0, 1], port).into();
+
+        let root_node = RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        );
+        let service = GraphQLService::new(root_node, |_: &_| {
+            futures::future::ready(Ok(Database::new()))
+        });
+
+        let (shutdown_fut, shutdown) = futures::future::abortable(async {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+        });
+        let shutdown_fut = async {
+            shutdown_fut.await.unwrap_err();
+        };
+
+        let server = if with_layer {
+            let not_found = service_fn(|_| async {
+                let mut resp = Response::new(Body::empty());
+                *resp.status_mut() = StatusCode::NOT_FOUND;
+                Ok::<_, Infallible>(resp)
+            });
+            let route = GraphQLLayer::new("/graphql", service).layer(not_found);
+            Server::bind(&addr)
+                .serve(make_service_fn(move |_| {
+                    let route = route.clone();
+                    async move { Ok::<_, Infallible>(route) }
+                }))
+                .with_graceful_shutdown(shutdown_fut)
+                .boxed()
+        } else {
+            Server::bind(&addr)
+                .serve(make_service_fn(move |_| {
+                    let service = service.clone();
+                    async move { Ok::<_, Infallible>(service) }
+                }))
+                .with_graceful_shutdown(shutdown_fut)
+                .boxed()
+        };
+
+        tokio::task::spawn_blocking(move || {
+            thread::sleep(Duration::from_millis(10)); // wait 10ms for server to bind
+            let integration = TestHyperIntegration { port };
+            http_tests::run_http_test_suite(&integration);
+            shutdown.abort();
+        });
+
+        if let Err(e) = server.await {
+            eprintln!("server error: {}", e);
+        }
+    }
+
+    #[cfg(feature = "tower")]
+    #[tokio::test]
+    async fn test_tower_service_integration() {
+        run_tower_integration(false).await
+    }
+
+    #[cfg(feature = "tower")]
+    #[tokio::test]
+    async fn test_tower_layer_integration() {
+        run_tower_integration(true).await
+    }
+
+    #[cfg(feature = "subscriptions")]
+    struct TestHyperWsIntegration {
+        port: u16,
+    }
+
+    #[cfg(feature = "subscriptions")]
+    impl TestHyperWsIntegration {
+        async fn run_async(
+            &self,
+            messages: Vec<http_tests::WsIntegrationMessage>,
+        ) -> Result<(), anyhow::Error> {
+            use futures::{SinkExt as _, StreamExt as _};
+            use tokio_tungstenite::tungstenite::Message;
+
+            let url = format!("ws://127.0.0.1:{}/subscriptions", self.port);
+            let (mut websocket, _) = tokio_tungstenite::connect_async(url)
+                .await
+                .map_err(|e| anyhow::anyhow!("WS error: {:?}", e))?;
+
+            for message in messages {
+                match message {
+                    http_tests::WsIntegrationMessage::Send(body) => {
+                        websocket
+                            .send(Message::Text(body))
+                            .await
+                            .map_err(|e| anyhow::anyhow!("WS error: {:?}", e))?;
+                    }
+                    http_tests::WsIntegrationMessage::Expect(body, message_timeout) => {
+                        let message = tokio::time::timeout(
+                            Duration::from_millis(message_timeout),
+                            websocket.next(),
+                        )
+                        .await
+                        .map_err(|_| anyhow::anyhow!("Timed-out waiting for message"))?
+                        .ok_or_else(|| anyhow::anyhow!("Empty message received"))?
+                        .map_err(|e| anyhow::anyhow!("WS error: {:?}", e))?;
+
+                        let text = match message {
+                            Message::Text(text) => text,
+                            _ => return Err(anyhow::anyhow!("Received non-text message")),
+                        };
+                        let expected_value = serde_json::from_str::<serde_json::Value>(&body)
+                            .map_err(|e| anyhow::anyhow!("Serde error: {:?}", e))?;
+                        let value = serde_json::from_str::<serde_json::Value>(&text)
+                            .map_err(|e| anyhow::anyhow!("Serde error: {:?}", e))?;
+                        if value != expected_value {
+                            return Err(anyhow::anyhow!(
+                                "Expected message: {}. Received message: {}",
+                                expected_value,
+                                value,
+                            ));
+                        }
+                    }
+                }
+            }
+
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "subscriptions")]
+    impl http_tests::WsIntegration for TestHyperWsIntegration {
+        fn run(
+            &self,
+            messages: Vec<http_tests::WsIntegrationMessage>,
+        ) -> juniper::LocalBoxFuture<'_, Result<(), anyhow::Error>> {
+            Box::pin(self.run_async(messages))
+        }
+    }
+
+    #[cfg(feature = "subscriptions")]
+    #[tokio::test]
+    async fn test_hyper_ws_integration() {
+        use juniper::tests::fixtures::starwars::schema::Subscription;
+        use juniper_graphql_ws::ConnectionConfig;
+
+        let port = 3005;
+        let addr: SocketAddr = ([127, 0, 0, 1], port).into();
+
+        let root_node = Arc::new(RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            Subscription,
+        ));
+
+        let new_service = make_service_fn(move |_| {
+            let root_node = root_node.clone();
+            async move {
+                Ok::<_, hyper::Error>(service_fn(move |req| {
+                    let root_node = root_node.clone();
+                    async move {
+                        let config = ConnectionConfig::new(Database::new());
+                        Ok::<_, Infallible>(
+                            super::subscriptions::graphql_ws(root_node, config, req).await,
+                        )
+                    }
+                }))
+            }
+        });
+
+        let (shutdown_fut, shutdown) = futures::future::abortable(async {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+        });
+        let server = Server::bind(&addr)
+            .serve(new_service)
+            .with_graceful_shutdown(async {
+                shutdown_fut.await.unwrap_err();
+            });
+        let server = tokio::spawn(server);
+
+        http_tests::run_ws_test_suite(&TestHyperWsIntegration { port }).await;
+
+        shutdown.abort();
+        server.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_graphql_response_media_type() {
+        let root_node = Arc::new(RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        ));
+
+        let req = Request::get("/graphql?query=%7Bhero%7Bname%7D%7D")
+            .header(header::ACCEPT, "application/graphql-response+json")
+            .body(Body::empty())
+            .unwrap();
+        let resp = super::graphql(root_node.clone(), Arc::new(Database::new()), req).await;
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers()[header::CONTENT_TYPE],
+            "application/graphql-response+json",
+        );
+
+        // {hero{blah}}
+        let req = Request::get("/graphql?query=%7Bhero%7Bblah%7D%7D")
+            .header(header::ACCEPT, "application/graphql-response+json")
+            .body(Body::empty())
+            .unwrap();
+        let resp = super::graphql(root_node, Arc::new(Database::new()), req).await;
+
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            resp.headers()[header::CONTENT_TYPE],
+            "application/graphql-response+json",
+        );
+    }
+
+    #[tokio::test]
+    async fn test_graphql_batch_config() {
+        let root_node = Arc::new(RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        ));
+
+        let mut req = Request::post("/graphql")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(
+                r#"[{"query": "{ hero { name } }"}, {"query": "{ hero { id } }"}]"#,
+            ))
+            .unwrap();
+        req.extensions_mut().insert(BatchConfig {
+            max_size: Some(1),
+            ..BatchConfig::default()
+        });
+        let resp = super::graphql(root_node, Arc::new(Database::new()), req).await;
+        let body = hyper::body::to_bytes(resp.into_body()).await.unwrap();
+
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+            serde_json::json!({"errors": [{
+                "message": "batch of 2 operations exceeds the limit of 1 operations",
+                "extensions": {"code": "BATCH_TOO_LARGE", "maxBatchSize": 1},
+            }]}),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_graphql_with_context_factory() {
+        let root_node = Arc::new(RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        ));
+        let context_factory = |parts: &RequestParts| {
+            futures::future::ready(match parts.header("authorization") {
+                Some("secret") => Ok(Database::new()),
+                _ => Err(ContextError::unauthorized("invalid token")),
+            })
+        };
+        let req = |token| {
+            Request::get("/graphql?query=%7Bhero%7Bname%7D%7D")
+                .header(header::AUTHORIZATION, token)
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let resp =
+            super::graphql_with_context_factory(root_node.clone(), &context_factory, req("wrong"))
+                .await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        let body = hyper::body::to_bytes(resp.into_body()).await.unwrap();
+        assert!(String::from_utf8_lossy(&body).contains("invalid token"));
+
+        let resp =
+            super::graphql_with_context_factory(root_node, &context_factory, req("secret")).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[cfg(feature = "sse")]
+    type EventStreams = juniper_subscriptions::sse::EventStreams<
+        Query,
+        EmptyMutation<Database>,
+        EmptySubscription<Database>,
+        Database,
+        juniper::DefaultScalarValue,
+    >;
+
+    #[cfg(feature = "sse")]
+    fn event_streams() -> Arc<EventStreams> {
+        let root_node = RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        );
+        Arc::new(EventStreams::new(
+            Arc::new(juniper_subscriptions::Coordinator::new(root_node)),
+            None,
+        ))
+    }
+
+    #[cfg(feature = "sse")]
+    #[tokio::test]
+    async fn test_graphql_sse_distinct_connections() {
+        let req = Request::post("/graphql/stream")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(r#"{"query": "{ hero { name } }"}"#))
+            .unwrap();
+        let resp = super::graphql_sse(event_streams(), Arc::new(Database::new()), req).await;
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers()[header::CONTENT_TYPE], "text/event-stream");
+        let body = hyper::body::to_bytes(resp.into_body()).await.unwrap();
+        assert_eq!(
+            body,
+            "event: next\ndata: {\"data\":{\"hero\":{\"name\":\"R2-D2\"}}}\n\n\
+             event: complete\ndata:\n\n",
+        );
+    }
+
+    #[cfg(feature = "sse")]
+    #[tokio::test]
+    async fn test_graphql_sse_single_connection() {
+        use futures::StreamExt as _;
+
+        let event_streams = event_streams();
+        let ctx = Arc::new(Database::new());
+
+        let req = Request::put("/graphql/stream").body(Body::empty()).unwrap();
+        let resp = super::graphql_sse(event_streams.clone(), ctx.clone(), req).await;
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        let token = hyper::body::to_bytes(resp.into_body()).await.unwrap();
+        let token = std::str::from_utf8(&token).unwrap();
+
+        let req = Request::get("/graphql/stream")
+            .header("x-graphql-event-stream-token", token)
+            .body(Body::empty())
+            .unwrap();
+        let resp = super::graphql_sse(event_streams.clone(), ctx.clone(), req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let mut events = resp.into_body();
+
+        let req = Request::post("/graphql/stream")
+            .header(header::CONTENT_TYPE, "application/json")
+            .header("x-graphql-event-stream-token", token)
+            .body(Body::from(
+                r#"{"query": "{ hero { name } }", "extensions": {"operationId": "1"}}"#,
+            ))
+            .unwrap();
+        let resp = super::graphql_sse(event_streams.clone(), ctx.clone(), req).await;
+        assert_eq!(resp.status(), StatusCode::ACCEPTED);
+
+        assert_eq!(
+            events.next().await.unwrap().unwrap(),
+            "event: next\ndata: {\"id\":\"1\",\"payload\":{\"data\":{\"hero\":{\"name\":\"R2-D2\"}}}}\n\n",
+        );
+        assert_eq!(
+            events.next().await.unwrap().unwrap(),
+            "event: complete\ndata: {\"id\":\"1\"}\n\n",
+        );
+
+        let req = Request::delete(format!("/graphql/stream?token={}&operationId=1", token))
+            .body(Body::empty())
+            .unwrap();
+        let resp = super::graphql_sse(event_streams, ctx, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
 }
@@ -0,0 +1,334 @@
+//! [`tower`] integration, allowing to mount GraphQL serving into anything compatible with it.
+//!
+//! [`tower`]: https://docs.rs/tower
+
+use std::{
+    convert::Infallible,
+    error::Error as StdError,
+    fmt,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use futures::future::{BoxFuture, FutureExt as _};
+use hyper::{body::HttpBody, Body, Request, Response, StatusCode};
+use juniper::{
+    http::ContextFactory, GraphQLSubscriptionType, GraphQLType, GraphQLTypeAsync, RootNode,
+    ScalarValue,
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// [`Service`] executing GraphQL requests against a [`RootNode`].
+///
+/// Accepts the same requests as [`graphql()`] does, with any [`HttpBody`]. The context of each
+/// request is created by the [`ContextFactory`], as [`graphql_with_context_factory()`] does.
+///
+/// ```
+/// # use std::{convert::Infallible, net::SocketAddr};
+/// # use futures::future;
+/// # use hyper::{service::make_service_fn, Server};
+/// # use juniper::{
+/// #     tests::fixtures::starwars::schema::{Database, Query},
+/// #     EmptyMutation, EmptySubscription, RootNode,
+/// # };
+/// # use juniper_hyper::service::GraphQLService;
+/// #
+/// # async fn run() {
+/// let root_node = RootNode::new(
+///     Query,
+///     EmptyMutation::<Database>::new(),
+///     EmptySubscription::<Database>::new(),
+/// );
+/// let service = GraphQLService::new(root_node, |_: &_| future::ready(Ok(Database::new())));
+///
+/// let addr = SocketAddr::from(([127, 0, 0, 1], 3000));
+/// Server::bind(&addr)
+///     .serve(make_service_fn(move |_| {
+///         let service = service.clone();
+///         async move { Ok::<_, Infallible>(service) }
+///     }))
+///     .await
+///     .unwrap();
+/// # }
+/// ```
+///
+/// [`graphql()`]: crate::graphql
+/// [`graphql_with_context_factory()`]: crate::graphql_with_context_factory
+pub struct GraphQLService<QueryT, MutationT, SubscriptionT, CtxT, S, F>
+where
+    QueryT: GraphQLType<S, Context = CtxT>,
+    MutationT: GraphQLType<S, Context = CtxT>,
+    SubscriptionT: GraphQLType<S, Context = CtxT>,
+    S: ScalarValue,
+{
+    root_node: Arc<RootNode<'static, QueryT, MutationT, SubscriptionT, S>>,
+    context_factory: Arc<F>,
+}
+
+impl<QueryT, MutationT, SubscriptionT, CtxT, S, F>
+    GraphQLService<QueryT, MutationT, SubscriptionT, CtxT, S, F>
+where
+    QueryT: GraphQLType<S, Context = CtxT>,
+    MutationT: GraphQLType<S, Context = CtxT>,
+    SubscriptionT: GraphQLType<S, Context = CtxT>,
+    S: ScalarValue,
+    F: ContextFactory<CtxT>,
+{
+    /// Creates a new [`GraphQLService`] executing requests against the given `root_node`, with
+    /// contexts created by the given `context_factory`.
+    pub fn new(
+        root_node: impl Into<Arc<RootNode<'static, QueryT, MutationT, SubscriptionT, S>>>,
+        context_factory: F,
+    ) -> Self {
+        Self {
+            root_node: root_node.into(),
+            context_factory: Arc::new(context_factory),
+        }
+    }
+}
+
+impl<QueryT, MutationT, SubscriptionT, CtxT, S, F> Clone
+    for GraphQLService<QueryT, MutationT, SubscriptionT, CtxT, S, F>
+where
+    QueryT: GraphQLType<S, Context = CtxT>,
+    MutationT: GraphQLType<S, Context = CtxT>,
+    SubscriptionT: GraphQLType<S, Context = CtxT>,
+    S: ScalarValue,
+{
+    fn clone(&self) -> Self {
+        Self {
+            root_node: self.root_node.clone(),
+            context_factory: self.context_factory.clone(),
+        }
+    }
+}
+
+impl<QueryT, MutationT, SubscriptionT, CtxT, S, F> fmt::Debug
+    for GraphQLService<QueryT, MutationT, SubscriptionT, CtxT, S, F>
+where
+    QueryT: GraphQLType<S, Context = CtxT>,
+    MutationT: GraphQLType<S, Context = CtxT>,
+    SubscriptionT: GraphQLType<S, Context = CtxT>,
+    S: ScalarValue,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GraphQLService").finish_non_exhaustive()
+    }
+}
+
+impl<QueryT, MutationT, SubscriptionT, CtxT, S, F, B> Service<Request<B>>
+    for GraphQLService<QueryT, MutationT, SubscriptionT, CtxT, S, F>
+where
+    QueryT: GraphQLTypeAsync<S, Context = CtxT> + Send + Sync + 'static,
+    QueryT::TypeInfo: Send + Sync,
+    MutationT: GraphQLTypeAsync<S, Context = CtxT> + Send + Sync + 'static,
+    MutationT::TypeInfo: Send + Sync,
+    SubscriptionT: GraphQLSubscriptionType<S, Context = CtxT> + Send + Sync + 'static,
+    SubscriptionT::TypeInfo: Send + Sync,
+    CtxT: Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+    F: ContextFactory<CtxT> + 'static,
+    B: HttpBody + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<Box<dyn StdError + Send + Sync>>,
+{
+    type Response = Response<Body>;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Response<Body>, Infallible>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let (parts, body) = req.into_parts();
+        let root_node = self.root_node.clone();
+        let context_factory = self.context_factory.clone();
+        async move {
+            // Buffering the body into a `hyper::Body` allows to reuse the request parsing of
+            // `graphql()` for any body type.
+            let body = match hyper::body::to_bytes(body).await {
+                Ok(body) => body,
+                Err(e) => {
+                    let mut resp = crate::new_response(StatusCode::BAD_REQUEST);
+                    *resp.body_mut() = Body::from(e.into().to_string());
+                    return Ok(resp);
+                }
+            };
+            let req = Request::from_parts(parts, Body::from(body));
+            Ok(crate::graphql_with_context_factory(root_node, &*context_factory, req).await)
+        }
+        .boxed()
+    }
+}
+
+/// [`Layer`] serving GraphQL requests to the given path with a [`GraphQLService`], and passing
+/// all the other requests to the inner [`Service`].
+///
+/// The inner [`Service`] should respond with a `hyper::Body`.
+pub struct GraphQLLayer<QueryT, MutationT, SubscriptionT, CtxT, S, F>
+where
+    QueryT: GraphQLType<S, Context = CtxT>,
+    MutationT: GraphQLType<S, Context = CtxT>,
+    SubscriptionT: GraphQLType<S, Context = CtxT>,
+    S: ScalarValue,
+{
+    path: Arc<str>,
+    service: GraphQLService<QueryT, MutationT, SubscriptionT, CtxT, S, F>,
+}
+
+impl<QueryT, MutationT, SubscriptionT, CtxT, S, F>
+    GraphQLLayer<QueryT, MutationT, SubscriptionT, CtxT, S, F>
+where
+    QueryT: GraphQLType<S, Context = CtxT>,
+    MutationT: GraphQLType<S, Context = CtxT>,
+    SubscriptionT: GraphQLType<S, Context = CtxT>,
+    S: ScalarValue,
+    F: ContextFactory<CtxT>,
+{
+    /// Creates a new [`GraphQLLayer`] serving GraphQL requests to the given `path` with the
+    /// given [`GraphQLService`].
+    pub fn new(
+        path: impl Into<Arc<str>>,
+        service: GraphQLService<QueryT, MutationT, SubscriptionT, CtxT, S, F>,
+    ) -> Self {
+        Self {
+            path: path.into(),
+            service,
+        }
+    }
+}
+
+impl<QueryT, MutationT, SubscriptionT, CtxT, S, F> Clone
+    for GraphQLLayer<QueryT, MutationT, SubscriptionT, CtxT, S, F>
+where
+    QueryT: GraphQLType<S, Context = CtxT>,
+    MutationT: GraphQLType<S, Context = CtxT>,
+    SubscriptionT: GraphQLType<S, Context = CtxT>,
+    S: ScalarValue,
+{
+    fn clone(&self) -> Self {
+        Self {
+            path: self.path.clone(),
+            service: self.service.clone(),
+        }
+    }
+}
+
+impl<QueryT, MutationT, SubscriptionT, CtxT, S, F> fmt::Debug
+    for GraphQLLayer<QueryT, MutationT, SubscriptionT, CtxT, S, F>
+where
+    QueryT: GraphQLType<S, Context = CtxT>,
+    MutationT: GraphQLType<S, Context = CtxT>,
+    SubscriptionT: GraphQLType<S, Context = CtxT>,
+    S: ScalarValue,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GraphQLLayer")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<QueryT, MutationT, SubscriptionT, CtxT, S, F, Inner> Layer<Inner>
+    for GraphQLLayer<QueryT, MutationT, SubscriptionT, CtxT, S, F>
+where
+    QueryT: GraphQLType<S, Context = CtxT>,
+    MutationT: GraphQLType<S, Context = CtxT>,
+    SubscriptionT: GraphQLType<S, Context = CtxT>,
+    S: ScalarValue,
+{
+    type Service = GraphQLRoute<QueryT, MutationT, SubscriptionT, CtxT, S, F, Inner>;
+
+    fn layer(&self, inner: Inner) -> Self::Service {
+        GraphQLRoute {
+            path: self.path.clone(),
+            service: self.service.clone(),
+            inner,
+        }
+    }
+}
+
+/// [`Service`] created by a [`GraphQLLayer`].
+pub struct GraphQLRoute<QueryT, MutationT, SubscriptionT, CtxT, S, F, Inner>
+where
+    QueryT: GraphQLType<S, Context = CtxT>,
+    MutationT: GraphQLType<S, Context = CtxT>,
+    SubscriptionT: GraphQLType<S, Context = CtxT>,
+    S: ScalarValue,
+{
+    path: Arc<str>,
+    service: GraphQLService<QueryT, MutationT, SubscriptionT, CtxT, S, F>,
+    inner: Inner,
+}
+
+impl<QueryT, MutationT, SubscriptionT, CtxT, S, F, Inner> Clone
+    for GraphQLRoute<QueryT, MutationT, SubscriptionT, CtxT, S, F, Inner>
+where
+    QueryT: GraphQLType<S, Context = CtxT>,
+    MutationT: GraphQLType<S, Context = CtxT>,
+    SubscriptionT: GraphQLType<S, Context = CtxT>,
+    S: ScalarValue,
+    Inner: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            path: self.path.clone(),
+            service: self.service.clone(),
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<QueryT, MutationT, SubscriptionT, CtxT, S, F, Inner> fmt::Debug
+    for GraphQLRoute<QueryT, MutationT, SubscriptionT, CtxT, S, F, Inner>
+where
+    QueryT: GraphQLType<S, Context = CtxT>,
+    MutationT: GraphQLType<S, Context = CtxT>,
+    SubscriptionT: GraphQLType<S, Context = CtxT>,
+    S: ScalarValue,
+    Inner: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GraphQLRoute")
+            .field("path", &self.path)
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<QueryT, MutationT, SubscriptionT, CtxT, S, F, Inner, B> Service<Request<B>>
+    for GraphQLRoute<QueryT, MutationT, SubscriptionT, CtxT, S, F, Inner>
+where
+    GraphQLService<QueryT, MutationT, SubscriptionT, CtxT, S, F>:
+        Service<Request<B>, Response = Response<Body>, Error = Infallible>,
+    <GraphQLService<QueryT, MutationT, SubscriptionT, CtxT, S, F> as Service<Request<B>>>::Future:
+        Send + 'static,
+    QueryT: GraphQLType<S, Context = CtxT>,
+    MutationT: GraphQLType<S, Context = CtxT>,
+    SubscriptionT: GraphQLType<S, Context = CtxT>,
+    S: ScalarValue,
+    Inner: Service<Request<B>, Response = Response<Body>>,
+    Inner::Future: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = Inner::Error;
+    type Future = BoxFuture<'static, Result<Response<Body>, Inner::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let path = req.uri().path();
+        if path == &*self.path || path.strip_suffix('/') == Some(&*self.path) {
+            self.service
+                .call(req)
+                .map(|res| Ok(res.unwrap_or_else(|e| match e {})))
+                .boxed()
+        } else {
+            self.inner.call(req).boxed()
+        }
+    }
+}
@@ -0,0 +1,217 @@
+//! GraphQL subscriptions over WebSocket, as of the [graphql-ws protocol][1].
+//!
+//! [1]: https://github.com/apollographql/subscriptions-transport-ws/blob/master/PROTOCOL.md
+
+use std::{convert::Infallible, fmt, sync::Arc};
+
+use futures::{
+    future::{self, Either},
+    SinkExt as _, StreamExt as _,
+};
+use hyper::{
+    header::{self, HeaderValue},
+    upgrade::Upgraded,
+    Body, Request, Response, StatusCode,
+};
+use juniper::{GraphQLSubscriptionType, GraphQLTypeAsync, RootNode, ScalarValue};
+use juniper_graphql_ws::{ArcSchema, ClientMessage, Connection, Init};
+use tokio_tungstenite::{
+    tungstenite::{self, handshake::derive_accept_key, protocol::Role},
+    WebSocketStream,
+};
+
+/// Sub-protocol of WebSocket connections served by [`graphql_ws()`].
+pub const PROTOCOL: &str = "graphql-ws";
+
+struct Message(tungstenite::Message);
+
+impl<S: ScalarValue> std::convert::TryFrom<Message> for ClientMessage<S> {
+    type Error = Error;
+
+    fn try_from(msg: Message) -> Result<Self, Self::Error> {
+        match msg.0 {
+            tungstenite::Message::Text(text) => serde_json::from_str(&text),
+            msg => serde_json::from_slice(&msg.into_data()),
+        }
+        .map_err(Error::Serde)
+    }
+}
+
+/// Errors that can happen while serving a connection.
+#[derive(Debug)]
+pub enum Error {
+    /// Errors that can happen in the WebSocket protocol while serving a connection.
+    WebSocket(Box<tungstenite::Error>),
+
+    /// Errors that can happen while serializing outgoing messages. Note that errors that occur
+    /// while deserializing incoming messages are handled internally by the protocol.
+    Serde(serde_json::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WebSocket(e) => write!(f, "websocket error: {}", e),
+            Self::Serde(e) => write!(f, "serde error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<tungstenite::Error> for Error {
+    fn from(err: tungstenite::Error) -> Self {
+        Self::WebSocket(Box::new(err))
+    }
+}
+
+impl From<Infallible> for Error {
+    fn from(_err: Infallible) -> Self {
+        unreachable!()
+    }
+}
+
+/// Handles a WebSocket upgrade request, serving the graphql-ws protocol over the upgraded
+/// connection.
+///
+/// Responds with `101 Switching Protocols` and serves the connection in a spawned task, so should
+/// be called within a [`tokio`] runtime. Errors of the served connection are discarded, use
+/// [`serve_graphql_ws()`] directly to handle them. Requests that are not WebSocket upgrades are
+/// responded with `400 Bad Request`.
+///
+/// The `init` argument is used to provide the context and additional configuration for
+/// connections. This can be a `juniper_graphql_ws::ConnectionConfig` if the context and
+/// configuration are already known, or it can be a closure that gets executed asynchronously
+/// when the client sends the ConnectionInit message. Using a closure allows you to perform
+/// authentication based on the parameters provided by the client.
+///
+/// [`tokio`]: https://docs.rs/tokio
+pub async fn graphql_ws<Query, Mutation, Subscription, CtxT, S, I>(
+    root_node: Arc<RootNode<'static, Query, Mutation, Subscription, S>>,
+    init: I,
+    req: Request<Body>,
+) -> Response<Body>
+where
+    Query: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Query::TypeInfo: Send + Sync,
+    Mutation: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Mutation::TypeInfo: Send + Sync,
+    Subscription: GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+    Subscription::TypeInfo: Send + Sync,
+    CtxT: Unpin + Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+    I: Init<S, CtxT> + Send + 'static,
+{
+    let accept_key = match websocket_accept_key(&req) {
+        Some(key) => key,
+        None => {
+            let mut resp = crate::new_response(StatusCode::BAD_REQUEST);
+            *resp.body_mut() = Body::from("expected a WebSocket upgrade request");
+            return resp;
+        }
+    };
+    let with_protocol = req
+        .headers()
+        .get_all(header::SEC_WEBSOCKET_PROTOCOL)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .any(|p| p.trim() == PROTOCOL);
+
+    tokio::spawn(async move {
+        if let Ok(upgraded) = hyper::upgrade::on(req).await {
+            let websocket = WebSocketStream::from_raw_socket(upgraded, Role::Server, None).await;
+            let _ = serve_graphql_ws(websocket, root_node, init).await;
+        }
+    });
+
+    let mut resp = crate::new_response(StatusCode::SWITCHING_PROTOCOLS);
+    let headers = resp.headers_mut();
+    headers.insert(header::CONNECTION, HeaderValue::from_static("upgrade"));
+    headers.insert(header::UPGRADE, HeaderValue::from_static("websocket"));
+    headers.insert(
+        header::SEC_WEBSOCKET_ACCEPT,
+        HeaderValue::from_str(&accept_key).unwrap(),
+    );
+    if with_protocol {
+        headers.insert(
+            header::SEC_WEBSOCKET_PROTOCOL,
+            HeaderValue::from_static(PROTOCOL),
+        );
+    }
+    resp
+}
+
+/// Computes the `Sec-WebSocket-Accept` header value of a response to the given request, if it's
+/// a valid WebSocket upgrade request.
+fn websocket_accept_key(req: &Request<Body>) -> Option<String> {
+    let headers = req.headers();
+    let header_contains = |name, token: &str| {
+        headers
+            .get_all(name)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .any(|v| v.trim().eq_ignore_ascii_case(token))
+    };
+    if !header_contains(header::CONNECTION, "upgrade")
+        || !header_contains(header::UPGRADE, "websocket")
+        || headers.get(header::SEC_WEBSOCKET_VERSION)? != "13"
+    {
+        return None;
+    }
+    headers
+        .get(header::SEC_WEBSOCKET_KEY)
+        .map(|key| derive_accept_key(key.as_bytes()))
+}
+
+/// Serves the graphql-ws protocol over an upgraded WebSocket connection.
+///
+/// The `init` argument is used to provide the context and additional configuration for
+/// connections. This can be a `juniper_graphql_ws::ConnectionConfig` if the context and
+/// configuration are already known, or it can be a closure that gets executed asynchronously
+/// when the client sends the ConnectionInit message. Using a closure allows you to perform
+/// authentication based on the parameters provided by the client.
+pub async fn serve_graphql_ws<Query, Mutation, Subscription, CtxT, S, I>(
+    websocket: WebSocketStream<Upgraded>,
+    root_node: Arc<RootNode<'static, Query, Mutation, Subscription, S>>,
+    init: I,
+) -> Result<(), Error>
+where
+    Query: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Query::TypeInfo: Send + Sync,
+    Mutation: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Mutation::TypeInfo: Send + Sync,
+    Subscription: GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+    Subscription::TypeInfo: Send + Sync,
+    CtxT: Unpin + Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+    I: Init<S, CtxT> + Send,
+{
+    let (ws_tx, ws_rx) = websocket.split();
+    let (s_tx, s_rx) = Connection::new(ArcSchema(root_node), init).split();
+
+    // Control frames are answered by `tungstenite` itself, so only data frames are passed further.
+    let ws_rx = ws_rx.filter_map(|r| {
+        future::ready(match r {
+            Ok(tungstenite::Message::Text(_)) | Ok(tungstenite::Message::Binary(_)) | Err(_) => {
+                Some(r.map(Message).map_err(Error::from))
+            }
+            Ok(_) => None,
+        })
+    });
+    let s_rx = s_rx.map(|msg| {
+        serde_json::to_string(&msg)
+            .map(tungstenite::Message::Text)
+            .map_err(Error::Serde)
+    });
+
+    match future::select(
+        ws_rx.forward(s_tx.sink_err_into()),
+        s_rx.forward(ws_tx.sink_err_into()),
+    )
+    .await
+    {
+        Either::Left((r, _)) | Either::Right((r, _)) => r,
+    }
+}
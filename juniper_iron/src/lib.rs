@@ -109,16 +109,19 @@ supported.
 use std::{error::Error, fmt, io::Read, ops::Deref as _};
 
 use iron::{
-    headers::ContentType,
+    headers::{Allow, ContentType},
     itry, method,
     middleware::Handler,
     mime::{Mime, TopLevel},
     prelude::*,
     status,
 };
+#[cfg(feature = "multipart")]
+use juniper::http::multipart::{self, MultipartError, MultipartLimits, MultipartParser};
 use juniper::{
-    http, http::GraphQLBatchRequest, DefaultScalarValue, GraphQLType, InputValue, RootNode,
-    ScalarValue,
+    http,
+    http::{BatchConfig, ContextFactory, GraphQLBatchRequest, RequestParts, ResponseMediaType},
+    DefaultScalarValue, GraphQLType, InputValue, RootNode, ScalarValue,
 };
 use serde_json::error::Error as SerdeError;
 use urlencoded::{UrlDecodingError, UrlEncodedQuery};
@@ -151,6 +154,45 @@ pub struct GraphQLHandler<
 {
     context_factory: CtxFactory,
     root_node: RootNode<'a, Query, Mutation, Subscription, S>,
+    batch_config: BatchConfig,
+    #[cfg(feature = "multipart")]
+    multipart_limits: MultipartLimits,
+}
+
+/// Adapts the given [`ContextFactory`] to be used as the context factory of a
+/// [`GraphQLHandler`], blocking the current thread while the context is created.
+///
+/// The [`RequestParts::remote_addr`] is the remote address of the request, and the
+/// [`RequestParts::extensions`] are left empty.
+///
+/// Responds with the status code of the [`ContextError`] if the context can't be created.
+///
+/// [`ContextError`]: juniper::http::ContextError
+pub fn from_context_factory<CtxT, F>(
+    context_factory: F,
+) -> impl Fn(&mut Request) -> IronResult<CtxT> + Send + Sync + 'static
+where
+    F: ContextFactory<CtxT> + 'static,
+{
+    move |req: &mut Request| {
+        let mut uri = format!("/{}", req.url.path().join("/"));
+        if let Some(query) = req.url.query() {
+            uri.push('?');
+            uri.push_str(query);
+        }
+        let mut parts = RequestParts::new(req.method.as_ref(), uri);
+        for header in req.headers.iter() {
+            parts.append_header(header.name(), header.value_string());
+        }
+        parts.remote_addr = Some(req.remote_addr);
+
+        futures::executor::block_on(context_factory.create(&parts)).map_err(|e| {
+            let status = status::Status::from_u16(e.status_code());
+            let body =
+                serde_json::to_string(&e.clone().into_response::<DefaultScalarValue>()).unwrap();
+            IronError::new(e, (status, body))
+        })
+    }
 }
 
 /// Handler that renders `GraphiQL` - a graphical query editor interface
@@ -221,9 +263,31 @@ where
         GraphQLHandler {
             context_factory,
             root_node: RootNode::new_with_scalar_value(query, mutation, subscription),
+            batch_config: BatchConfig::default(),
+            #[cfg(feature = "multipart")]
+            multipart_limits: MultipartLimits::default(),
         }
     }
 
+    /// Sets the [`BatchConfig`] of executing the batch requests accepted by this handler.
+    pub fn with_batch_config(mut self, config: BatchConfig) -> Self {
+        self.batch_config = config;
+        self
+    }
+
+    /// Sets the [`MultipartLimits`] of the [GraphQL multipart requests][1] accepted by this
+    /// handler.
+    ///
+    /// Multipart requests must carry an `Apollo-Require-Preflight` or `X-Apollo-Operation-Name`
+    /// header, so they can't be forged cross-origin.
+    ///
+    /// [1]: https://github.com/jaydenseric/graphql-multipart-request-spec
+    #[cfg(feature = "multipart")]
+    pub fn with_multipart_limits(mut self, limits: MultipartLimits) -> Self {
+        self.multipart_limits = limits;
+        self
+    }
+
     fn handle_get(&self, req: &mut Request) -> IronResult<GraphQLBatchRequest<S>> {
         let url_query = req
             .get_mut::<UrlEncodedQuery>()
@@ -233,12 +297,11 @@ where
             .ok_or(GraphQLIronError::InvalidData("No query provided"))?;
         let operation_name = parse_url_param(url_query.remove("operationName"))?;
         let variables = parse_variable_param(url_query.remove("variables"))?;
+        let extensions = parse_variable_param(url_query.remove("extensions"))?;
 
-        Ok(GraphQLBatchRequest::Single(http::GraphQLRequest::new(
-            query,
-            operation_name,
-            variables,
-        )))
+        Ok(GraphQLBatchRequest::Single(
+            http::GraphQLRequest::new(query, operation_name, variables).with_extensions(extensions),
+        ))
     }
 
     fn handle_post_json(&self, req: &mut Request) -> IronResult<GraphQLBatchRequest<S>> {
@@ -260,20 +323,61 @@ where
         )))
     }
 
+    #[cfg(feature = "multipart")]
+    fn handle_post_multipart(&self, req: &mut Request) -> IronResult<GraphQLBatchRequest<S>> {
+        multipart::check_preflight(|name| {
+            req.headers
+                .get_raw(name)
+                .map_or(false, |values| values.iter().any(|v| !v.is_empty()))
+        })
+        .map_err(GraphQLIronError::Multipart)?;
+        let content_type = req
+            .headers
+            .get::<ContentType>()
+            .map(ToString::to_string)
+            .unwrap_or_default();
+
+        let mut parser = MultipartParser::new(&content_type, &self.multipart_limits)
+            .map_err(GraphQLIronError::Multipart)?;
+        let mut chunk = [0; 8 * 1024];
+        loop {
+            match itry!(req.body.read(&mut chunk)) {
+                0 => break,
+                len => parser
+                    .feed(&chunk[..len])
+                    .map_err(GraphQLIronError::Multipart)?,
+            }
+        }
+        Ok(parser.finish().map_err(GraphQLIronError::Multipart)?)
+    }
+
     fn execute_sync(
         &self,
         context: &CtxT,
         request: GraphQLBatchRequest<S>,
+        media_type: ResponseMediaType,
     ) -> IronResult<Response> {
-        let response = request.execute_sync(&self.root_node, context);
-        let content_type = "application/json".parse::<Mime>().unwrap();
+        let response =
+            request.execute_sync_with_batch_config(&self.root_node, context, self.batch_config);
+        if response.is_rejected_mutation() {
+            let content_type = "text/plain; charset=utf-8".parse::<Mime>().unwrap();
+            let mut resp = Response::with((
+                content_type,
+                status::MethodNotAllowed,
+                "mutations are not allowed over GET",
+            ));
+            resp.headers.set(Allow(vec![method::Post]));
+            return Ok(resp);
+        }
+        let content_type = media_type.as_str().parse::<Mime>().unwrap();
         let json = serde_json::to_string_pretty(&response).unwrap();
-        let status = if response.is_ok() {
-            status::Ok
-        } else {
-            status::BadRequest
-        };
-        Ok(Response::with((content_type, status, json)))
+        let status = status::Status::from_u16(response.status_code());
+        let mut resp = Response::with((content_type, status, json));
+        if let Some(cache_control) = response.cache_policy().and_then(|p| p.header_value()) {
+            resp.headers
+                .set_raw("Cache-Control", vec![cache_control.into_bytes()]);
+        }
+        Ok(resp)
     }
 }
 
@@ -316,21 +420,28 @@ where
 {
     fn handle(&self, mut req: &mut Request) -> IronResult<Response> {
         let context = (self.context_factory)(req)?;
+        let accept = req.headers.get_raw("Accept").and_then(|v| v.first());
+        let media_type =
+            ResponseMediaType::negotiate(accept.and_then(|v| std::str::from_utf8(v).ok()));
 
         let graphql_request = match req.method {
-            method::Get => self.handle_get(&mut req)?,
+            method::Get => self.handle_get(&mut req)?.forbid_mutations(),
             method::Post => match req.headers.get::<ContentType>().map(ContentType::deref) {
                 Some(Mime(TopLevel::Application, sub_lvl, _)) => match sub_lvl.as_str() {
                     "json" => self.handle_post_json(&mut req)?,
                     "graphql" => self.handle_post_graphql(&mut req)?,
                     _ => return Ok(Response::with(status::BadRequest)),
                 },
+                #[cfg(feature = "multipart")]
+                Some(Mime(TopLevel::Multipart, iron::mime::SubLevel::FormData, _)) => {
+                    self.handle_post_multipart(&mut req)?
+                }
                 _ => return Ok(Response::with(status::BadRequest)),
             },
             _ => return Ok(Response::with(status::MethodNotAllowed)),
         };
 
-        self.execute_sync(&context, graphql_request)
+        self.execute_sync(&context, graphql_request, media_type)
     }
 }
 
@@ -368,6 +479,8 @@ impl Handler for PlaygroundHandler {
 enum GraphQLIronError {
     Serde(SerdeError),
     Url(UrlDecodingError),
+    #[cfg(feature = "multipart")]
+    Multipart(MultipartError),
     InvalidData(&'static str),
 }
 
@@ -376,6 +489,8 @@ impl fmt::Display for GraphQLIronError {
         match *self {
             GraphQLIronError::Serde(ref err) => fmt::Display::fmt(err, &mut f),
             GraphQLIronError::Url(ref err) => fmt::Display::fmt(err, &mut f),
+            #[cfg(feature = "multipart")]
+            GraphQLIronError::Multipart(ref err) => fmt::Display::fmt(err, &mut f),
             GraphQLIronError::InvalidData(err) => fmt::Display::fmt(err, &mut f),
         }
     }
@@ -386,6 +501,8 @@ impl Error for GraphQLIronError {
         match *self {
             GraphQLIronError::Serde(ref err) => Some(err),
             GraphQLIronError::Url(ref err) => Some(err),
+            #[cfg(feature = "multipart")]
+            GraphQLIronError::Multipart(ref err) => Some(err),
             GraphQLIronError::InvalidData(_) => None,
         }
     }
@@ -394,7 +511,14 @@ impl Error for GraphQLIronError {
 impl From<GraphQLIronError> for IronError {
     fn from(err: GraphQLIronError) -> IronError {
         let message = format!("{}", err);
-        IronError::new(err, (status::BadRequest, message))
+        let status = match err {
+            #[cfg(feature = "multipart")]
+            GraphQLIronError::Multipart(ref e) if e.is_server_error() => {
+                status::InternalServerError
+            }
+            _ => status::BadRequest,
+        };
+        IronError::new(err, (status, message))
     }
 }
 
@@ -410,7 +534,7 @@ mod tests {
     use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
 
     use juniper::{
-        http::tests as http_tests,
+        http::{tests as http_tests, ContextError},
         tests::fixtures::starwars::schema::{Database, Query},
         DefaultScalarValue, EmptyMutation, EmptySubscription,
     };
@@ -474,6 +598,34 @@ mod tests {
         http_tests::run_http_test_suite(&integration);
     }
 
+    #[test]
+    fn test_from_context_factory() {
+        let handler = <GraphQLHandler<_, _, _, _, _, DefaultScalarValue>>::new(
+            super::from_context_factory(|parts: &RequestParts| {
+                futures::future::ready(match parts.header("authorization") {
+                    Some("secret") => Ok(Database::new()),
+                    _ => Err(ContextError::unauthorized("invalid token")),
+                })
+            }),
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        );
+        let headers = |token: &str| {
+            let mut headers = Headers::new();
+            headers.set_raw("authorization", vec![token.as_bytes().to_vec()]);
+            headers
+        };
+        let url = fixup_url("/?query={hero{name}}");
+
+        let err = request::get(&url, headers("wrong"), &handler).unwrap_err();
+        assert_eq!(err.response.status, Some(status::Unauthorized));
+        assert!(response::extract_body_to_string(err.response).contains("invalid token"));
+
+        let resp = request::get(&url, headers("secret"), &handler).unwrap();
+        assert_eq!(resp.status, Some(status::Ok));
+    }
+
     fn context_factory(_: &mut Request) -> IronResult<Database> {
         Ok(Database::new())
     }
@@ -38,21 +38,32 @@ Check the LICENSE file for details.
 
 #![doc(html_root_url = "https://docs.rs/juniper_rocket/0.7.1")]
 
-use std::{borrow::Cow, io::Cursor};
+#[cfg(feature = "sse")]
+pub mod sse;
+#[cfg(feature = "subscriptions")]
+pub mod subscriptions;
+
+use std::{borrow::Cow, io::Cursor, sync::Arc};
 
 use rocket::{
     data::{self, FromData, ToByteUnit},
     form::{error::ErrorKind, DataField, Error, Errors, FromForm, Options, ValueField},
-    http::{ContentType, Status},
-    outcome::Outcome::{Failure, Forward, Success},
+    http::{ContentType, Header, Status},
+    outcome::Outcome::{Error as Failure, Forward, Success},
+    request::{self as request_guard, FromRequest},
     response::{self, content, Responder, Response},
     Data, Request,
 };
 
+#[cfg(feature = "multipart")]
+use juniper::http::multipart::{self, MultipartLimits, MultipartParser};
 use juniper::{
-    http::{self, GraphQLBatchRequest},
-    DefaultScalarValue, FieldError, GraphQLSubscriptionType, GraphQLType, GraphQLTypeAsync,
-    InputValue, RootNode, ScalarValue,
+    http::{
+        self, BatchConfig, ContextError, ContextFactory, GraphQLBatchRequest, RequestParts,
+        ResponseMediaType,
+    },
+    CachePolicy, DefaultScalarValue, FieldError, GraphQLSubscriptionType, GraphQLType,
+    GraphQLTypeAsync, InputValue, RootNode, ScalarValue, Value,
 };
 
 /// Simple wrapper around an incoming GraphQL request
@@ -60,20 +71,37 @@ use juniper::{
 /// See the `http` module for more information. This type can be constructed
 /// automatically from both GET and POST routes by implementing the `FromForm`
 /// and `FromData` traits.
+///
+/// Mutations received via GET are not executed, but replied with `405 Method Not Allowed`, and
+/// batch requests received via POST are executed with the managed `juniper::http::BatchConfig`,
+/// if any.
+///
+/// With the `multipart` feature, [GraphQL multipart requests][1] carrying an
+/// `Apollo-Require-Preflight` or `X-Apollo-Operation-Name` header are accepted too, limited by
+/// the managed `juniper::http::multipart::MultipartLimits`, if any.
+///
+/// [1]: https://github.com/jaydenseric/graphql-multipart-request-spec
 #[derive(Debug, PartialEq)]
-pub struct GraphQLRequest<S = DefaultScalarValue>(GraphQLBatchRequest<S>)
+pub struct GraphQLRequest<S = DefaultScalarValue>(GraphQLBatchRequest<S>, BatchConfig)
 where
     S: ScalarValue;
 
 /// Simple wrapper around the result of executing a GraphQL query
 pub struct GraphQLResponse(pub Status, pub String);
 
+/// [`GraphQLResponse`] sending its [`CachePolicy`] as the `Cache-Control` header.
+///
+/// Constructed via [`GraphQLResponse::with_cache_policy()`], or returned by
+/// [`GraphQLRequest::execute_with_cache_control()`] and
+/// [`GraphQLRequest::execute_sync_with_cache_control()`].
+pub struct CacheControlledResponse(GraphQLResponse, Option<CachePolicy>);
+
 /// Generate an HTML page containing GraphiQL
 pub fn graphiql_source(
     graphql_endpoint_url: &str,
     subscriptions_endpoint_url: Option<&str>,
-) -> content::Html<String> {
-    content::Html(juniper::http::graphiql::graphiql_source(
+) -> content::RawHtml<String> {
+    content::RawHtml(juniper::http::graphiql::graphiql_source(
         graphql_endpoint_url,
         subscriptions_endpoint_url,
     ))
@@ -83,8 +111,8 @@ pub fn graphiql_source(
 pub fn playground_source(
     graphql_endpoint_url: &str,
     subscriptions_endpoint_url: Option<&str>,
-) -> content::Html<String> {
-    content::Html(juniper::http::playground::playground_source(
+) -> content::RawHtml<String> {
+    content::RawHtml(juniper::http::playground::playground_source(
         graphql_endpoint_url,
         subscriptions_endpoint_url,
     ))
@@ -105,15 +133,31 @@ where
         MutationT: GraphQLType<S, Context = CtxT>,
         SubscriptionT: GraphQLType<S, Context = CtxT>,
     {
-        let response = self.0.execute_sync(root_node, context);
-        let status = if response.is_ok() {
-            Status::Ok
-        } else {
-            Status::BadRequest
-        };
+        self.execute_sync_with_cache_control(root_node, context).0
+    }
+
+    /// Synchronously execute an incoming GraphQL query, sending the [`CachePolicy`] of the
+    /// response as the `Cache-Control` header.
+    pub fn execute_sync_with_cache_control<CtxT, QueryT, MutationT, SubscriptionT>(
+        &self,
+        root_node: &RootNode<QueryT, MutationT, SubscriptionT, S>,
+        context: &CtxT,
+    ) -> CacheControlledResponse
+    where
+        QueryT: GraphQLType<S, Context = CtxT>,
+        MutationT: GraphQLType<S, Context = CtxT>,
+        SubscriptionT: GraphQLType<S, Context = CtxT>,
+    {
+        let response = self
+            .0
+            .execute_sync_with_batch_config(root_node, context, self.1);
+        if response.is_rejected_mutation() {
+            return GraphQLResponse::mutation_over_get().with_cache_policy(None);
+        }
+        let status = Status::from_code(response.status_code()).unwrap();
         let json = serde_json::to_string(&response).unwrap();
 
-        GraphQLResponse(status, json)
+        GraphQLResponse(status, json).with_cache_policy(response.cache_policy())
     }
 
     /// Asynchronously execute an incoming GraphQL query.
@@ -132,15 +176,98 @@ where
         CtxT: Sync,
         S: Send + Sync,
     {
-        let response = self.0.execute(root_node, context).await;
-        let status = if response.is_ok() {
-            Status::Ok
-        } else {
-            Status::BadRequest
-        };
+        self.execute_with_cache_control(root_node, context).await.0
+    }
+
+    /// Asynchronously execute an incoming GraphQL query, sending the [`CachePolicy`] of the
+    /// response as the `Cache-Control` header.
+    pub async fn execute_with_cache_control<CtxT, QueryT, MutationT, SubscriptionT>(
+        &self,
+        root_node: &RootNode<'_, QueryT, MutationT, SubscriptionT, S>,
+        context: &CtxT,
+    ) -> CacheControlledResponse
+    where
+        QueryT: GraphQLTypeAsync<S, Context = CtxT>,
+        QueryT::TypeInfo: Sync,
+        MutationT: GraphQLTypeAsync<S, Context = CtxT>,
+        MutationT::TypeInfo: Sync,
+        SubscriptionT: GraphQLSubscriptionType<S, Context = CtxT>,
+        SubscriptionT::TypeInfo: Sync,
+        CtxT: Sync,
+        S: Send + Sync,
+    {
+        let response = self
+            .0
+            .execute_with_batch_config(root_node, context, self.1)
+            .await;
+        if response.is_rejected_mutation() {
+            return GraphQLResponse::mutation_over_get().with_cache_policy(None);
+        }
+        let status = Status::from_code(response.status_code()).unwrap();
         let json = serde_json::to_string(&response).unwrap();
 
-        GraphQLResponse(status, json)
+        GraphQLResponse(status, json).with_cache_policy(response.cache_policy())
+    }
+
+    /// Executes this request over a new event stream of [GraphQL over SSE][1], streaming the
+    /// results of the requested operation as server-sent events.
+    ///
+    /// Batch requests are replied with `400 Bad Request`, while mutations received via GET are
+    /// rejected with an error event.
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use juniper::{
+    /// #     tests::fixtures::starwars::schema::{Database, Query, Subscription},
+    /// #     DefaultScalarValue, EmptyMutation,
+    /// # };
+    /// # use juniper_rocket::{
+    /// #     sse::{EventStreams, GraphQLEventStream},
+    /// #     GraphQLRequest, GraphQLResponse,
+    /// # };
+    /// # use rocket::State;
+    /// #
+    /// type Streams =
+    ///     EventStreams<Query, EmptyMutation<Database>, Subscription, Database, DefaultScalarValue>;
+    ///
+    /// #[rocket::post("/graphql/stream", data = "<request>")]
+    /// fn post_graphql_stream(
+    ///     request: GraphQLRequest,
+    ///     event_streams: &State<Streams>,
+    /// ) -> Result<GraphQLEventStream, GraphQLResponse> {
+    ///     request.subscribe(event_streams, Arc::new(Database::new()))
+    /// }
+    /// ```
+    ///
+    /// [1]: https://github.com/enisdenjo/graphql-sse/blob/master/PROTOCOL.md
+    #[cfg(feature = "sse")]
+    pub fn subscribe<CtxT, QueryT, MutationT, SubscriptionT>(
+        self,
+        event_streams: &sse::EventStreams<QueryT, MutationT, SubscriptionT, CtxT, S>,
+        context: Arc<CtxT>,
+    ) -> Result<sse::GraphQLEventStream, GraphQLResponse>
+    where
+        QueryT: GraphQLTypeAsync<S, Context = CtxT> + Send + Sync + 'static,
+        QueryT::TypeInfo: Send + Sync,
+        MutationT: GraphQLTypeAsync<S, Context = CtxT> + Send + Sync + 'static,
+        MutationT::TypeInfo: Send + Sync,
+        SubscriptionT: GraphQLSubscriptionType<S, Context = CtxT> + Send + Sync + 'static,
+        SubscriptionT::TypeInfo: Send + Sync,
+        CtxT: Send + Sync + 'static,
+        S: Send + Sync + 'static,
+    {
+        let request = match self.0 {
+            GraphQLBatchRequest::Single(request) => request,
+            GraphQLBatchRequest::Batch(_) => {
+                let error: FieldError =
+                    FieldError::new("Batch requests are not supported over SSE", Value::null());
+                let json = serde_json::to_string(&http::GraphQLResponse::error(error)).unwrap();
+                return Err(GraphQLResponse(Status::BadRequest, json));
+            }
+        };
+        Ok(sse::GraphQLEventStream(
+            event_streams.subscribe(request, context),
+        ))
     }
 
     /// Returns the operation names associated with this request.
@@ -197,6 +324,109 @@ impl GraphQLResponse {
         let json = serde_json::to_string(&response).unwrap();
         GraphQLResponse(status, json)
     }
+
+    /// Constructs a response rejecting the request with the given [`ContextError`], as of a
+    /// failing [`FactoryContext`] request guard.
+    pub fn context_error(error: ContextError) -> Self {
+        let status = Status::from_code(error.status_code()).unwrap_or(Status::InternalServerError);
+        let json = serde_json::to_string(&error.into_response::<DefaultScalarValue>()).unwrap();
+        GraphQLResponse(status, json)
+    }
+
+    /// Attaches the given [`CachePolicy`] to this response, sending it as the `Cache-Control`
+    /// header.
+    pub fn with_cache_policy(self, policy: Option<CachePolicy>) -> CacheControlledResponse {
+        CacheControlledResponse(self, policy)
+    }
+
+    fn mutation_over_get() -> Self {
+        let error: FieldError =
+            FieldError::new("Mutations are not allowed over GET", Value::null());
+        let json = serde_json::to_string(&http::GraphQLResponse::error(error)).unwrap();
+        GraphQLResponse(Status::MethodNotAllowed, json)
+    }
+}
+
+/// Request guard providing the context created by the [`ContextFactory`] managed as an
+/// `Arc<dyn ContextFactory<CtxT>>`.
+///
+/// The [`RequestParts::remote_addr`] is the remote address of the request, and the
+/// [`RequestParts::extensions`] are left empty.
+///
+/// Fails with the status code of the [`ContextError`] if the context can't be created, or with
+/// `500 Internal Server Error` if no [`ContextFactory`] is managed. To respond with the error,
+/// guard with a `Result<FactoryContext<CtxT>, ContextError>` and use
+/// [`GraphQLResponse::context_error()`].
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use futures::future;
+/// # use rocket::{post, routes, State};
+/// # use juniper::{
+/// #     http::{ContextError, ContextFactory, RequestParts},
+/// #     tests::fixtures::starwars::schema::{Database, Query},
+/// #     EmptyMutation, EmptySubscription, RootNode,
+/// # };
+/// # use juniper_rocket::{FactoryContext, GraphQLRequest, GraphQLResponse};
+/// #
+/// type Schema = RootNode<'static, Query, EmptyMutation<Database>, EmptySubscription<Database>>;
+///
+/// #[post("/graphql", data = "<request>")]
+/// async fn graphql(
+///     context: Result<FactoryContext<Database>, ContextError>,
+///     request: GraphQLRequest,
+///     schema: &State<Schema>,
+/// ) -> GraphQLResponse {
+///     match context {
+///         Ok(FactoryContext(context)) => request.execute(&*schema, &context).await,
+///         Err(e) => GraphQLResponse::context_error(e),
+///     }
+/// }
+///
+/// let context_factory: Arc<dyn ContextFactory<Database>> = Arc::new(|parts: &RequestParts| {
+///     future::ready(match parts.header("authorization") {
+///         Some(_) => Ok(Database::new()),
+///         None => Err(ContextError::unauthorized("missing `Authorization` header")),
+///     })
+/// });
+/// let rocket = rocket::build()
+///     .manage(context_factory)
+///     .manage(Schema::new(Query, EmptyMutation::new(), EmptySubscription::new()))
+///     .mount("/", routes![graphql]);
+/// ```
+#[derive(Debug)]
+pub struct FactoryContext<CtxT>(pub CtxT);
+
+#[rocket::async_trait]
+impl<'r, CtxT> FromRequest<'r> for FactoryContext<CtxT>
+where
+    CtxT: Send + 'static,
+{
+    type Error = ContextError;
+
+    async fn from_request(req: &'r Request<'_>) -> request_guard::Outcome<Self, Self::Error> {
+        let context_factory = match req.rocket().state::<Arc<dyn ContextFactory<CtxT>>>() {
+            Some(f) => f,
+            None => {
+                let error = ContextError::internal("no `ContextFactory` is managed");
+                return Failure((Status::InternalServerError, error));
+            }
+        };
+
+        let mut parts = RequestParts::new(req.method().as_str(), req.uri().to_string());
+        for header in req.headers().iter() {
+            parts.append_header(header.name(), header.value());
+        }
+        parts.remote_addr = req.remote();
+
+        match context_factory.create(&parts).await {
+            Ok(context) => Success(Self(context)),
+            Err(e) => Failure((
+                Status::from_code(e.status_code()).unwrap_or(Status::InternalServerError),
+                e,
+            )),
+        }
+    }
 }
 
 pub struct GraphQLContext<'f, S: ScalarValue> {
@@ -204,6 +434,7 @@ pub struct GraphQLContext<'f, S: ScalarValue> {
     query: Option<String>,
     operation_name: Option<String>,
     variables: Option<InputValue<S>>,
+    extensions: Option<InputValue<S>>,
     errors: Errors<'f>,
 }
 
@@ -247,6 +478,26 @@ impl<'f, S: ScalarValue> GraphQLContext<'f, S> {
             }
         }
     }
+
+    fn extensions(&mut self, value: String) {
+        if self.extensions.is_some() {
+            let error = Error::from(ErrorKind::Duplicate).with_name("extensions");
+
+            self.errors.push(error)
+        } else {
+            let parse_result = serde_json::from_str::<InputValue<S>>(&value);
+
+            match parse_result {
+                Ok(extensions) => self.extensions = Some(extensions),
+                Err(e) => {
+                    let error = Error::from(ErrorKind::Validation(Cow::Owned(e.to_string())))
+                        .with_name("extensions");
+
+                    self.errors.push(error);
+                }
+            }
+        }
+    }
 }
 
 #[rocket::async_trait]
@@ -262,6 +513,7 @@ where
             query: None,
             operation_name: None,
             variables: None,
+            extensions: None,
             errors: Errors::new(),
         }
     }
@@ -271,6 +523,7 @@ where
             Some("query") => ctx.query(field.value.to_owned()),
             Some("operation_name") => ctx.operation_name(field.value.to_owned()),
             Some("variables") => ctx.variables(field.value.to_owned()),
+            Some("extensions") => ctx.extensions(field.value.to_owned()),
             Some(key) => {
                 if ctx.opts.strict {
                     let error = Error::from(ErrorKind::Unknown).with_name(key);
@@ -304,9 +557,18 @@ where
         }
 
         match ctx.errors.is_empty() {
-            true => Ok(GraphQLRequest(GraphQLBatchRequest::Single(
-                http::GraphQLRequest::new(ctx.query.unwrap(), ctx.operation_name, ctx.variables),
-            ))),
+            true => Ok(GraphQLRequest(
+                GraphQLBatchRequest::Single(
+                    http::GraphQLRequest::new(
+                        ctx.query.unwrap(),
+                        ctx.operation_name,
+                        ctx.variables,
+                    )
+                    .with_extensions(ctx.extensions)
+                    .forbid_mutations(),
+                ),
+                BatchConfig::default(),
+            )),
             false => Err(ctx.errors),
         }
     }
@@ -314,6 +576,15 @@ where
 
 const BODY_LIMIT: u64 = 1024 * 100;
 
+#[cfg(feature = "multipart")]
+fn multipart_error_status(err: &multipart::MultipartError) -> Status {
+    if err.is_server_error() {
+        Status::InternalServerError
+    } else {
+        Status::BadRequest
+    }
+}
+
 #[rocket::async_trait]
 impl<'r, S> FromData<'r> for GraphQLRequest<S>
 where
@@ -327,13 +598,70 @@ where
     ) -> data::Outcome<'r, Self, Self::Error> {
         use rocket::tokio::io::AsyncReadExt as _;
 
+        let batch_config = req
+            .rocket()
+            .state::<BatchConfig>()
+            .copied()
+            .unwrap_or_default();
+
+        #[cfg(feature = "multipart")]
+        if let Some(content_type) = req
+            .content_type()
+            .filter(|ct| ct.top() == "multipart" && ct.sub() == "form-data")
+        {
+            let content_type = content_type.to_string();
+            return Box::pin(async move {
+                if let Err(e) = multipart::check_preflight(|name| {
+                    req.headers().get(name).any(|v| !v.is_empty())
+                }) {
+                    return Failure((Status::BadRequest, format!("{}", e)));
+                }
+                let limits = req
+                    .rocket()
+                    .state::<MultipartLimits>()
+                    .copied()
+                    .unwrap_or_default();
+                let mut parser = match MultipartParser::new(&content_type, &limits) {
+                    Ok(parser) => parser,
+                    Err(e) => return Failure((Status::BadRequest, format!("{}", e))),
+                };
+
+                let body_limit = limits
+                    .max_file_size
+                    .saturating_mul(limits.max_files as u64)
+                    .saturating_add(2 * limits.max_field_size as u64 + BODY_LIMIT);
+                let mut reader = data.open(body_limit.bytes());
+                let mut chunk = vec![0; 8 * 1024];
+                loop {
+                    let len = match reader.read(&mut chunk).await {
+                        Ok(0) => break,
+                        Ok(len) => len,
+                        Err(e) => {
+                            return Failure((Status::InternalServerError, format!("{:?}", e)))
+                        }
+                    };
+                    if let Err(e) = parser.feed(&chunk[..len]) {
+                        return Failure((multipart_error_status(&e), format!("{}", e)));
+                    }
+                }
+
+                match parser.finish() {
+                    Ok(req) => Success(GraphQLRequest(req, batch_config)),
+                    Err(e) => Failure((multipart_error_status(&e), format!("{}", e))),
+                }
+            })
+            .await;
+        }
+
         let content_type = req
             .content_type()
             .map(|ct| (ct.top().as_str(), ct.sub().as_str()));
         let is_json = match content_type {
             Some(("application", "json")) => true,
             Some(("application", "graphql")) => false,
-            _ => return Box::pin(async move { Forward(data) }).await,
+            _ => {
+                return Box::pin(async move { Forward((data, Status::UnsupportedMediaType)) }).await
+            }
         };
 
         Box::pin(async move {
@@ -343,31 +671,51 @@ where
                 return Failure((Status::InternalServerError, format!("{:?}", e)));
             }
 
-            Success(GraphQLRequest(if is_json {
+            let request = if is_json {
                 match serde_json::from_str(&body) {
                     Ok(req) => req,
                     Err(e) => return Failure((Status::BadRequest, format!("{}", e))),
                 }
             } else {
                 GraphQLBatchRequest::Single(http::GraphQLRequest::new(body, None, None))
-            }))
+            };
+            Success(GraphQLRequest(request, batch_config))
         })
         .await
     }
 }
 
 impl<'r, 'o: 'r> Responder<'r, 'o> for GraphQLResponse {
-    fn respond_to(self, _req: &'r Request<'_>) -> response::Result<'o> {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
         let GraphQLResponse(status, body) = self;
 
+        let content_type = match ResponseMediaType::negotiate(req.headers().get_one("Accept")) {
+            ResponseMediaType::GraphQLResponseJson => {
+                ContentType::new("application", "graphql-response+json")
+            }
+            ResponseMediaType::Json => ContentType::JSON,
+        };
+
         Response::build()
-            .header(ContentType::new("application", "json"))
+            .header(content_type)
             .status(status)
             .sized_body(body.len(), Cursor::new(body))
             .ok()
     }
 }
 
+impl<'r, 'o: 'r> Responder<'r, 'o> for CacheControlledResponse {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'o> {
+        let CacheControlledResponse(response, cache_policy) = self;
+
+        let mut resp = response.respond_to(req)?;
+        if let Some(cache_control) = cache_policy.and_then(|p| p.header_value()) {
+            resp.set_header(Header::new("Cache-Control", cache_control));
+        }
+        Ok(resp)
+    }
+}
+
 #[cfg(test)]
 mod fromform_tests {
     use super::*;
@@ -472,11 +820,13 @@ mod fromform_tests {
             Form::parse_encoded(RawStr::new(r#"query=test&variables={"foo":"bar"}"#));
         assert!(result.is_ok());
         let variables = ::serde_json::from_str::<InputValue>(r#"{"foo":"bar"}"#).unwrap();
-        let expected = GraphQLRequest(GraphQLBatchRequest::Single(http::GraphQLRequest::new(
-            "test".to_string(),
-            None,
-            Some(variables),
-        )));
+        let expected = GraphQLRequest(
+            GraphQLBatchRequest::Single(
+                http::GraphQLRequest::new("test".to_string(), None, Some(variables))
+                    .forbid_mutations(),
+            ),
+            BatchConfig::default(),
+        );
         assert_eq!(result.unwrap(), expected);
     }
 
@@ -486,11 +836,13 @@ mod fromform_tests {
             r#"query=test&variables={"foo":"x%20y%26%3F+z"}"#,
         ));
         let variables = ::serde_json::from_str::<InputValue>(r#"{"foo":"x y&? z"}"#).unwrap();
-        let expected = GraphQLRequest(GraphQLBatchRequest::Single(http::GraphQLRequest::new(
-            "test".to_string(),
-            None,
-            Some(variables),
-        )));
+        let expected = GraphQLRequest(
+            GraphQLBatchRequest::Single(
+                http::GraphQLRequest::new("test".to_string(), None, Some(variables))
+                    .forbid_mutations(),
+            ),
+            BatchConfig::default(),
+        );
         assert_eq!(result.unwrap(), expected);
     }
 
@@ -500,11 +852,17 @@ mod fromform_tests {
             "query=%25foo%20bar+baz%26%3F&operation_name=test",
         ));
         assert!(result.is_ok());
-        let expected = GraphQLRequest(GraphQLBatchRequest::Single(http::GraphQLRequest::new(
-            "%foo bar baz&?".to_string(),
-            Some("test".to_string()),
-            None,
-        )));
+        let expected = GraphQLRequest(
+            GraphQLBatchRequest::Single(
+                http::GraphQLRequest::new(
+                    "%foo bar baz&?".to_string(),
+                    Some("test".to_string()),
+                    None,
+                )
+                .forbid_mutations(),
+            ),
+            BatchConfig::default(),
+        );
         assert_eq!(result.unwrap(), expected);
     }
 }
@@ -512,16 +870,18 @@ mod fromform_tests {
 #[cfg(test)]
 mod tests {
 
+    use std::sync::Arc;
+
     use futures;
 
     use juniper::{
-        http::tests as http_tests,
+        http::{tests as http_tests, ContextError, ContextFactory, RequestParts},
         tests::fixtures::starwars::schema::{Database, Query},
         EmptyMutation, EmptySubscription, RootNode,
     };
     use rocket::{
         self, get,
-        http::ContentType,
+        http::{ContentType, Header},
         local::asynchronous::{Client, LocalResponse},
         post, routes, Build, Rocket, State,
     };
@@ -583,6 +943,39 @@ mod tests {
         http_tests::run_http_test_suite(&integration);
     }
 
+    #[rocket::async_test]
+    async fn test_graphql_response_media_type() {
+        let client = Client::untracked(make_rocket())
+            .await
+            .expect("valid rocket");
+
+        // {hero{blah}}
+        let resp = client
+            .get("/?query=%7Bhero%7Bblah%7D%7D")
+            .header(Header::new("Accept", "application/graphql-response+json"))
+            .dispatch()
+            .await;
+        let resp = make_test_response(resp).await;
+        assert_eq!(resp.status_code, 400);
+        assert_eq!(resp.content_type, "application/graphql-response+json");
+
+        let resp = client
+            .post("/")
+            .header(ContentType::JSON)
+            .header(Header::new("Accept", "application/graphql-response+json"))
+            .body(r#"[{"query": "{hero{name}}"}, {"query": "{hero{blah}}"}]"#)
+            .dispatch()
+            .await;
+        assert_eq!(make_test_response(resp).await.status_code, 400);
+
+        let resp = client
+            .get("/?query=%7Bhero%7Bname%7D%7D")
+            .header(Header::new("Accept", "application/graphql-response+json"))
+            .dispatch()
+            .await;
+        assert_eq!(make_test_response(resp).await.status_code, 200);
+    }
+
     #[rocket::async_test]
     async fn test_operation_names() {
         #[post("/", data = "<request>")]
@@ -610,6 +1003,49 @@ mod tests {
         assert_eq!(resp.await.status_code, 200);
     }
 
+    #[rocket::async_test]
+    async fn test_factory_context() {
+        #[get("/?<request..>")]
+        async fn handler(
+            context: Result<super::FactoryContext<Database>, ContextError>,
+            request: super::GraphQLRequest,
+            schema: &State<Schema>,
+        ) -> super::GraphQLResponse {
+            match context {
+                Ok(super::FactoryContext(context)) => request.execute(&*schema, &context).await,
+                Err(e) => super::GraphQLResponse::context_error(e),
+            }
+        }
+
+        let context_factory: Arc<dyn ContextFactory<Database>> =
+            Arc::new(|parts: &RequestParts| {
+                futures::future::ready(match parts.header("authorization") {
+                    Some("secret") => Ok(Database::new()),
+                    _ => Err(ContextError::unauthorized("invalid token")),
+                })
+            });
+        let rocket = make_rocket_without_routes()
+            .manage(context_factory)
+            .mount("/", routes![handler]);
+        let client = Client::untracked(rocket).await.expect("valid rocket");
+
+        let resp = client
+            .get("/?query=%7Bhero%7Bname%7D%7D")
+            .header(Header::new("Authorization", "wrong"))
+            .dispatch()
+            .await;
+        let resp = make_test_response(resp).await;
+        assert_eq!(resp.status_code, 401);
+        assert!(resp.body.unwrap().contains("invalid token"));
+
+        let resp = client
+            .get("/?query=%7Bhero%7Bname%7D%7D")
+            .header(Header::new("Authorization", "secret"))
+            .dispatch()
+            .await;
+        assert_eq!(make_test_response(resp).await.status_code, 200);
+    }
+
     fn make_rocket() -> Rocket<Build> {
         make_rocket_without_routes().mount("/", routes![post_graphql_handler, get_graphql_handler])
     }
@@ -622,6 +1058,177 @@ mod tests {
         ))
     }
 
+    #[cfg(feature = "sse")]
+    #[rocket::async_test]
+    async fn test_graphql_sse_distinct_connections() {
+        use std::sync::Arc;
+
+        use juniper::DefaultScalarValue;
+
+        use crate::sse::{EventStreams, GraphQLEventStream};
+
+        type Streams = EventStreams<
+            Query,
+            EmptyMutation<Database>,
+            EmptySubscription<Database>,
+            Database,
+            DefaultScalarValue,
+        >;
+
+        #[post("/stream", data = "<request>")]
+        fn post_graphql_stream_handler(
+            request: super::GraphQLRequest,
+            event_streams: &State<Streams>,
+        ) -> Result<GraphQLEventStream, super::GraphQLResponse> {
+            request.subscribe(event_streams, Arc::new(Database::new()))
+        }
+
+        let root_node = Schema::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        );
+        let event_streams = Streams::new(
+            Arc::new(juniper_subscriptions::Coordinator::new(root_node)),
+            None,
+        );
+        let rocket = Rocket::build()
+            .manage(event_streams)
+            .mount("/", routes![post_graphql_stream_handler]);
+        let client = Client::untracked(rocket).await.expect("valid rocket");
+
+        let resp = client
+            .post("/stream")
+            .header(ContentType::JSON)
+            .body(r#"{"query": "{ hero { name } }"}"#)
+            .dispatch()
+            .await;
+
+        assert_eq!(resp.status(), rocket::http::Status::Ok);
+        assert_eq!(resp.content_type(), Some(ContentType::EventStream));
+        assert_eq!(
+            resp.into_string().await.unwrap(),
+            "event: next\ndata: {\"data\":{\"hero\":{\"name\":\"R2-D2\"}}}\n\n\
+             event: complete\ndata:\n\n",
+        );
+    }
+
+    #[cfg(feature = "subscriptions")]
+    struct TestRocketWsIntegration {
+        port: u16,
+    }
+
+    #[cfg(feature = "subscriptions")]
+    impl TestRocketWsIntegration {
+        async fn run_async(
+            &self,
+            messages: Vec<http_tests::WsIntegrationMessage>,
+        ) -> Result<(), anyhow::Error> {
+            use std::time::Duration;
+
+            use futures::{SinkExt as _, StreamExt as _};
+            use tokio_tungstenite::tungstenite::Message;
+
+            let url = format!("ws://127.0.0.1:{}/subscriptions", self.port);
+            let (mut websocket, _) = tokio_tungstenite::connect_async(url)
+                .await
+                .map_err(|e| anyhow::anyhow!("WS error: {:?}", e))?;
+
+            for message in messages {
+                match message {
+                    http_tests::WsIntegrationMessage::Send(body) => {
+                        websocket
+                            .send(Message::Text(body))
+                            .await
+                            .map_err(|e| anyhow::anyhow!("WS error: {:?}", e))?;
+                    }
+                    http_tests::WsIntegrationMessage::Expect(body, message_timeout) => {
+                        let message = tokio::time::timeout(
+                            Duration::from_millis(message_timeout),
+                            websocket.next(),
+                        )
+                        .await
+                        .map_err(|_| anyhow::anyhow!("Timed-out waiting for message"))?
+                        .ok_or_else(|| anyhow::anyhow!("Empty message received"))?
+                        .map_err(|e| anyhow::anyhow!("WS error: {:?}", e))?;
+
+                        let text = match message {
+                            Message::Text(text) => text,
+                            _ => return Err(anyhow::anyhow!("Received non-text message")),
+                        };
+                        let expected_value = serde_json::from_str::<serde_json::Value>(&body)
+                            .map_err(|e| anyhow::anyhow!("Serde error: {:?}", e))?;
+                        let value = serde_json::from_str::<serde_json::Value>(&text)
+                            .map_err(|e| anyhow::anyhow!("Serde error: {:?}", e))?;
+                        if value != expected_value {
+                            return Err(anyhow::anyhow!(
+                                "Expected message: {}. Received message: {}",
+                                expected_value,
+                                value,
+                            ));
+                        }
+                    }
+                }
+            }
+
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "subscriptions")]
+    impl http_tests::WsIntegration for TestRocketWsIntegration {
+        fn run(
+            &self,
+            messages: Vec<http_tests::WsIntegrationMessage>,
+        ) -> juniper::LocalBoxFuture<'_, Result<(), anyhow::Error>> {
+            Box::pin(self.run_async(messages))
+        }
+    }
+
+    #[cfg(feature = "subscriptions")]
+    #[rocket::async_test]
+    async fn test_rocket_ws_integration() {
+        use std::sync::Arc;
+
+        use juniper::tests::fixtures::starwars::schema::Subscription;
+        use juniper_graphql_ws::ConnectionConfig;
+
+        type Schema = RootNode<'static, Query, EmptyMutation<Database>, Subscription>;
+
+        #[get("/subscriptions")]
+        fn subscriptions_handler(
+            ws: rocket_ws::WebSocket,
+            schema: &State<Arc<Schema>>,
+        ) -> rocket_ws::Channel<'static> {
+            let config = ConnectionConfig::new(Database::new());
+            crate::subscriptions::graphql_ws(ws, schema.inner().clone(), config)
+        }
+
+        let port = 3006;
+        let config = rocket::Config {
+            port,
+            ..rocket::Config::debug_default()
+        };
+        let rocket = rocket::custom(config)
+            .manage(Arc::new(Schema::new(
+                Query,
+                EmptyMutation::<Database>::new(),
+                Subscription,
+            )))
+            .mount("/", routes![subscriptions_handler])
+            .ignite()
+            .await
+            .expect("valid rocket");
+        let shutdown = rocket.shutdown();
+        let server = rocket::tokio::spawn(rocket.launch());
+        rocket::tokio::time::sleep(std::time::Duration::from_millis(100)).await; // wait for bind
+
+        http_tests::run_ws_test_suite(&TestRocketWsIntegration { port }).await;
+
+        shutdown.notify();
+        server.await.unwrap().expect("server to shut down");
+    }
+
     async fn make_test_response(response: LocalResponse<'_>) -> http_tests::TestResponse {
         let status_code = response.status().code as i32;
         let content_type = response
@@ -0,0 +1,31 @@
+//! GraphQL over [SSE][1], serving subscriptions without WebSocket support.
+//!
+//! Only the "distinct connections" mode of the protocol is supported, which is the default one of
+//! its clients.
+//!
+//! [1]: https://github.com/enisdenjo/graphql-sse/blob/master/PROTOCOL.md
+
+use futures::stream::{BoxStream, StreamExt as _};
+use juniper_subscriptions::sse::Event;
+use rocket::{
+    http::ContentType,
+    response::{self, stream::TextStream, Responder, Response},
+    Request,
+};
+
+pub use juniper_subscriptions::sse::EventStreams;
+
+/// Stream of server-sent events, responding to a request executed with
+/// [`GraphQLRequest::subscribe()`].
+///
+/// [`GraphQLRequest::subscribe()`]: crate::GraphQLRequest::subscribe
+pub struct GraphQLEventStream(pub(crate) BoxStream<'static, Event>);
+
+impl<'r> Responder<'r, 'r> for GraphQLEventStream {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'r> {
+        let events = TextStream(self.0.map(|event| event.to_string()));
+        Response::build_from(events.respond_to(req)?)
+            .header(ContentType::EventStream)
+            .ok()
+    }
+}
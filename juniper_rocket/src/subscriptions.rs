@@ -0,0 +1,174 @@
+//! GraphQL subscriptions over WebSocket, as of the [graphql-ws protocol][1].
+//!
+//! [1]: https://github.com/apollographql/subscriptions-transport-ws/blob/master/PROTOCOL.md
+
+use std::{convert::Infallible, fmt, io, sync::Arc};
+
+use futures::{
+    future::{self, Either},
+    SinkExt as _, StreamExt as _,
+};
+use juniper::{GraphQLSubscriptionType, GraphQLTypeAsync, RootNode, ScalarValue};
+use juniper_graphql_ws::{ArcSchema, ClientMessage, Connection, Init};
+use rocket_ws::{stream::DuplexStream, Channel, WebSocket};
+
+struct Message(rocket_ws::Message);
+
+impl<S: ScalarValue> std::convert::TryFrom<Message> for ClientMessage<S> {
+    type Error = Error;
+
+    fn try_from(msg: Message) -> Result<Self, Self::Error> {
+        match msg.0 {
+            rocket_ws::Message::Text(text) => serde_json::from_str(&text),
+            msg => serde_json::from_slice(&msg.into_data()),
+        }
+        .map_err(Error::Serde)
+    }
+}
+
+/// Errors that can happen while serving a connection.
+#[derive(Debug)]
+pub enum Error {
+    /// Errors that can happen in the WebSocket protocol while serving a connection.
+    WebSocket(Box<rocket_ws::result::Error>),
+
+    /// Errors that can happen while serializing outgoing messages. Note that errors that occur
+    /// while deserializing incoming messages are handled internally by the protocol.
+    Serde(serde_json::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WebSocket(e) => write!(f, "websocket error: {}", e),
+            Self::Serde(e) => write!(f, "serde error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<rocket_ws::result::Error> for Error {
+    fn from(err: rocket_ws::result::Error) -> Self {
+        Self::WebSocket(Box::new(err))
+    }
+}
+
+impl From<Infallible> for Error {
+    fn from(_err: Infallible) -> Self {
+        unreachable!()
+    }
+}
+
+impl From<Error> for rocket_ws::result::Error {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::WebSocket(e) => *e,
+            Error::Serde(e) => io::Error::new(io::ErrorKind::InvalidData, e).into(),
+        }
+    }
+}
+
+/// Upgrades the given [`WebSocket`] request to a [`Channel`] serving the graphql-ws protocol.
+///
+/// The `init` argument is used to provide the context and additional configuration for
+/// connections. This can be a `juniper_graphql_ws::ConnectionConfig` if the context and
+/// configuration are already known, or it can be a closure that gets executed asynchronously
+/// when the client sends the ConnectionInit message. Using a closure allows you to perform
+/// authentication based on the parameters provided by the client.
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use juniper::{
+/// #     tests::fixtures::starwars::schema::{Database, Query, Subscription},
+/// #     EmptyMutation, RootNode,
+/// # };
+/// # use juniper_graphql_ws::ConnectionConfig;
+/// # use rocket::State;
+/// #
+/// type Schema = RootNode<'static, Query, EmptyMutation<Database>, Subscription>;
+///
+/// #[rocket::get("/subscriptions")]
+/// fn subscriptions(
+///     ws: rocket_ws::WebSocket,
+///     schema: &State<Arc<Schema>>,
+/// ) -> rocket_ws::Channel<'static> {
+///     let config = ConnectionConfig::new(Database::new());
+///     juniper_rocket::subscriptions::graphql_ws(ws, schema.inner().clone(), config)
+/// }
+/// ```
+pub fn graphql_ws<Query, Mutation, Subscription, CtxT, S, I>(
+    ws: WebSocket,
+    root_node: Arc<RootNode<'static, Query, Mutation, Subscription, S>>,
+    init: I,
+) -> Channel<'static>
+where
+    Query: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Query::TypeInfo: Send + Sync,
+    Mutation: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Mutation::TypeInfo: Send + Sync,
+    Subscription: GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+    Subscription::TypeInfo: Send + Sync,
+    CtxT: Unpin + Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+    I: Init<S, CtxT> + Send + 'static,
+{
+    ws.channel(move |stream| {
+        Box::pin(async move {
+            serve_graphql_ws(stream, root_node, init)
+                .await
+                .map_err(Into::into)
+        })
+    })
+}
+
+/// Serves the graphql-ws protocol over an upgraded WebSocket connection.
+///
+/// The `init` argument is used to provide the context and additional configuration for
+/// connections. This can be a `juniper_graphql_ws::ConnectionConfig` if the context and
+/// configuration are already known, or it can be a closure that gets executed asynchronously
+/// when the client sends the ConnectionInit message. Using a closure allows you to perform
+/// authentication based on the parameters provided by the client.
+pub async fn serve_graphql_ws<Query, Mutation, Subscription, CtxT, S, I>(
+    websocket: DuplexStream,
+    root_node: Arc<RootNode<'static, Query, Mutation, Subscription, S>>,
+    init: I,
+) -> Result<(), Error>
+where
+    Query: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Query::TypeInfo: Send + Sync,
+    Mutation: GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Mutation::TypeInfo: Send + Sync,
+    Subscription: GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+    Subscription::TypeInfo: Send + Sync,
+    CtxT: Unpin + Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+    I: Init<S, CtxT> + Send,
+{
+    let (ws_tx, ws_rx) = websocket.split();
+    let (s_tx, s_rx) = Connection::new(ArcSchema(root_node), init).split();
+
+    // Control frames are answered by `tungstenite` itself, so only data frames are passed further.
+    let ws_rx = ws_rx.filter_map(|r| {
+        future::ready(match r {
+            Ok(rocket_ws::Message::Text(_)) | Ok(rocket_ws::Message::Binary(_)) | Err(_) => {
+                Some(r.map(Message).map_err(Error::from))
+            }
+            Ok(_) => None,
+        })
+    });
+    let s_rx = s_rx.map(|msg| {
+        serde_json::to_string(&msg)
+            .map(rocket_ws::Message::Text)
+            .map_err(Error::Serde)
+    });
+
+    match future::select(
+        ws_rx.forward(s_tx.sink_err_into()),
+        s_rx.forward(ws_tx.sink_err_into()),
+    )
+    .await
+    {
+        Either::Left((r, _)) | Either::Right((r, _)) => r,
+    }
+}
@@ -11,6 +11,9 @@
 #![deny(warnings)]
 #![doc(html_root_url = "https://docs.rs/juniper_subscriptions/0.16.0")]
 
+#[cfg(feature = "sse")]
+pub mod sse;
+
 use std::{
     iter::FromIterator,
     pin::Pin,
@@ -59,9 +62,10 @@ where
     }
 }
 
-impl<'a, QueryT, MutationT, SubscriptionT, CtxT, S> SubscriptionCoordinator<'a, CtxT, S>
+impl<'a, 'r, QueryT, MutationT, SubscriptionT, CtxT, S> SubscriptionCoordinator<'r, CtxT, S>
     for Coordinator<'a, QueryT, MutationT, SubscriptionT, CtxT, S>
 where
+    'a: 'r,
     QueryT: GraphQLTypeAsync<S, Context = CtxT> + Send,
     QueryT::TypeInfo: Send + Sync,
     MutationT: GraphQLTypeAsync<S, Context = CtxT> + Send,
@@ -69,17 +73,17 @@ where
     SubscriptionT: GraphQLSubscriptionType<S, Context = CtxT> + Send,
     SubscriptionT::TypeInfo: Send + Sync,
     CtxT: Sync,
-    S: ScalarValue + Send + Sync + 'a,
+    S: ScalarValue + Send + Sync + 'r,
 {
-    type Connection = Connection<'a, S>;
+    type Connection = Connection<'r, S>;
 
-    type Error = GraphQLError<'a>;
+    type Error = GraphQLError<'r>;
 
     fn subscribe(
-        &'a self,
-        req: &'a GraphQLRequest<S>,
-        context: &'a CtxT,
-    ) -> BoxFuture<'a, Result<Self::Connection, Self::Error>> {
+        &'r self,
+        req: &'r GraphQLRequest<S>,
+        context: &'r CtxT,
+    ) -> BoxFuture<'r, Result<Self::Connection, Self::Error>> {
         juniper::http::resolve_into_stream(req, &self.root_node, context)
             .map_ok(|(stream, errors)| Connection::from_stream(stream, errors))
             .boxed()
@@ -0,0 +1,749 @@
+//! [Server-Sent Events][1] transport for GraphQL operations, as of the [GraphQL over SSE
+//! protocol][2].
+//!
+//! Both modes of the protocol are supported by [`EventStreams`]:
+//! - in the "distinct connections" mode every operation is executed over its own event stream,
+//!   which is returned by [`EventStreams::subscribe()`];
+//! - in the "single connection" mode a client [reserves][`EventStreams::reserve()`] an event
+//!   stream, [opens][`EventStreams::open()`] it and then [executes][`EventStreams::execute()`] or
+//!   [cancels][`EventStreams::cancel()`] operations over separate HTTP requests, identifying the
+//!   stream with the token sent in the [`TOKEN_HEADER`].
+//!
+//! Integration crates are expected to write [`Event`]s into response bodies with the
+//! [`CONTENT_TYPE`] by their [`Display`] implementation.
+//!
+//! [1]: https://html.spec.whatwg.org/multipage/server-sent-events.html
+//! [2]: https://github.com/enisdenjo/graphql-sse/blob/master/PROTOCOL.md
+//! [`Display`]: std::fmt::Display
+
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    error::Error as StdError,
+    fmt,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{self, Poll},
+    time::Duration,
+};
+
+use futures::{
+    channel::mpsc,
+    future, stream,
+    stream::{AbortHandle, BoxStream, SelectAll},
+    FutureExt as _, SinkExt as _, Stream, StreamExt as _,
+};
+use juniper::{
+    http::{GraphQLRequest, GraphQLResponse},
+    GraphQLSubscriptionType, GraphQLTypeAsync, RootNode, ScalarValue, SubscriptionCoordinator as _,
+};
+use tokio::time::{self, Instant, Interval};
+
+use crate::Coordinator;
+
+/// `Content-Type` of GraphQL over SSE responses.
+pub const CONTENT_TYPE: &str = "text/event-stream";
+
+/// Header carrying the token of an event stream in the "single connection" mode.
+pub const TOKEN_HEADER: &str = "x-graphql-event-stream-token";
+
+/// Default time a reserved event stream waits to be opened, before its token is released.
+pub const DEFAULT_RESERVATION_TTL: Duration = Duration::from_secs(30);
+
+/// Maximum number of operations queued over an event stream, while it doesn't pick them up.
+const MAX_PENDING_OPERATIONS: usize = 16;
+
+/// Single event of a GraphQL over SSE stream.
+///
+/// Its [`Display`] implementation renders the event in the SSE wire format.
+///
+/// [`Display`]: std::fmt::Display
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event {
+    /// Result of an operation.
+    ///
+    /// Carries the ID of the operation in the "single connection" mode only.
+    Next {
+        /// ID of the operation this result belongs to.
+        id: Option<String>,
+
+        /// Serialized GraphQL response.
+        payload: serde_json::Value,
+    },
+
+    /// Completion of an operation, after which no more results of it are sent.
+    ///
+    /// Carries the ID of the operation in the "single connection" mode only.
+    Complete {
+        /// ID of the completed operation.
+        id: Option<String>,
+    },
+
+    /// Comment keeping the connection alive while there are no results to send.
+    KeepAlive,
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Next { id: None, payload } => write!(f, "event: next\ndata: {}\n\n", payload),
+            Self::Next {
+                id: Some(id),
+                payload,
+            } => write!(
+                f,
+                "event: next\ndata: {}\n\n",
+                serde_json::json!({ "id": id, "payload": payload }),
+            ),
+            Self::Complete { id: None } => f.write_str("event: complete\ndata:\n\n"),
+            Self::Complete { id: Some(id) } => write!(
+                f,
+                "event: complete\ndata: {}\n\n",
+                serde_json::json!({ "id": id }),
+            ),
+            Self::KeepAlive => f.write_str(":\n\n"),
+        }
+    }
+}
+
+/// Error of an operation over an event stream in the "single connection" mode.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SseError {
+    /// No event stream has been reserved for the given token.
+    UnknownStream,
+
+    /// Event stream for the given token has been opened already.
+    StreamAlreadyOpen,
+
+    /// Request doesn't specify the `operationId` extension.
+    MissingOperationId,
+
+    /// Operation with the given ID is running already.
+    DuplicateOperation,
+
+    /// No operation with the given ID is running.
+    UnknownOperation,
+
+    /// Too many operations are queued over the event stream already.
+    TooManyOperations,
+}
+
+impl SseError {
+    /// Returns the HTTP status code of a response reporting this [`SseError`].
+    pub fn status_code(&self) -> u16 {
+        match self {
+            Self::UnknownStream | Self::UnknownOperation => 404,
+            Self::StreamAlreadyOpen | Self::DuplicateOperation => 409,
+            Self::MissingOperationId => 400,
+            Self::TooManyOperations => 429,
+        }
+    }
+}
+
+impl fmt::Display for SseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::UnknownStream => "unknown event stream token",
+            Self::StreamAlreadyOpen => "event stream is open already",
+            Self::MissingOperationId => "`operationId` extension is missing",
+            Self::DuplicateOperation => "operation with this ID is running already",
+            Self::UnknownOperation => "unknown operation ID",
+            Self::TooManyOperations => "too many operations are queued over this event stream",
+        })
+    }
+}
+
+impl StdError for SseError {}
+
+type Operations = mpsc::Receiver<BoxStream<'static, Event>>;
+
+/// Event stream of the "single connection" mode.
+struct StreamEntry {
+    /// Sender of the operations to run over this stream.
+    operations: mpsc::Sender<BoxStream<'static, Event>>,
+
+    /// Receiver of the operations, taken once this stream is opened.
+    pending: Option<Operations>,
+
+    /// Moment this stream is released at, unless it's opened before.
+    expires_at: Option<Instant>,
+
+    /// Handles to cancel the running operations by their IDs.
+    running: HashMap<String, AbortHandle>,
+}
+
+impl StreamEntry {
+    fn is_expired(&self, now: Instant) -> bool {
+        self.expires_at.map_or(false, |at| at <= now)
+    }
+}
+
+type Registry = Arc<Mutex<HashMap<String, StreamEntry>>>;
+
+/// Looks up the unexpired event stream with the given `token` in the given `streams`.
+fn entry_mut<'s>(
+    streams: &'s mut HashMap<String, StreamEntry>,
+    token: &str,
+) -> Result<&'s mut StreamEntry, SseError> {
+    streams
+        .get_mut(token)
+        .filter(|entry| !entry.is_expired(Instant::now()))
+        .ok_or(SseError::UnknownStream)
+}
+
+/// GraphQL over SSE event streams of a schema.
+///
+/// Keeps track of the event streams opened in the "single connection" mode, so should be shared
+/// between requests, typically in an [`Arc`].
+pub struct EventStreams<QueryT, MutationT, SubscriptionT, CtxT, S>
+where
+    QueryT: GraphQLTypeAsync<S, Context = CtxT> + Send,
+    QueryT::TypeInfo: Send + Sync,
+    MutationT: GraphQLTypeAsync<S, Context = CtxT> + Send,
+    MutationT::TypeInfo: Send + Sync,
+    SubscriptionT: GraphQLSubscriptionType<S, Context = CtxT> + Send,
+    SubscriptionT::TypeInfo: Send + Sync,
+    CtxT: Sync,
+    S: ScalarValue + Send + Sync,
+{
+    coordinator: Arc<Coordinator<'static, QueryT, MutationT, SubscriptionT, CtxT, S>>,
+    keep_alive: Option<Duration>,
+    reservation_ttl: Duration,
+    streams: Registry,
+}
+
+impl<QueryT, MutationT, SubscriptionT, CtxT, S>
+    EventStreams<QueryT, MutationT, SubscriptionT, CtxT, S>
+where
+    QueryT: GraphQLTypeAsync<S, Context = CtxT> + Send + Sync + 'static,
+    QueryT::TypeInfo: Send + Sync,
+    MutationT: GraphQLTypeAsync<S, Context = CtxT> + Send + Sync + 'static,
+    MutationT::TypeInfo: Send + Sync,
+    SubscriptionT: GraphQLSubscriptionType<S, Context = CtxT> + Send + Sync + 'static,
+    SubscriptionT::TypeInfo: Send + Sync,
+    CtxT: Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+{
+    /// Creates new [`EventStreams`] executing operations with the given `coordinator`.
+    ///
+    /// If `keep_alive` is specified, a comment is sent over every opened event stream at that
+    /// interval, so proxies don't close it while there are no results to send.
+    pub fn new(
+        coordinator: Arc<Coordinator<'static, QueryT, MutationT, SubscriptionT, CtxT, S>>,
+        keep_alive: Option<Duration>,
+    ) -> Self {
+        Self {
+            coordinator,
+            keep_alive,
+            reservation_ttl: DEFAULT_RESERVATION_TTL,
+            streams: Arc::default(),
+        }
+    }
+
+    /// Sets the time a [reserved][`EventStreams::reserve()`] event stream waits to be opened,
+    /// before its token is released along with the operations executed over it.
+    ///
+    /// Defaults to [`DEFAULT_RESERVATION_TTL`].
+    pub fn with_reservation_ttl(mut self, ttl: Duration) -> Self {
+        self.reservation_ttl = ttl;
+        self
+    }
+
+    /// Returns the schema operations are executed against.
+    pub fn root_node(&self) -> &RootNode<'static, QueryT, MutationT, SubscriptionT, S> {
+        &self.coordinator.root_node
+    }
+
+    /// Executes the given `request` over a new event stream, as of the "distinct connections"
+    /// mode.
+    ///
+    /// The returned stream yields a [`Event::Next`] for every result of the operation, which is a
+    /// single one for queries and mutations, and finishes with an [`Event::Complete`]. Errors
+    /// preventing the execution are sent as a result too.
+    pub fn subscribe(
+        &self,
+        request: GraphQLRequest<S>,
+        context: Arc<CtxT>,
+    ) -> BoxStream<'static, Event> {
+        let events = execute(self.coordinator.clone(), request, context)
+            .map(|payload| Event::Next { id: None, payload })
+            .chain(stream::once(future::ready(Event::Complete { id: None })));
+        with_keep_alive(events.boxed(), self.keep_alive)
+    }
+
+    /// Reserves a new event stream of the "single connection" mode, returning its token.
+    ///
+    /// The event stream is released if it's not [opened][`EventStreams::open()`] within the
+    /// [reservation TTL][`EventStreams::with_reservation_ttl()`].
+    ///
+    /// # Panics
+    ///
+    /// If the operating system fails to provide random bytes for the token.
+    pub fn reserve(&self) -> String {
+        let mut bytes = [0; 16];
+        getrandom::getrandom(&mut bytes).expect("failed to generate event stream token");
+        let token = bytes
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>();
+
+        let now = Instant::now();
+        let (operations, pending) = mpsc::channel(MAX_PENDING_OPERATIONS);
+        let mut streams = self.streams.lock().unwrap();
+        streams.retain(|_, entry| !entry.is_expired(now));
+        streams.insert(
+            token.clone(),
+            StreamEntry {
+                operations,
+                pending: Some(pending),
+                expires_at: Some(now + self.reservation_ttl),
+                running: HashMap::new(),
+            },
+        );
+        token
+    }
+
+    /// Opens the reserved event stream with the given `token`.
+    ///
+    /// The returned stream yields [`Event`]s of all the operations [executed][1] over it, and
+    /// never finishes on its own. Once it's dropped, the `token` is released.
+    ///
+    /// # Errors
+    ///
+    /// If no event stream is reserved for the `token`, its reservation has expired, or it's
+    /// opened already.
+    ///
+    /// [1]: EventStreams::execute
+    pub fn open(&self, token: &str) -> Result<BoxStream<'static, Event>, SseError> {
+        let mut streams = self.streams.lock().unwrap();
+        let entry = entry_mut(&mut streams, token)?;
+        let incoming = entry.pending.take().ok_or(SseError::StreamAlreadyOpen)?;
+        entry.expires_at = None;
+        drop(streams);
+
+        let events = OpenStream {
+            token: token.into(),
+            streams: self.streams.clone(),
+            incoming,
+            incoming_done: false,
+            running: stream::select_all(vec![]),
+        };
+        Ok(with_keep_alive(events.boxed(), self.keep_alive))
+    }
+
+    /// Executes the given `request` over the event stream with the given `token`, identifying it
+    /// with its `operationId` extension.
+    ///
+    /// Results of the operation are sent over the event stream, even if it's not opened yet.
+    ///
+    /// # Errors
+    ///
+    /// If no event stream is reserved for the `token`, the `request` has no `operationId`
+    /// extension, an operation with the same ID is running already, or too many operations are
+    /// queued over the event stream, as it's not opened yet or doesn't keep up with them.
+    pub fn execute(
+        &self,
+        token: &str,
+        request: GraphQLRequest<S>,
+        context: Arc<CtxT>,
+    ) -> Result<(), SseError> {
+        let id = request
+            .extensions()
+            .and_then(|e| e.to_object_value())
+            .and_then(|e| e.get("operationId").and_then(|id| id.as_string_value()))
+            .map(str::to_owned)
+            .ok_or(SseError::MissingOperationId)?;
+
+        let mut streams = self.streams.lock().unwrap();
+        let entry = entry_mut(&mut streams, token)?;
+        let (results, abort) =
+            stream::abortable(execute(self.coordinator.clone(), request, context));
+        match entry.running.entry(id.clone()) {
+            Entry::Occupied(_) => return Err(SseError::DuplicateOperation),
+            Entry::Vacant(e) => e.insert(abort),
+        };
+
+        let (registry, token) = (self.streams.clone(), token.to_owned());
+        let (next_id, complete_id) = (id.clone(), id.clone());
+        let events = results
+            .map(move |payload| Event::Next {
+                id: Some(next_id.clone()),
+                payload,
+            })
+            .chain(stream::once(async move {
+                if let Some(entry) = registry.lock().unwrap().get_mut(&token) {
+                    entry.running.remove(&complete_id);
+                }
+                Event::Complete {
+                    id: Some(complete_id),
+                }
+            }));
+        match entry.operations.try_send(events.boxed()) {
+            Err(e) if e.is_full() => {
+                entry.running.remove(&id);
+                Err(SseError::TooManyOperations)
+            }
+            // The receiver is dropped along with the whole event stream, so there is no one to
+            // deliver the results to anyway.
+            Ok(()) | Err(_) => Ok(()),
+        }
+    }
+
+    /// Cancels the operation with the given `id` running over the event stream with the given
+    /// `token`.
+    ///
+    /// An [`Event::Complete`] is still sent for the cancelled operation.
+    ///
+    /// # Errors
+    ///
+    /// If no event stream is reserved for the `token`, or no operation with the `id` is running.
+    pub fn cancel(&self, token: &str, id: &str) -> Result<(), SseError> {
+        let mut streams = self.streams.lock().unwrap();
+        let entry = entry_mut(&mut streams, token)?;
+        let abort = entry.running.remove(id).ok_or(SseError::UnknownOperation)?;
+        abort.abort();
+        Ok(())
+    }
+}
+
+/// Executes the given `request`, returning a stream of its serialized results.
+fn execute<QueryT, MutationT, SubscriptionT, CtxT, S>(
+    coordinator: Arc<Coordinator<'static, QueryT, MutationT, SubscriptionT, CtxT, S>>,
+    request: GraphQLRequest<S>,
+    context: Arc<CtxT>,
+) -> BoxStream<'static, serde_json::Value>
+where
+    QueryT: GraphQLTypeAsync<S, Context = CtxT> + Send + Sync + 'static,
+    QueryT::TypeInfo: Send + Sync,
+    MutationT: GraphQLTypeAsync<S, Context = CtxT> + Send + Sync + 'static,
+    MutationT::TypeInfo: Send + Sync,
+    SubscriptionT: GraphQLSubscriptionType<S, Context = CtxT> + Send + Sync + 'static,
+    SubscriptionT::TypeInfo: Send + Sync,
+    CtxT: Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+{
+    // Connections borrow the request and the context, so the execution is driven by the returned
+    // stream itself, passing the results through a channel.
+    let (mut tx, rx) = mpsc::channel(0);
+    let execution = async move {
+        // Only subscriptions fail to be executed directly, so other operations are parsed and
+        // validated just once.
+        let response = request.execute(&coordinator.root_node, &context).await;
+        if !response.is_subscription() {
+            let _ = tx.send(to_payload(&response)).await;
+            return;
+        }
+        match coordinator.subscribe(&request, &context).await {
+            Ok(mut connection) => {
+                while let Some(output) = connection.next().await {
+                    let response = GraphQLResponse::from_result(Ok((output.data, output.errors)));
+                    if tx.send(to_payload(&response)).await.is_err() {
+                        break;
+                    }
+                }
+            }
+            Err(e) => {
+                let _ = tx
+                    .send(to_payload(&GraphQLResponse::<S>::from_result(Err(e))))
+                    .await;
+            }
+        }
+    };
+    stream::select(
+        execution.into_stream().filter_map(|()| future::ready(None)),
+        rx,
+    )
+    .boxed()
+}
+
+fn to_payload<S: ScalarValue>(response: &GraphQLResponse<'_, S>) -> serde_json::Value {
+    serde_json::to_value(response).expect("GraphQL response should be serializable")
+}
+
+/// Interleaves the given `events` with [`Event::KeepAlive`]s sent at the `keep_alive` interval.
+fn with_keep_alive(
+    events: BoxStream<'static, Event>,
+    keep_alive: Option<Duration>,
+) -> BoxStream<'static, Event> {
+    let period = match keep_alive {
+        Some(period) => period,
+        None => return events,
+    };
+    let keep_alives = stream::unfold(None, move |interval: Option<Interval>| async move {
+        let mut interval =
+            interval.unwrap_or_else(|| time::interval_at(Instant::now() + period, period));
+        interval.tick().await;
+        Some((Some(Event::KeepAlive), Some(interval)))
+    });
+    stream::select(
+        events.map(Some).chain(stream::once(future::ready(None))),
+        keep_alives,
+    )
+    .take_while(|event| future::ready(event.is_some()))
+    .filter_map(future::ready)
+    .boxed()
+}
+
+/// Opened event stream of the "single connection" mode, releasing its token once dropped.
+struct OpenStream {
+    token: String,
+    streams: Registry,
+    incoming: Operations,
+    incoming_done: bool,
+    running: SelectAll<BoxStream<'static, Event>>,
+}
+
+impl Stream for OpenStream {
+    type Item = Event;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Event>> {
+        while !self.incoming_done {
+            match self.incoming.poll_next_unpin(cx) {
+                Poll::Ready(Some(operation)) => self.running.push(operation),
+                Poll::Ready(None) => self.incoming_done = true,
+                Poll::Pending => break,
+            }
+        }
+        match self.running.poll_next_unpin(cx) {
+            Poll::Ready(Some(event)) => Poll::Ready(Some(event)),
+            Poll::Ready(None) if self.incoming_done => Poll::Ready(None),
+            Poll::Ready(None) | Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl Drop for OpenStream {
+    fn drop(&mut self) {
+        if let Ok(mut streams) = self.streams.lock() {
+            streams.remove(&self.token);
+        }
+    }
+}
+
+/// Extracts the event stream token from the given `header` value or the `token` query parameter
+/// of the given `query` string, as integrations receive them.
+pub fn stream_token<'q>(header: Option<&'q str>, query: Option<&'q str>) -> Option<&'q str> {
+    header.or_else(|| query_param(query?, "token"))
+}
+
+/// Extracts the ID of the operation to cancel from the `operationId` query parameter of the given
+/// `query` string.
+pub fn operation_id(query: Option<&str>) -> Option<&str> {
+    query_param(query?, "operationId")
+}
+
+fn query_param<'q>(query: &'q str, name: &str) -> Option<&'q str> {
+    query.split('&').find_map(|pair| {
+        let mut pair = pair.splitn(2, '=');
+        (pair.next()? == name).then(|| pair.next().unwrap_or_default())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use futures::{stream, StreamExt as _};
+    use juniper::{
+        graphql_object, graphql_subscription, http::GraphQLRequest, DefaultScalarValue,
+        EmptyMutation, InputValue, RootNode,
+    };
+
+    use super::{Event, EventStreams, SseError, MAX_PENDING_OPERATIONS};
+    use crate::Coordinator;
+
+    struct Query;
+
+    #[graphql_object]
+    impl Query {
+        fn ping() -> bool {
+            true
+        }
+    }
+
+    struct Subscription;
+
+    type Counter = std::pin::Pin<Box<dyn futures::Stream<Item = i32> + Send>>;
+
+    #[graphql_subscription]
+    impl Subscription {
+        async fn count(to: i32) -> Counter {
+            Box::pin(stream::iter(1..=to))
+        }
+    }
+
+    type Schema = RootNode<'static, Query, EmptyMutation, Subscription>;
+
+    fn event_streams() -> EventStreams<Query, EmptyMutation, Subscription, (), DefaultScalarValue> {
+        let schema = Schema::new(Query, EmptyMutation::new(), Subscription);
+        EventStreams::new(Arc::new(Coordinator::new(schema)), None)
+    }
+
+    fn request(query: &str) -> GraphQLRequest {
+        GraphQLRequest::new(query.into(), None, None)
+    }
+
+    fn operation(id: &str) -> GraphQLRequest {
+        request("subscription { count(to: 1) }").with_extensions(Some(InputValue::object(
+            vec![("operationId", InputValue::scalar(id))]
+                .into_iter()
+                .collect(),
+        )))
+    }
+
+    #[test]
+    fn renders_events() {
+        let payload = serde_json::json!({"data": {"ping": true}});
+        for (event, expected) in [
+            (
+                Event::Next {
+                    id: None,
+                    payload: payload.clone(),
+                },
+                "event: next\ndata: {\"data\":{\"ping\":true}}\n\n",
+            ),
+            (
+                Event::Next {
+                    id: Some("1".into()),
+                    payload,
+                },
+                "event: next\ndata: {\"id\":\"1\",\"payload\":{\"data\":{\"ping\":true}}}\n\n",
+            ),
+            (Event::Complete { id: None }, "event: complete\ndata:\n\n"),
+            (
+                Event::Complete {
+                    id: Some("1".into()),
+                },
+                "event: complete\ndata: {\"id\":\"1\"}\n\n",
+            ),
+            (Event::KeepAlive, ":\n\n"),
+        ] {
+            assert_eq!(event.to_string(), expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn streams_subscription_in_distinct_connection() {
+        let events = event_streams()
+            .subscribe(request("subscription { count(to: 2) }"), Arc::new(()))
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(
+            events,
+            vec![
+                Event::Next {
+                    id: None,
+                    payload: serde_json::json!({"data": {"count": 1}}),
+                },
+                Event::Next {
+                    id: None,
+                    payload: serde_json::json!({"data": {"count": 2}}),
+                },
+                Event::Complete { id: None },
+            ],
+        );
+    }
+
+    #[tokio::test]
+    async fn streams_query_in_distinct_connection() {
+        let events = event_streams()
+            .subscribe(request("{ ping }"), Arc::new(()))
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(
+            events,
+            vec![
+                Event::Next {
+                    id: None,
+                    payload: serde_json::json!({"data": {"ping": true}}),
+                },
+                Event::Complete { id: None },
+            ],
+        );
+    }
+
+    #[tokio::test]
+    async fn streams_operations_in_single_connection() {
+        let streams = event_streams();
+        let token = streams.reserve();
+
+        streams
+            .execute(&token, operation("a"), Arc::new(()))
+            .unwrap();
+        assert_eq!(
+            streams.execute(&token, operation("a"), Arc::new(())),
+            Err(SseError::DuplicateOperation),
+        );
+        assert_eq!(
+            streams.execute(&token, request("{ ping }"), Arc::new(())),
+            Err(SseError::MissingOperationId),
+        );
+
+        let events = streams.open(&token).unwrap();
+        assert_eq!(
+            streams.open(&token).err(),
+            Some(SseError::StreamAlreadyOpen),
+        );
+        let events = events.take(2).collect::<Vec<_>>().await;
+
+        assert_eq!(
+            events,
+            vec![
+                Event::Next {
+                    id: Some("a".into()),
+                    payload: serde_json::json!({"data": {"count": 1}}),
+                },
+                Event::Complete {
+                    id: Some("a".into()),
+                },
+            ],
+        );
+        assert_eq!(streams.open(&token).err(), Some(SseError::UnknownStream));
+    }
+
+    #[test]
+    fn generates_random_tokens() {
+        let streams = event_streams();
+        let (a, b) = (streams.reserve(), streams.reserve());
+
+        assert_eq!(a.len(), 32);
+        assert!(a.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn releases_expired_reservations() {
+        let streams = event_streams().with_reservation_ttl(Duration::from_secs(0));
+        let token = streams.reserve();
+
+        assert_eq!(
+            streams.execute(&token, operation("a"), Arc::new(())),
+            Err(SseError::UnknownStream),
+        );
+        assert_eq!(streams.open(&token).err(), Some(SseError::UnknownStream));
+
+        streams.reserve();
+        assert_eq!(streams.streams.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn bounds_pending_operations() {
+        let streams = event_streams();
+        let token = streams.reserve();
+
+        for id in 0..=MAX_PENDING_OPERATIONS {
+            streams
+                .execute(&token, operation(&id.to_string()), Arc::new(()))
+                .unwrap();
+        }
+        assert_eq!(
+            streams.execute(&token, operation("last"), Arc::new(())),
+            Err(SseError::TooManyOperations),
+        );
+        assert_eq!(
+            streams.cancel(&token, "last"),
+            Err(SseError::UnknownOperation),
+        );
+    }
+}
@@ -42,13 +42,41 @@ Check the LICENSE file for details.
 
 use anyhow::anyhow;
 use futures::{FutureExt as _, TryFutureExt};
+#[cfg(feature = "multipart")]
+use futures::{Stream, StreamExt as _};
+#[cfg(feature = "multipart")]
+use juniper::http::multipart::{self, MultipartLimits, MultipartParser};
 use juniper::{
-    http::{GraphQLBatchRequest, GraphQLRequest},
-    ScalarValue,
+    http::{
+        BatchConfig, ContextError, ContextFactory, GraphQLBatchRequest, GraphQLRequest,
+        RequestParts, ResponseMediaType,
+    },
+    CachePolicy, DefaultScalarValue, ScalarValue,
 };
-use std::{collections::HashMap, str, sync::Arc};
+use std::{collections::HashMap, net::SocketAddr, str, sync::Arc};
 use tokio::task;
-use warp::{body, filters::BoxedFilter, http, hyper::body::Bytes, query, Filter};
+#[cfg(feature = "multipart")]
+use warp::hyper::body::Buf;
+use warp::{
+    body, filters::BoxedFilter, http, hyper::body::Bytes, path::FullPath, query, Filter, Rejection,
+};
+
+/// Configuration of the filters made by [`make_graphql_filter_with_config()`] and
+/// [`make_graphql_filter_sync_with_config()`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct GraphQLFilterConfig {
+    /// [`BatchConfig`] of executing the accepted batch requests.
+    pub batch: BatchConfig,
+
+    /// [`MultipartLimits`] of the accepted [GraphQL multipart requests][1].
+    ///
+    /// Multipart requests must carry an `Apollo-Require-Preflight` or `X-Apollo-Operation-Name`
+    /// header, so they can't be forged cross-origin.
+    ///
+    /// [1]: https://github.com/jaydenseric/graphql-multipart-request-spec
+    #[cfg(feature = "multipart")]
+    pub multipart: MultipartLimits,
+}
 
 /// Make a filter for graphql queries/mutations.
 ///
@@ -118,28 +146,135 @@ where
     CtxT: Send + Sync + 'static,
     S: ScalarValue + Send + Sync + 'static,
 {
-    let schema = Arc::new(schema);
+    make_graphql_filter_with_config(schema, context_extractor, GraphQLFilterConfig::default())
+}
+
+/// Make a filter for graphql queries/mutations, configured by the given
+/// [`GraphQLFilterConfig`].
+pub fn make_graphql_filter_with_config<Query, Mutation, Subscription, CtxT, S>(
+    schema: juniper::RootNode<'static, Query, Mutation, Subscription, S>,
+    context_extractor: BoxedFilter<(CtxT,)>,
+    config: GraphQLFilterConfig,
+) -> BoxedFilter<(http::Response<Vec<u8>>,)>
+where
+    Query: juniper::GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Query::TypeInfo: Send + Sync,
+    Mutation: juniper::GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Mutation::TypeInfo: Send + Sync,
+    Subscription: juniper::GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+    Subscription::TypeInfo: Send + Sync,
+    CtxT: Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+{
+    #[cfg(feature = "multipart")]
+    {
+        graphql_multipart_filter(Arc::new(schema), context_extractor, config)
+    }
+    #[cfg(not(feature = "multipart"))]
+    {
+        graphql_filter(Arc::new(schema), context_extractor, config.batch)
+    }
+}
+
+/// Make a filter for graphql queries/mutations, also accepting [GraphQL multipart requests][1].
+///
+/// [1]: https://github.com/jaydenseric/graphql-multipart-request-spec
+#[cfg(feature = "multipart")]
+fn graphql_multipart_filter<Query, Mutation, Subscription, CtxT, S>(
+    schema: Arc<juniper::RootNode<'static, Query, Mutation, Subscription, S>>,
+    context_extractor: BoxedFilter<(CtxT,)>,
+    config: GraphQLFilterConfig,
+) -> BoxedFilter<(http::Response<Vec<u8>>,)>
+where
+    Query: juniper::GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Query::TypeInfo: Send + Sync,
+    Mutation: juniper::GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Mutation::TypeInfo: Send + Sync,
+    Subscription: juniper::GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+    Subscription::TypeInfo: Send + Sync,
+    CtxT: Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+{
+    let post_multipart_schema = schema.clone();
+
+    let handle_post_multipart_request =
+        move |context: CtxT,
+              media_type: ResponseMediaType,
+              req: Result<GraphQLBatchRequest<S>, http::Response<Vec<u8>>>| {
+            let schema = post_multipart_schema.clone();
+            async move {
+                let req = match req {
+                    Ok(req) => req,
+                    Err(resp) => return Ok::<_, warp::Rejection>(resp),
+                };
+
+                let resp = req
+                    .execute_with_batch_config(&schema, &context, config.batch)
+                    .await;
+
+                Ok(build_response(
+                    serde_json::to_vec(&resp)
+                        .map(|json| (json, resp.status_code(), resp.cache_policy()))
+                        .map_err(Into::into),
+                    media_type,
+                ))
+            }
+        };
+    let post_multipart_filter = warp::post()
+        .and(context_extractor.clone())
+        .and(response_media_type())
+        .and(multipart_request(config.multipart))
+        .and_then(handle_post_multipart_request);
+
+    post_multipart_filter
+        .or(graphql_filter(schema, context_extractor, config.batch))
+        .unify()
+        .boxed()
+}
+
+fn graphql_filter<Query, Mutation, Subscription, CtxT, S>(
+    schema: Arc<juniper::RootNode<'static, Query, Mutation, Subscription, S>>,
+    context_extractor: BoxedFilter<(CtxT,)>,
+    batch_config: BatchConfig,
+) -> BoxedFilter<(http::Response<Vec<u8>>,)>
+where
+    Query: juniper::GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Query::TypeInfo: Send + Sync,
+    Mutation: juniper::GraphQLTypeAsync<S, Context = CtxT> + Send + 'static,
+    Mutation::TypeInfo: Send + Sync,
+    Subscription: juniper::GraphQLSubscriptionType<S, Context = CtxT> + Send + 'static,
+    Subscription::TypeInfo: Send + Sync,
+    CtxT: Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+{
     let post_json_schema = schema.clone();
     let post_graphql_schema = schema.clone();
 
-    let handle_post_json_request = move |context: CtxT, req: GraphQLBatchRequest<S>| {
-        let schema = post_json_schema.clone();
-        async move {
-            let resp = req.execute(&schema, &context).await;
-
-            Ok::<_, warp::Rejection>(build_response(
-                serde_json::to_vec(&resp)
-                    .map(|json| (json, resp.is_ok()))
-                    .map_err(Into::into),
-            ))
-        }
-    };
+    let handle_post_json_request =
+        move |context: CtxT, media_type: ResponseMediaType, req: GraphQLBatchRequest<S>| {
+            let schema = post_json_schema.clone();
+            async move {
+                let resp = req
+                    .execute_with_batch_config(&schema, &context, batch_config)
+                    .await;
+
+                Ok::<_, warp::Rejection>(build_response(
+                    serde_json::to_vec(&resp)
+                        .map(|json| (json, resp.status_code(), resp.cache_policy()))
+                        .map_err(Into::into),
+                    media_type,
+                ))
+            }
+        };
     let post_json_filter = warp::post()
         .and(context_extractor.clone())
+        .and(response_media_type())
         .and(body::json())
         .and_then(handle_post_json_request);
 
-    let handle_post_graphql_request = move |context: CtxT, body: Bytes| {
+    let handle_post_graphql_request = move |context: CtxT,
+                                            media_type: ResponseMediaType,
+                                            body: Bytes| {
         let schema = post_graphql_schema.clone();
         async move {
             let query = str::from_utf8(body.as_ref())
@@ -148,16 +283,23 @@ where
 
             let resp = req.execute(&schema, &context).await;
 
-            Ok((serde_json::to_vec(&resp)?, resp.is_ok()))
+            Ok((
+                serde_json::to_vec(&resp)?,
+                resp.status_code(),
+                resp.cache_policy(),
+            ))
         }
-        .then(|res| async { Ok::<_, warp::Rejection>(build_response(res)) })
+        .then(move |res| async move { Ok::<_, warp::Rejection>(build_response(res, media_type)) })
     };
     let post_graphql_filter = warp::post()
         .and(context_extractor.clone())
+        .and(response_media_type())
         .and(body::bytes())
         .and_then(handle_post_graphql_request);
 
-    let handle_get_request = move |context: CtxT, mut qry: HashMap<String, String>| {
+    let handle_get_request = move |context: CtxT,
+                                   media_type: ResponseMediaType,
+                                   mut qry: HashMap<String, String>| {
         let schema = schema.clone();
         async move {
             let req = GraphQLRequest::new(
@@ -167,16 +309,35 @@ where
                 qry.remove("variables")
                     .map(|vs| serde_json::from_str(&vs))
                     .transpose()?,
-            );
+            )
+            .with_extensions(
+                qry.remove("extensions")
+                    .map(|es| serde_json::from_str(&es))
+                    .transpose()?,
+            )
+            .forbid_mutations();
 
             let resp = req.execute(&schema, &context).await;
+            if resp.is_rejected_mutation() {
+                return Ok(mutation_over_get_response());
+            }
 
-            Ok((serde_json::to_vec(&resp)?, resp.is_ok()))
+            Ok(build_response(
+                Ok((
+                    serde_json::to_vec(&resp)?,
+                    resp.status_code(),
+                    resp.cache_policy(),
+                )),
+                media_type,
+            ))
         }
-        .then(|res| async move { Ok::<_, warp::Rejection>(build_response(res)) })
+        .then(move |res: anyhow::Result<_>| async move {
+            Ok::<_, warp::Rejection>(res.unwrap_or_else(|e| build_response(Err(e), media_type)))
+        })
     };
     let get_filter = warp::get()
         .and(context_extractor)
+        .and(response_media_type())
         .and(query::query())
         .and_then(handle_get_request);
 
@@ -200,75 +361,198 @@ where
     CtxT: Send + Sync + 'static,
     S: ScalarValue + Send + Sync + 'static,
 {
-    let schema = Arc::new(schema);
+    make_graphql_filter_sync_with_config(schema, context_extractor, GraphQLFilterConfig::default())
+}
+
+/// Make a synchronous filter for graphql endpoint, configured by the given
+/// [`GraphQLFilterConfig`].
+pub fn make_graphql_filter_sync_with_config<Query, Mutation, Subscription, CtxT, S>(
+    schema: juniper::RootNode<'static, Query, Mutation, Subscription, S>,
+    context_extractor: BoxedFilter<(CtxT,)>,
+    config: GraphQLFilterConfig,
+) -> BoxedFilter<(http::Response<Vec<u8>>,)>
+where
+    Query: juniper::GraphQLType<S, Context = CtxT, TypeInfo = ()> + Send + Sync + 'static,
+    Mutation: juniper::GraphQLType<S, Context = CtxT, TypeInfo = ()> + Send + Sync + 'static,
+    Subscription: juniper::GraphQLType<S, Context = CtxT, TypeInfo = ()> + Send + Sync + 'static,
+    CtxT: Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+{
+    #[cfg(feature = "multipart")]
+    {
+        graphql_multipart_filter_sync(Arc::new(schema), context_extractor, config)
+    }
+    #[cfg(not(feature = "multipart"))]
+    {
+        graphql_filter_sync(Arc::new(schema), context_extractor, config.batch)
+    }
+}
+
+/// Make a synchronous filter for graphql endpoint, also accepting
+/// [GraphQL multipart requests][1].
+///
+/// [1]: https://github.com/jaydenseric/graphql-multipart-request-spec
+#[cfg(feature = "multipart")]
+fn graphql_multipart_filter_sync<Query, Mutation, Subscription, CtxT, S>(
+    schema: Arc<juniper::RootNode<'static, Query, Mutation, Subscription, S>>,
+    context_extractor: BoxedFilter<(CtxT,)>,
+    config: GraphQLFilterConfig,
+) -> BoxedFilter<(http::Response<Vec<u8>>,)>
+where
+    Query: juniper::GraphQLType<S, Context = CtxT, TypeInfo = ()> + Send + Sync + 'static,
+    Mutation: juniper::GraphQLType<S, Context = CtxT, TypeInfo = ()> + Send + Sync + 'static,
+    Subscription: juniper::GraphQLType<S, Context = CtxT, TypeInfo = ()> + Send + Sync + 'static,
+    CtxT: Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+{
+    let post_multipart_schema = schema.clone();
+
+    let handle_post_multipart_request =
+        move |context: CtxT,
+              media_type: ResponseMediaType,
+              req: Result<GraphQLBatchRequest<S>, http::Response<Vec<u8>>>| {
+            let schema = post_multipart_schema.clone();
+            async move {
+                let req = match req {
+                    Ok(req) => req,
+                    Err(resp) => return Ok(resp),
+                };
+
+                task::spawn_blocking(move || {
+                    let resp = req.execute_sync_with_batch_config(&schema, &context, config.batch);
+                    build_response(
+                        serde_json::to_vec(&resp)
+                            .map(|json| (json, resp.status_code(), resp.cache_policy()))
+                            .map_err(Into::into),
+                        media_type,
+                    )
+                })
+                .await
+            }
+            .map_err(|e: task::JoinError| warp::reject::custom(JoinError(e)))
+        };
+    let post_multipart_filter = warp::post()
+        .and(context_extractor.clone())
+        .and(response_media_type())
+        .and(multipart_request(config.multipart))
+        .and_then(handle_post_multipart_request);
+
+    post_multipart_filter
+        .or(graphql_filter_sync(schema, context_extractor, config.batch))
+        .unify()
+        .boxed()
+}
+
+fn graphql_filter_sync<Query, Mutation, Subscription, CtxT, S>(
+    schema: Arc<juniper::RootNode<'static, Query, Mutation, Subscription, S>>,
+    context_extractor: BoxedFilter<(CtxT,)>,
+    batch_config: BatchConfig,
+) -> BoxedFilter<(http::Response<Vec<u8>>,)>
+where
+    Query: juniper::GraphQLType<S, Context = CtxT, TypeInfo = ()> + Send + Sync + 'static,
+    Mutation: juniper::GraphQLType<S, Context = CtxT, TypeInfo = ()> + Send + Sync + 'static,
+    Subscription: juniper::GraphQLType<S, Context = CtxT, TypeInfo = ()> + Send + Sync + 'static,
+    CtxT: Send + Sync + 'static,
+    S: ScalarValue + Send + Sync + 'static,
+{
     let post_json_schema = schema.clone();
     let post_graphql_schema = schema.clone();
 
-    let handle_post_json_request = move |context: CtxT, req: GraphQLBatchRequest<S>| {
-        let schema = post_json_schema.clone();
-        async move {
-            let res = task::spawn_blocking(move || {
-                let resp = req.execute_sync(&schema, &context);
-                Ok((serde_json::to_vec(&resp)?, resp.is_ok()))
-            })
-            .await?;
+    let handle_post_json_request =
+        move |context: CtxT, media_type: ResponseMediaType, req: GraphQLBatchRequest<S>| {
+            let schema = post_json_schema.clone();
+            async move {
+                let res = task::spawn_blocking(move || {
+                    let resp = req.execute_sync_with_batch_config(&schema, &context, batch_config);
+                    Ok((
+                        serde_json::to_vec(&resp)?,
+                        resp.status_code(),
+                        resp.cache_policy(),
+                    ))
+                })
+                .await?;
 
-            Ok(build_response(res))
-        }
-        .map_err(|e: task::JoinError| warp::reject::custom(JoinError(e)))
-    };
+                Ok(build_response(res, media_type))
+            }
+            .map_err(|e: task::JoinError| warp::reject::custom(JoinError(e)))
+        };
     let post_json_filter = warp::post()
         .and(context_extractor.clone())
+        .and(response_media_type())
         .and(body::json())
         .and_then(handle_post_json_request);
 
-    let handle_post_graphql_request = move |context: CtxT, body: Bytes| {
-        let schema = post_graphql_schema.clone();
-        async move {
-            let res = task::spawn_blocking(move || {
-                let query = str::from_utf8(body.as_ref())
-                    .map_err(|e| anyhow!("Request body is not a valid UTF-8 string: {}", e))?;
-                let req = GraphQLRequest::new(query.into(), None, None);
-
-                let resp = req.execute_sync(&schema, &context);
-                Ok((serde_json::to_vec(&resp)?, resp.is_ok()))
-            })
-            .await?;
+    let handle_post_graphql_request =
+        move |context: CtxT, media_type: ResponseMediaType, body: Bytes| {
+            let schema = post_graphql_schema.clone();
+            async move {
+                let res = task::spawn_blocking(move || {
+                    let query = str::from_utf8(body.as_ref())
+                        .map_err(|e| anyhow!("Request body is not a valid UTF-8 string: {}", e))?;
+                    let req = GraphQLRequest::new(query.into(), None, None);
+
+                    let resp = req.execute_sync(&schema, &context);
+                    Ok((
+                        serde_json::to_vec(&resp)?,
+                        resp.status_code(),
+                        resp.cache_policy(),
+                    ))
+                })
+                .await?;
 
-            Ok(build_response(res))
-        }
-        .map_err(|e: task::JoinError| warp::reject::custom(JoinError(e)))
-    };
+                Ok(build_response(res, media_type))
+            }
+            .map_err(|e: task::JoinError| warp::reject::custom(JoinError(e)))
+        };
     let post_graphql_filter = warp::post()
         .and(context_extractor.clone())
+        .and(response_media_type())
         .and(body::bytes())
         .and_then(handle_post_graphql_request);
 
-    let handle_get_request = move |context: CtxT, mut qry: HashMap<String, String>| {
-        let schema = schema.clone();
-        async move {
-            let res = task::spawn_blocking(move || {
-                let req = GraphQLRequest::new(
-                    qry.remove("query").ok_or_else(|| {
-                        anyhow!("Missing GraphQL query string in query parameters")
-                    })?,
-                    qry.remove("operation_name"),
-                    qry.remove("variables")
-                        .map(|vs| serde_json::from_str(&vs))
-                        .transpose()?,
-                );
-
-                let resp = req.execute_sync(&schema, &context);
-                Ok((serde_json::to_vec(&resp)?, resp.is_ok()))
-            })
-            .await?;
-
-            Ok(build_response(res))
-        }
-        .map_err(|e: task::JoinError| warp::reject::custom(JoinError(e)))
-    };
+    let handle_get_request =
+        move |context: CtxT, media_type: ResponseMediaType, mut qry: HashMap<String, String>| {
+            let schema = schema.clone();
+            async move {
+                let res = task::spawn_blocking(move || {
+                    let req = GraphQLRequest::new(
+                        qry.remove("query").ok_or_else(|| {
+                            anyhow!("Missing GraphQL query string in query parameters")
+                        })?,
+                        qry.remove("operation_name"),
+                        qry.remove("variables")
+                            .map(|vs| serde_json::from_str(&vs))
+                            .transpose()?,
+                    )
+                    .with_extensions(
+                        qry.remove("extensions")
+                            .map(|es| serde_json::from_str(&es))
+                            .transpose()?,
+                    )
+                    .forbid_mutations();
+
+                    let resp = req.execute_sync(&schema, &context);
+                    if resp.is_rejected_mutation() {
+                        return Ok(mutation_over_get_response());
+                    }
+                    Ok(build_response(
+                        Ok((
+                            serde_json::to_vec(&resp)?,
+                            resp.status_code(),
+                            resp.cache_policy(),
+                        )),
+                        media_type,
+                    ))
+                })
+                .await?;
+
+                Ok(res.unwrap_or_else(|e: anyhow::Error| build_response(Err(e), media_type)))
+            }
+            .map_err(|e: task::JoinError| warp::reject::custom(JoinError(e)))
+        };
     let get_filter = warp::get()
         .and(context_extractor)
+        .and(response_media_type())
         .and(query::query())
         .and_then(handle_get_request);
 
@@ -288,13 +572,219 @@ pub struct JoinError(task::JoinError);
 
 impl warp::reject::Reject for JoinError {}
 
-fn build_response(response: Result<(Vec<u8>, bool), anyhow::Error>) -> http::Response<Vec<u8>> {
-    match response {
-        Ok((body, is_ok)) => http::Response::builder()
-            .status(if is_ok { 200 } else { 400 })
+/// Make a filter providing the GraphQL context created by the given [`ContextFactory`], to be
+/// passed as the `context_extractor` of [`make_graphql_filter()`] or
+/// [`make_graphql_filter_sync()`].
+///
+/// The [`RequestParts::extensions`] are left empty.
+///
+/// Rejects with a [`ContextRejection`] if the context can't be created, which may be turned
+/// into a response by recovering with [`recover_context_rejection()`].
+///
+/// Example:
+///
+/// ```
+/// # use futures::future;
+/// # use warp::Filter;
+/// # use juniper::{
+/// #     http::{ContextError, RequestParts},
+/// #     tests::fixtures::starwars::schema::{Database, Query},
+/// #     EmptyMutation, EmptySubscription, RootNode,
+/// # };
+/// # use juniper_warp::{context_factory_filter, make_graphql_filter, recover_context_rejection};
+/// #
+/// let schema = RootNode::new(Query, EmptyMutation::new(), EmptySubscription::new());
+///
+/// let context_extractor = context_factory_filter(|parts: &RequestParts| {
+///     future::ready(match parts.header("authorization") {
+///         Some(_) => Ok(Database::new()),
+///         None => Err(ContextError::unauthorized("missing `Authorization` header")),
+///     })
+/// });
+///
+/// let graphql_endpoint = warp::path("graphql")
+///     .and(make_graphql_filter(schema, context_extractor))
+///     .recover(recover_context_rejection);
+/// ```
+pub fn context_factory_filter<CtxT, F>(context_factory: F) -> BoxedFilter<(CtxT,)>
+where
+    CtxT: Send + 'static,
+    F: ContextFactory<CtxT> + 'static,
+{
+    let context_factory = Arc::new(context_factory);
+    warp::method()
+        .and(warp::path::full())
+        .and(query::raw().or(warp::any().map(String::new)).unify())
+        .and(warp::header::headers_cloned())
+        .and(warp::addr::remote())
+        .and_then(
+            move |method: http::Method,
+                  path: FullPath,
+                  query: String,
+                  headers: http::HeaderMap,
+                  remote_addr: Option<SocketAddr>| {
+                let context_factory = context_factory.clone();
+                async move {
+                    let mut uri = path.as_str().to_owned();
+                    if !query.is_empty() {
+                        uri.push('?');
+                        uri.push_str(&query);
+                    }
+                    let mut parts = RequestParts::new(method.as_str(), uri);
+                    for (name, value) in &headers {
+                        if let Ok(value) = value.to_str() {
+                            parts.append_header(name, value);
+                        }
+                    }
+                    parts.remote_addr = remote_addr;
+
+                    context_factory
+                        .create(&parts)
+                        .await
+                        .map_err(|e| warp::reject::custom(ContextRejection(e)))
+                }
+            },
+        )
+        .boxed()
+}
+
+/// Rejection of a [`context_factory_filter()`], carrying the [`ContextError`] of the
+/// [`ContextFactory`].
+#[derive(Debug)]
+pub struct ContextRejection(pub ContextError);
+
+impl warp::reject::Reject for ContextRejection {}
+
+/// Recovers from a [`ContextRejection`], responding with the status code of its
+/// [`ContextError`], and passes any other [`Rejection`] through.
+pub async fn recover_context_rejection(
+    rejection: Rejection,
+) -> Result<http::Response<Vec<u8>>, Rejection> {
+    match rejection.find::<ContextRejection>() {
+        Some(ContextRejection(e)) => Ok(http::Response::builder()
+            .status(e.status_code())
             .header("content-type", "application/json")
-            .body(body)
-            .expect("response is valid"),
+            .body(
+                serde_json::to_vec_pretty(&e.clone().into_response::<DefaultScalarValue>())
+                    .expect("response is serializable"),
+            )
+            .unwrap_or_else(|_| {
+                let mut resp = http::Response::new(Vec::new());
+                *resp.status_mut() = http::StatusCode::INTERNAL_SERVER_ERROR;
+                resp
+            })),
+        None => Err(rejection),
+    }
+}
+
+/// Filter negotiating the [`ResponseMediaType`] against the `Accept` header.
+fn response_media_type() -> BoxedFilter<(ResponseMediaType,)> {
+    warp::header::optional::<String>("accept")
+        .map(|accept: Option<String>| ResponseMediaType::negotiate(accept.as_deref()))
+        .boxed()
+}
+
+/// Filter parsing GraphQL multipart requests while their body is being received, limited by the
+/// given [`MultipartLimits`].
+///
+/// Passes only the requests with a multipart `Content-Type`, and extracts the response to reply
+/// with if the request is rejected.
+#[cfg(feature = "multipart")]
+fn multipart_request<S>(
+    limits: MultipartLimits,
+) -> BoxedFilter<(Result<GraphQLBatchRequest<S>, http::Response<Vec<u8>>>,)>
+where
+    S: ScalarValue + Send + Sync + 'static,
+{
+    warp::header::<String>("content-type")
+        .and_then(|content_type: String| async move {
+            if multipart::is_multipart_request(&content_type) {
+                Ok(content_type)
+            } else {
+                Err(warp::reject())
+            }
+        })
+        .and(warp::header::headers_cloned())
+        .and(body::stream())
+        .then(
+            move |content_type: String, headers: http::HeaderMap, body| {
+                parse_multipart(content_type, headers, body, limits)
+            },
+        )
+        .boxed()
+}
+
+fn mutation_over_get_response() -> http::Response<Vec<u8>> {
+    http::Response::builder()
+        .status(http::StatusCode::METHOD_NOT_ALLOWED)
+        .header("allow", "POST")
+        .header("content-type", "text/plain; charset=utf-8")
+        .body(b"mutations are not allowed over GET".to_vec())
+        .expect("response is valid")
+}
+
+/// Parses a GraphQL multipart request while its `body` is being received.
+#[cfg(feature = "multipart")]
+async fn parse_multipart<S: ScalarValue>(
+    content_type: String,
+    headers: http::HeaderMap,
+    body: impl Stream<Item = Result<impl Buf, warp::Error>>,
+    limits: MultipartLimits,
+) -> Result<GraphQLBatchRequest<S>, http::Response<Vec<u8>>> {
+    multipart::check_preflight(|name| {
+        headers
+            .get(name)
+            .map_or(false, |v| !v.as_bytes().is_empty())
+    })
+    .map_err(multipart_error_response)?;
+
+    let mut parser =
+        MultipartParser::new(&content_type, &limits).map_err(multipart_error_response)?;
+    futures::pin_mut!(body);
+    while let Some(chunk) = body.next().await {
+        let mut chunk = chunk.map_err(|e| {
+            http::Response::builder()
+                .status(http::StatusCode::BAD_REQUEST)
+                .body(e.to_string().into_bytes())
+                .expect("status code is valid")
+        })?;
+        while chunk.has_remaining() {
+            let bytes = chunk.chunk();
+            let len = bytes.len();
+            parser.feed(bytes).map_err(multipart_error_response)?;
+            chunk.advance(len);
+        }
+    }
+    parser.finish().map_err(multipart_error_response)
+}
+
+#[cfg(feature = "multipart")]
+fn multipart_error_response(err: multipart::MultipartError) -> http::Response<Vec<u8>> {
+    let status = if err.is_server_error() {
+        http::StatusCode::INTERNAL_SERVER_ERROR
+    } else {
+        http::StatusCode::BAD_REQUEST
+    };
+    http::Response::builder()
+        .status(status)
+        .body(err.to_string().into_bytes())
+        .expect("status code is valid")
+}
+
+fn build_response(
+    response: Result<(Vec<u8>, u16, Option<CachePolicy>), anyhow::Error>,
+    media_type: ResponseMediaType,
+) -> http::Response<Vec<u8>> {
+    match response {
+        Ok((body, status_code, cache_policy)) => {
+            let mut builder = http::Response::builder()
+                .status(status_code)
+                .header("content-type", media_type.as_str());
+            if let Some(cache_control) = cache_policy.and_then(|p| p.header_value()) {
+                builder = builder.header("cache-control", cache_control);
+            }
+            builder.body(body).expect("response is valid")
+        }
         Err(_) => http::Response::builder()
             .status(http::StatusCode::INTERNAL_SERVER_ERROR)
             .body(Vec::new())
@@ -480,11 +970,210 @@ pub mod subscriptions {
     }
 }
 
+/// `juniper_warp` [GraphQL over SSE][1] handler implementation.
+///
+/// [1]: https://github.com/enisdenjo/graphql-sse/blob/master/PROTOCOL.md
+#[cfg(feature = "sse")]
+pub mod sse {
+    use std::{collections::HashMap, convert::Infallible, sync::Arc};
+
+    use juniper::{
+        futures::stream::{BoxStream, StreamExt as _},
+        http::GraphQLRequest,
+        GraphQLSubscriptionType, GraphQLTypeAsync, ScalarValue,
+    };
+    use juniper_subscriptions::sse::{self, EventStreams, SseError};
+    use warp::{
+        body,
+        filters::BoxedFilter,
+        http::{self, Method},
+        hyper::{body::Bytes, Body},
+        query, Filter,
+    };
+
+    /// Makes a filter handling [GraphQL over SSE][1] requests.
+    ///
+    /// Requests carrying an event stream token, either in the
+    /// `x-graphql-event-stream-token` header or in the `token` query parameter, are handled in
+    /// the "single connection" mode:
+    /// - `PUT` reserves a new event stream, responding with its token;
+    /// - `GET` opens the reserved event stream;
+    /// - `POST` executes an operation over the event stream, responding with `202 Accepted`;
+    /// - `DELETE` cancels the operation specified in the `operationId` query parameter.
+    ///
+    /// Any other `GET` or `POST` request is executed over its own event stream, as of the
+    /// "distinct connections" mode.
+    ///
+    /// The `context_extractor` argument should be a filter that provides the GraphQL context
+    /// required by the schema.
+    ///
+    /// [1]: https://github.com/enisdenjo/graphql-sse/blob/master/PROTOCOL.md
+    pub fn make_graphql_sse_filter<Query, Mutation, Subscription, CtxT, S>(
+        event_streams: Arc<EventStreams<Query, Mutation, Subscription, CtxT, S>>,
+        context_extractor: BoxedFilter<(CtxT,)>,
+    ) -> BoxedFilter<(http::Response<Body>,)>
+    where
+        Query: GraphQLTypeAsync<S, Context = CtxT> + Send + Sync + 'static,
+        Query::TypeInfo: Send + Sync,
+        Mutation: GraphQLTypeAsync<S, Context = CtxT> + Send + Sync + 'static,
+        Mutation::TypeInfo: Send + Sync,
+        Subscription: GraphQLSubscriptionType<S, Context = CtxT> + Send + Sync + 'static,
+        Subscription::TypeInfo: Send + Sync,
+        CtxT: Send + Sync + 'static,
+        S: ScalarValue + Send + Sync + 'static,
+    {
+        let params = query::query::<HashMap<String, String>>()
+            .or(warp::any().map(HashMap::new))
+            .unify();
+
+        warp::method()
+            .and(warp::header::optional::<String>(sse::TOKEN_HEADER))
+            .and(params)
+            .and(context_extractor)
+            .and(body::bytes())
+            .map(
+                move |method: Method,
+                      token: Option<String>,
+                      mut params: HashMap<String, String>,
+                      context: CtxT,
+                      body: Bytes| {
+                    let token = token.or_else(|| params.remove("token"));
+                    match (method, token) {
+                        (Method::PUT, _) => http::Response::builder()
+                            .status(http::StatusCode::CREATED)
+                            .header("content-type", "text/plain; charset=utf-8")
+                            .body(event_streams.reserve().into())
+                            .expect("response is valid"),
+                        (Method::GET, Some(token)) => match event_streams.open(&token) {
+                            Ok(events) => sse_response(events),
+                            Err(e) => sse_error_response(e),
+                        },
+                        (Method::DELETE, Some(token)) => match params.get("operationId") {
+                            Some(id) => match event_streams.cancel(&token, id) {
+                                Ok(()) => empty_response(http::StatusCode::OK),
+                                Err(e) => sse_error_response(e),
+                            },
+                            None => bad_request("'operationId' parameter is missing"),
+                        },
+                        (method @ Method::GET, token @ None) | (method @ Method::POST, token) => {
+                            let request = if method == Method::GET {
+                                request_from_params(params).map(GraphQLRequest::forbid_mutations)
+                            } else {
+                                serde_json::from_slice::<GraphQLRequest<S>>(&body)
+                                    .map_err(|e| e.to_string())
+                            };
+                            let request = match request {
+                                Ok(request) => request,
+                                Err(e) => return bad_request(&e),
+                            };
+                            let context = Arc::new(context);
+                            match token {
+                                Some(token) => {
+                                    match event_streams.execute(&token, request, context) {
+                                        Ok(()) => empty_response(http::StatusCode::ACCEPTED),
+                                        Err(e) => sse_error_response(e),
+                                    }
+                                }
+                                None => sse_response(event_streams.subscribe(request, context)),
+                            }
+                        }
+                        _ => empty_response(http::StatusCode::METHOD_NOT_ALLOWED),
+                    }
+                },
+            )
+            .boxed()
+    }
+
+    fn request_from_params<S: ScalarValue>(
+        mut params: HashMap<String, String>,
+    ) -> Result<GraphQLRequest<S>, String> {
+        let query = params
+            .remove("query")
+            .ok_or("Missing GraphQL query string in query parameters")?;
+        let variables = params
+            .remove("variables")
+            .map(|vs| serde_json::from_str(&vs))
+            .transpose()
+            .map_err(|e| e.to_string())?;
+        let extensions = params
+            .remove("extensions")
+            .map(|es| serde_json::from_str(&es))
+            .transpose()
+            .map_err(|e| e.to_string())?;
+        Ok(
+            GraphQLRequest::new(query, params.remove("operation_name"), variables)
+                .with_extensions(extensions),
+        )
+    }
+
+    fn sse_response(events: BoxStream<'static, sse::Event>) -> http::Response<Body> {
+        http::Response::builder()
+            .header("content-type", sse::CONTENT_TYPE)
+            .header("cache-control", "no-cache")
+            .body(Body::wrap_stream(
+                events.map(|event| Ok::<_, Infallible>(event.to_string())),
+            ))
+            .expect("response is valid")
+    }
+
+    fn sse_error_response(err: SseError) -> http::Response<Body> {
+        http::Response::builder()
+            .status(err.status_code())
+            .body(err.to_string().into())
+            .expect("status code is valid")
+    }
+
+    fn bad_request(message: &str) -> http::Response<Body> {
+        http::Response::builder()
+            .status(http::StatusCode::BAD_REQUEST)
+            .body(message.to_owned().into())
+            .expect("status code is valid")
+    }
+
+    fn empty_response(status: http::StatusCode) -> http::Response<Body> {
+        http::Response::builder()
+            .status(status)
+            .body(Body::empty())
+            .expect("status code is valid")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use warp::{http, test::request};
 
+    #[tokio::test]
+    async fn context_factory_filter_creates_context() {
+        let filter = context_factory_filter(|parts: &RequestParts| {
+            let context = parts
+                .header("authorization")
+                .map(|token| format!("{} {} {}", parts.method, parts.uri, token))
+                .ok_or_else(|| ContextError::unauthorized("invalid token"));
+            futures::future::ready(context)
+        })
+        .map(|context: String| context)
+        .recover(recover_context_rejection);
+
+        let response = request()
+            .method("POST")
+            .path("/graphql?a=b")
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), http::StatusCode::UNAUTHORIZED);
+        let body = String::from_utf8(response.body().to_vec()).unwrap();
+        assert!(body.contains("invalid token"));
+
+        let response = request()
+            .method("POST")
+            .path("/graphql?a=b")
+            .header("Authorization", "secret")
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), http::StatusCode::OK);
+        assert_eq!(response.body(), "POST /graphql?a=b secret");
+    }
+
     #[test]
     fn graphiql_response_does_not_panic() {
         graphiql_response("/abcd", None);
@@ -678,6 +1367,50 @@ mod tests {
     }
 }
 
+#[cfg(all(test, feature = "sse"))]
+mod tests_sse {
+    use std::sync::Arc;
+
+    use juniper::{
+        tests::fixtures::starwars::schema::{Database, Query},
+        EmptyMutation, EmptySubscription, RootNode,
+    };
+    use juniper_subscriptions::{sse::EventStreams, Coordinator};
+    use warp::{http, test::request, Filter};
+
+    use super::sse::make_graphql_sse_filter;
+
+    #[tokio::test]
+    async fn streams_query_over_distinct_connection() {
+        let schema = RootNode::new(
+            Query,
+            EmptyMutation::<Database>::new(),
+            EmptySubscription::<Database>::new(),
+        );
+        let event_streams = Arc::new(EventStreams::new(Arc::new(Coordinator::new(schema)), None));
+        let filter = warp::path("stream").and(make_graphql_sse_filter(
+            event_streams,
+            warp::any().map(Database::new).boxed(),
+        ));
+
+        let response = request()
+            .method("POST")
+            .path("/stream")
+            .header("content-type", "application/json")
+            .body(r#"{"query": "{ hero { name } }"}"#)
+            .reply(&filter)
+            .await;
+
+        assert_eq!(response.status(), http::StatusCode::OK);
+        assert_eq!(response.headers()["content-type"], "text/event-stream");
+        assert_eq!(
+            response.body(),
+            "event: next\ndata: {\"data\":{\"hero\":{\"name\":\"R2-D2\"}}}\n\n\
+             event: complete\ndata:\n\n",
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests_http_harness {
     use juniper::{